@@ -349,7 +349,12 @@ fn stmt_word_count(s: &Stmt) -> usize {
         Stmt::SpSetLights { num_dir, .. } => *num_dir as usize + 2,
         // gsSPLookAt: two G_MOVEMEM(G_MV_LIGHT) commands (S/T basis axes to DMEM slots 0/1).
         Stmt::SpLookAt { .. } => 2,
-        Stmt::DpSetFogColor { .. } | Stmt::DpSetBlendColor { .. } | Stmt::SpFogPosition { .. } => 1,
+        Stmt::DpSetFogColor { .. }
+        | Stmt::DpSetBlendColor { .. }
+        | Stmt::DpSetKeyR { .. }
+        | Stmt::DpSetPrimDepth { .. }
+        | Stmt::DpSetKeyGB { .. }
+        | Stmt::SpFogPosition { .. } => 1,
         Stmt::SpTextureRectangle { .. } => 3,
         Stmt::DpSetColorImage { .. }
         | Stmt::DpSetDepthImage { .. }
@@ -546,7 +551,7 @@ fn encode_texture_data(
             }
         },
         (4, 1) => {
-            for pixel in rgba8.chunks_exact(4) {
+            for pixel in rgba8.as_chunks::<4>().0 {
                 rdram.push(encode_i8_texel(pixel[0], pixel[1], pixel[2], pixel[3]));
             }
             None
@@ -563,13 +568,13 @@ fn encode_texture_data(
             None
         }
         (3, 2) => {
-            for pixel in rgba8.chunks_exact(4) {
+            for pixel in rgba8.as_chunks::<4>().0 {
                 rdram.extend_from_slice(&encode_ia16_texel(pixel[0], pixel[1], pixel[2], pixel[3]));
             }
             None
         }
         (3, 1) => {
-            for pixel in rgba8.chunks_exact(4) {
+            for pixel in rgba8.as_chunks::<4>().0 {
                 rdram.push(encode_ia8_texel(pixel[0], pixel[1], pixel[2], pixel[3]));
             }
             None
@@ -585,7 +590,7 @@ fn encode_texture_data(
             None
         }
         _ => {
-            for pixel in rgba8.chunks_exact(4) {
+            for pixel in rgba8.as_chunks::<4>().0 {
                 rdram.extend_from_slice(&encode_rgba16_texel(
                     pixel[0], pixel[1], pixel[2], pixel[3],
                 ));
@@ -1463,6 +1468,30 @@ fn emit_stmt(rdram: &mut Vec<u8>, s: &Stmt, line: usize, ctx: &EmitCtx, diags: &
             let (w0, w1) = gdp_set_blend_color(*rgba);
             push_word(rdram, w0, w1);
         }
+        Stmt::DpSetPrimDepth { z, dz } => {
+            let (w0, w1) = gdp_set_prim_depth(*z, *dz);
+            push_word(rdram, w0, w1);
+        }
+        Stmt::DpSetKeyR {
+            center,
+            scale,
+            width,
+        } => {
+            let (w0, w1) = gdp_set_key_r(*center, *scale, *width);
+            push_word(rdram, w0, w1);
+        }
+        Stmt::DpSetKeyGB {
+            center_g,
+            scale_g,
+            width_g,
+            center_b,
+            scale_b,
+            width_b,
+        } => {
+            let (w0, w1) =
+                gdp_set_key_gb(*center_g, *scale_g, *width_g, *center_b, *scale_b, *width_b);
+            push_word(rdram, w0, w1);
+        }
         Stmt::SpFogPosition { min, max } => {
             let (w0, w1) = gsp_fog_position(*min, *max);
             push_word(rdram, w0, w1);
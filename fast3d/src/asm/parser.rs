@@ -490,6 +490,26 @@ pub enum Stmt {
     DpSetBlendColor {
         rgba: u32,
     },
+    /// gsDPSetKeyR(cR, sR, wR): red chroma-key center, scale and 12-bit width.
+    DpSetKeyR {
+        center: u32,
+        scale: u32,
+        width: u32,
+    },
+    /// gsDPSetKeyGB(cG, sG, wG, cB, sB, wB): green/blue chroma-key center, scale and width.
+    DpSetKeyGB {
+        center_g: u32,
+        scale_g: u32,
+        width_g: u32,
+        center_b: u32,
+        scale_b: u32,
+        width_b: u32,
+    },
+    /// gsDPSetPrimDepth(z, dz): constant primitive depth for G_ZS_PRIM.
+    DpSetPrimDepth {
+        z: u32,
+        dz: u32,
+    },
     /// gsSPFogPosition(min, max): set fog range; encodes fm/fo via G_MOVEWORD/G_MW_FOG.
     SpFogPosition {
         min: i32,
@@ -2465,6 +2485,223 @@ pub fn parse(source: &str) -> (Vec<(usize, Stmt)>, Vec<Diag>) {
                     msg: "gsDPSetBlendColor expects (r, g, b, a) or (rgba)".into(),
                 }),
             }
+        } else if line.starts_with("gsDPSetKeyR") {
+            match call_args(line, "gsDPSetKeyR") {
+                Some(a) if a.len() == 3 => {
+                    match (
+                        parse_u32_token(a[0]),
+                        parse_u32_token(a[1]),
+                        parse_u32_token(a[2]),
+                    ) {
+                        (Some(center), Some(scale), Some(width)) => stmts.push((
+                            n,
+                            Stmt::DpSetKeyR {
+                                center,
+                                scale,
+                                width,
+                            },
+                        )),
+                        _ => diags.push(Diag {
+                            line: n,
+                            msg: "gsDPSetKeyR: parse error in args".into(),
+                        }),
+                    }
+                }
+                _ => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetKeyR expects (center, scale, width)".into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetKeyGB") {
+            match call_args(line, "gsDPSetKeyGB") {
+                Some(a) if a.len() == 6 => {
+                    match (
+                        parse_u32_token(a[0]),
+                        parse_u32_token(a[1]),
+                        parse_u32_token(a[2]),
+                        parse_u32_token(a[3]),
+                        parse_u32_token(a[4]),
+                        parse_u32_token(a[5]),
+                    ) {
+                        (
+                            Some(center_g),
+                            Some(scale_g),
+                            Some(width_g),
+                            Some(center_b),
+                            Some(scale_b),
+                            Some(width_b),
+                        ) => stmts.push((
+                            n,
+                            Stmt::DpSetKeyGB {
+                                center_g,
+                                scale_g,
+                                width_g,
+                                center_b,
+                                scale_b,
+                                width_b,
+                            },
+                        )),
+                        _ => diags.push(Diag {
+                            line: n,
+                            msg: "gsDPSetKeyGB: parse error in args".into(),
+                        }),
+                    }
+                }
+                _ => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetKeyGB expects (cG, sG, wG, cB, sB, wB)".into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetPrimDepth") {
+            match call_args(line, "gsDPSetPrimDepth") {
+                Some(a) if a.len() == 2 => match (parse_u32_token(a[0]), parse_u32_token(a[1])) {
+                    (Some(z), Some(dz)) => stmts.push((n, Stmt::DpSetPrimDepth { z, dz })),
+                    _ => diags.push(Diag {
+                        line: n,
+                        msg: "gsDPSetPrimDepth: parse error in args".into(),
+                    }),
+                },
+                _ => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetPrimDepth expects (z, dz)".into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetDepthSource") {
+            // gsDPSetDepthSource(G_ZS_PIXEL|G_ZS_PRIM): the one-bit othermode_L ZSRC field.
+            let src = match call_args(line, "gsDPSetDepthSource").as_deref() {
+                Some(["G_ZS_PIXEL"]) => Some(0),
+                Some(["G_ZS_PRIM"]) => Some(1),
+                _ => None,
+            };
+            match src {
+                Some(z) => stmts.push((
+                    n,
+                    Stmt::DpSetOtherModeL {
+                        shift: 2,
+                        length: 1,
+                        data: z << 2,
+                    },
+                )),
+                None => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetDepthSource expects (G_ZS_PIXEL|G_ZS_PRIM)".into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetCombineKey") {
+            // gsDPSetCombineKey(G_CK_NONE|G_CK_KEY): the one-bit othermode_H COMBKEY field.
+            let key = match call_args(line, "gsDPSetCombineKey").as_deref() {
+                Some(["G_CK_NONE"]) => Some(0),
+                Some(["G_CK_KEY"]) => Some(1),
+                _ => None,
+            };
+            match key {
+                Some(k) => stmts.push((
+                    n,
+                    Stmt::DpSetOtherModeH {
+                        shift: 8,
+                        length: 1,
+                        data: k << 8,
+                    },
+                )),
+                None => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetCombineKey expects (G_CK_NONE|G_CK_KEY)".into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetTextureFilter") {
+            // gsDPSetTextureFilter(G_TF_POINT|G_TF_BILERP|G_TF_AVERAGE): othermode_H TEXTFILT.
+            let filt = match call_args(line, "gsDPSetTextureFilter").as_deref() {
+                Some(["G_TF_POINT"]) => Some(0),
+                Some(["G_TF_BILERP"]) => Some(2),
+                Some(["G_TF_AVERAGE"]) => Some(3),
+                _ => None,
+            };
+            match filt {
+                Some(f) => stmts.push((
+                    n,
+                    Stmt::DpSetOtherModeH {
+                        shift: 12,
+                        length: 2,
+                        data: f << 12,
+                    },
+                )),
+                None => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetTextureFilter expects (G_TF_POINT|G_TF_BILERP|G_TF_AVERAGE)"
+                        .into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetTexturePersp") {
+            // gsDPSetTexturePersp(G_TP_NONE|G_TP_PERSP): othermode_H TEXTPERSP.
+            let persp = match call_args(line, "gsDPSetTexturePersp").as_deref() {
+                Some(["G_TP_NONE"]) => Some(0),
+                Some(["G_TP_PERSP"]) => Some(1),
+                _ => None,
+            };
+            match persp {
+                Some(p) => stmts.push((
+                    n,
+                    Stmt::DpSetOtherModeH {
+                        shift: 19,
+                        length: 1,
+                        data: p << 19,
+                    },
+                )),
+                None => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetTexturePersp expects (G_TP_NONE|G_TP_PERSP)".into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetColorDither") {
+            // gsDPSetColorDither(G_CD_MAGICSQ|G_CD_BAYER|G_CD_NOISE|G_CD_DISABLE): othermode_H
+            // RGBDITHER.
+            let sel = match call_args(line, "gsDPSetColorDither").as_deref() {
+                Some(["G_CD_MAGICSQ"]) => Some(0),
+                Some(["G_CD_BAYER"]) => Some(1),
+                Some(["G_CD_NOISE"]) => Some(2),
+                Some(["G_CD_DISABLE"]) => Some(3),
+                _ => None,
+            };
+            match sel {
+                Some(d) => stmts.push((
+                    n,
+                    Stmt::DpSetOtherModeH {
+                        shift: 6,
+                        length: 2,
+                        data: d << 6,
+                    },
+                )),
+                None => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetColorDither expects (G_CD_MAGICSQ|G_CD_BAYER|G_CD_NOISE|G_CD_DISABLE)"
+                        .into(),
+                }),
+            }
+        } else if line.starts_with("gsDPSetAlphaDither") {
+            // gsDPSetAlphaDither(G_AD_PATTERN|G_AD_NOTPATTERN|G_AD_NOISE|G_AD_DISABLE): othermode_H
+            // ALPHADITHER.
+            let sel = match call_args(line, "gsDPSetAlphaDither").as_deref() {
+                Some(["G_AD_PATTERN"]) => Some(0),
+                Some(["G_AD_NOTPATTERN"]) => Some(1),
+                Some(["G_AD_NOISE"]) => Some(2),
+                Some(["G_AD_DISABLE"]) => Some(3),
+                _ => None,
+            };
+            match sel {
+                Some(d) => stmts.push((
+                    n,
+                    Stmt::DpSetOtherModeH {
+                        shift: 4,
+                        length: 2,
+                        data: d << 4,
+                    },
+                )),
+                None => diags.push(Diag {
+                    line: n,
+                    msg: "gsDPSetAlphaDither expects (G_AD_PATTERN|G_AD_NOTPATTERN|G_AD_NOISE|G_AD_DISABLE)"
+                        .into(),
+                }),
+            }
         } else if line.starts_with("gsSPFogPosition") {
             match call_args(line, "gsSPFogPosition") {
                 Some(a) if a.len() == 2 => match (parse_u32_token(a[0]), parse_u32_token(a[1])) {
@@ -2647,6 +2884,107 @@ mod slice2_grammar_tests {
         }
     }
 
+    #[test]
+    fn prim_depth_macros_parse() {
+        let (stmts, diags) = parse("gsDPSetDepthSource(G_ZS_PRIM)\ngsDPSetPrimDepth(0x100, 0)\n");
+        assert!(diags.is_empty(), "diags: {diags:?}");
+        assert!(matches!(
+            stmts[0].1,
+            Stmt::DpSetOtherModeL {
+                shift: 2,
+                length: 1,
+                data: 4
+            }
+        ));
+        assert!(matches!(
+            stmts[1].1,
+            Stmt::DpSetPrimDepth { z: 0x100, dz: 0 }
+        ));
+    }
+
+    #[test]
+    fn chroma_key_macros_parse() {
+        let (stmts, diags) =
+            parse("gsDPSetCombineKey(G_CK_KEY)\ngsDPSetKeyGB(255, 64, 0xFFF, 0, 64, 0xFFF)\n");
+        assert!(diags.is_empty(), "diags: {diags:?}");
+        assert!(matches!(
+            stmts[0].1,
+            Stmt::DpSetOtherModeH {
+                shift: 8,
+                length: 1,
+                data: 256
+            }
+        ));
+        assert!(matches!(
+            stmts[1].1,
+            Stmt::DpSetKeyGB {
+                center_g: 255,
+                width_g: 0xFFF,
+                center_b: 0,
+                ..
+            }
+        ));
+        let (_s, diags) = parse("gsDPSetKeyR(0, 64)\n");
+        assert!(diags.iter().any(|d| d.msg.contains("gsDPSetKeyR")));
+    }
+
+    #[test]
+    fn texture_filter_macro_parses() {
+        let (stmts, diags) = parse("gsDPSetTextureFilter(G_TF_BILERP)\n");
+        assert!(diags.is_empty(), "diags: {diags:?}");
+        assert!(matches!(
+            stmts[0].1,
+            Stmt::DpSetOtherModeH {
+                shift: 12,
+                length: 2,
+                data: 0x2000
+            }
+        ));
+        let (_s, diags) = parse("gsDPSetTextureFilter(G_TF_SMOOTH)\n");
+        assert!(diags.iter().any(|d| d.msg.contains("gsDPSetTextureFilter")));
+    }
+
+    #[test]
+    fn texture_persp_macro_parses() {
+        let (stmts, diags) = parse("gsDPSetTexturePersp(G_TP_PERSP)\n");
+        assert!(diags.is_empty(), "diags: {diags:?}");
+        assert!(matches!(
+            stmts[0].1,
+            Stmt::DpSetOtherModeH {
+                shift: 19,
+                length: 1,
+                data: 0x80000
+            }
+        ));
+        let (_s, diags) = parse("gsDPSetTexturePersp(ON)\n");
+        assert!(diags.iter().any(|d| d.msg.contains("gsDPSetTexturePersp")));
+    }
+
+    #[test]
+    fn dither_macros_parse() {
+        let (stmts, diags) =
+            parse("gsDPSetColorDither(G_CD_BAYER)\ngsDPSetAlphaDither(G_AD_NOISE)\n");
+        assert!(diags.is_empty(), "diags: {diags:?}");
+        assert!(matches!(
+            stmts[0].1,
+            Stmt::DpSetOtherModeH {
+                shift: 6,
+                length: 2,
+                data: 0x40
+            }
+        ));
+        assert!(matches!(
+            stmts[1].1,
+            Stmt::DpSetOtherModeH {
+                shift: 4,
+                length: 2,
+                data: 0x20
+            }
+        ));
+        let (_s, diags) = parse("gsDPSetAlphaDither(G_CD_BAYER)\n");
+        assert!(diags.iter().any(|d| d.msg.contains("gsDPSetAlphaDither")));
+    }
+
     #[test]
     fn bad_perspective_arity_diagnoses() {
         let (_s, diags) = parse("Mtx p = perspective(45, 1, 10)\n");
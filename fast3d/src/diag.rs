@@ -41,6 +41,23 @@ pub enum DiagKind {
     UnwiredSelector {
         slots: u16,
     },
+    /// An S2DEX gsSPObjSprite whose object matrix rotates or shears it; only axis-aligned sprites
+    /// map onto a texture rectangle, so the sprite is dropped.
+    RotatedObjSprite,
+    /// G_LOAD_UCODE named a microcode image (text + data `hash`) that is neither a known build nor
+    /// carries a recognized ident string; the walk keeps decoding with the current microcode.
+    UnknownMicrocode {
+        hash: u64,
+    },
+    /// `Renderer::process_task` found an `OSTask` microcode image (text + data `hash`) that is
+    /// neither a known build nor carries a recognized ident string; the task's display list is
+    /// walked with `RendererConfig::default_microcode` instead.
+    UnknownTaskMicrocode {
+        hash: u64,
+    },
+    /// A raw RDP edge-walker triangle whose coefficient blocks run past the end of the command
+    /// list; the triangle is dropped and the walk stops.
+    TruncatedTriangle,
 }
 
 /// Combiner selector slot names, in bit order (bit 0 = CA … bit 7 = AD). Shared with
@@ -67,7 +84,11 @@ impl DiagKind {
             | DiagKind::VtxOutOfRange { .. }
             | DiagKind::NoTextureLoaded
             | DiagKind::SecondTextureUndecodable
-            | DiagKind::UnwiredSelector { .. } => Severity::Error,
+            | DiagKind::UnwiredSelector { .. }
+            | DiagKind::RotatedObjSprite
+            | DiagKind::UnknownMicrocode { .. }
+            | DiagKind::UnknownTaskMicrocode { .. }
+            | DiagKind::TruncatedTriangle => Severity::Error,
             DiagKind::RenderModeNeverSet
             | DiagKind::UnhandledMovemem(_)
             | DiagKind::UnhandledMoveword(_)
@@ -107,7 +128,7 @@ impl std::fmt::Display for DiagKind {
             DiagKind::NonCanonicalBlend => {
                 write!(
                     f,
-                    "non-canonical blended render mode clamps to AlphaOver fallback"
+                    "non-canonical blended render mode (framebuffer-read blender; AlphaOver outside framebuffer pairs)"
                 )
             }
             DiagKind::StrayRdphalf => write!(f, "stray RDPHALF — rect decode desync"),
@@ -130,6 +151,24 @@ impl std::fmt::Display for DiagKind {
                     unwired_slot_names(*slots)
                 )
             }
+            DiagKind::RotatedObjSprite => {
+                write!(f, "rotated/sheared S2DEX object sprite dropped")
+            }
+            DiagKind::UnknownMicrocode { hash } => {
+                write!(
+                    f,
+                    "G_LOAD_UCODE of unknown microcode (image hash {hash:#018x})"
+                )
+            }
+            DiagKind::UnknownTaskMicrocode { hash } => {
+                write!(
+                    f,
+                    "OSTask of unknown microcode (image hash {hash:#018x}), walked as the default"
+                )
+            }
+            DiagKind::TruncatedTriangle => {
+                write!(f, "RDP triangle runs past the end of the command list")
+            }
         }
     }
 }
@@ -180,6 +219,8 @@ pub struct DlSummary {
     pub warns: u32,
     pub errors: u32,
     pub dropped_runs: u32,
+    /// Sub-DLs ended early by G_CULLDL because their vertex range was offscreen.
+    pub culled_dls: u32,
     pub renderable: bool,
 }
 
@@ -200,6 +241,10 @@ mod tests {
             DiagKind::NoTextureLoaded,
             DiagKind::SecondTextureUndecodable,
             DiagKind::UnwiredSelector { slots: 0b0100 },
+            DiagKind::RotatedObjSprite,
+            DiagKind::UnknownMicrocode { hash: 0 },
+            DiagKind::UnknownTaskMicrocode { hash: 0 },
+            DiagKind::TruncatedTriangle,
         ] {
             assert_eq!(k.severity(), Severity::Error, "{k} must be Error");
         }
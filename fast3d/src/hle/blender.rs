@@ -28,6 +28,51 @@ pub enum AlphaCompare {
     Dither,
 }
 
+/// othermode_H G_MDSFT_RGBDITHER: how the RDP dithers the blended color when it rounds it to a
+/// 16-bit framebuffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorDither {
+    /// G_CD_MAGICSQ: the 4x4 magic-square matrix.
+    #[default]
+    MagicSquare,
+    /// G_CD_BAYER: the 4x4 Bayer matrix.
+    Bayer,
+    /// G_CD_NOISE: per-pixel noise.
+    Noise,
+    /// G_CD_DISABLE: plain truncation.
+    Disable,
+}
+
+/// othermode_H G_MDSFT_ALPHADITHER: the per-pixel threshold pattern G_AC_DITHER compares the
+/// combined alpha against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlphaDither {
+    /// G_AD_PATTERN: the color dither's matrix.
+    #[default]
+    Pattern,
+    /// G_AD_NOTPATTERN: the same matrix, inverted.
+    NotPattern,
+    /// G_AD_NOISE: per-pixel noise.
+    Noise,
+    /// G_AD_DISABLE: no dither pattern (the compare falls back to noise, as the RDP's
+    /// random-threshold compare does).
+    Disable,
+}
+
+/// othermode_L CVG_DST: how a pixel's coverage updates the coverage already in the color image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CvgDest {
+    /// CVG_DST_CLAMP: add the pixel's coverage to memory's, saturating at full.
+    #[default]
+    Clamp,
+    /// CVG_DST_WRAP: add the coverages modulo full coverage.
+    Wrap,
+    /// CVG_DST_FULL: store full coverage.
+    Full,
+    /// CVG_DST_SAVE: leave memory's coverage as it was.
+    Save,
+}
+
 /// Decoded `other_mode_l` (+ G_FOG geometry bit) for one run.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct RenderMode {
@@ -38,15 +83,43 @@ pub struct RenderMode {
     /// True when the mode blends but is NOT the canonical M=CLR_MEM,B=1MA,A=A_IN lerp.
     /// The §4.4/§9 [IMP12] diagnostic is emitted by `snapshot_run` in A6 (pure decode here).
     pub non_canonical_blend: bool,
+    /// The fixed-function blend paths cannot reproduce this mode: a non-canonical blend, or a
+    /// 2-cycle first-cycle blender that is more than a passthrough or the G_RM_FOG_SHADE_A mix the
+    /// shaders already apply. The renderer draws such runs through the framebuffer-read blender,
+    /// which evaluates both cycles' P/A/M/B against a copy of the color image.
+    pub fb_blend: bool,
     pub z_test: bool,
     pub z_write: bool,
     pub z_mode: ZMode,
+    /// othermode_L ZSRC = G_ZS_PRIM: every pixel takes the constant `prim_depth` instead of its
+    /// interpolated Z, for both the depth test and the depth write.
+    pub z_source_prim: bool,
+    /// The G_SETPRIMDEPTH Z (15-bit screen Z) in effect for a `z_source_prim` run; 0 otherwise.
+    /// Not part of the othermode decode — `snapshot_run`/`snapshot_rect_run` fill it from the RDP.
+    pub prim_depth: u16,
     pub fog: bool,
     pub alpha_compare: AlphaCompare,
     pub cvg_x_alpha: bool,
+    /// Coverage bits, consumed when the renderer emulates coverage: AA_EN (blend partially
+    /// covered edges with memory), IM_RD (read memory color and coverage), CLR_ON_CVG (write color
+    /// only where coverage overflows), ALPHA_CVG_SEL (the blender's alpha is the coverage) and
+    /// the CVG_DST update.
+    pub aa_en: bool,
+    pub im_rd: bool,
+    pub clr_on_cvg: bool,
+    pub alpha_cvg_sel: bool,
+    pub cvg_dst: CvgDest,
+    /// othermode_H G_MDSFT_TEXTPERSP = G_TP_PERSP: texcoords are perspective-corrected. Clear
+    /// (G_TP_NONE) draws the run with affine, screen-linear texcoords.
+    pub tex_persp: bool,
+    /// othermode_H color and alpha dither selections. The color dither only shows when the
+    /// renderer quantizes to a 16-bit framebuffer; the alpha dither drives `AlphaCompare::Dither`.
+    pub color_dither: ColorDither,
+    pub alpha_dither: AlphaDither,
 }
 
-/// Decode the RDP othermode low word into a `RenderMode`. `geom` supplies the G_FOG bit.
+/// Decode the RDP othermode low word into a `RenderMode`. `other_mode_h` supplies the cycle type,
+/// the texture-perspective bit and the dither selections; `geom` supplies the G_FOG bit.
 pub fn decode_render_mode(other_mode_l: u32, other_mode_h: u32, geom: u32) -> RenderMode {
     let cycle_type = (other_mode_h >> 20) & 3;
     let blender_mux = ((other_mode_l >> 16) & 0xFFFF) as u16;
@@ -76,9 +149,12 @@ pub fn decode_render_mode(other_mode_l: u32, other_mode_h: u32, geom: u32) -> Re
         blend_class,
         fallback_class,
         non_canonical_blend,
+        fb_blend: non_canonical_blend || blends_in_first_cycle(blender_mux, cycle_type),
         z_test: other_mode_l & Z_CMP != 0,
         z_write: other_mode_l & Z_UPD != 0,
         z_mode,
+        z_source_prim: other_mode_l & G_ZS_PRIM != 0,
+        prim_depth: 0,
         // NOTE: `crate::hle::consts::G_FOG` is retained here (not threaded via GbiConstants) because this
         // is public API consumed cross-crate by the renderer, and the geom-mode G_FOG bit is
         // ucode-invariant across F3D/F3DEX/F3DEX2. Kept in lockstep with the scene-fog read
@@ -86,9 +162,60 @@ pub fn decode_render_mode(other_mode_l: u32, other_mode_h: u32, geom: u32) -> Re
         fog: (geom & crate::hle::consts::G_FOG != 0) || fog_from_mux,
         alpha_compare,
         cvg_x_alpha: other_mode_l & CVG_X_ALPHA != 0,
+        aa_en: other_mode_l & AA_EN != 0,
+        im_rd: other_mode_l & IM_RD != 0,
+        clr_on_cvg: other_mode_l & CLR_ON_CVG != 0,
+        alpha_cvg_sel: other_mode_l & ALPHA_CVG_SEL != 0,
+        cvg_dst: match other_mode_l & CVG_DST_SAVE {
+            CVG_DST_WRAP => CvgDest::Wrap,
+            CVG_DST_FULL => CvgDest::Full,
+            CVG_DST_SAVE => CvgDest::Save,
+            _ => CvgDest::Clamp,
+        },
+        tex_persp: other_mode_h & G_TP_PERSP != 0,
+        color_dither: match other_mode_h & G_CD_DISABLE {
+            G_CD_MAGICSQ => ColorDither::MagicSquare,
+            G_CD_BAYER => ColorDither::Bayer,
+            G_CD_NOISE => ColorDither::Noise,
+            _ => ColorDither::Disable,
+        },
+        alpha_dither: match other_mode_h & G_AD_DISABLE {
+            G_AD_PATTERN => AlphaDither::Pattern,
+            G_AD_NOTPATTERN => AlphaDither::NotPattern,
+            G_AD_NOISE => AlphaDither::Noise,
+            _ => AlphaDither::Disable,
+        },
+    }
+}
+
+impl RenderMode {
+    /// Whether the run reads or shapes coverage — the runs an emulated coverage model has to draw
+    /// through the framebuffer-read blender.
+    pub fn uses_coverage(&self) -> bool {
+        self.aa_en
+            || self.clr_on_cvg
+            || self.alpha_cvg_sel
+            || self.cvg_x_alpha
+            || self.cvg_dst != CvgDest::Clamp
     }
 }
 
+/// Whether a 2-cycle mode's first blender cycle changes the color on its way to the second.
+/// The passthrough (`A=0, M=CLR_IN, B=1`, or `P=M=CLR_IN`) and G_RM_FOG_SHADE_A (the fog mix
+/// every fragment entry applies) don't; neither does a surface mode repeated into cycle 1, whose
+/// CLR_MEM term only weighs in on partially covered edges (left to the optional coverage model).
+fn blends_in_first_cycle(mux: u16, cycle_type: u32) -> bool {
+    if cycle_type != 1 {
+        return false;
+    }
+    let bi = mux as u32;
+    let (p, a, m, b) = ((bi >> 14) & 3, (bi >> 10) & 3, (bi >> 6) & 3, (bi >> 2) & 3);
+    let passthrough = (a == A_0 && m == CLR_IN && b == B_1) || (p == CLR_IN && m == CLR_IN);
+    let fog_shade = p == CLR_FOG && a == A_SHADE && m == CLR_IN && b == B_1MA;
+    let surface = p == CLR_MEM || m == CLR_MEM;
+    !(passthrough || fog_shade || surface)
+}
+
 /// `usesAlphaBlendCycle` predicate.
 /// `all_inputs`: false → blends only if P==CLR_MEM;
 /// true → if (P==CLR_MEM && A!=0) or (M==CLR_MEM && B!=0).
@@ -155,7 +282,7 @@ mod tests {
     use super::*;
     use crate::hle::consts::rdp::{
         gbl_c1, gbl_c2, AC_DITHER, AC_THRESHOLD, A_0, A_IN, B_1, B_1MA, B_A_MEM, CLR_IN, CLR_MEM,
-        FORCE_BL,
+        FORCE_BL, G_ZS_PRIM,
     };
 
     fn rm(l: u32) -> RenderMode {
@@ -191,6 +318,17 @@ mod tests {
         assert!(r.z_test && !r.z_write);
     }
 
+    #[test]
+    fn zs_prim_selects_primitive_depth_source() {
+        assert!(!rm(0x2078).z_source_prim);
+        let r = rm(0x2078 | G_ZS_PRIM);
+        assert!(r.z_source_prim && r.z_test && r.z_write);
+        assert_eq!(
+            r.prim_depth, 0,
+            "the decode never reads the RDP prim depth register"
+        );
+    }
+
     #[test]
     fn tex_edge_sets_cvg_x_alpha() {
         // flags 0x3078 = 0x2078 + CVG_X_ALPHA(0x1000).
@@ -389,4 +527,69 @@ mod tests {
             "additive blend must be flagged for the §4.4 diag"
         );
     }
+
+    #[test]
+    fn texture_perspective_bit_decodes_per_mode() {
+        assert!(!decode_render_mode(0, 0, 0).tex_persp);
+        assert!(decode_render_mode(0, G_TP_PERSP, 0).tex_persp);
+    }
+
+    #[test]
+    fn fb_blend_covers_non_canonical_and_first_cycle_blends() {
+        use crate::hle::consts::rdp::{A_FOG, B_1, CLR_FOG, G_RM_FOG_SHADE_A};
+        let two_cycle = 1 << 20;
+        // Canonical XLU and plain opaque stay on the fixed-function paths.
+        assert!(!rm(G_RM_AA_ZB_XLU_SURF | G_RM_AA_ZB_XLU_SURF2).fb_blend);
+        assert!(!rm(G_RM_AA_ZB_OPA_SURF | G_RM_AA_ZB_OPA_SURF2).fb_blend);
+        // Additive (P + M) is non-canonical.
+        let add =
+            FORCE_BL | gbl_c1(CLR_IN, A_FOG, CLR_MEM, B_1) | gbl_c2(CLR_IN, A_FOG, CLR_MEM, B_1);
+        assert!(rm(add).fb_blend);
+        // 2-cycle: the FOG_SHADE_A mix and a repeated surface mode are covered by the fast paths,
+        // a fog-color-alpha mix in cycle 1 is not.
+        let fog_shade = G_RM_FOG_SHADE_A | G_RM_AA_ZB_OPA_SURF2;
+        assert!(!decode_render_mode(fog_shade, two_cycle, 0).fb_blend);
+        let surf = G_RM_AA_ZB_OPA_SURF | G_RM_AA_ZB_OPA_SURF2;
+        assert!(!decode_render_mode(surf, two_cycle, 0).fb_blend);
+        let fog_prim = gbl_c1(CLR_FOG, A_FOG, CLR_IN, B_1MA) | G_RM_AA_ZB_OPA_SURF2;
+        assert!(decode_render_mode(fog_prim, two_cycle, 0).fb_blend);
+        // The same cycle-1 bits are ignored in 1-cycle mode.
+        assert!(!rm(fog_prim).fb_blend);
+    }
+
+    #[test]
+    fn dither_fields_decode_from_othermode_h() {
+        let r = decode_render_mode(0, 0, 0);
+        assert_eq!(r.color_dither, ColorDither::MagicSquare);
+        assert_eq!(r.alpha_dither, AlphaDither::Pattern);
+        let r = decode_render_mode(0, G_CD_BAYER | G_AD_NOISE, 0);
+        assert_eq!(r.color_dither, ColorDither::Bayer);
+        assert_eq!(r.alpha_dither, AlphaDither::Noise);
+        let r = decode_render_mode(0, G_CD_DISABLE | G_AD_NOTPATTERN, 0);
+        assert_eq!(r.color_dither, ColorDither::Disable);
+        assert_eq!(r.alpha_dither, AlphaDither::NotPattern);
+        assert_eq!(
+            decode_render_mode(0, G_CD_NOISE | G_AD_DISABLE, 0).alpha_dither,
+            AlphaDither::Disable
+        );
+    }
+
+    #[test]
+    fn coverage_fields_decode_from_render_mode() {
+        use crate::hle::consts::rdp::{
+            G_RM_AA_ZB_TEX_EDGE, G_RM_AA_ZB_XLU_SURF, G_RM_CLD_SURF, G_RM_OPA_SURF,
+        };
+        let edge = rm(G_RM_AA_ZB_TEX_EDGE & 0xFFFF);
+        assert!(edge.aa_en && edge.im_rd && edge.alpha_cvg_sel && edge.cvg_x_alpha);
+        assert!(!edge.clr_on_cvg);
+        assert_eq!(edge.cvg_dst, CvgDest::Clamp);
+        let xlu = rm(G_RM_AA_ZB_XLU_SURF & 0xFFFF);
+        assert!(xlu.clr_on_cvg && !xlu.alpha_cvg_sel);
+        assert_eq!(xlu.cvg_dst, CvgDest::Wrap);
+        assert_eq!(rm(G_RM_CLD_SURF & 0xFFFF).cvg_dst, CvgDest::Save);
+        assert!(edge.uses_coverage() && rm(G_RM_CLD_SURF & 0xFFFF).uses_coverage());
+        // OPA_SURF reads memory but never shapes coverage.
+        let opa = rm(G_RM_OPA_SURF & 0xFFFF);
+        assert!(opa.im_rd && !opa.uses_coverage());
+    }
 }
@@ -66,6 +66,23 @@ impl ColorIn {
                 // these (byte-identity guarded by the scene mux-decode regression test).
                 | ColorIn::LodFraction
                 | ColorIn::PrimLodFrac
+                // KEY_CENTER / KEY_SCALE (G_SETKEYR/GB) and K4 / K5 (G_SETCONVERT) wired: the
+                // WGSL color_b / color_c slots read them from the Combiner uniform, which is what
+                // the libultra YUV->RGB combine (TEXEL1 - K4) * K5 + TEXEL1 relies on.
+                | ColorIn::KeyCenter
+                | ColorIn::K4
+                | ColorIn::KeyScale
+                | ColorIn::K5
+                // COMBINED_ALPHA / PRIMITIVE_ALPHA / SHADE_ALPHA / ENV_ALPHA: the color_c slot
+                // broadcasts the alpha to all three channels. NOISE: the color_a slot reads a
+                // per-pixel value re-rolled each frame (`combiner_noise`). With these, every
+                // hardware selector draws; `UnwiredSelector` is left to invalid combinations
+                // (a 1-cycle TEXEL1 reference).
+                | ColorIn::CombinedAlpha
+                | ColorIn::PrimitiveAlpha
+                | ColorIn::ShadeAlpha
+                | ColorIn::EnvAlpha
+                | ColorIn::Noise
         )
     }
 }
@@ -145,15 +162,15 @@ pub fn color_b(idx: u32) -> ColorIn {
 /// True iff `color_a(a_idx)` and `color_b(b_idx)` are PROVABLY the same value for every possible
 /// runtime state, i.e. the `(a - b)` term these two color-combiner slots feed is guaranteed to
 /// cancel to zero. Index equality ALONE is not sufficient: the two mux tables are asymmetric —
-/// `color_a` idx 6 = ONE (constant 1.0) but `color_b` idx 6 = KEY_CENTER (unwired, resolves to
-/// 0.0), so `a_idx == b_idx == 6` must NOT be treated as annulled. Provably equal iff
-/// `a_idx == b_idx` and both are in `0..=5` (the identical runtime source feeds both sides), or
-/// `a_idx` is in `7..=15` and `b_idx` is in `6..=15` (both sides are the constant ZERO). Used by
+/// `color_a` idx 6 = ONE (constant 1.0) but `color_b` idx 6 = KEY_CENTER (the G_SETKEYR/GB
+/// center), and idx 7 is NOISE vs K4 — so `a_idx == b_idx` in `6..=7` must NOT be treated as
+/// annulled. Provably equal iff `a_idx == b_idx` and both are in `0..=5` (the identical runtime
+/// source feeds both sides), or both are in `8..=15` (both sides are the constant ZERO). Used by
 /// the LOD byte-identity regression guard (`tests/goldens.rs`) to decide whether a color-C LOD
 /// selector (idx 13/14) can affect output through a given A/B pair. Test-only.
 #[cfg(test)]
 pub(crate) fn color_ab_provably_equal(a_idx: u32, b_idx: u32) -> bool {
-    (a_idx == b_idx && a_idx <= 5) || (a_idx >= 7 && b_idx >= 6)
+    (a_idx == b_idx && a_idx <= 5) || (a_idx >= 8 && b_idx >= 8)
 }
 
 /// color_c slot: 5-bit index. 16 entries: 0-5 common; 6=KEY_SCALE, 7=COMBINED_ALPHA,
@@ -357,11 +374,63 @@ pub struct Tex1 {
     /// Wrap mode from the TEXEL1 tile (cms/cmt): 0=WRAP 1=MIRROR 2=CLAMP.
     pub wrap_s: u8,
     pub wrap_t: u8,
+    /// Shift/mask/clamp addressing of the TEXEL1 tile.
+    pub tile_addr: TileAddress,
     /// Tile format/size of the TEXEL1 tile (diagnostic; decode is CPU-side).
     pub fmt: u8,
     pub siz: u8,
 }
 
+/// Per-tile texcoord addressing, indexed `[s, t]`: the G_SETTILE shift and mask fields and the
+/// G_SETTILESIZE origin and clamp bound. The wrap/mirror/clamp enables stay in the `wrap_s`/`wrap_t` fields
+/// beside it; `render::tile_words` packs both for the shader.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TileAddress {
+    /// shifts/shiftt: 1..=10 shift the texcoord right, 11..=15 shift it left by `16 - shift`.
+    pub shift: [u8; 2],
+    /// masks/maskt: wrap (or mirror) every `1 << mask` texels. 0 disables wrapping and forces clamp.
+    pub mask: [u8; 2],
+    /// Clamp bound `lr - ul` in 10.2 fixed point (the last reachable texel is `clamp >> 2`).
+    pub clamp: [u16; 2],
+    /// The G_SETTILESIZE upper-left corner (uls, ult) in 10.2 fixed point, subtracted from every
+    /// shifted texcoord before clamp and mask; games scroll textures by animating it.
+    pub origin: [u16; 2],
+}
+
+impl TileAddress {
+    /// The addressing `tile` applies to its texcoords.
+    pub fn from_tile(tile: &crate::hle::rdp::TileDescriptor) -> Self {
+        TileAddress {
+            shift: [tile.shifts, tile.shiftt],
+            mask: [tile.masks, tile.maskt],
+            clamp: [
+                tile.lrs.saturating_sub(tile.uls),
+                tile.lrt.saturating_sub(tile.ult),
+            ],
+            origin: [tile.uls, tile.ult],
+        }
+    }
+
+    /// Unshifted addressing whose wrap period (when `w`/`h` is a power of two) and clamp bound
+    /// both land on the edges of a `w`×`h` texture — the hardware sampler's own behavior.
+    pub fn whole(w: u32, h: u32) -> Self {
+        let mask = |n: u32| {
+            if n.is_power_of_two() {
+                n.trailing_zeros() as u8
+            } else {
+                0
+            }
+        };
+        let clamp = |n: u32| (n.max(1) - 1).min(0x3FF) as u16 * 4;
+        TileAddress {
+            shift: [0; 2],
+            mask: [mask(w), mask(h)],
+            clamp: [clamp(w), clamp(h)],
+            origin: [0; 2],
+        }
+    }
+}
+
 /// N64 hardware maximum LOD level count (the G_TEXTURE `level` field is 3 bits → 0..7, so
 /// `num_levels = level + 1` is at most 8). The decode caps `num_levels` here to match the renderer's
 /// fixed per-level bindings. Kept in sync with `render::MAX_LOD`.
@@ -379,6 +448,18 @@ pub struct MipLevel {
     pub h: u32,
 }
 
+/// Texture filter from othermode_H G_MDSFT_TEXTFILT (bits [13:12]). Copy-cycle draws always
+/// point-sample regardless of the field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextureFilter {
+    /// G_TF_POINT: nearest texel.
+    Point,
+    /// G_TF_BILERP: the RDP's 3-point (triangle) filter, evaluated in the fragment shader.
+    Bilerp,
+    /// G_TF_AVERAGE: 2x2 box average. Approximated with the sampler's standard bilinear filter.
+    Average,
+}
+
 /// The complete material produced by the HLE from the display list.
 /// Carries decoded texture (RGBA8), combiner selectors (for diagnostic), raw combine
 /// words (for the shader), cycle type, prim/env, and whether texture is enabled.
@@ -399,12 +480,27 @@ pub struct Material {
     /// Wrap mode from the render tile (cms/cmt): 0=WRAP 1=MIRROR 2=CLAMP. Consumed by the renderer sampler.
     pub wrap_s: u8,
     pub wrap_t: u8,
+    /// Shift/mask/clamp addressing of the render tile. The renderer keeps the hardware sampler
+    /// only when it reproduces this exactly, and otherwise emulates it in the shader.
+    pub tile_addr: TileAddress,
     /// Tile format/size (diagnostic; decode is CPU-side so the GPU never sees these).
     pub fmt: u8,
     pub siz: u8,
     /// Blend color RGBA (sourced from gsDPSetBlendColor; Phase D dependency).
     /// Defaulted to [0, 0, 0, 255] until B1+ plumbing wires the real RDP register.
     pub blend_color: [u8; 4],
+    /// Combiner K4/K5 inputs: the 9-bit signed G_SETCONVERT coefficients `Rdp::convert[4..6]`.
+    pub k4_k5: [i16; 2],
+    /// Combiner KEY_CENTER / KEY_SCALE inputs (G_SETKEYR/GB), per RGB channel.
+    pub key_center: [u8; 3],
+    pub key_scale: [u8; 3],
+    /// Chroma-key width per channel (4.8 fixed point, G_SETKEYR/GB) and the othermode_H
+    /// G_CK_KEY bit that enables the key test. The shader discards pixels inside the key range.
+    pub key_width: [u16; 3],
+    pub chroma_key: bool,
+    /// Texture filter (othermode_H G_MDSFT_TEXTFILT); picks the renderer's sampler and, for
+    /// `Bilerp`, the shader's 3-point filter.
+    pub tex_filter: TextureFilter,
     /// Tile-count decision: 2 = 2-cycle two-texture (TEXEL1 used), 1 = single texture
     /// (TEXEL0 used), 0 = textureless. TEXEL0 <- tiles[base]; when 2, TEXEL1 <- tiles[(base+1)&7]
     /// (base = the G_TEXTURE render-tile index).
@@ -544,8 +640,8 @@ fn cycle_uses_texel1(sel: &CombinerSelectors, cycle_type: u32) -> bool {
 }
 
 /// Whether `tile` can be decoded through the N64-faithful byte-addressable path
-/// (`Tmem::sample_tile`) rather than the legacy linear `FormatInfo::decode` fallback. The nine
-/// supported formats (six non-paletted, CI4/CI8, RGBA32) qualify; faithful-vs-legacy then depends
+/// (`Tmem::sample_tile`) rather than the legacy linear `FormatInfo::decode` fallback. The ten
+/// supported formats (six non-paletted, CI4/CI8, RGBA32, YUV16) qualify; faithful-vs-legacy then depends
 /// on which load path populated the bank:
 ///   * LoadBlock packs rows contiguously, so the write and read swaps only cancel when each texel
 ///     row is a whole number of 64-bit words — hence the `line_bytes % 8 == 0` gate.
@@ -564,7 +660,7 @@ fn tile_takes_faithful_path(
     let line_bytes = ((tex_w as usize) << tile.siz) >> 1;
     let format_ok = matches!(
         (tile.fmt, tile.siz),
-        (0, 2) | (0, 3) | (4, 1) | (4, 0) | (3, 2) | (3, 1) | (3, 0) | (2, 0) | (2, 1)
+        (0, 2) | (0, 3) | (1, 2) | (4, 1) | (4, 0) | (3, 2) | (3, 1) | (3, 0) | (2, 0) | (2, 1)
     );
     format_ok && (line_bytes.is_multiple_of(8) || rdp.load_via_tile)
 }
@@ -581,7 +677,13 @@ fn decode_tile_texture(
     tlut_fmt: u8,
 ) -> Vec<u8> {
     if tile_takes_faithful_path(rdp, tile, tex_w) {
-        return rdp.tmem_bank.sample_tile(tile, tlut_fmt);
+        let mut texels = rdp.tmem_bank.sample_tile(tile, tlut_fmt);
+        // YUV16 samples raw as [U, V, Y, Y]; the texture unit converts it unless TEXT_CONV is
+        // G_TC_FILT (which passes the raw components through to the combiner).
+        if tile.fmt == 1 && rdp.text_convert() {
+            crate::hle::texdec::convert_yuv(&mut texels, rdp.convert);
+        }
+        return texels;
     }
 
     // Legacy linear fallback (sub-word rows, or formats sample_tile does not handle). The palette
@@ -682,9 +784,16 @@ pub fn build_material(
             tex_enable: false,
             wrap_s: 2,
             wrap_t: 2,
+            tile_addr: TileAddress::whole(1, 1),
             fmt: 0,
             siz: 0,
             blend_color: rdp.blend_color,
+            k4_k5: [rdp.convert[4], rdp.convert[5]],
+            key_center: rdp.key_center,
+            key_scale: rdp.key_scale,
+            key_width: rdp.key_width,
+            chroma_key: rdp.chroma_key(),
+            tex_filter: rdp.texture_filter(),
             tile_count: 0, // textureless
             tex1: None,
             prim_lod_frac: rdp.prim_lod_frac,
@@ -765,6 +874,7 @@ pub fn build_material(
             tex_h: t1_h,
             wrap_s: t1.cms,
             wrap_t: t1.cmt,
+            tile_addr: TileAddress::from_tile(t1),
             fmt: t1.fmt,
             siz: t1.siz,
         })
@@ -840,9 +950,16 @@ pub fn build_material(
         tex_enable,
         wrap_s: tile.cms,
         wrap_t: tile.cmt,
+        tile_addr: TileAddress::from_tile(tile),
         fmt: tile.fmt,
         siz: tile.siz,
         blend_color: rdp.blend_color,
+        k4_k5: [rdp.convert[4], rdp.convert[5]],
+        key_center: rdp.key_center,
+        key_scale: rdp.key_scale,
+        key_width: rdp.key_width,
+        chroma_key: rdp.chroma_key(),
+        tex_filter: rdp.texture_filter(),
         tile_count,
         tex1,
         prim_lod_frac: rdp.prim_lod_frac,
@@ -892,9 +1009,16 @@ pub fn build_rect_material(
         tex_enable: true, // a TEXRECT always samples its tile, regardless of gsSPTexture state
         wrap_s: tile.cms,
         wrap_t: tile.cmt,
+        tile_addr: TileAddress::from_tile(tile),
         fmt: tile.fmt,
         siz: tile.siz,
         blend_color: rdp.blend_color,
+        k4_k5: [rdp.convert[4], rdp.convert[5]],
+        key_center: rdp.key_center,
+        key_scale: rdp.key_scale,
+        key_width: rdp.key_width,
+        chroma_key: rdp.chroma_key(),
+        tex_filter: rdp.texture_filter(),
         // A TEXRECT always samples its single tile — one texture, never a second.
         tile_count: 1,
         tex1: None,
@@ -956,25 +1080,44 @@ mod tests {
         assert_eq!(alpha_c(6), AlphaIn::PrimLodFrac);
     }
 
+    #[test]
+    fn alpha_broadcast_and_noise_selectors_are_wired() {
+        assert_eq!(color_a(7), ColorIn::Noise);
+        assert_eq!(color_c(7), ColorIn::CombinedAlpha);
+        assert_eq!(color_c(10), ColorIn::PrimitiveAlpha);
+        assert_eq!(color_c(11), ColorIn::ShadeAlpha);
+        assert_eq!(color_c(12), ColorIn::EnvAlpha);
+        // (NOISE - ZERO) * COMBINED_ALPHA + ZERO, then (PRIMITIVE - ENVIRONMENT) * SHADE_ALPHA
+        // + ZERO: cyc1 a=L[5,4] c=L[0,5] b=H[24,4] d=H[6,3].
+        let l = (7u32 << 5) | 7;
+        let h = (15u32 << 24) | (7 << 6);
+        assert!(decode_combine(l, h).cyc1.unwired().is_empty());
+        let l = (3u32 << 5) | 11;
+        let h = (5u32 << 24) | (7 << 6);
+        assert!(decode_combine(l, h).cyc1.unwired().is_empty());
+        assert!(ColorIn::EnvAlpha.wired() && ColorIn::PrimitiveAlpha.wired());
+    }
+
     #[test]
     fn color_ab_idx6_pair_is_not_provably_equal_and_guard_flags_it() {
         // Regression for the byte-identity-guard hardening fix. The two color mux tables are
-        // asymmetric at idx 6: color_a(6) = ONE (constant 1.0), color_b(6) = KEY_CENTER, which the
-        // shader resolves to 0.0 (unwired) — see `color_a_rgb`/`color_b_rgb` in
-        // render/combiner_prelude.wgsl. A naive "a_idx == b_idx => annulled" test (the guard's
-        // pre-fix logic) wrongly treated this pair as a byte-identity no-op.
+        // asymmetric at idx 6: color_a(6) = ONE (constant 1.0), color_b(6) = KEY_CENTER, the
+        // G_SETKEYR/GB center — see `color_a_rgb`/`color_b_rgb` in render/combiner_prelude.wgsl.
+        // A naive "a_idx == b_idx => annulled" test (the guard's pre-fix logic) wrongly treated
+        // this pair as a byte-identity no-op.
         assert_eq!(color_a(6), ColorIn::One);
         assert_eq!(color_b(6), ColorIn::KeyCenter);
-        assert!(
-            !ColorIn::KeyCenter.wired(),
-            "KEY_CENTER is unwired => the shader resolves it to the constant 0.0"
-        );
+        // Likewise idx 7 (NOISE vs K4) and a ZERO A against a KEY_CENTER / K4 B.
+        assert!(!color_ab_provably_equal(7, 7));
+        assert!(!color_ab_provably_equal(15, 6));
+        assert!(!color_ab_provably_equal(8, 7));
+        assert!(color_ab_provably_equal(15, 8), "ZERO - ZERO is annulled");
 
         // The corrected, extracted annulment predicate must NOT treat this pair as provably zero.
         assert!(
             !color_ab_provably_equal(6, 6),
             "a_idx == b_idx == 6 must NOT be annulled: color_a(6) = ONE(1.0) != color_b(6) = \
-             KEY_CENTER(0.0)"
+             KEY_CENTER"
         );
 
         // Positive-detection: a synthetic cyc1 combine word with A=6 (ONE), B=6 (KEY_CENTER),
@@ -1097,6 +1240,7 @@ mod tests {
         let mut rec = crate::hle::rsp::PairRec::default();
         let mut dropped = 0u32;
         let mut seen = [false; 256];
+        let mut culled = 0u32;
         let table = crate::hle::gbi::Gbi::<RdramImage>::new(
             crate::hle::gbi::GbiUcode::F3dex2,
             crate::hle::mem::GbiDataFormat::Fixed,
@@ -1120,6 +1264,10 @@ mod tests {
                 rec: &mut rec,
                 dropped_runs: &mut dropped,
                 unknown_seen: &mut seen,
+                branch: None,
+                end_dl: false,
+                culled_dls: &mut culled,
+                switch_ucode: None,
             };
             table[cmd.opcode() as usize](&cmd, &mut cx);
         }
@@ -1154,6 +1302,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn yuv16_tile_decodes_through_set_convert_unless_tc_filt() {
+        // One 8×1 YUV16 row (two words: `U Y0 V Y1` groups) with neutral chroma in the first pair
+        // and a red-ish pair (u = -28, v = +72) next; the rest neutral. Faithful path (8 bytes per
+        // bank row), libultra default coefficients.
+        let mut src = [128u8, 90, 128, 90].repeat(4);
+        src[4..8].copy_from_slice(&[100, 100, 200, 110]);
+        let mut rdp = crate::hle::rdp::Rdp {
+            convert: [175, -43, -89, 222, 114, 42],
+            ..Default::default()
+        };
+        rdp.tmem_bank.write_block(&src, 0, 0, 0, 2, 1, 2);
+        let tile = crate::hle::rdp::TileDescriptor {
+            fmt: 1, // YUV
+            siz: 2,
+            width: 8,
+            height: 1,
+            line: 1,
+            ..Default::default()
+        };
+        assert!(tile_takes_faithful_path(&rdp, &tile, 8));
+
+        let rgb = decode_tile_texture(&rdp, &tile, 8, 1, 0);
+        assert_eq!(&rgb[0..4], &[90, 90, 90, 90], "neutral chroma → grey");
+        assert_eq!(
+            &rgb[8..12],
+            &[198, 59, 51, 100],
+            "texel 2 converts with its pair's U,V"
+        );
+        assert_eq!(
+            &rgb[12..16],
+            &[208, 69, 61, 110],
+            "texel 3 shares the chroma, own Y"
+        );
+
+        // G_TC_FILT bypasses the conversion: the raw [U, V, Y, Y] reaches the combiner.
+        rdp.other_mode_h = crate::hle::consts::rdp::G_TC_FILT;
+        let raw = decode_tile_texture(&rdp, &tile, 8, 1, 0);
+        assert_eq!(&raw[8..16], &[100, 200, 100, 100, 100, 200, 110, 110]);
+    }
+
+    #[test]
+    fn key_and_convert_selectors_are_wired() {
+        assert_eq!(color_b(6), ColorIn::KeyCenter);
+        assert_eq!(color_b(7), ColorIn::K4);
+        assert_eq!(color_c(6), ColorIn::KeyScale);
+        assert_eq!(color_c(15), ColorIn::K5);
+        // libultra G_CC_YUV2RGB: (TEXEL1 - K4) * K5 + TEXEL1 must draw.
+        let l = (2u32 << 5) | 15; // cyc1 a = TEXEL1, c = K5
+        let h = (7u32 << 24) | (2 << 6); // cyc1 b = K4, d = TEXEL1
+        assert!(decode_combine(l, h).cyc1.unwired().is_empty());
+    }
+
+    #[test]
+    fn chroma_key_state_reaches_material() {
+        let rdp = crate::hle::rdp::Rdp {
+            other_mode_h: crate::hle::consts::rdp::G_CK_KEY,
+            key_center: [0xFF, 0x00, 0xFF],
+            key_scale: [0x80, 0x80, 0x80],
+            key_width: [0x100, 0x200, 0x300],
+            ..Default::default()
+        };
+        let rsp = crate::hle::rsp::Rsp::default();
+        let mat = build_rect_material(&rdp, &rsp, &mut Vec::new(), 0);
+        assert!(mat.chroma_key);
+        assert_eq!(mat.key_center, [0xFF, 0x00, 0xFF]);
+        assert_eq!(mat.key_width, [0x100, 0x200, 0x300]);
+        let plain = build_rect_material(&crate::hle::rdp::Rdp::default(), &rsp, &mut Vec::new(), 0);
+        assert!(!plain.chroma_key);
+    }
+
+    #[test]
+    fn tile_address_reads_shift_mask_and_clamp_bounds() {
+        let tile = crate::hle::rdp::TileDescriptor {
+            uls: 8,
+            ult: 4,
+            lrs: 132,
+            lrt: 64,
+            masks: 5,
+            maskt: 4,
+            shifts: 15,
+            shiftt: 1,
+            ..Default::default()
+        };
+        let addr = TileAddress::from_tile(&tile);
+        assert_eq!(addr.shift, [15, 1]);
+        assert_eq!(addr.mask, [5, 4]);
+        assert_eq!(addr.clamp, [124, 60]);
+        assert_eq!(addr.origin, [8, 4]);
+        let whole = TileAddress::whole(32, 6);
+        assert_eq!(whole.mask, [5, 0], "non-power-of-two axes clamp");
+        assert_eq!(whole.clamp, [31 << 2, 5 << 2]);
+        assert_eq!(whole.origin, [0, 0]);
+    }
+
+    #[test]
+    fn texture_filter_reaches_material() {
+        let rdp = crate::hle::rdp::Rdp {
+            other_mode_h: crate::hle::consts::rdp::G_TF_BILERP,
+            ..Default::default()
+        };
+        let rsp = crate::hle::rsp::Rsp::default();
+        let mat = build_rect_material(&rdp, &rsp, &mut Vec::new(), 0);
+        assert_eq!(mat.tex_filter, TextureFilter::Bilerp);
+        let plain = build_rect_material(&crate::hle::rdp::Rdp::default(), &rsp, &mut Vec::new(), 0);
+        assert_eq!(plain.tex_filter, TextureFilter::Point);
+    }
+
     // --- multitexturing CPU half -------------------------------------------------------------
 
     /// Build an Rdp whose faithful TMEM bank holds a 128-byte gradient (byte i = i) as 16 RGBA16
@@ -1174,7 +1430,7 @@ mod tests {
             ..Default::default()
         };
         let data: Vec<u8> = (0..128u32).map(|i| i as u8).collect();
-        rdp.tmem_bank.write_block(&data, 0, 0, 0, 16, 2);
+        rdp.tmem_bank.write_block(&data, 0, 0, 0, 16, 0, 2);
         let mk = |tmem_words: u16| crate::hle::rdp::TileDescriptor {
             fmt: 0, // RGBA
             siz: 2, // 16b → line_bytes = (4<<2)>>1 = 8 (word-aligned → faithful)
@@ -1435,7 +1691,7 @@ mod tests {
             ..Default::default()
         };
         let data: Vec<u8> = (0..128u32).map(|i| i as u8).collect();
-        rdp.tmem_bank.write_block(&data, 0, 0, 0, 16, 2);
+        rdp.tmem_bank.write_block(&data, 0, 0, 0, 16, 0, 2);
         let mk = |w: u16, h: u16, tmem_words: u16| crate::hle::rdp::TileDescriptor {
             fmt: 0, // RGBA
             siz: 2, // 16b
@@ -1570,11 +1826,11 @@ mod tests {
         // The ONLY remaining LOD build-gate constraint after the per-level rework: every level must
         // take the faithful `sample_tile` decode path (a level needing the legacy linear fallback —
         // which ignores `tmem_addr` and could read the wrong bank — forces `lod = false`). Here level
-        // 1 uses a format `sample_tile` does not handle (fmt 5 / YUV), so the gate must reject the set
-        // and fall back to a single level-0, byte-identical to a non-LOD material.
+        // 1 uses a format `sample_tile` does not handle (fmt 5 is not an RDP format), so the gate
+        // must reject the set and fall back to a single level-0, byte-identical to a non-LOD material.
         let mut rdp = rdp_three_level_chain(false);
         rdp.load_via_tile = false; // force the LoadBlock alignment path for the fallback decision
-        rdp.tiles[1].fmt = 5; // YUV — not one of the nine faithful formats
+        rdp.tiles[1].fmt = 5; // not one of the ten faithful formats
         let mut rsp = crate::hle::rsp::Rsp::default();
         rsp.texture_state.tile = 0;
         rsp.texture_state.level = 2;
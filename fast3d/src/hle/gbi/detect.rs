@@ -1,41 +1,216 @@
-//! Microcode-hash → GBI detection. SCAFFOLD: our consumers emit pre-decoded DLs, so there
-//! is NO live caller. The streaming-hash DB lands with a
-//! real-ROM consumer (sarchar/n64, wafel real-ROM). Until then: a pure (hash → ucode) table.
+//! Microcode image → GBI detection. Detection is string-based: every Nintendo graphics microcode
+//! carries an ident string in its data segment (`"RSP Gfx ucode F3DEX       fifo 2.08 Yoshitaka
+//! Yasumoto 1999 Nintendo."`, or `"RSP SW Version: 2.0D, 04-01-96"` for F3D), and `identify`
+//! parses it. No image hashes are pinned; `ucode_hash` is FNV-1a over a loaded image — the
+//! `UCODE_TEXT_SIZE` text bytes, then the `UCODE_DATA_SIZE` data bytes — and only names an
+//! unrecognized image in its diagnostic.
 //!
 //! No float entry exists by design: the RSP is fixed-point, so there is no float microcode image
-//! to hash. `GBI_FLOATS` is a PC-port data-format choice set explicitly via `set_data_format`, not
-//! a detectable microcode — do not add a "float microcode" fixture here.
+//! to identify. `GBI_FLOATS` is a PC-port data-format choice set explicitly via `set_data_format`,
+//! not a detectable microcode — do not add a "float microcode" fixture here.
 use super::GbiUcode;
+use crate::hle::mem::{GbiDataFormat, Rdram};
+use std::borrow::Cow;
 
-/// Map a known RSP-microcode hash to its GBI variant. `None` if unknown.
-pub fn detect_from_ucode_hash(hash: u64) -> Option<GbiUcode> {
-    KNOWN.iter().find(|(h, _)| *h == hash).map(|(_, u)| *u)
+/// Bytes of microcode text hashed for detection: libultra's `SP_UCODE_SIZE` IMEM image.
+pub(crate) const UCODE_TEXT_SIZE: u64 = 0x1000;
+/// Bytes of microcode data hashed for detection: libultra's `SP_UCODE_DATA_SIZE`.
+pub(crate) const UCODE_DATA_SIZE: u64 = 0x800;
+
+/// A recognized microcode build.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct UcodeBuild {
+    pub(crate) ucode: GbiUcode,
+    pub(crate) data_format: GbiDataFormat,
+    /// Build name, e.g. `"F3DEX2.fifo 2.08"`.
+    pub(crate) build: Cow<'static, str>,
+}
+
+/// `len` bytes at physical `addr`, or nothing when the range runs past RDRAM.
+fn segment<M: Rdram>(mem: &M, addr: u64, len: u64) -> Cow<'_, [u8]> {
+    if mem.in_bounds(addr, len) {
+        mem.read_bytes(addr, len as usize)
+    } else {
+        Cow::Borrowed(&[])
+    }
+}
+
+/// FNV-1a over the microcode text at physical `text` followed by its data at `data`. A segment
+/// that runs past RDRAM contributes no bytes.
+pub(crate) fn ucode_hash<M: Rdram>(mem: &M, text: u64, data: u64) -> u64 {
+    let text = segment(mem, text, UCODE_TEXT_SIZE);
+    let data = segment(mem, data, UCODE_DATA_SIZE);
+    text.iter()
+        .chain(data.iter())
+        .fold(0xCBF2_9CE4_8422_2325, |h, &b| {
+            (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+        })
+}
+
+/// Identify the microcode image at `text`/`data` by its data segment's ident string. `Err`
+/// carries the image hash for the `UnknownMicrocode` / `UnknownTaskMicrocode` diagnostic.
+pub(crate) fn identify<M: Rdram>(mem: &M, text: u64, data: u64) -> Result<UcodeBuild, u64> {
+    ident_build(&segment(mem, data, UCODE_DATA_SIZE)).ok_or_else(|| ucode_hash(mem, text, data))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
 }
 
-/// Hand-written fixtures. Replace with real microcode hashes when a real-ROM consumer exists.
-const KNOWN: &[(u64, GbiUcode)] = &[
-    (0xF3D2_0000_0000_0001, GbiUcode::F3dex2),
-    (0xF3D0_0000_0000_0003, GbiUcode::F3d),
-];
+/// Parse a data segment's ident string. Gfx ucode idents read `<name>[.<flavor>] [fifo|xbus]
+/// <version> …`; a 2.x version is the F3DEX2-family build of the same name. The 1.x L3DEX and
+/// other builds without a handler table are not recognized.
+fn ident_build(data: &[u8]) -> Option<UcodeBuild> {
+    const F3D: &[u8] = b"RSP SW Version: ";
+    const GFX: &[u8] = b"RSP Gfx ucode ";
+    let tail = |at: usize| {
+        let rest = &data[at..];
+        let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        std::str::from_utf8(&rest[..end]).ok()
+    };
+    if let Some(at) = find(data, F3D) {
+        let version = tail(at + F3D.len())?.split([',', ' ']).next()?;
+        return Some(UcodeBuild {
+            ucode: GbiUcode::F3d,
+            data_format: GbiDataFormat::Fixed,
+            build: Cow::Owned(format!("F3D {version}")),
+        });
+    }
+    let mut words = tail(find(data, GFX)? + GFX.len())?.split_ascii_whitespace();
+    let name = words.next()?;
+    let mut version = words.next()?;
+    let bus = if version.starts_with(|c: char| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(std::mem::replace(&mut version, words.next()?))
+    };
+    let v2 = version.split('.').next()?.parse::<u32>().ok()? >= 2;
+    let (base, flavor) = name
+        .split_once('.')
+        .map_or((name, None), |(b, f)| (b, Some(f)));
+    let ucode = match (base, v2) {
+        ("F3DEX" | "F3DLX" | "F3DLP", true) => GbiUcode::F3dex2,
+        ("F3DZEX", true) => GbiUcode::F3dzex2,
+        ("L3DEX", true) => GbiUcode::L3dex2,
+        ("F3DEX" | "F3DLX" | "F3DLP", false) => GbiUcode::F3dex,
+        ("S2DEX", true) => GbiUcode::S2dex2,
+        ("S2DEX", false) => GbiUcode::S2dex,
+        _ => return None,
+    };
+    let mut build = base.to_string();
+    if v2 {
+        build.push('2');
+    }
+    for part in [flavor, bus].into_iter().flatten() {
+        build.push('.');
+        build.push_str(part);
+    }
+    Some(UcodeBuild {
+        ucode,
+        data_format: GbiDataFormat::Fixed,
+        build: Cow::Owned(format!("{build} {version}")),
+    })
+}
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::hle::mem::RdramImage;
+
+    /// Ident string of each variant's stock build, and the build name `identify` derives from it.
+    const IDENTS: &[(GbiUcode, &[u8], &str)] = &[
+        (
+            GbiUcode::F3dex2,
+            b"RSP Gfx ucode F3DEX       fifo 2.08  Yoshitaka Yasumoto 1999 Nintendo.\0",
+            "F3DEX2.fifo 2.08",
+        ),
+        (
+            GbiUcode::F3dzex2,
+            b"RSP Gfx ucode F3DZEX.NoN  fifo 2.06H Yoshitaka Yasumoto 1998 Nintendo.\0",
+            "F3DZEX2.NoN.fifo 2.06H",
+        ),
+        (
+            GbiUcode::L3dex2,
+            b"RSP Gfx ucode L3DEX       fifo 2.08  Yoshitaka Yasumoto 1999 Nintendo.\0",
+            "L3DEX2.fifo 2.08",
+        ),
+        (
+            GbiUcode::F3dex,
+            b"RSP Gfx ucode F3DEX       1.23 Yoshitaka Yasumoto 1997 Nintendo.\0",
+            "F3DEX 1.23",
+        ),
+        (
+            GbiUcode::S2dex2,
+            b"RSP Gfx ucode S2DEX  fifo 2.05  Yoshitaka Yasumoto 1998 Nintendo.\0",
+            "S2DEX2.fifo 2.05",
+        ),
+        (
+            GbiUcode::S2dex,
+            b"RSP Gfx ucode S2DEX       1.06 Yoshitaka Yasumoto 1997 Nintendo.\0",
+            "S2DEX 1.06",
+        ),
+        (
+            GbiUcode::F3d,
+            b"RSP SW Version: 2.0D, 04-01-96\0",
+            "F3D 2.0D",
+        ),
+    ];
+
+    /// Synthetic microcode image for `ucode`: `UCODE_TEXT_SIZE` text bytes then
+    /// `UCODE_DATA_SIZE` data bytes, with the variant's stock ident string in the data segment.
+    pub(crate) fn fixture_ucode(ucode: GbiUcode) -> Vec<u8> {
+        let &(_, ident, _) = IDENTS
+            .iter()
+            .find(|e| e.0 == ucode)
+            .expect("every variant has an ident");
+        image_with_data(ident)
+    }
+
+    fn image_with_data(ident: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x5A; (UCODE_TEXT_SIZE + UCODE_DATA_SIZE) as usize];
+        let at = UCODE_TEXT_SIZE as usize + 0x130;
+        bytes[at..at + ident.len()].copy_from_slice(ident);
+        bytes
+    }
+
+    fn identify_image(bytes: &[u8]) -> Result<UcodeBuild, u64> {
+        identify(&RdramImage::new(bytes), 0, UCODE_TEXT_SIZE)
+    }
+
+    #[test]
+    fn fixtures_identify_at_any_address() {
+        for &(ucode, _, build) in IDENTS {
+            let mut bytes = vec![0; 0x100];
+            bytes.extend(fixture_ucode(ucode));
+            let mem = RdramImage::new(&bytes);
+            let found = identify(&mem, 0x100, 0x1100).expect(build);
+            assert_eq!((found.ucode, &*found.build), (ucode, build));
+        }
+    }
+
+    #[test]
+    fn segments_past_rdram_hash_as_empty() {
+        let bytes = fixture_ucode(GbiUcode::F3dex2);
+        let mem = RdramImage::new(&bytes);
+        // Text at 0x1000 would need 0x2000 bytes; data at 0x1400 would need 0x1C00.
+        assert_eq!(ucode_hash(&mem, 0x1000, 0x1400), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(identify(&mem, 0x1000, 0x1400), Err(0xCBF2_9CE4_8422_2325));
+    }
 
     #[test]
-    fn known_hashes_resolve() {
-        assert_eq!(
-            detect_from_ucode_hash(0xF3D2_0000_0000_0001),
-            Some(GbiUcode::F3dex2)
-        );
-        assert_eq!(
-            detect_from_ucode_hash(0xF3D0_0000_0000_0003),
-            Some(GbiUcode::F3d)
-        );
+    fn ident_strings_name_the_build() {
+        for &(ucode, ident, build) in IDENTS {
+            let found = identify_image(&image_with_data(ident)).expect(build);
+            assert_eq!((found.ucode, &*found.build), (ucode, build));
+            assert_eq!(found.data_format, GbiDataFormat::Fixed);
+        }
     }
 
     #[test]
-    fn unknown_hash_is_none() {
-        assert_eq!(detect_from_ucode_hash(0xDEAD_BEEF), None);
+    fn unrecognized_idents_report_the_hash() {
+        let line = image_with_data(b"RSP Gfx ucode L3DEX       fifo 1.23  Yoshitaka Yasumoto\0");
+        let expected = ucode_hash(&RdramImage::new(&line), 0, UCODE_TEXT_SIZE);
+        assert_eq!(identify_image(&line), Err(expected));
+        assert!(identify_image(&image_with_data(b"no ident here")).is_err());
     }
 }
@@ -16,6 +16,7 @@ pub(crate) const F3D_CONSTS: GbiConstants = GbiConstants {
     g_mw_segment: rsp_f3d::G_MW_SEGMENT,
     g_mw_perspnorm: rsp_f3d::G_MW_PERSPNORM,
     g_mw_clip: rsp_f3d::G_MW_CLIP,
+    g_mw_matrix: rsp_f3d::G_MW_MATRIX,
     g_mw_numlight: rsp_f3d::G_MW_NUMLIGHT,
     g_mw_fog: rsp_f3d::G_MW_FOG,
     g_fog_geom: rsp_f3d::G_FOG,
@@ -23,9 +24,12 @@ pub(crate) const F3D_CONSTS: GbiConstants = GbiConstants {
     g_lighting: rsp_f3d::G_LIGHTING,
     g_texture_gen: rsp_f3d::G_TEXTURE_GEN,
     g_texture_gen_linear: rsp_f3d::G_TEXTURE_GEN_LINEAR,
+    g_lighting_positional: 0,
     g_cull_front: rsp_f3d::G_CULL_FRONT,
     g_cull_back: rsp_f3d::G_CULL_BACK,
     g_cull_both: rsp_f3d::G_CULL_BOTH,
+    g_shading_smooth: rsp_f3d::G_SHADING_SMOOTH,
+    flat_provoking_last: true,
 };
 
 pub(crate) fn install_f3d<M: Rdram>(table: &mut [Handler<M>; 256]) {
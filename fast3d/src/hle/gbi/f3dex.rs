@@ -0,0 +1,39 @@
+//! F3DEX 1.x microcode constants and dispatch table. F3DEX shares F3D's symbolic constants; its
+//! differences are all in command encodings, handled by `rsp_f3dex`.
+use crate::hle::consts::rsp_f3dex;
+use crate::hle::gbi::GbiConstants;
+use crate::hle::interp::Handler;
+use crate::hle::mem::Rdram;
+
+pub(crate) const F3DEX_CONSTS: GbiConstants = GbiConstants {
+    g_dl: rsp_f3dex::G_DL,
+    g_enddl: rsp_f3dex::G_ENDDL,
+    mtx_param_xor: 0x00,
+    g_mtx_projection: rsp_f3dex::G_MTX_PROJECTION,
+    g_mtx_load: rsp_f3dex::G_MTX_LOAD,
+    g_mtx_push: rsp_f3dex::G_MTX_PUSH,
+    g_mv_viewport: rsp_f3dex::G_MV_VIEWPORT,
+    g_mv_light: rsp_f3dex::G_MV_LIGHT,
+    g_mw_segment: rsp_f3dex::G_MW_SEGMENT,
+    g_mw_perspnorm: rsp_f3dex::G_MW_PERSPNORM,
+    g_mw_clip: rsp_f3dex::G_MW_CLIP,
+    g_mw_matrix: rsp_f3dex::G_MW_MATRIX,
+    g_mw_numlight: rsp_f3dex::G_MW_NUMLIGHT,
+    g_mw_fog: rsp_f3dex::G_MW_FOG,
+    g_fog_geom: rsp_f3dex::G_FOG,
+    g_clipping: rsp_f3dex::G_CLIPPING,
+    g_lighting: rsp_f3dex::G_LIGHTING,
+    g_texture_gen: rsp_f3dex::G_TEXTURE_GEN,
+    g_texture_gen_linear: rsp_f3dex::G_TEXTURE_GEN_LINEAR,
+    g_lighting_positional: 0,
+    g_cull_front: rsp_f3dex::G_CULL_FRONT,
+    g_cull_back: rsp_f3dex::G_CULL_BACK,
+    g_cull_both: rsp_f3dex::G_CULL_BOTH,
+    g_shading_smooth: rsp_f3dex::G_SHADING_SMOOTH,
+    flat_provoking_last: true,
+};
+
+pub(crate) fn install_f3dex<M: Rdram>(table: &mut [Handler<M>; 256]) {
+    crate::hle::rdp::install_defaults(table);
+    crate::hle::rsp_f3dex::install_overrides(table);
+}
@@ -15,6 +15,7 @@ pub(crate) const F3DEX2_CONSTS: GbiConstants = GbiConstants {
     g_mw_segment: crate::hle::consts::G_MW_SEGMENT,
     g_mw_perspnorm: crate::hle::consts::G_MW_PERSPNORM,
     g_mw_clip: crate::hle::consts::G_MW_CLIP,
+    g_mw_matrix: crate::hle::consts::G_MW_MATRIX,
     g_mw_numlight: crate::hle::consts::G_MW_NUMLIGHT,
     g_mw_fog: crate::hle::consts::G_MW_FOG,
     g_fog_geom: crate::hle::consts::G_FOG,
@@ -22,9 +23,12 @@ pub(crate) const F3DEX2_CONSTS: GbiConstants = GbiConstants {
     g_lighting: crate::hle::consts::G_LIGHTING,
     g_texture_gen: crate::hle::consts::G_TEXTURE_GEN,
     g_texture_gen_linear: crate::hle::consts::G_TEXTURE_GEN_LINEAR,
+    g_lighting_positional: 0,
     g_cull_front: crate::hle::consts::G_CULL_FRONT,
     g_cull_back: crate::hle::consts::G_CULL_BACK,
     g_cull_both: crate::hle::consts::G_CULL_BOTH,
+    g_shading_smooth: crate::hle::consts::G_SHADING_SMOOTH,
+    flat_provoking_last: false,
 };
 
 /// Install the F3DEX2 opcode→handler table: RDP defaults first, then RSP overrides (overrides win).
@@ -0,0 +1,16 @@
+//! F3DZEX2 microcode install: F3DEX2's constants and table plus the OoT/MM additions.
+use crate::hle::consts::rsp_f3dzex2;
+use crate::hle::gbi::GbiConstants;
+use crate::hle::interp::Handler;
+use crate::hle::mem::Rdram;
+
+pub(crate) const F3DZEX2_CONSTS: GbiConstants = GbiConstants {
+    g_lighting_positional: rsp_f3dzex2::G_LIGHTING_POSITIONAL,
+    ..super::f3dex2::F3DEX2_CONSTS
+};
+
+/// Install the F3DZEX2 table: F3DEX2's RDP defaults and RSP overrides, then the F3DZEX2 deltas.
+pub(crate) fn install_f3dzex2<M: Rdram>(table: &mut [Handler<M>; 256]) {
+    crate::hle::rdp::install_defaults(table);
+    crate::hle::rsp_f3dzex2::install_overrides(table);
+}
@@ -0,0 +1,12 @@
+//! L3DEX2 microcode install: F3DEX2's constants, with lines in place of triangles.
+use crate::hle::gbi::GbiConstants;
+use crate::hle::interp::Handler;
+use crate::hle::mem::Rdram;
+
+pub(crate) const L3DEX2_CONSTS: GbiConstants = super::f3dex2::F3DEX2_CONSTS;
+
+/// Install the L3DEX2 table: RDP defaults, then F3DEX2's RSP overrides with the line deltas.
+pub(crate) fn install_l3dex2<M: Rdram>(table: &mut [Handler<M>; 256]) {
+    crate::hle::rdp::install_defaults(table);
+    crate::hle::rsp_l3dex2::install_overrides(table);
+}
@@ -7,8 +7,14 @@ use crate::hle::mem::{GbiDataFormat, Rdram};
 
 mod detect;
 mod f3d;
+mod f3dex;
 pub(crate) mod f3dex2;
-pub use detect::detect_from_ucode_hash;
+mod f3dzex2;
+mod l3dex2;
+mod s2dex;
+pub(crate) use detect::{identify, ucode_hash, UcodeBuild};
+#[cfg(test)]
+pub(crate) use detect::{tests::fixture_ucode, UCODE_TEXT_SIZE};
 
 /// Per-ucode symbolic constants. `Copy`, M-free. Every field except `mtx_param_xor`
 /// is sourced from the selected microcode's constants module; original F3D supplies its
@@ -26,6 +32,7 @@ pub struct GbiConstants {
     pub g_mw_segment: u8,
     pub g_mw_perspnorm: u8,
     pub g_mw_clip: u8,
+    pub g_mw_matrix: u8,
     pub g_mw_numlight: u8,
     pub g_mw_fog: u8,
     /// Geometry-mode G_FOG bit. Read at the scene-fog site (interp.rs). NOTE: the per-render-mode
@@ -36,9 +43,17 @@ pub struct GbiConstants {
     pub g_lighting: u32,
     pub g_texture_gen: u32,
     pub g_texture_gen_linear: u32,
+    /// Geometry-mode bit turning lights with a non-zero constant attenuation into point lights;
+    /// 0 on microcodes without positional lighting.
+    pub g_lighting_positional: u32,
     pub g_cull_front: u32,
     pub g_cull_back: u32,
     pub g_cull_both: u32,
+    /// Geometry-mode bit selecting Gouraud shading; when clear, triangles are flat-shaded.
+    pub g_shading_smooth: u32,
+    /// Flat-shaded triangles take the last vertex's color (F3D family) rather than the first
+    /// (F3DEX2 family).
+    pub flat_provoking_last: bool,
 }
 
 /// Identity of a microcode variant. M-free.
@@ -49,6 +64,17 @@ pub enum GbiUcode {
     F3dex2,
     /// Original F3D microcode.
     F3d,
+    /// F3DEX 1.x: the F3D opcode map with a 32-entry vertex cache and F3DEX's own encodings.
+    F3dex,
+    /// F3DZEX2 (OoT/MM): F3DEX2's opcode map plus G_DMA_IO/G_SPECIAL_*, a `vtx*5` G_BRANCH_Z
+    /// and positional point lights.
+    F3dzex2,
+    /// L3DEX2 line microcode: F3DEX2's opcode map with G_LINE3D in place of the triangles.
+    L3dex2,
+    /// S2DEX 1.x sprite microcode: F3D's opcode map with object/background commands.
+    S2dex,
+    /// S2DEX2 sprite microcode: F3DEX2's opcode map with object/background commands.
+    S2dex2,
 }
 
 impl GbiUcode {
@@ -56,6 +82,11 @@ impl GbiUcode {
         match self {
             GbiUcode::F3dex2 => f3dex2::install_f3dex2(table),
             GbiUcode::F3d => f3d::install_f3d(table),
+            GbiUcode::F3dex => f3dex::install_f3dex(table),
+            GbiUcode::F3dzex2 => f3dzex2::install_f3dzex2(table),
+            GbiUcode::L3dex2 => l3dex2::install_l3dex2(table),
+            GbiUcode::S2dex => s2dex::install_s2dex(table),
+            GbiUcode::S2dex2 => s2dex::install_s2dex2(table),
         }
     }
 
@@ -63,6 +94,11 @@ impl GbiUcode {
         match self {
             GbiUcode::F3dex2 => f3dex2::F3DEX2_CONSTS,
             GbiUcode::F3d => f3d::F3D_CONSTS,
+            GbiUcode::F3dex => f3dex::F3DEX_CONSTS,
+            GbiUcode::F3dzex2 => f3dzex2::F3DZEX2_CONSTS,
+            GbiUcode::L3dex2 => l3dex2::L3DEX2_CONSTS,
+            GbiUcode::S2dex => s2dex::S2DEX_CONSTS,
+            GbiUcode::S2dex2 => s2dex::S2DEX2_CONSTS,
         }
     }
 }
@@ -119,6 +155,9 @@ mod tests {
         assert_eq!(c.g_cull_back, crate::hle::consts::G_CULL_BACK);
         assert_eq!(c.g_cull_both, crate::hle::consts::G_CULL_BOTH);
         assert_eq!(c.g_mw_clip, crate::hle::consts::G_MW_CLIP);
+        assert_eq!(c.g_mw_matrix, crate::hle::consts::G_MW_MATRIX);
+        assert_eq!(c.g_shading_smooth, crate::hle::consts::G_SHADING_SMOOTH);
+        assert!(!c.flat_provoking_last);
     }
 
     #[test]
@@ -137,6 +176,7 @@ mod tests {
         assert_eq!(c.g_mw_segment, f3d::G_MW_SEGMENT);
         assert_eq!(c.g_mw_perspnorm, f3d::G_MW_PERSPNORM);
         assert_eq!(c.g_mw_clip, f3d::G_MW_CLIP);
+        assert_eq!(c.g_mw_matrix, f3d::G_MW_MATRIX);
         assert_eq!(c.g_mw_numlight, f3d::G_MW_NUMLIGHT);
         assert_eq!(c.g_mw_fog, f3d::G_MW_FOG);
         assert_eq!(c.g_fog_geom, f3d::G_FOG);
@@ -147,6 +187,8 @@ mod tests {
         assert_eq!(c.g_cull_front, f3d::G_CULL_FRONT);
         assert_eq!(c.g_cull_back, f3d::G_CULL_BACK);
         assert_eq!(c.g_cull_both, f3d::G_CULL_BOTH);
+        assert_eq!(c.g_shading_smooth, f3d::G_SHADING_SMOOTH);
+        assert!(c.flat_provoking_last);
 
         assert_eq!(c.g_dl, 0x06);
         assert_eq!(c.g_cull_front, 0x0000_1000);
@@ -259,4 +301,186 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn f3dex_consts_are_f3d_consts() {
+        // F3DEX 1.x changed command encodings, not the symbolic vocabulary.
+        assert_eq!(GbiUcode::F3dex.constants(), GbiUcode::F3d.constants());
+    }
+
+    #[test]
+    fn f3dex_table_extends_f3d() {
+        use crate::hle::consts::rsp_f3dex;
+
+        let built = Gbi::<RdramImage<'static>>::new(GbiUcode::F3dex, GbiDataFormat::Fixed).table;
+        let f3d = Gbi::<RdramImage<'static>>::new(GbiUcode::F3d, GbiDataFormat::Fixed).table;
+        let unknown = unknown::<RdramImage<'static>> as Handler<RdramImage<'static>>;
+
+        // Encoding-specific slots get F3DEX handlers.
+        for op in [rsp_f3dex::G_VTX, rsp_f3dex::G_TRI1, rsp_f3dex::G_RDPHALF_1] {
+            assert_ne!(
+                built[op as usize] as usize, f3d[op as usize] as usize,
+                "slot 0x{op:02X}"
+            );
+        }
+        // F3DEX-only commands are installed where F3D has nothing.
+        for op in [
+            rsp_f3dex::G_MODIFYVTX,
+            rsp_f3dex::G_TRI2,
+            rsp_f3dex::G_BRANCH_Z,
+            rsp_f3dex::G_LOAD_UCODE,
+        ] {
+            assert_eq!(
+                f3d[op as usize] as usize, unknown as usize,
+                "slot 0x{op:02X}"
+            );
+            assert_ne!(
+                built[op as usize] as usize, unknown as usize,
+                "slot 0x{op:02X}"
+            );
+        }
+        // Everything else is shared with F3D.
+        for op in [
+            rsp_f3dex::G_MTX,
+            rsp_f3dex::G_MOVEMEM,
+            rsp_f3dex::G_MOVEWORD,
+            rsp_f3dex::G_TEXTURE,
+            rsp_f3dex::G_POPMTX,
+            rsp_f3dex::G_SETGEOMETRYMODE,
+            rsp_f3dex::G_CLEARGEOMETRYMODE,
+        ] {
+            assert_eq!(
+                built[op as usize] as usize, f3d[op as usize] as usize,
+                "slot 0x{op:02X}"
+            );
+        }
+    }
+
+    #[test]
+    fn f3dzex2_extends_the_f3dex2_table() {
+        use crate::hle::consts::rsp_f3dzex2 as zex;
+
+        let c = GbiUcode::F3dzex2.constants();
+        assert_eq!(c.g_lighting_positional, zex::G_LIGHTING_POSITIONAL);
+        assert_eq!(
+            GbiConstants {
+                g_lighting_positional: 0,
+                ..c
+            },
+            GbiUcode::F3dex2.constants()
+        );
+
+        let unknown = unknown::<RdramImage<'static>> as Handler<RdramImage<'static>>;
+        let f3dzex2 =
+            Gbi::<RdramImage<'static>>::new(GbiUcode::F3dzex2, GbiDataFormat::Fixed).table;
+        let f3dex2 = Gbi::<RdramImage<'static>>::new(GbiUcode::F3dex2, GbiDataFormat::Fixed).table;
+        let added = [
+            zex::G_DMA_IO,
+            zex::G_SPECIAL_1,
+            zex::G_SPECIAL_2,
+            zex::G_SPECIAL_3,
+        ];
+        for op in 0..=255u8 {
+            if added.contains(&op) {
+                assert_eq!(f3dex2[op as usize] as usize, unknown as usize, "0x{op:02X}");
+                assert_ne!(
+                    f3dzex2[op as usize] as usize, unknown as usize,
+                    "0x{op:02X}"
+                );
+            } else if op == zex::G_BRANCH_Z {
+                assert_ne!(f3dzex2[op as usize] as usize, f3dex2[op as usize] as usize);
+            } else {
+                assert_eq!(
+                    f3dzex2[op as usize] as usize, f3dex2[op as usize] as usize,
+                    "0x{op:02X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn l3dex2_swaps_triangles_for_lines() {
+        use crate::hle::consts::rsp_l3dex2 as l3d;
+
+        assert_eq!(GbiUcode::L3dex2.constants(), GbiUcode::F3dex2.constants());
+        let unknown = unknown::<RdramImage<'static>> as Handler<RdramImage<'static>>;
+        let l3dex2 = Gbi::<RdramImage<'static>>::new(GbiUcode::L3dex2, GbiDataFormat::Fixed).table;
+        let f3dex2 = Gbi::<RdramImage<'static>>::new(GbiUcode::F3dex2, GbiDataFormat::Fixed).table;
+        for op in 0..=255u8 {
+            match op {
+                l3d::G_TRI1 | l3d::G_TRI2 => {
+                    assert_eq!(l3dex2[op as usize] as usize, unknown as usize, "0x{op:02X}")
+                }
+                l3d::G_LINE3D => {
+                    assert_eq!(f3dex2[op as usize] as usize, unknown as usize);
+                    assert_ne!(l3dex2[op as usize] as usize, unknown as usize);
+                }
+                _ => assert_eq!(
+                    l3dex2[op as usize] as usize, f3dex2[op as usize] as usize,
+                    "0x{op:02X}"
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn s2dex_tables_replace_geometry_with_object_commands() {
+        use crate::hle::consts::{rsp_f3d, rsp_f3dex2, rsp_s2dex, rsp_s2dex2};
+
+        assert_eq!(GbiUcode::S2dex.constants(), GbiUcode::F3d.constants());
+        assert_eq!(GbiUcode::S2dex2.constants(), GbiUcode::F3dex2.constants());
+
+        let unknown = unknown::<RdramImage<'static>> as Handler<RdramImage<'static>>;
+        let s2dex = Gbi::<RdramImage<'static>>::new(GbiUcode::S2dex, GbiDataFormat::Fixed).table;
+        let f3d = Gbi::<RdramImage<'static>>::new(GbiUcode::F3d, GbiDataFormat::Fixed).table;
+        for op in [
+            rsp_s2dex::G_BG_1CYC,
+            rsp_s2dex::G_BG_COPY,
+            rsp_s2dex::G_OBJ_RECTANGLE,
+            rsp_s2dex::G_OBJ_SPRITE,
+            rsp_s2dex::G_OBJ_MOVEMEM,
+            rsp_s2dex::G_OBJ_LOADTXTR,
+        ] {
+            assert_ne!(s2dex[op as usize] as usize, unknown as usize, "0x{op:02X}");
+            assert_ne!(
+                s2dex[op as usize] as usize, f3d[op as usize] as usize,
+                "0x{op:02X}"
+            );
+        }
+        for op in [rsp_f3d::G_TRI1, rsp_f3d::G_QUAD, rsp_f3d::G_TEXTURE] {
+            assert_eq!(s2dex[op as usize] as usize, unknown as usize, "0x{op:02X}");
+        }
+        assert_eq!(
+            s2dex[rsp_f3d::G_MOVEWORD as usize] as usize,
+            f3d[rsp_f3d::G_MOVEWORD as usize] as usize
+        );
+
+        let s2dex2 = Gbi::<RdramImage<'static>>::new(GbiUcode::S2dex2, GbiDataFormat::Fixed).table;
+        let f3dex2 = Gbi::<RdramImage<'static>>::new(GbiUcode::F3dex2, GbiDataFormat::Fixed).table;
+        for op in [
+            rsp_s2dex2::G_BG_1CYC,
+            rsp_s2dex2::G_BG_COPY,
+            rsp_s2dex2::G_OBJ_RECTANGLE,
+            rsp_s2dex2::G_OBJ_SPRITE,
+            rsp_s2dex2::G_OBJ_MOVEMEM,
+            rsp_s2dex2::G_OBJ_LOADTXTR,
+        ] {
+            assert_ne!(s2dex2[op as usize] as usize, unknown as usize, "0x{op:02X}");
+            assert_ne!(
+                s2dex2[op as usize] as usize, f3dex2[op as usize] as usize,
+                "0x{op:02X}"
+            );
+        }
+        for op in [
+            rsp_f3dex2::G_GEOMETRYMODE,
+            rsp_f3dex2::G_TEXTURE,
+            rsp_f3dex2::G_CULLDL,
+        ] {
+            assert_eq!(s2dex2[op as usize] as usize, unknown as usize, "0x{op:02X}");
+        }
+        assert_eq!(
+            s2dex2[rsp_f3dex2::G_MOVEWORD as usize] as usize,
+            f3dex2[rsp_f3dex2::G_MOVEWORD as usize] as usize
+        );
+    }
 }
@@ -0,0 +1,19 @@
+//! S2DEX / S2DEX2 sprite microcode constants and dispatch tables. Each shares the symbolic
+//! constants of the 3D microcode whose opcode map it extends (F3D / F3DEX2); its object and
+//! background commands are handled by `rsp_s2dex`.
+use crate::hle::gbi::GbiConstants;
+use crate::hle::interp::Handler;
+use crate::hle::mem::Rdram;
+
+pub(crate) const S2DEX_CONSTS: GbiConstants = super::f3d::F3D_CONSTS;
+pub(crate) const S2DEX2_CONSTS: GbiConstants = super::f3dex2::F3DEX2_CONSTS;
+
+pub(crate) fn install_s2dex<M: Rdram>(table: &mut [Handler<M>; 256]) {
+    crate::hle::rdp::install_defaults(table);
+    crate::hle::rsp_s2dex::install_overrides_s2dex(table);
+}
+
+pub(crate) fn install_s2dex2<M: Rdram>(table: &mut [Handler<M>; 256]) {
+    crate::hle::rdp::install_defaults(table);
+    crate::hle::rsp_s2dex::install_overrides_s2dex2(table);
+}
@@ -33,6 +33,7 @@ fn probe_log_nonident(args: core::fmt::Arguments) {
     }
 }
 
+#[derive(Clone)]
 pub struct HostRam<'a> {
     pub segments: [u64; 16],
     _frame: PhantomData<&'a [u8]>,
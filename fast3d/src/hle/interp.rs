@@ -46,6 +46,16 @@ pub(crate) struct Ctx<'a, M: Rdram> {
     pub dropped_runs: &'a mut u32,
     /// Anti-flood set for `UnknownOpcode`: emit each distinct unknown opcode ONCE (spec §3.6).
     pub unknown_seen: &'a mut [bool; 256],
+    /// Set by a handler to redirect the walk (G_BRANCH_Z). Branch semantics: the next command is
+    /// read from this address and no return address is pushed.
+    pub branch: Option<u64>,
+    /// Set by a handler to end the current DL as G_ENDDL would (G_CULLDL on an offscreen range).
+    pub end_dl: bool,
+    /// Sub-DLs ended early by G_CULLDL. Rolled into `DlSummary.culled_dls` by `process_dl`.
+    pub culled_dls: &'a mut u32,
+    /// Set by G_LOAD_UCODE: the walk swaps its dispatch table and constants to this microcode
+    /// before the next command. RSP, RDP and segment state carry over.
+    pub switch_ucode: Option<crate::hle::gbi::GbiUcode>,
 }
 
 /// Sign-extend the low 24 bits of `w` (RDP float-GBI rect coords are s23 in the command word).
@@ -56,6 +66,21 @@ fn sext24(w: u32) -> i32 {
 
 pub(crate) type Handler<M> = fn(&Cmd, &mut Ctx<M>);
 
+/// gSPLoadUcode: identify the image (text in w1, data in the preceding G_RDPHALF_1) and switch
+/// the walk to the matching microcode. Every microcode family encodes it the same way, so the one
+/// handler serves each opcode slot.
+pub(crate) fn load_ucode<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let text = cx.mem.resolve_masked(c.w1_addr);
+    let data = cx.mem.resolve_masked(cx.rsp.take_rdphalf_1());
+    match crate::hle::gbi::identify(cx.mem, text, data) {
+        Ok(found) => cx.switch_ucode = Some(found.ucode),
+        Err(hash) => cx.diags.push(Diagnostic {
+            at: cx.pc,
+            kind: DiagKind::UnknownMicrocode { hash },
+        }),
+    }
+}
+
 pub(crate) fn unknown<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     let op = c.opcode();
     if cx.unknown_seen[op as usize] {
@@ -79,6 +104,8 @@ pub struct InterpResult {
     pub commands: u32,
     /// Draw runs discarded during the walk.
     pub dropped_runs: u32,
+    /// Sub-DLs ended early by G_CULLDL.
+    pub culled_dls: u32,
 }
 
 /// Max command dispatches before the runaway guard fires. Needed because we run
@@ -92,14 +119,38 @@ pub fn interpret<M: Rdram>(
     entry: u64,
     ucode: crate::hle::gbi::GbiUcode,
     data_format: crate::hle::mem::GbiDataFormat,
+) -> InterpResult {
+    walk(mem, entry, ucode, data_format, false)
+}
+
+/// `interpret` for `guess_microcode`'s dry walks of a list whose data format is unknown: each
+/// G_VTX reads only the vertices that fit in the bytes it covers at the fixed-point stride, so a
+/// float walk of a fixed-point list stays inside its vertex arrays.
+pub(crate) fn interpret_dry<M: Rdram>(
+    mem: M,
+    entry: u64,
+    ucode: crate::hle::gbi::GbiUcode,
+    data_format: crate::hle::mem::GbiDataFormat,
+) -> InterpResult {
+    walk(mem, entry, ucode, data_format, true)
+}
+
+fn walk<M: Rdram>(
+    mem: M,
+    entry: u64,
+    ucode: crate::hle::gbi::GbiUcode,
+    data_format: crate::hle::mem::GbiDataFormat,
+    fixed_vertex_span: bool,
 ) -> InterpResult {
     let mut mem = mem;
-    let gbi = crate::hle::gbi::Gbi::<M>::new(ucode, data_format);
+    let mut gbi = crate::hle::gbi::Gbi::<M>::new(ucode, data_format);
     let mut rsp = crate::hle::rsp::Rsp::new(gbi.consts, gbi.data_format);
-    let mut rdp = crate::hle::rdp::Rdp::default();
+    rsp.fixed_vertex_span = fixed_vertex_span;
+    let mut rdp = crate::hle::rdp::Rdp::for_walk();
     let mut scene = Scene::default();
     let mut diags = Vec::new();
     let mut dropped_runs: u32 = 0;
+    let mut culled_dls: u32 = 0;
     let mut unknown_seen = [false; 256];
 
     let mut pc: u64 = entry;
@@ -206,7 +257,6 @@ pub fn interpret<M: Rdram>(
             let dsdx = (cmd2.w1 >> 16) as i16;
             let dtdy = cmd2.w1 as i16;
             let flip = op == crate::hle::consts::G_TEXRECTFLIP;
-            let copy_mode = ((rdp.other_mode_h >> 20) & 3) == crate::hle::consts::G_CYC_COPY;
             let rect = crate::hle::rsp::Rect { ulx, uly, lrx, lry };
 
             if !rec.have_seen_cimg {
@@ -220,44 +270,18 @@ pub fn interpret<M: Rdram>(
                 continue;
             }
 
-            crate::hle::rsp::ensure_pair_open(&mut scene, &mut rdp, &mut rec);
-            crate::hle::rsp::record_scissor_if_changed(&mut scene, &rdp, &mut rec);
-            let (material_index, render_mode_index) =
-                crate::hle::rsp::snapshot_rect_run(&rsp, &rdp, &mut diags, &mut scene, pc);
-
-            // fb_source: the latest PRIOR pair whose framebuffer byte-range contains the texture
-            // image address (a framebuffer-as-texture read-back). The current pair is excluded
-            // (it is not yet recorded as a finished framebuffer).
-            let tex_addr = rdp.tex_image.3;
-            let cur = rec.cur_pair;
-            let fb_source = scene.framebuffer_pairs[..cur]
-                .iter()
-                .rev()
-                .filter(|p| !p.is_depth_clear)
-                .find(|p| {
-                    let start = p.color_image.addr;
-                    let end = start
-                        + (p.color_image.width as u64)
-                            * (p.size_extent.1 as u64)
-                            * crate::hle::rsp::bpp(p.color_image.siz);
-                    (start..end).contains(&tex_addr)
-                })
-                .map(|p| p.color_image.addr);
-
-            scene.framebuffer_pairs[cur]
-                .ops
-                .push(crate::hle::rsp::SceneOp::TexRect {
-                    rect,
-                    uls,
-                    ult,
-                    dsdx,
-                    dtdy,
-                    flip,
-                    copy_mode,
-                    material_index,
-                    render_mode_index,
-                    fb_source,
-                });
+            crate::hle::rsp::record_texrect(
+                &rsp,
+                &mut rdp,
+                &mut scene,
+                &mut rec,
+                &mut diags,
+                pc,
+                rect,
+                (uls, ult),
+                (dsdx, dtdy),
+                flip,
+            );
             pc += 3 * stride;
             continue;
         }
@@ -306,12 +330,7 @@ pub fn interpret<M: Rdram>(
                 continue;
             }
 
-            crate::hle::rsp::ensure_pair_open(&mut scene, &mut rdp, &mut rec);
-            crate::hle::rsp::record_scissor_if_changed(&mut scene, &rdp, &mut rec);
-            let color_raw = rdp.fill_color_raw;
-            scene.framebuffer_pairs[rec.cur_pair]
-                .ops
-                .push(crate::hle::rsp::SceneOp::FillRect { rect, color_raw });
+            crate::hle::rsp::record_fillrect(&mut scene, &mut rdp, &mut rec, rect);
             pc += words * stride;
             continue;
         }
@@ -327,11 +346,69 @@ pub fn interpret<M: Rdram>(
             rec: &mut rec,
             dropped_runs: &mut dropped_runs,
             unknown_seen: &mut unknown_seen,
+            branch: None,
+            end_dl: false,
+            culled_dls: &mut culled_dls,
+            switch_ucode: None,
         };
         gbi.table[op as usize](&c, &mut cx);
-        pc += stride;
+        if let Some(at) = cx.rsp.rdphalf_1_at.filter(|&at| at != pc) {
+            // The command after a G_RDPHALF_1 left its operand unconsumed.
+            cx.rsp.rdphalf_1_at = None;
+            cx.diags.push(Diagnostic {
+                at,
+                kind: DiagKind::StrayRdphalf,
+            });
+        }
+        if let Some(next) = cx.switch_ucode {
+            // Swap the dispatch table in place; the RSP keeps its matrices/lights/vertex cache.
+            gbi = crate::hle::gbi::Gbi::<M>::new(next, gbi.data_format);
+            cx.rsp.set_constants(gbi.consts);
+        }
+        if cx.end_dl {
+            match return_stack.pop() {
+                Some(ret) => pc = ret,
+                None => break,
+            }
+            continue;
+        }
+        match cx.branch {
+            Some(target) => pc = target,
+            None => pc += stride,
+        }
     }
 
+    finish_walk(
+        rsp,
+        rdp,
+        scene,
+        diags,
+        pc,
+        dispatched as u32,
+        dropped_runs,
+        culled_dls,
+    )
+}
+
+/// Close a walk: the render-mode-never-set check, the RSP table flush and the scene-global fog /
+/// color-image snapshot. Shared by the display-list walk and the raw RDP command-list walk.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn finish_walk(
+    rsp: crate::hle::rsp::Rsp,
+    rdp: crate::hle::rdp::Rdp,
+    mut scene: Scene,
+    mut diags: Vec<Diagnostic>,
+    pc: u64,
+    commands: u32,
+    dropped_runs: u32,
+    culled_dls: u32,
+) -> InterpResult {
+    if let Some(at) = rsp.rdphalf_1_at {
+        diags.push(Diagnostic {
+            at,
+            kind: DiagKind::StrayRdphalf,
+        });
+    }
     let geometry_mode = rsp.geometry_mode();
     // Per-run materials/render_modes/draw_runs are populated DURING the walk (snapshot_run).
     // A9: emit a loud diagnostic if geometry was drawn without a render mode being set. Covers both
@@ -363,8 +440,9 @@ pub fn interpret<M: Rdram>(
         diags,
         geometry_mode,
         rdp,
-        commands: dispatched as u32,
+        commands,
         dropped_runs,
+        culled_dls,
     }
 }
 
@@ -641,6 +719,7 @@ mod task8_tests {
         let mut rec = crate::hle::rsp::PairRec::default();
         let mut dropped = 0u32;
         let mut seen = [false; 256];
+        let mut culled = 0u32;
         let cmd = Cmd {
             w0,
             w1,
@@ -662,6 +741,10 @@ mod task8_tests {
             rec: &mut rec,
             dropped_runs: &mut dropped,
             unknown_seen: &mut seen,
+            branch: None,
+            end_dl: false,
+            culled_dls: &mut culled,
+            switch_ucode: None,
         };
         table[cmd.opcode() as usize](&cmd, &mut cx);
         (rdp.other_mode_h >> 20) & 3
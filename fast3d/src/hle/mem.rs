@@ -99,6 +99,7 @@ pub struct Command {
     pub w1_addr: u64,
 }
 
+#[derive(Clone)]
 pub struct RdramImage<'a> {
     pub bytes: &'a [u8],
     /// Segment base table. Zero-init: the default table is an identity map, so
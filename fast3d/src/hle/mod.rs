@@ -9,15 +9,23 @@ pub mod interp;
 pub mod math;
 pub mod mem;
 pub mod rdp;
+pub mod rdp_list;
 pub mod rsp;
 pub mod rsp_f3d;
+pub mod rsp_f3dex;
 pub mod rsp_f3dex2;
+pub mod rsp_f3dzex2;
+pub mod rsp_l3dex2;
+pub mod rsp_s2dex;
 pub mod texdec;
 pub mod tmem;
 
 #[cfg_attr(not(all(test, feature = "asm")), allow(unused_imports))]
-pub use blender::{decode_render_mode, AlphaCompare, BlendClass, RenderMode, ZMode};
-pub use combiner::{decode_rgba16, Material, MipLevel, MAX_LOD_LEVELS};
+pub use blender::{
+    decode_render_mode, AlphaCompare, AlphaDither, BlendClass, ColorDither, CvgDest, RenderMode,
+    ZMode,
+};
+pub use combiner::{decode_rgba16, Material, MipLevel, TextureFilter, TileAddress, MAX_LOD_LEVELS};
 #[cfg_attr(not(all(test, feature = "asm")), allow(unused_imports))]
 pub use gbi::GbiUcode;
 #[cfg(all(not(target_arch = "wasm32"), target_pointer_width = "64"))]
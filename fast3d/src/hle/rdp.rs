@@ -1,9 +1,11 @@
 use crate::diag::{DiagKind, Diagnostic};
+use crate::hle::combiner::TextureFilter;
 use crate::hle::consts::rdp::{
-    G_LOADBLOCK, G_LOADTILE, G_LOADTLUT, G_NOOP, G_RDPFULLSYNC, G_RDPHALF_1, G_RDPHALF_2,
-    G_RDPLOADSYNC, G_RDPPIPESYNC, G_RDPSETOTHERMODE, G_RDPTILESYNC, G_SETBLENDCOLOR, G_SETCIMG,
-    G_SETCOMBINE, G_SETENVCOLOR, G_SETFILLCOLOR, G_SETFOGCOLOR, G_SETPRIMCOLOR, G_SETSCISSOR,
-    G_SETTILE, G_SETTILESIZE, G_SETZIMG,
+    G_CK_KEY, G_CYC_COPY, G_LOADBLOCK, G_LOADTILE, G_LOADTLUT, G_NOOP, G_RDPFULLSYNC, G_RDPHALF_1,
+    G_RDPHALF_2, G_RDPLOADSYNC, G_RDPPIPESYNC, G_RDPSETOTHERMODE, G_RDPTILESYNC, G_SETBLENDCOLOR,
+    G_SETCIMG, G_SETCOMBINE, G_SETCONVERT, G_SETENVCOLOR, G_SETFILLCOLOR, G_SETFOGCOLOR,
+    G_SETKEYGB, G_SETKEYR, G_SETPRIMCOLOR, G_SETPRIMDEPTH, G_SETSCISSOR, G_SETTILE, G_SETTILESIZE,
+    G_SETZIMG, G_TC_FILT, G_TF_AVERAGE, G_TF_BILERP, G_TP_PERSP,
 };
 use crate::hle::interp::{Cmd, Ctx, Handler};
 use crate::hle::mem::Rdram;
@@ -49,6 +51,10 @@ pub struct Rdp {
     pub combine_l: u32,                // = w0
     pub combine_h: u32,                // = w1
     pub other_mode_h: u32,             // authoritative; cycle type at (h>>20)&3
+    /// othermode_H bits no command has written yet. `Rdp::for_walk` starts a display-list walk
+    /// with `OTHER_MODE_H_UNSET` here, so fields a list never writes keep the output the renderer
+    /// had before it decoded them; every othermode write clears the bits it covers.
+    pub other_mode_h_unset: u32,
     pub other_mode_l: u32, // RDP othermode low word: blender mux + z-mode + render flags
     pub fog_color: [u8; 4], // G_SETFOGCOLOR RGBA8
     pub fog_mul: i16,      // gSPFogPosition fm
@@ -64,6 +70,21 @@ pub struct Rdp {
     /// Blend color RGBA8 — set by G_SETBLENDCOLOR (0xF9). Used by CLR_BL blender selector and
     /// THRESHOLD alpha-compare (alpha_threshold = blend_color[3] / 255). Default [0,0,0,255].
     pub blend_color: [u8; 4],
+    /// G_SETCONVERT coefficients K0..K5 (9-bit signed, sign-extended). K0..K3 drive the YUV16
+    /// texel conversion in `texdec::convert_yuv`; K4/K5 feed the combiner's K4/K5 inputs.
+    pub convert: [i16; 6],
+    /// Chroma-key center per channel (R from G_SETKEYR, G/B from G_SETKEYGB). The combiner's
+    /// KEY_CENTER input.
+    pub key_center: [u8; 3],
+    /// Chroma-key scale per channel. The combiner's KEY_SCALE input.
+    pub key_scale: [u8; 3],
+    /// Chroma-key width per channel (12-bit unsigned 4.8 fixed point). Only read by the
+    /// G_CK_KEY test: a pixel is keyed when |color - center| * scale <= width on all channels.
+    pub key_width: [u16; 3],
+    /// G_SETPRIMDEPTH primitive Z (15-bit screen Z, `0..=0x7FFF`) and delta Z. Replaces every
+    /// pixel's depth when othermode_L selects G_ZS_PRIM; see `RenderMode::z_source_prim`.
+    pub prim_depth: u16,
+    pub prim_depth_dz: u16,
     /// TLUT entry format from othermode_H TT (0=NONE, 2=RGBA16, 3=IA16). Diagnostic; decode reads it inline.
     pub tlut_fmt: u8,
     // --- 2D / framebuffer state (set by G_SETCIMG / G_SETZIMG / G_SETSCISSOR / G_SETFILLCOLOR) ---
@@ -81,7 +102,20 @@ pub struct Rdp {
     pub fill_color_raw: u32,
 }
 
+/// othermode_H fields whose decode would change existing output while a display list leaves them
+/// unwritten: G_MDSFT_TEXTFILT (bits [13:12]), which draws bilinear-filtered until it is set, and
+/// G_MDSFT_TEXTPERSP (bit 19), which stays perspective-correct until it is set.
+pub const OTHER_MODE_H_UNSET: u32 = (3 << 12) | G_TP_PERSP;
+
 impl Rdp {
+    /// The RDP state a display-list walk starts from: all-zero, with `OTHER_MODE_H_UNSET` pending.
+    pub fn for_walk() -> Self {
+        Self {
+            other_mode_h_unset: OTHER_MODE_H_UNSET,
+            ..Self::default()
+        }
+    }
+
     /// G_MDSFT_TEXTLOD (othermode_h bit 16): true = G_TL_LOD (LOD / mipmapping enabled). N64
     /// othermode_h layout.
     pub fn lod_enable(&self) -> bool {
@@ -93,6 +127,41 @@ impl Rdp {
     pub fn text_detail(&self) -> u8 {
         ((self.other_mode_h >> 17) & 3) as u8
     }
+
+    /// G_MDSFT_TEXTCONV (othermode_h bits [11:9]): true unless the field is G_TC_FILT, i.e. the
+    /// texture unit runs the G_SETCONVERT YUV->RGB conversion (G_TC_CONV / G_TC_FILTCONV).
+    pub fn text_convert(&self) -> bool {
+        self.other_mode_h & (7 << 9) != G_TC_FILT
+    }
+
+    /// G_MDSFT_COMBKEY (othermode_h bit 8): true = G_CK_KEY, the chroma-key test is enabled.
+    pub fn chroma_key(&self) -> bool {
+        self.other_mode_h & G_CK_KEY != 0
+    }
+
+    /// G_MDSFT_TEXTPERSP (othermode_h bit 19): true = G_TP_PERSP, texcoords are
+    /// perspective-corrected. A field no command has written yet stays perspective-correct.
+    pub fn tex_persp(&self) -> bool {
+        (self.other_mode_h | self.other_mode_h_unset) & G_TP_PERSP != 0
+    }
+
+    /// G_MDSFT_TEXTFILT (othermode_h bits [13:12]). A field no command has written yet filters
+    /// with `Average`, the sampler's bilinear filter every texture used before the field was
+    /// decoded, in every cycle type. Once written, copy cycle reads texels directly, so it always
+    /// point-samples; the reserved value 1 is treated as G_TF_POINT.
+    pub fn texture_filter(&self) -> TextureFilter {
+        if self.other_mode_h_unset & (3 << 12) != 0 {
+            return TextureFilter::Average;
+        }
+        if (self.other_mode_h >> 20) & 3 == G_CYC_COPY {
+            return TextureFilter::Point;
+        }
+        match self.other_mode_h & (3 << 12) {
+            G_TF_BILERP => TextureFilter::Bilerp,
+            G_TF_AVERAGE => TextureFilter::Average,
+            _ => TextureFilter::Point,
+        }
+    }
 }
 
 pub(crate) fn install_defaults<M: Rdram>(t: &mut [Handler<M>; 256]) {
@@ -107,6 +176,10 @@ pub(crate) fn install_defaults<M: Rdram>(t: &mut [Handler<M>; 256]) {
     t[G_SETENVCOLOR as usize] = set_env_color::<M>;
     t[G_SETFOGCOLOR as usize] = set_fog_color::<M>;
     t[G_SETBLENDCOLOR as usize] = set_blend_color::<M>;
+    t[G_SETCONVERT as usize] = set_convert::<M>;
+    t[G_SETKEYR as usize] = set_key_r::<M>;
+    t[G_SETKEYGB as usize] = set_key_gb::<M>;
+    t[G_SETPRIMDEPTH as usize] = set_prim_depth::<M>;
     for op in [G_RDPLOADSYNC, G_RDPPIPESYNC, G_RDPTILESYNC, G_RDPFULLSYNC] {
         t[op as usize] = sync::<M>;
     }
@@ -118,7 +191,9 @@ pub(crate) fn install_defaults<M: Rdram>(t: &mut [Handler<M>; 256]) {
     // RDPHALF continuation words are consumed INLINE by the walk loop's rect decode (never
     // table-dispatched in a well-formed DL). If one reaches the table, the rect word-count
     // desynced — surface it loudly instead of silently no-oping (Task 3, Step 2 canary).
-    t[G_RDPHALF_1 as usize] = rdp_half::<M>;
+    // G_RDPHALF_1 also carries the gsSPBranchLessZ / gsSPLoadUcode operand on F3DEX2, so it
+    // latches and only reports once nothing consumes it.
+    t[G_RDPHALF_1 as usize] = rdphalf_1::<M>;
     t[G_RDPHALF_2 as usize] = rdp_half::<M>;
 }
 
@@ -132,9 +207,24 @@ fn rdp_half<M: Rdram>(_c: &Cmd, cx: &mut Ctx<M>) {
     });
 }
 
+/// G_RDPHALF_1 on microcodes with gsSPBranchLessZ / gsSPLoadUcode: latch the operand for the
+/// command that follows. The walk keeps the rect-desync canary — a latch nothing consumes is
+/// still reported as a stray RDPHALF.
+pub(crate) fn rdphalf_1<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    if let Some(at) = cx.rsp.rdphalf_1_at {
+        cx.diags.push(Diagnostic {
+            at,
+            kind: DiagKind::StrayRdphalf,
+        });
+    }
+    cx.rsp.rdphalf_1 = c.w1_addr;
+    cx.rsp.rdphalf_1_at = Some(cx.pc);
+}
+
 fn rdp_set_other_mode<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     // G_RDPSETOTHERMODE: w0 low 24 bits → other_mode_h field; w1 → other_mode_l.
     cx.rdp.other_mode_h = c.w0 & 0x00FF_FFFF;
+    cx.rdp.other_mode_h_unset = 0;
     cx.rdp.other_mode_l = c.w1;
     cx.rsp.material_dirty = true;
 }
@@ -174,6 +264,48 @@ fn set_blend_color<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     cx.rsp.material_dirty = true;
 }
 
+fn set_convert<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    // G_SETCONVERT (0xEC): six 9-bit signed coefficients; K2 straddles the word boundary (its top
+    // 4 bits end w0, its low 5 bits start w1).
+    let k2 = (c.p0(0, 4) << 5) | c.p1(27, 5);
+    let fields = [
+        c.p0(13, 9),
+        c.p0(4, 9),
+        k2,
+        c.p1(18, 9),
+        c.p1(9, 9),
+        c.p1(0, 9),
+    ];
+    cx.rdp.convert = fields.map(|k| ((k << 7) as i16) >> 7);
+    cx.rsp.material_dirty = true;
+}
+
+fn set_key_r<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    // G_SETKEYR (0xEB): w1 = width(12) << 16 | center << 8 | scale.
+    cx.rdp.key_center[0] = c.p1(8, 8) as u8;
+    cx.rdp.key_scale[0] = c.p1(0, 8) as u8;
+    cx.rdp.key_width[0] = c.p1(16, 12) as u16;
+    cx.rsp.material_dirty = true;
+}
+
+fn set_key_gb<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    // G_SETKEYGB (0xEA): w0 carries the two widths; w1 = centerG, scaleG, centerB, scaleB.
+    cx.rdp.key_center[1] = c.p1(24, 8) as u8;
+    cx.rdp.key_scale[1] = c.p1(16, 8) as u8;
+    cx.rdp.key_center[2] = c.p1(8, 8) as u8;
+    cx.rdp.key_scale[2] = c.p1(0, 8) as u8;
+    cx.rdp.key_width[1] = c.p0(12, 12) as u16;
+    cx.rdp.key_width[2] = c.p0(0, 12) as u16;
+    cx.rsp.material_dirty = true;
+}
+
+fn set_prim_depth<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    // G_SETPRIMDEPTH (0xEE): w1 = z << 16 | dz. The render mode (not the material) carries it, and
+    // that is re-decoded at every draw, so no dirty flag is needed.
+    cx.rdp.prim_depth = c.p1(16, 16) as u16;
+    cx.rdp.prim_depth_dz = c.p1(0, 16) as u16;
+}
+
 fn set_tile<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     let tile = c.p1(24, 3) as usize;
     let t = &mut cx.rdp.tiles[tile];
@@ -198,15 +330,16 @@ fn set_tile_size<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     t.ult = c.p0(0, 12) as u16;
     t.lrs = c.p1(12, 12) as u16;
     t.lrt = c.p1(0, 12) as u16;
-    // lr_s/lr_t are in 10.2 fixed point; texel dims = (lrs>>2)+1, (lrt>>2)+1.
-    t.width = (t.lrs >> 2) + 1;
-    t.height = (t.lrt >> 2) + 1;
+    // The corners are 10.2 fixed point; the tile spans texels (uls>>2)..=(lrs>>2). Texcoords are
+    // measured from uls/ult at draw time, so the decoded texture starts at the tile's TMEM base.
+    t.width = (t.lrs >> 2).saturating_sub(t.uls >> 2) + 1;
+    t.height = (t.lrt >> 2).saturating_sub(t.ult >> 2) + 1;
     cx.rsp.material_dirty = true;
 }
 
 fn load_block<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     // contiguous, linear: N64 RDP hardware word count.
-    let (_fmt, siz, _w, addr) = cx.rdp.tex_image;
+    let (fmt, siz, _w, addr) = cx.rdp.tex_image;
     let tile_idx = c.p1(24, 3) as usize; // load tile (7 for a well-formed LoadTextureBlock)
     let uls = c.p0(12, 12);
     let lrs = c.p1(12, 12);
@@ -223,7 +356,7 @@ fn load_block<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     let line_words = cx.rdp.tiles[tile_idx].line as usize;
     cx.rdp
         .tmem_bank
-        .write_block(&src, dst_words, line_words, dxt, words as usize, siz);
+        .write_block(&src, dst_words, line_words, dxt, words as usize, fmt, siz);
     cx.rdp.tmem = src;
     cx.rdp.load_via_tile = false; // contiguous rows: faithful path gated on word-aligned rows.
     cx.rsp.material_dirty = true;
@@ -233,7 +366,7 @@ fn load_tile<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     // G_LOADTILE (0xF4): row-by-row load of a strided sub-rectangle of the current tex_image into
     // TMEM. Params (w0: uls/ult, w1: tile/lrs/lrt) are 10.2
     // fixed point; integer texel coords are the fields >> 2.
-    let (_fmt, siz, width, addr) = cx.rdp.tex_image;
+    let (fmt, siz, width, addr) = cx.rdp.tex_image;
     let tile_idx = c.p1(24, 3) as usize; // load tile (7 for a well-formed LoadTextureTile)
     let uls = c.p0(12, 12) >> 2;
     let ult = c.p0(0, 12) >> 2;
@@ -267,6 +400,7 @@ fn load_tile<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
         row_count as usize,
         words_per_row as usize,
         bytes_per_row as usize,
+        fmt,
         siz,
     );
     // Keep the linear source for the `tmem.is_empty()` gate; mark the padded-row (LoadTile) path so
@@ -354,6 +488,7 @@ mod tests {
         let mut rec = crate::hle::rsp::PairRec::default();
         let mut dropped = 0u32;
         let mut seen = [false; 256];
+        let mut culled = 0u32;
         let cmd = Cmd {
             w0,
             w1,
@@ -370,6 +505,10 @@ mod tests {
             rec: &mut rec,
             dropped_runs: &mut dropped,
             unknown_seen: &mut seen,
+            branch: None,
+            end_dl: false,
+            culled_dls: &mut culled,
+            switch_ucode: None,
         };
         t[cmd.opcode() as usize](&cmd, &mut cx);
         (rdp, diags)
@@ -426,12 +565,116 @@ mod tests {
         assert_eq!(Rdp::default().text_detail(), 0);
     }
 
+    #[test]
+    fn set_convert_sign_extends_all_six_coefficients() {
+        // gsDPSetConvert(G_CV_K0..G_CV_K5) = (0xEC15_FD5D, 0x3B78_E42A); K2 = -89 straddles w0/w1.
+        let (rdp, diags) = run_cmd(&[], Rdp::default(), 0xEC15_FD5D, 0x3B78_E42A);
+        assert!(diags.is_empty());
+        assert_eq!(rdp.convert, [175, -43, -89, 222, 114, 42]);
+    }
+
+    #[test]
+    fn set_key_r_and_gb_fill_center_scale_and_width() {
+        let (rdp, _) = run_cmd(&[], Rdp::default(), 0xEB00_0000, 0x0123_8040);
+        assert_eq!((rdp.key_center[0], rdp.key_scale[0]), (0x80, 0x40));
+        assert_eq!(rdp.key_width[0], 0x123);
+        let (rdp, _) = run_cmd(&[], rdp, 0xEA45_6789, 0x1020_3040);
+        assert_eq!(rdp.key_center, [0x80, 0x10, 0x30]);
+        assert_eq!(rdp.key_scale, [0x40, 0x20, 0x40]);
+        assert_eq!(rdp.key_width, [0x123, 0x456, 0x789]);
+    }
+
+    #[test]
+    fn set_prim_depth_fills_z_and_dz() {
+        let (rdp, diags) = run_cmd(&[], Rdp::default(), 0xEE00_0000, 0x1234_0001);
+        assert!(diags.is_empty());
+        assert_eq!((rdp.prim_depth, rdp.prim_depth_dz), (0x1234, 1));
+    }
+
+    #[test]
+    fn chroma_key_reads_combkey_bit() {
+        assert!(!Rdp::default().chroma_key());
+        let keyed = Rdp {
+            other_mode_h: G_CK_KEY,
+            ..Rdp::default()
+        };
+        assert!(keyed.chroma_key());
+    }
+
+    #[test]
+    fn texture_filter_decodes_textfilt_and_forces_point_in_copy() {
+        assert_eq!(Rdp::default().texture_filter(), TextureFilter::Point);
+        let with = |h: u32| Rdp {
+            other_mode_h: h,
+            ..Rdp::default()
+        };
+        assert_eq!(with(G_TF_BILERP).texture_filter(), TextureFilter::Bilerp);
+        assert_eq!(with(G_TF_AVERAGE).texture_filter(), TextureFilter::Average);
+        assert_eq!(
+            with(G_TF_BILERP | (G_CYC_COPY << 20)).texture_filter(),
+            TextureFilter::Point,
+            "copy mode point-samples"
+        );
+    }
+
+    #[test]
+    fn unwritten_texture_filter_keeps_bilinear_until_set() {
+        let mut rdp = Rdp::for_walk();
+        assert_eq!(rdp.texture_filter(), TextureFilter::Average);
+        let mut rsp = crate::hle::rsp::Rsp::default();
+        rsp.set_other_mode_h_raw(20, 2, G_CYC_COPY << 20, &mut rdp);
+        assert_eq!(
+            rdp.texture_filter(),
+            TextureFilter::Average,
+            "cycle type only, even copy"
+        );
+        rsp.set_other_mode_h_raw(12, 2, G_TF_BILERP, &mut rdp);
+        assert_eq!(
+            rdp.texture_filter(),
+            TextureFilter::Point,
+            "copy point-samples"
+        );
+        rsp.set_other_mode_h_raw(20, 2, 0, &mut rdp);
+        assert_eq!(rdp.texture_filter(), TextureFilter::Bilerp);
+    }
+
+    #[test]
+    fn unwritten_texture_persp_stays_perspective_until_set() {
+        let mut rdp = Rdp::for_walk();
+        assert!(rdp.tex_persp());
+        let mut rsp = crate::hle::rsp::Rsp::default();
+        rsp.set_other_mode_h_raw(20, 2, 0, &mut rdp);
+        assert!(rdp.tex_persp(), "cycle type only");
+        rsp.set_other_mode_h_raw(19, 1, 0, &mut rdp);
+        assert!(!rdp.tex_persp(), "G_TP_NONE");
+        assert!(!Rdp::default().tex_persp(), "raw RDP lists start zeroed");
+    }
+
+    #[test]
+    fn text_convert_is_off_only_for_tc_filt() {
+        assert!(Rdp::default().text_convert(), "G_TC_CONV (0) converts");
+        let filt = Rdp {
+            other_mode_h: G_TC_FILT,
+            ..Rdp::default()
+        };
+        assert!(!filt.text_convert());
+    }
+
     #[test]
     fn set_tile_size_fills_render_tile() {
         // gdp_set_tile_size(0, 0, 0, 124, 124) = (0xF200_0000, 0x0007_C07C)
         let (rdp, _) = run_cmd(&[], Rdp::default(), 0xF200_0000, 0x0007_C07C);
         assert_eq!(rdp.tiles[0].lrs, 124);
         assert_eq!(rdp.tiles[0].lrt, 124);
+        assert_eq!((rdp.tiles[0].width, rdp.tiles[0].height), (32, 32));
+    }
+
+    #[test]
+    fn set_tile_size_measures_the_tile_from_its_origin() {
+        // gdp_set_tile_size(0, 16, 8, 140, 132): a 32×32 tile whose corner sits at texel (4, 2).
+        let (rdp, _) = run_cmd(&[], Rdp::default(), 0xF201_0008, 0x0008_C084);
+        assert_eq!((rdp.tiles[0].uls, rdp.tiles[0].ult), (16, 8));
+        assert_eq!((rdp.tiles[0].width, rdp.tiles[0].height), (32, 32));
     }
 
     #[test]
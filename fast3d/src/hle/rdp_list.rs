@@ -0,0 +1,517 @@
+//! Raw RDP command-list walk: the DPC_START..DPC_END buffer an RSP task (or libdragon's rdpq)
+//! hands the RDP directly. State commands share the display-list RDP handlers; the commands only
+//! the RDP itself sees — edge-walker triangles and the two-word texture rectangle — are decoded
+//! here into the same Scene geometry the display-list walk produces.
+
+use crate::diag::{DiagKind, Diagnostic};
+use crate::hle::consts::{
+    G_FILLRECT, G_RDP_TRI_SHADE_MASK, G_RDP_TRI_TXTR_MASK, G_RDP_TRI_ZBUFF_MASK, G_SETCIMG,
+    G_TEXRECT, G_TEXRECTFLIP, G_TP_PERSP, G_TRI_FILL,
+};
+use crate::hle::interp::{unknown, Cmd, Ctx, Handler, InterpResult};
+use crate::hle::mem::Rdram;
+use crate::hle::rsp::{Rect, Scene, ScreenVertex, TextureState};
+
+/// Bytes per RDP command word.
+const WORD: u64 = 8;
+
+/// The RDP no-op (opcode 0x00 before the 0xC0 normalization).
+const RDP_NOOP: u8 = 0xC0;
+
+/// Sign-extend a 14-bit s11.2 edge Y coordinate to pixels.
+#[inline]
+fn edge_y(v: u32) -> f32 {
+    (((v << 18) as i32) >> 18) as f32 / 4.0
+}
+
+/// An s15.16 word to a float.
+#[inline]
+fn fixed(v: u32) -> f32 {
+    v as i32 as f32 / 65536.0
+}
+
+/// One attribute of a shade or texture coefficient block: the integer half of word `int` and the
+/// fraction half of word `int + 2`, four 16-bit fields per word.
+#[inline]
+fn block_field(block: &[(u32, u32)], int: usize, field: usize) -> f32 {
+    let half = |(w0, w1): (u32, u32)| match field {
+        0 => w0 >> 16,
+        1 => w0 & 0xFFFF,
+        2 => w1 >> 16,
+        _ => w1 & 0xFFFF,
+    };
+    fixed((half(block[int]) << 16) | half(block[int + 2]))
+}
+
+/// Value, d/dx and d/de (along the major edge) of each of a block's four attributes.
+fn block_coeffs(block: &[(u32, u32)]) -> [[f32; 3]; 4] {
+    std::array::from_fn(|i| {
+        [
+            block_field(block, 0, i),
+            block_field(block, 1, i),
+            block_field(block, 4, i),
+        ]
+    })
+}
+
+/// Number of 64-bit words an edge-walker triangle with opcode `op` occupies.
+fn tri_words(op: u8) -> u64 {
+    let mut words = 4;
+    if op & G_RDP_TRI_SHADE_MASK != 0 {
+        words += 8;
+    }
+    if op & G_RDP_TRI_TXTR_MASK != 0 {
+        words += 8;
+    }
+    if op & G_RDP_TRI_ZBUFF_MASK != 0 {
+        words += 2;
+    }
+    words
+}
+
+/// Recover the three corners of an edge-walker triangle. The RDP stores the edges rather than the
+/// vertices: the major (H) edge runs from `yh` to `yl`, the middle (M) edge from `yh` to `ym`
+/// and the low (L) edge from `ym` to `yl`, with the X starts and every attribute given on the
+/// first scanline `floor(yh)`. Attributes step `de` down the major edge and `dx` across a span.
+fn decode_tri(words: &[(u32, u32)], persp: bool) -> [ScreenVertex; 3] {
+    let op = (words[0].0 >> 24) as u8;
+    let (w0, w1) = words[0];
+    let yl = edge_y(w0 & 0x3FFF);
+    let ym = edge_y(w1 >> 16);
+    let yh = edge_y(w1 & 0x3FFF);
+    let xl = fixed(words[1].0);
+    let (xh, dxhdy) = (fixed(words[2].0), fixed(words[2].1));
+    let y0 = yh.floor();
+    let major_x = |y: f32| xh + dxhdy * (y - y0);
+    let corners = [(major_x(yh), yh), (xl, ym), (major_x(yl), yl)];
+    let at = |c: [f32; 3], (x, y): (f32, f32)| c[0] + c[2] * (y - y0) + c[1] * (x - major_x(y));
+
+    let mut next = 4;
+    let mut take = |present: bool, len: usize| {
+        present.then(|| {
+            let block = &words[next..next + len];
+            next += len;
+            block
+        })
+    };
+    let shade = take(op & G_RDP_TRI_SHADE_MASK != 0, 8).map(block_coeffs);
+    let tex = take(op & G_RDP_TRI_TXTR_MASK != 0, 8).map(block_coeffs);
+    let z = take(op & G_RDP_TRI_ZBUFF_MASK != 0, 2)
+        .map(|b| [fixed(b[0].0), fixed(b[0].1), fixed(b[1].0)]);
+
+    corners.map(|p| {
+        let rgba = shade.map_or([0; 4], |c| {
+            c.map(|ch| at(ch, p).round().clamp(0.0, 255.0) as u8)
+        });
+        let (st, w) = match tex {
+            Some([s, t, w, _]) => {
+                let (s, t, w) = (at(s, p), at(t, p), at(w, p));
+                // Perspective: S and T carry a 1/w factor the RDP divides back out by W, which the
+                // setup normalized so its largest value is 0x7FFF.
+                if persp && w > 0.0 {
+                    ([s / w * 1024.0, t / w * 1024.0], 32767.0 / w)
+                } else {
+                    ([s / 32.0, t / 32.0], 1.0)
+                }
+            }
+            None => ([0.0; 2], 1.0),
+        };
+        ScreenVertex {
+            pos: [p.0, p.1],
+            z: z.map_or(0.0, |c| (at(c, p) / 32768.0).clamp(0.0, 1.0)),
+            w,
+            st,
+            rgba,
+        }
+    })
+}
+
+/// Walk the raw RDP command list at `[start, end)` (64-bit big-endian words, as the RDP reads
+/// them from RDRAM). Opcodes are read from the low six bits, so both the libultra `0xC0..=0xFF`
+/// encoding and libdragon's `0x00..=0x3F` one decode.
+pub fn interpret_rdp_list<M: Rdram>(mem: M, start: u64, end: u64) -> InterpResult {
+    let mut mem = mem;
+    let consts = crate::hle::gbi::GbiUcode::F3dex2.constants();
+    let mut table = [unknown::<M> as Handler<M>; 256];
+    crate::hle::rdp::install_defaults(&mut table);
+    let mut rsp = crate::hle::rsp::Rsp::new(consts, crate::hle::mem::GbiDataFormat::Fixed);
+    let mut rdp = crate::hle::rdp::Rdp::default();
+    let mut scene = Scene::default();
+    let mut diags = Vec::new();
+    let mut dropped_runs: u32 = 0;
+    let mut culled_dls: u32 = 0;
+    let mut unknown_seen = [false; 256];
+    let mut rec = crate::hle::rsp::PairRec::default();
+    let mut commands: u32 = 0;
+
+    let read = |mem: &M, at: u64| {
+        let b = mem.read_bytes(at, WORD as usize);
+        (
+            u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            u32::from_be_bytes([b[4], b[5], b[6], b[7]]),
+        )
+    };
+
+    let mut pc = start;
+    while pc + WORD <= end {
+        if !mem.in_bounds(pc, WORD) {
+            diags.push(Diagnostic {
+                at: pc,
+                kind: DiagKind::DlPastRdram,
+            });
+            break;
+        }
+        commands += 1;
+        let (w0, w1) = read(&mem, pc);
+        let op = ((w0 >> 24) as u8 & 0x3F) | 0xC0;
+        let c = Cmd {
+            w0: (w0 & 0x00FF_FFFF) | ((op as u32) << 24),
+            w1,
+            w1_addr: w1 as u64,
+        };
+
+        if op == RDP_NOOP {
+            pc += WORD;
+            continue;
+        }
+        if op == G_SETCIMG {
+            rec.have_seen_cimg = true;
+        }
+
+        if op & 0xF8 == G_TRI_FILL {
+            let len = tri_words(op);
+            if pc + len * WORD > end || !mem.in_bounds(pc, len * WORD) {
+                dropped_runs += 1;
+                diags.push(Diagnostic {
+                    at: pc,
+                    kind: DiagKind::TruncatedTriangle,
+                });
+                break;
+            }
+            if !rec.have_seen_cimg {
+                dropped_runs += 1;
+                diags.push(Diagnostic {
+                    at: pc,
+                    kind: DiagKind::DrawBeforeCimg,
+                });
+                pc += len * WORD;
+                continue;
+            }
+            let mut words: Vec<(u32, u32)> = (0..len).map(|i| read(&mem, pc + i * WORD)).collect();
+            words[0].0 = c.w0;
+            let texture_state = TextureState {
+                tile: c.p0(16, 3) as u8,
+                level: c.p0(19, 3) as u8,
+                on: op & G_RDP_TRI_TXTR_MASK != 0,
+                ..rsp.texture_state
+            };
+            if texture_state != rsp.texture_state {
+                rsp.texture_state = texture_state;
+                rsp.material_dirty = true;
+            }
+            let verts = decode_tri(&words, rdp.other_mode_h & G_TP_PERSP != 0);
+            match crate::hle::rsp::snapshot_run(&mut rsp, &rdp, &mut diags, &mut scene, pc) {
+                Some((mi, ri)) => crate::hle::rsp::record_screen_tri(
+                    &mut rsp, &mut rdp, &mut scene, &mut rec, &verts, mi, ri,
+                ),
+                None => dropped_runs += 1,
+            }
+            pc += len * WORD;
+            continue;
+        }
+
+        if op == G_TEXRECT || op == G_TEXRECTFLIP || op == G_FILLRECT {
+            let rect = Rect {
+                lrx: (c.p0(12, 12) as i32) >> 2,
+                lry: (c.p0(0, 12) as i32) >> 2,
+                ulx: (c.p1(12, 12) as i32) >> 2,
+                uly: (c.p1(0, 12) as i32) >> 2,
+            };
+            let fill = op == G_FILLRECT;
+            // The texture rectangle carries its texture coordinates in a second word.
+            let words = if fill { 1 } else { 2 };
+            if pc + words * WORD > end || !mem.in_bounds(pc, words * WORD) {
+                dropped_runs += 1;
+                diags.push(Diagnostic {
+                    at: pc,
+                    kind: DiagKind::TruncatedRect { fill },
+                });
+                break;
+            }
+            if !rec.have_seen_cimg {
+                dropped_runs += 1;
+                diags.push(Diagnostic {
+                    at: pc,
+                    kind: DiagKind::DrawBeforeCimg,
+                });
+            } else if fill {
+                crate::hle::rsp::record_fillrect(&mut scene, &mut rdp, &mut rec, rect);
+            } else {
+                let (st, step) = read(&mem, pc + WORD);
+                crate::hle::rsp::record_texrect(
+                    &rsp,
+                    &mut rdp,
+                    &mut scene,
+                    &mut rec,
+                    &mut diags,
+                    pc,
+                    rect,
+                    ((st >> 16) as i16, st as i16),
+                    ((step >> 16) as i16, step as i16),
+                    op == G_TEXRECTFLIP,
+                );
+            }
+            pc += words * WORD;
+            continue;
+        }
+
+        let mut cx = Ctx {
+            rsp: &mut rsp,
+            rdp: &mut rdp,
+            mem: &mut mem,
+            scene: &mut scene,
+            diags: &mut diags,
+            pc,
+            gbi_consts: consts,
+            rec: &mut rec,
+            dropped_runs: &mut dropped_runs,
+            unknown_seen: &mut unknown_seen,
+            branch: None,
+            end_dl: false,
+            culled_dls: &mut culled_dls,
+            switch_ucode: None,
+        };
+        table[op as usize](&c, &mut cx);
+        pc += WORD;
+    }
+
+    crate::hle::interp::finish_walk(
+        rsp,
+        rdp,
+        scene,
+        diags,
+        pc,
+        commands,
+        dropped_runs,
+        culled_dls,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hle::consts::{G_RM_OPA_SURF, G_RM_OPA_SURF2, G_TRI_SHADE, G_TRI_TXTR_ZBUFF};
+    use crate::hle::mem::RdramImage;
+    use crate::hle::rsp::SceneOp;
+
+    fn to_fixed(v: f32) -> u32 {
+        (v * 65536.0).round() as i32 as u32
+    }
+
+    /// Edge words for a triangle with corners (`xh`, `yh`), (`xl`, `ym`) and a vertical major
+    /// edge, so every `x` start is exact on the first scanline.
+    fn edges(op: u8, tile: u32, yh: f32, ym: f32, yl: f32, xh: f32, xl: f32) -> Vec<(u32, u32)> {
+        let y = |v: f32| ((v * 4.0) as i32 as u32) & 0x3FFF;
+        let dxmdy = (xl - xh) / (ym - yh);
+        let dxldy = (xh - xl) / (yl - ym);
+        vec![
+            ((op as u32) << 24 | tile << 16 | y(yl), y(ym) << 16 | y(yh)),
+            (to_fixed(xl), to_fixed(dxldy)),
+            (to_fixed(xh), 0),
+            (to_fixed(xh), to_fixed(dxmdy)),
+        ]
+    }
+
+    /// A coefficient block: `value`, `dx` and `de` per attribute (`dy` = `de` on a vertical
+    /// major edge).
+    fn block(value: [f32; 4], dx: [f32; 4], de: [f32; 4]) -> Vec<(u32, u32)> {
+        let pack = |v: [f32; 4], hi: bool| {
+            let h = v.map(|f| {
+                let x = to_fixed(f);
+                if hi {
+                    x >> 16
+                } else {
+                    x & 0xFFFF
+                }
+            });
+            (h[0] << 16 | h[1], h[2] << 16 | h[3])
+        };
+        vec![
+            pack(value, true),
+            pack(dx, true),
+            pack(value, false),
+            pack(dx, false),
+            pack(de, true),
+            pack(de, true),
+            pack(de, false),
+            pack(de, false),
+        ]
+    }
+
+    /// gsDPSetCombineMode(G_CC_SHADE, G_CC_SHADE).
+    const SHADE_COMBINE: (u32, u32) = (0xFCFF_FFFF, 0xFFFE_793C);
+
+    fn set_other_mode(h: u32) -> (u32, u32) {
+        (0xEF00_0000 | h, G_RM_OPA_SURF | G_RM_OPA_SURF2)
+    }
+
+    /// gsDPSetColorImage(G_IM_FMT_RGBA, G_IM_SIZ_16b, 320, 0x100000).
+    const SET_CIMG: (u32, u32) = (0xFF10_013F, 0x0010_0000);
+
+    fn run(words: &[(u32, u32)]) -> InterpResult {
+        let mut bytes = Vec::new();
+        for (w0, w1) in words {
+            bytes.extend_from_slice(&w0.to_be_bytes());
+            bytes.extend_from_slice(&w1.to_be_bytes());
+        }
+        bytes.resize(bytes.len() + 0x100, 0);
+        let end = (words.len() as u64) * WORD;
+        interpret_rdp_list(RdramImage::new(&bytes), 0, end)
+    }
+
+    #[test]
+    fn shade_triangle_corners_take_the_interpolated_colors() {
+        let mut words = vec![SET_CIMG, set_other_mode(0), SHADE_COMBINE];
+        words.extend(edges(G_TRI_SHADE, 0, 20.0, 40.0, 60.0, 10.0, 30.0));
+        words.extend(block(
+            [100.0, 50.0, 0.0, 255.0],
+            [2.0, 0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+        ));
+        let result = run(&words);
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+
+        let scene = &result.scene;
+        match scene.framebuffer_pairs[0].ops[..] {
+            [SceneOp::Tris(ref run)] => assert_eq!(run.cull, crate::hle::rsp::CullKind::None),
+            ref other => panic!("expected one Tris run, got {other:?}"),
+        }
+        let corners: Vec<_> = scene
+            .indices
+            .iter()
+            .map(|&i| {
+                let s = scene.modify_screen[i as usize];
+                (s[0], s[1], scene.cn[i as usize].to_le_bytes())
+            })
+            .collect();
+        assert_eq!(
+            corners,
+            [
+                (10.0, 20.0, [100, 50, 0, 255]),
+                (30.0, 40.0, [160, 50, 0, 255]),
+                (10.0, 60.0, [140, 50, 0, 255]),
+            ]
+        );
+        assert!(scene
+            .indices
+            .iter()
+            .all(|&i| scene.modify_flags[i as usize] == 3));
+    }
+
+    #[test]
+    fn perspective_texture_and_depth_divide_through_w() {
+        let mut words = vec![SET_CIMG, set_other_mode(G_TP_PERSP), SHADE_COMBINE];
+        words.extend(edges(G_TRI_TXTR_ZBUFF, 3, 0.0, 8.0, 16.0, 0.0, 8.0));
+        // W halves down the major edge; S/W stays at 2 texels there and steps 1 texel per pixel.
+        let w = 16384.0;
+        words.extend(block(
+            [2.0 * w / 1024.0, 0.0, w, 0.0],
+            [w / 1024.0, 0.0, 0.0, 0.0],
+            [-2.0 * w / 2048.0 / 16.0, 0.0, -w / 32.0, 0.0],
+        ));
+        words.push((to_fixed(8192.0), 0));
+        words.push((to_fixed(512.0), 0));
+        let result = run(&words);
+
+        let scene = &result.scene;
+        assert_eq!(scene.indices.len(), 3);
+        let top = scene.indices[0] as usize;
+        let bottom = scene.indices[2] as usize;
+        assert_eq!(scene.raw_st[top], [2.0, 0.0]);
+        assert!((scene.raw_pos[top][2] - 32767.0 / w).abs() < 1e-3);
+        assert!((scene.raw_st[bottom][0] - 2.0).abs() < 1e-3);
+        assert!((scene.raw_pos[bottom][2] - 2.0 * 32767.0 / w).abs() < 1e-3);
+        assert_eq!(scene.modify_screen[top][2], 0.25);
+        assert_eq!(scene.modify_screen[bottom][2], 0.5);
+    }
+
+    #[test]
+    fn raw_rectangles_decode_in_either_opcode_encoding() {
+        let result = run(&[
+            // libdragon encodings: SET_COLOR_IMAGE, SET_FILL_COLOR, FILL_RECTANGLE.
+            (0x3F10_013F, 0x0010_0000),
+            (0x3700_0000, 0xF800_F800),
+            (0x364F_C0EC, 0x0002_0010),
+            // libultra encodings: TEXTURE_RECTANGLE_FLIP and its coordinate word.
+            (0xE528_0050, 0x0014_0028),
+            (0x0020_0040, 0x0400_0800),
+        ]);
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        let ops = &result.scene.framebuffer_pairs[0].ops;
+        assert_eq!(ops.len(), 2, "{ops:?}");
+        assert_eq!(
+            ops[0],
+            SceneOp::FillRect {
+                rect: Rect {
+                    ulx: 8,
+                    uly: 4,
+                    lrx: 319,
+                    lry: 59,
+                },
+                color_raw: 0xF800_F800,
+            }
+        );
+        match ops[1] {
+            SceneOp::TexRect {
+                rect,
+                uls,
+                ult,
+                dsdx,
+                dtdy,
+                flip,
+                ..
+            } => {
+                assert_eq!(
+                    rect,
+                    Rect {
+                        ulx: 80,
+                        uly: 10,
+                        lrx: 160,
+                        lry: 20,
+                    }
+                );
+                assert_eq!(
+                    (uls, ult, dsdx, dtdy, flip),
+                    (0x20, 0x40, 0x400, 0x800, true)
+                );
+            }
+            ref other => panic!("expected TexRect, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn triangle_before_set_color_image_is_dropped() {
+        let mut words = vec![set_other_mode(0), SHADE_COMBINE];
+        words.extend(edges(G_TRI_SHADE, 0, 0.0, 4.0, 8.0, 0.0, 4.0));
+        words.extend(block([0.0; 4], [0.0; 4], [0.0; 4]));
+        let result = run(&words);
+        assert_eq!(result.diags.len(), 1, "{:?}", result.diags);
+        assert_eq!(result.diags[0].kind, DiagKind::DrawBeforeCimg);
+        assert_eq!(result.diags[0].at, 2 * WORD);
+        assert_eq!(result.dropped_runs, 1);
+        assert!(result.scene.indices.is_empty());
+        assert_eq!(result.commands, 3);
+    }
+
+    #[test]
+    fn truncated_triangle_is_dropped_with_a_diagnostic() {
+        let mut words = edges(G_TRI_SHADE, 0, 0.0, 4.0, 8.0, 0.0, 4.0);
+        words.extend(block([0.0; 4], [0.0; 4], [0.0; 4]));
+        words.truncate(9);
+        let result = run(&words);
+        assert_eq!(result.diags.len(), 1, "{:?}", result.diags);
+        assert_eq!(result.diags[0].kind, DiagKind::TruncatedTriangle);
+        assert_eq!(result.dropped_runs, 1);
+        assert!(result.scene.indices.is_empty());
+    }
+}
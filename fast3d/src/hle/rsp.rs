@@ -2,7 +2,7 @@
 //! the CPU keeps the DL walk, RSP/RDP state, the G_CULL_FRONT swap, and state-index tables.
 
 use crate::hle::gbi::f3dex2::F3DEX2_CONSTS;
-use crate::hle::math::{identity, mul4, mul_col_vec3, Mat4};
+use crate::hle::math::{identity, mul4, mul_col_vec3, mul_row_vec4, Mat4};
 use crate::hle::mem::Rdram;
 pub use crate::scene::{
     ColorImage, CullKind, DrawRun, FramebufferPair, Rect, Scene, SceneOp, Scissor,
@@ -13,12 +13,25 @@ pub const RSP_MAX_VERTICES: usize = 256;
 pub const RSP_MAX_LIGHTS: u32 = 7;
 pub const DEPTH_RANGE: f32 = 1024.0;
 /// Fixed framebuffer the viewport maps into. The real per-DL resolution comes from
-/// gsDPSetColorImage / the VI, which we don't model yet — 320x240 is the classic N64 res.
+/// gsDPSetColorImage / the VI, which we don't model yet — 320x240 is the classic N64 res. The
+/// GPU fold reads it through `RspProcessParams.screen`; it lands in NDC, so it stays in native
+/// pixels at any `RendererConfig::resolution_multiplier`.
 pub const FB_WIDTH: f32 = 320.0;
 pub const FB_HEIGHT: f32 = 240.0;
 /// F3DEX2 modelview matrix stack size. Pushes past 32 are silently dropped.
 pub const RSP_MATRIX_STACK_SIZE: usize = 32;
 
+/// A vertex the RDP already sees in screen space (a decoded edge-walker triangle corner): pixel
+/// position, depth in NDC, the perspective `w`, texel-space texcoords and the shade color.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct ScreenVertex {
+    pub pos: [f32; 2],
+    pub z: f32,
+    pub w: f32,
+    pub st: [f32; 2],
+    pub rgba: [u8; 4],
+}
+
 /// Texture scaling state set by gsSPTexture.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct TextureState {
@@ -29,6 +42,23 @@ pub struct TextureState {
     pub tc: u16,
 }
 
+/// The positional half of a light slot (MM `PointLight_t`): an s16 position in the space the
+/// modelview maps into, and the raw u8 constant/linear/quadratic attenuation factors. Only used
+/// when the microcode's `G_LIGHTING_POSITIONAL` bit is set and `kc` is non-zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointLight {
+    pub pos: [f32; 3],
+    pub kc: u8,
+    pub kl: u8,
+    pub kq: u8,
+}
+
+/// Scales from the raw u8 attenuation factors to the `kc + kl*d + kq*d^2` falloff divisor. An
+/// approximation of F3DZEX2's fixed-point falloff, not a bit-exact one.
+const POINT_KC_SCALE: f32 = 1.0 / 16.0;
+const POINT_KL_SCALE: f32 = 1.0 / 2048.0;
+const POINT_KQ_SCALE: f32 = 1.0 / 524288.0;
+
 pub struct Rsp {
     cache_global_index: [u32; RSP_MAX_VERTICES],
     used: [bool; RSP_MAX_VERTICES],
@@ -50,13 +80,15 @@ pub struct Rsp {
     texcoord_table: Vec<[f32; 2]>,
     cur_texcoord_index: u32,
     modify_unit_texcoord_index: Option<u32>,
+    screen_mvp_index: Option<u32>,
     // Light state (dir camera-space s8/127, col u8/255).
     pub lights: [([f32; 3], [f32; 3]); 8],
+    pub point_lights: [PointLight; 8],
     pub ambient_col: [f32; 3],
     pub num_dir: u32,
     pub light_version: u64,
     // Dedup state for object-space light prefold.
-    last_light_key: Option<(u64, Mat4)>,
+    last_light_key: Option<(u64, Mat4, bool)>,
     cur_light_index: u32,
     // LookAt axes (S=0, T=1) in eye space (s8/127), and texgen prefold dedup state.
     pub lookat_axes: [[f32; 3]; 2],
@@ -71,6 +103,17 @@ pub struct Rsp {
     last_material_index: Option<u32>,
     last_render_mode: Option<crate::hle::blender::RenderMode>,
     last_render_mode_index: Option<u32>,
+    /// Operand of the last RSP-side G_RDPHALF_1 (the branch target of gsSPBranchLessZ / the data
+    /// segment of gsSPLoadUcode), consumed by the command that follows it.
+    pub rdphalf_1: u64,
+    /// Address of a G_RDPHALF_1 no command has consumed yet. The walk reports it as a stray
+    /// RDPHALF once the following command leaves it in place.
+    pub rdphalf_1_at: Option<u64>,
+    /// Dry-walk bound (`interpret_dry`): G_VTX reads no further than `count` fixed-point vertices
+    /// would reach.
+    pub(crate) fixed_vertex_span: bool,
+    /// S2DEX 2D object matrix (gSPObjMatrix / gSPObjSubMatrix); identity until one is loaded.
+    pub obj_mtx: crate::hle::rsp_s2dex::ObjMtx,
 }
 
 impl Default for Rsp {
@@ -100,7 +143,9 @@ impl Default for Rsp {
             texcoord_table: vec![[0.0, 0.0]],
             cur_texcoord_index: 0,
             modify_unit_texcoord_index: None,
+            screen_mvp_index: None,
             lights: [([0.0; 3], [0.0; 3]); 8],
+            point_lights: [PointLight::default(); 8],
             ambient_col: [0.0; 3],
             num_dir: 0,
             light_version: 0,
@@ -117,6 +162,10 @@ impl Default for Rsp {
             last_material_index: None,
             last_render_mode: None,
             last_render_mode_index: None,
+            rdphalf_1: 0,
+            rdphalf_1_at: None,
+            fixed_vertex_span: false,
+            obj_mtx: crate::hle::rsp_s2dex::ObjMtx::default(),
         }
     }
 }
@@ -134,6 +183,34 @@ impl Rsp {
         }
     }
 
+    /// Adopt a newly loaded microcode's constants (G_LOAD_UCODE). Matrices, lights and the vertex
+    /// cache carry over untouched; the geometry mode is remapped onto the new microcode's bit
+    /// layout (F3D and F3DEX2 place culling and smooth shading differently).
+    pub(crate) fn set_constants(&mut self, consts: crate::hle::gbi::GbiConstants) {
+        let old = self.consts;
+        let pairs = [
+            (old.g_cull_front, consts.g_cull_front),
+            (old.g_cull_back, consts.g_cull_back),
+            (old.g_shading_smooth, consts.g_shading_smooth),
+            (old.g_fog_geom, consts.g_fog_geom),
+            (old.g_lighting, consts.g_lighting),
+            (old.g_texture_gen, consts.g_texture_gen),
+            (old.g_texture_gen_linear, consts.g_texture_gen_linear),
+            (old.g_lighting_positional, consts.g_lighting_positional),
+            (old.g_clipping, consts.g_clipping),
+        ];
+        // Bits no constant names (G_ZBUFFER, G_SHADE, G_LOD) sit at the same place everywhere.
+        let named = pairs.iter().fold(0, |acc, &(from, _)| acc | from);
+        let mut geom = self.geom & !named;
+        for (from, to) in pairs {
+            if from != 0 && self.geom & from == from {
+                geom |= to;
+            }
+        }
+        self.geom = geom;
+        self.consts = consts;
+    }
+
     pub fn geometry_mode(&self) -> u32 {
         self.geom
     }
@@ -191,6 +268,50 @@ impl Rsp {
     /// their state.
     pub fn force_matrix<M: Rdram>(&mut self, mem: &M, addr: u64) {
         self.mvp = mem.read_matrix(addr, self.data_format);
+        self.publish_mvp();
+    }
+
+    /// F3D G_MV_MATRIX_2/3/4: the continuation DMAs of gSPForceMatrix, each carrying one 16-byte
+    /// quarter (`quarter` 1..=3: integer rows 2-3, fraction rows 0-1, fraction rows 2-3) of the
+    /// forced `Mtx`. Applied word by word as `insert_matrix`, so a split load that patches only
+    /// some quarters keeps the rest of the current MVP.
+    pub fn force_matrix_part<M: Rdram>(&mut self, mem: &M, addr: u64, quarter: u32) {
+        for word in 0..4u32 {
+            let at = addr + word as u64 * 4;
+            let value = ((mem.read_u16(at) as u32) << 16) | mem.read_u16(at + 2) as u32;
+            self.patch_mvp_word(quarter * 16 + word * 4, value);
+        }
+        self.publish_mvp();
+    }
+
+    /// G_MW_MATRIX (gSPInsertMatrix): overwrite the MVP word at byte offset `offset` of its
+    /// fixed-point `Mtx` image. Like `force_matrix`, the stacks are untouched, so the next matrix
+    /// operation recomputes the MVP from them.
+    pub fn insert_matrix(&mut self, offset: u32, value: u32) {
+        self.patch_mvp_word(offset, value);
+        self.publish_mvp();
+    }
+
+    /// Write one 32-bit word of the MVP's s15.16 `Mtx` layout. Words below 0x20 carry the s16
+    /// integer halves of two consecutive elements, words from 0x20 their u16 fractions; the other
+    /// half of each element is kept.
+    fn patch_mvp_word(&mut self, offset: u32, value: u32) {
+        let offset = offset & 0x3C;
+        let first = (offset & 0x1F) as usize / 2;
+        for (k, half) in [(first, (value >> 16) as u16), (first + 1, value as u16)] {
+            let cell = &mut self.mvp[k / 4][k % 4];
+            let full = (*cell as f64 * 65536.0).round() as i32;
+            let full = if offset < 0x20 {
+                ((half as i16 as i32) << 16) | (full & 0xFFFF)
+            } else {
+                (full & !0xFFFF) | half as i32
+            };
+            *cell = full as f32 / 65536.0;
+        }
+    }
+
+    /// Make the current `mvp` the one subsequent vertices reference.
+    fn publish_mvp(&mut self) {
         if self.mvp_table.last() != Some(&self.mvp) {
             self.mvp_table.push(self.mvp);
         }
@@ -294,24 +415,49 @@ impl Rsp {
         // Object-space light set for these vertices: bring each eye-space light dir INTO object
         // space via the INVERSE modelview rotation (mul_col_vec3 = M·v), so the lit region stays
         // world-fixed as the model spins. (mul_row_vec4 here would apply the FORWARD rotation and
-        // co-rotate the light with the geometry — see math::mul_col_vec3.)
+        // co-rotate the light with the geometry — see math::mul_col_vec3.) Point lights (F3DZEX2
+        // `G_LIGHTING_POSITIONAL`) bring their position in through the full inverse modelview
+        // instead, assuming a uniformly scaled rotation; `light_atten` carries their falloff.
         let lit = (self.geom & self.consts.g_lighting) != 0;
         let light_count = if lit { self.num_dir + 1 } else { 0 };
         if lit {
             let mv = self.model_stack[self.model_stack_size - 1];
-            let key = (self.light_version, mv);
+            let positional = (self.geom & self.consts.g_lighting_positional) != 0;
+            let key = (self.light_version, mv, positional);
             if self.last_light_key != Some(key) {
                 self.cur_light_index = scene.lights_table.len() as u32;
+                let scale_sq = mv[0][0] * mv[0][0] + mv[0][1] * mv[0][1] + mv[0][2] * mv[0][2];
                 for k in 0..self.num_dir as usize {
                     let (dir, col) = self.lights[k];
+                    let point = self.point_lights[k];
+                    if positional && point.kc != 0 && scale_sq > 0.0 {
+                        let rel = [
+                            point.pos[0] - mv[3][0],
+                            point.pos[1] - mv[3][1],
+                            point.pos[2] - mv[3][2],
+                        ];
+                        let o = mul_col_vec3(mv, rel);
+                        scene
+                            .lights_table
+                            .push(([o[0] / scale_sq, o[1] / scale_sq, o[2] / scale_sq], col));
+                        scene.light_atten.push([
+                            point.kc as f32 * POINT_KC_SCALE,
+                            point.kl as f32 * POINT_KL_SCALE,
+                            point.kq as f32 * POINT_KQ_SCALE,
+                            scale_sq.sqrt(),
+                        ]);
+                        continue;
+                    }
                     let o = mul_col_vec3(mv, dir);
                     let len = (o[0] * o[0] + o[1] * o[1] + o[2] * o[2]).sqrt();
                     let inv = if len > 0.0 { 1.0 / len } else { 0.0 };
                     scene
                         .lights_table
                         .push(([o[0] * inv, o[1] * inv, o[2] * inv], col));
+                    scene.light_atten.push([0.0; 4]);
                 }
                 scene.lights_table.push(([0.0, 0.0, 0.0], self.ambient_col)); // ambient last
+                scene.light_atten.push([0.0; 4]);
                 self.last_light_key = Some(key);
             }
         }
@@ -346,6 +492,12 @@ impl Rsp {
         // Vertex stride + field layout are backend-decided (fixed-point vs GBI_FLOATS) — see
         // `Rdram::read_vertex`. Decoding a float-GBI vertex as s16 misreads every position.
         let stride = mem.vertex_stride(self.data_format);
+        let count = if self.fixed_vertex_span {
+            let span = count as u64 * mem.vertex_stride(crate::hle::mem::GbiDataFormat::Fixed);
+            (span / stride) as u32
+        } else {
+            count
+        };
         // Per-vertex fog capture (fog indices): the G_FOG geom bit AT LOAD TIME.
         // sm64 sets G_FOG before loading fogged terrain and clears it before the HUD/dialog, so this
         // fogs terrain while leaving overlay geometry's alpha untouched.
@@ -360,6 +512,8 @@ impl Rsp {
             scene.raw_pos.push(v.pos);
             scene.modify_flags.push(0);
             scene.modify_screen.push([0.0; 4]);
+            scene.line_offset.push(0.0);
+            scene.line_other.push(0);
             scene.mtx_index.push(self.cur_mvp_index);
             scene.viewport_index.push(self.cur_viewport_index);
             scene.raw_st.push([v.st[0] as f32, v.st[1] as f32]);
@@ -391,6 +545,43 @@ impl Rsp {
         index
     }
 
+    /// Matrix that maps the object-space position `(0, 0, w)` to clip `(0, 0, 0, w)`; screen
+    /// vertices carry their `w` there and take x/y/z from the modify overrides.
+    fn screen_mvp_index(&mut self) -> u32 {
+        if let Some(index) = self.screen_mvp_index {
+            return index;
+        }
+        let mut m = [[0.0; 4]; 4];
+        m[2][3] = 1.0;
+        self.mvp_table.push(m);
+        let index = (self.mvp_table.len() - 1) as u32;
+        self.screen_mvp_index = Some(index);
+        index
+    }
+
+    /// Append a screen-space vertex row; returns its global index. The row rides the
+    /// gSPModifyVertex overrides for position and depth, so the kernel only recovers `w` (for
+    /// perspective-correct texcoords) from the transform.
+    pub(crate) fn push_screen_vertex(&mut self, v: &ScreenVertex, scene: &mut Scene) -> u32 {
+        let gi = scene.raw_pos.len() as u32;
+        scene.raw_pos.push([0.0, 0.0, v.w]);
+        scene.modify_flags.push(3);
+        scene.modify_screen.push([v.pos[0], v.pos[1], v.z, 0.0]);
+        scene.line_offset.push(0.0);
+        scene.line_other.push(0);
+        scene.mtx_index.push(self.screen_mvp_index());
+        scene.viewport_index.push(self.cur_viewport_index);
+        scene.raw_st.push(v.st);
+        scene.texcoord_index.push(self.modify_unit_texcoord_index());
+        scene.cn.push(u32::from_le_bytes(v.rgba));
+        scene.light_index.push(0);
+        scene.light_count.push(0);
+        scene.texgen_mode.push(0);
+        scene.fog.push(0);
+        scene.lookat_index.push(0);
+        gi
+    }
+
     /// gSPModifyVertex (F3D G_MW_POINTS). `attr` is `where % 40` and `value` is w1.
     pub fn modify_vertex(&mut self, dst_index: u32, attr: u32, value: u32, scene: &mut Scene) {
         let slot = dst_index as usize;
@@ -405,20 +596,7 @@ impl Rsp {
         // RT64 copy-on-use: a modify must not edit a vertex row already recorded by a draw.
         // Intentional RT64 divergence: carry modify state into the clone, matching in-place DMEM writes.
         if self.used[slot] {
-            let next = scene.raw_pos.len();
-            scene.raw_pos.push(scene.raw_pos[gi]);
-            scene.raw_st.push(scene.raw_st[gi]);
-            scene.mtx_index.push(scene.mtx_index[gi]);
-            scene.viewport_index.push(scene.viewport_index[gi]);
-            scene.texcoord_index.push(scene.texcoord_index[gi]);
-            scene.cn.push(scene.cn[gi]);
-            scene.light_index.push(scene.light_index[gi]);
-            scene.light_count.push(scene.light_count[gi]);
-            scene.texgen_mode.push(scene.texgen_mode[gi]);
-            scene.fog.push(scene.fog[gi]);
-            scene.lookat_index.push(scene.lookat_index[gi]);
-            scene.modify_flags.push(scene.modify_flags[gi]);
-            scene.modify_screen.push(scene.modify_screen[gi]);
+            let next = clone_vertex_row(scene, gi);
             self.cache_global_index[slot] = next as u32;
             self.used[slot] = false;
             gi = next;
@@ -453,13 +631,63 @@ impl Rsp {
         }
     }
 
+    /// Consume the latched G_RDPHALF_1 operand (G_BRANCH_Z / G_LOAD_UCODE), clearing the stray
+    /// check.
+    pub fn take_rdphalf_1(&mut self) -> u64 {
+        self.rdphalf_1_at = None;
+        self.rdphalf_1
+    }
+
+    /// Screen-space depth of vertex-cache `slot` in viewport Z units (`0..=G_MAXZ`), the value
+    /// G_BRANCH_Z compares against. A CPU mirror of the `rsp_process.wgsl` transform using the
+    /// matrix and viewport the vertex was loaded under. `None` for an empty slot or `w == 0`.
+    pub fn vertex_screen_z(&self, slot: u32, scene: &Scene) -> Option<f32> {
+        let slot = slot as usize;
+        if slot >= RSP_MAX_VERTICES {
+            return None;
+        }
+        let gi = self.cache_global_index[slot] as usize;
+        let pos = *scene.raw_pos.get(gi)?;
+        let mvp = self.mvp_table[scene.mtx_index[gi] as usize];
+        let (scale, trans) = self.viewport_table[scene.viewport_index[gi] as usize];
+        let clip = mul_row_vec4([pos[0], pos[1], pos[2], 1.0], mvp);
+        if clip[3] == 0.0 {
+            return None;
+        }
+        Some((clip[2] / clip[3] * scale[2] + trans[2]) * DEPTH_RANGE)
+    }
+
+    /// Frustum clip codes of vertex-cache `slot` under the MVP it was loaded with — one bit per
+    /// plane the clip-space position lies outside of (`-x`, `+x`, `-y`, `+y`, near, far). G_CULLDL
+    /// ANDs these across a range: a shared bit means every vertex is beyond the same plane. `None`
+    /// for an empty slot.
+    pub fn vertex_clip_codes(&self, slot: u32, scene: &Scene) -> Option<u8> {
+        let slot = slot as usize;
+        if slot >= RSP_MAX_VERTICES {
+            return None;
+        }
+        let gi = self.cache_global_index[slot] as usize;
+        let pos = *scene.raw_pos.get(gi)?;
+        let mvp = self.mvp_table[scene.mtx_index[gi] as usize];
+        let [x, y, z, w] = mul_row_vec4([pos[0], pos[1], pos[2], 1.0], mvp);
+        let codes = [x < -w, x > w, y < -w, y > w, z < -w, z > w];
+        Some(
+            codes
+                .iter()
+                .enumerate()
+                .fold(0u8, |acc, (bit, &out)| acc | ((out as u8) << bit)),
+        )
+    }
+
     /// Record a triangle. `pair_target` routes the per-triangle `DrawRun`:
     /// - `None`  → the flat `scene.draw_runs` (the 3D / pair-less path, byte-identical to before);
     /// - `Some(p)` → `scene.framebuffer_pairs[p].ops` as a `SceneOp::Tris` (the 2D-recording path).
     ///
     /// Either way the three indices are pushed onto the shared `scene.indices` buffer in draw order,
     /// and coalescing only extends the LAST run/op when it is a `Tris` with a matching
-    /// `(cull, material_index, render_mode_index)` key.
+    /// `(cull, flat, material_index, render_mode_index)` key. With G_SHADING_SMOOTH clear the
+    /// triangle is rotated so the microcode's provoking vertex (first on F3DEX2, last on F3D)
+    /// leads; rotation keeps the winding, so culling is unaffected.
     #[allow(clippy::too_many_arguments)]
     pub fn draw_tri(
         &mut self,
@@ -479,7 +707,7 @@ impl Rsp {
         for slot in [a, b, c] {
             self.used[slot as usize] = true;
         }
-        let (mut a, b, mut c) = (a, b, c);
+        let (mut a, mut b, mut c) = (a, b, c);
         // Cull-front: swap a<->c so the single binary cull state culls front faces.
         // The GPU rasterizer does the area test.
         if cull == self.consts.g_cull_front {
@@ -490,62 +718,83 @@ impl Rsp {
         } else {
             CullKind::None
         };
-        // The kernel emits TEXEL-space texcoords for non-texgen verts but NORMALIZED texcoords for
-        // texgen verts, and the renderer applies ONE per-run `inv_tex_size` scalar. Never coalesce
-        // across a texgen boundary, or a run would mix the two conventions and the single scalar
-        // would mis-scale half of it. texgen is a per-vertex geometry-mode state; toggling it changes
-        // neither material_index nor render_mode_index, so it is not otherwise in the coalesce key.
+        let flat = self.geom & self.consts.g_shading_smooth == 0;
+        // Flat shading: the provoking vertex is the command's first slot (F3DEX2) or last (F3D).
+        // The cull-front swap moved it to the other end; rotating it to the front keeps winding.
+        if flat && self.consts.flat_provoking_last != (cull == self.consts.g_cull_front) {
+            (a, b, c) = (c, a, b);
+        }
         let tri_texgen = scene
             .texgen_mode
             .get(self.cache_global_index[a as usize] as usize)
             .copied()
             .unwrap_or(0)
             != 0;
-        let last_first_index = match pair_target {
-            Some(p) => match scene.framebuffer_pairs[p].ops.last() {
-                Some(SceneOp::Tris(run)) => Some(run.index_start),
-                _ => None,
-            },
-            None => scene.draw_runs.last().map(|run| run.index_start),
-        };
-        let last_run_texgen = last_first_index
-            .and_then(|i| scene.indices.get(i as usize).copied())
-            .and_then(|gi| scene.texgen_mode.get(gi as usize).copied())
-            .unwrap_or(0)
-            != 0;
-        let texgen_matches = last_run_texgen == tri_texgen;
-        let coalesces = |run: &DrawRun| {
-            texgen_matches
-                && run.cull == kind
-                && run.material_index == material_index
-                && run.render_mode_index == render_mode_index
-        };
-        let index_start = scene.indices.len() as u32;
-        match pair_target {
-            Some(p) => match scene.framebuffer_pairs[p].ops.last_mut() {
-                Some(SceneOp::Tris(run)) if coalesces(run) => run.index_count += 3,
-                _ => scene.framebuffer_pairs[p].ops.push(SceneOp::Tris(DrawRun {
-                    material_index,
-                    render_mode_index,
-                    cull: kind,
-                    index_count: 3,
-                    index_start,
-                })),
-            },
-            None => match scene.draw_runs.last_mut() {
-                Some(run) if coalesces(run) => run.index_count += 3,
-                _ => scene.draw_runs.push(DrawRun {
-                    material_index,
-                    render_mode_index,
-                    cull: kind,
-                    index_count: 3,
-                    index_start,
-                }),
-            },
+        let indices = [a, b, c].map(|slot| self.cache_global_index[slot as usize]);
+        push_run_indices(
+            scene,
+            pair_target,
+            kind,
+            flat,
+            tri_texgen,
+            material_index,
+            render_mode_index,
+            &indices,
+        );
+    }
+
+    /// Record a G_LINE3D segment between cache slots `a` and `b`, `width` pixels wide. The line
+    /// becomes a quad of four corner vertices, clones of the endpoints whose `line_offset` /
+    /// `line_other` tell the renderer to push them half the width off the segment in screen space,
+    /// so it rasterizes through the same run, combiner and blender path as a triangle. Lines are
+    /// never culled. `pair_target` routes the run as in `draw_tri`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_line(
+        &mut self,
+        a: u32,
+        b: u32,
+        width: f32,
+        material_index: u32,
+        render_mode_index: u32,
+        scene: &mut Scene,
+        pair_target: Option<usize>,
+    ) {
+        let (ga, gb) = (
+            self.cache_global_index[a as usize] as usize,
+            self.cache_global_index[b as usize] as usize,
+        );
+        if ga >= scene.raw_pos.len() || gb >= scene.raw_pos.len() {
+            return;
         }
-        scene.indices.push(self.cache_global_index[a as usize]);
-        scene.indices.push(self.cache_global_index[b as usize]);
-        scene.indices.push(self.cache_global_index[c as usize]);
+        self.used[a as usize] = true;
+        self.used[b as usize] = true;
+        let half = width / 2.0;
+        // The perpendicular flips with the segment direction, so the `b` corners take the
+        // opposite signs to land on the same sides as the `a` ones.
+        let corners = [
+            (ga, gb, half),
+            (ga, gb, -half),
+            (gb, ga, half),
+            (gb, ga, -half),
+        ]
+        .map(|(end, other, offset)| {
+            let corner = clone_vertex_row(scene, end);
+            scene.line_offset[corner] = offset;
+            scene.line_other[corner] = other as u32;
+            corner as u32
+        });
+        let [a_pos, a_neg, b_neg, b_pos] = corners;
+        let line_texgen = scene.texgen_mode.get(ga).copied().unwrap_or(0) != 0;
+        push_run_indices(
+            scene,
+            pair_target,
+            CullKind::None,
+            false,
+            line_texgen,
+            material_index,
+            render_mode_index,
+            &[a_pos, a_neg, b_neg, a_pos, b_neg, b_pos],
+        );
     }
 
     /// gsSPTexture: set the texture scaling state.
@@ -621,6 +870,7 @@ impl Rsp {
             ((1u32 << length) - 1) << shift
         };
         rdp.other_mode_h = (rdp.other_mode_h & !mask) | data;
+        rdp.other_mode_h_unset &= !mask;
     }
 
     pub fn set_texture_image(
@@ -668,6 +918,18 @@ impl Rsp {
                 mem.read_i8(addr + 10) as f32 / 127.0,
             ];
             self.lights[light_idx as usize] = (dir, col);
+            // The same bytes read as a PointLight_t: kc/kl sit in the Light_t pad bytes, the s16
+            // position overlays the direction, and kq follows it.
+            self.point_lights[light_idx as usize] = PointLight {
+                pos: [
+                    mem.read_i16(addr + 8) as f32,
+                    mem.read_i16(addr + 10) as f32,
+                    mem.read_i16(addr + 12) as f32,
+                ],
+                kc: mem.read_u8(addr + 3),
+                kl: mem.read_u8(addr + 7),
+                kq: mem.read_u8(addr + 14),
+            };
         }
         self.light_version += 1;
     }
@@ -747,7 +1009,7 @@ pub fn snapshot_run(
     };
 
     // --- Render mode (decoded every call; cheap, no texture) ---
-    let rm = crate::hle::blender::decode_render_mode(rdp.other_mode_l, rdp.other_mode_h, rsp.geom);
+    let rm = prim_depth_render_mode(rdp, rsp.geom);
     let render_mode_index = if rsp.last_render_mode == Some(rm) {
         // Same render mode: reuse the existing index (dedup).
         rsp.last_render_mode_index.unwrap()
@@ -769,6 +1031,17 @@ pub fn snapshot_run(
     Some((material_index, render_mode_index))
 }
 
+/// Decode the run's render mode and, under G_ZS_PRIM, stamp the RDP's primitive depth into it so
+/// the renderer can draw the whole primitive at that constant Z.
+fn prim_depth_render_mode(rdp: &crate::hle::rdp::Rdp, geom: u32) -> crate::hle::RenderMode {
+    let mut rm = crate::hle::blender::decode_render_mode(rdp.other_mode_l, rdp.other_mode_h, geom);
+    rm.tex_persp = rdp.tex_persp();
+    if rm.z_source_prim {
+        rm.prim_depth = rdp.prim_depth;
+    }
+    rm
+}
+
 /// Walk-state for the 2D framebuffer-pair recorder (spec §1.1). Lives as a local in `interpret`
 /// and is threaded through `Ctx` so both the inline rect slot and `draw_tri` (via `record_tri`)
 /// share it. Pair-less scenes (no G_SETCIMG) leave this all-default → no pairs, flat `draw_runs`.
@@ -850,7 +1123,7 @@ pub(crate) fn snapshot_rect_run(
             (scene.materials.len() - 1) as u32
         }
     };
-    let rm = crate::hle::blender::decode_render_mode(rdp.other_mode_l, rdp.other_mode_h, rsp.geom);
+    let rm = prim_depth_render_mode(rdp, rsp.geom);
     let render_mode_index = match scene.render_modes.last() {
         Some(last) if *last == rm => (scene.render_modes.len() - 1) as u32,
         _ => {
@@ -861,6 +1134,152 @@ pub(crate) fn snapshot_rect_run(
     (material_index, render_mode_index)
 }
 
+/// fb_source: the latest PRIOR pair whose framebuffer byte-range contains `tex_addr` (a
+/// framebuffer-as-texture read-back). The current pair is excluded (it is not yet recorded as a
+/// finished framebuffer).
+pub(crate) fn fb_source_for(scene: &Scene, rec: &PairRec, tex_addr: u64) -> Option<u64> {
+    scene.framebuffer_pairs[..rec.cur_pair]
+        .iter()
+        .rev()
+        .filter(|p| !p.is_depth_clear)
+        .find(|p| {
+            let start = p.color_image.addr;
+            let end = start
+                + (p.color_image.width as u64) * (p.size_extent.1 as u64) * bpp(p.color_image.siz);
+            (start..end).contains(&tex_addr)
+        })
+        .map(|p| p.color_image.addr)
+}
+
+/// Record a texture rectangle into the current pair: opens the pair / emits a `SetScissor` as
+/// needed, snapshots the rect material, resolves `fb_source` and pushes the `SceneOp::TexRect`.
+/// Shared by the inline G_TEXRECT decode and the S2DEX object/background commands; the caller has
+/// already dropped rects drawn before the first CIMG. `st` = `(uls, ult)` (S10.5) and `step` =
+/// `(dsdx, dtdy)` (S5.10), both in the RDP's TEXRECT encoding.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_texrect(
+    rsp: &Rsp,
+    rdp: &mut crate::hle::rdp::Rdp,
+    scene: &mut Scene,
+    rec: &mut PairRec,
+    diags: &mut Vec<crate::diag::Diagnostic>,
+    pc: u64,
+    rect: Rect,
+    st: (i16, i16),
+    step: (i16, i16),
+    flip: bool,
+) {
+    ensure_pair_open(scene, rdp, rec);
+    record_scissor_if_changed(scene, rdp, rec);
+    let (material_index, render_mode_index) = snapshot_rect_run(rsp, rdp, diags, scene, pc);
+    let copy_mode = ((rdp.other_mode_h >> 20) & 3) == crate::hle::consts::G_CYC_COPY;
+
+    let fb_source = fb_source_for(scene, rec, rdp.tex_image.3);
+    scene.framebuffer_pairs[rec.cur_pair]
+        .ops
+        .push(SceneOp::TexRect {
+            rect,
+            uls: st.0,
+            ult: st.1,
+            dsdx: step.0,
+            dtdy: step.1,
+            flip,
+            copy_mode,
+            material_index,
+            render_mode_index,
+            fb_source,
+        });
+}
+
+/// Append `indices` (whole triangles) to the run they extend, or open a new one. `pair_target`
+/// picks `scene.draw_runs` (`None`) or pair `p`'s op-stream; only the LAST run/op extends, and only
+/// when its `(cull, flat, material_index, render_mode_index)` key and texgen convention match.
+#[allow(clippy::too_many_arguments)]
+fn push_run_indices(
+    scene: &mut Scene,
+    pair_target: Option<usize>,
+    kind: CullKind,
+    flat: bool,
+    texgen: bool,
+    material_index: u32,
+    render_mode_index: u32,
+    indices: &[u32],
+) {
+    // The kernel emits TEXEL-space texcoords for non-texgen verts but NORMALIZED texcoords for
+    // texgen verts, and the renderer applies ONE per-run `inv_tex_size` scalar. Never coalesce
+    // across a texgen boundary, or a run would mix the two conventions and the single scalar
+    // would mis-scale half of it. texgen is a per-vertex geometry-mode state; toggling it changes
+    // neither material_index nor render_mode_index, so it is not otherwise in the coalesce key.
+    let last_first_index = match pair_target {
+        Some(p) => match scene.framebuffer_pairs[p].ops.last() {
+            Some(SceneOp::Tris(run)) => Some(run.index_start),
+            _ => None,
+        },
+        None => scene.draw_runs.last().map(|run| run.index_start),
+    };
+    let last_run_texgen = last_first_index
+        .and_then(|i| scene.indices.get(i as usize).copied())
+        .and_then(|gi| scene.texgen_mode.get(gi as usize).copied())
+        .unwrap_or(0)
+        != 0;
+    let texgen_matches = last_run_texgen == texgen;
+    let coalesces = |run: &DrawRun| {
+        texgen_matches
+            && run.cull == kind
+            && run.flat == flat
+            && run.material_index == material_index
+            && run.render_mode_index == render_mode_index
+    };
+    let index_start = scene.indices.len() as u32;
+    let index_count = indices.len() as u32;
+    match pair_target {
+        Some(p) => match scene.framebuffer_pairs[p].ops.last_mut() {
+            Some(SceneOp::Tris(run)) if coalesces(run) => run.index_count += index_count,
+            _ => scene.framebuffer_pairs[p].ops.push(SceneOp::Tris(DrawRun {
+                material_index,
+                render_mode_index,
+                cull: kind,
+                flat,
+                index_count,
+                index_start,
+            })),
+        },
+        None => match scene.draw_runs.last_mut() {
+            Some(run) if coalesces(run) => run.index_count += index_count,
+            _ => scene.draw_runs.push(DrawRun {
+                material_index,
+                render_mode_index,
+                cull: kind,
+                flat,
+                index_count,
+                index_start,
+            }),
+        },
+    }
+    scene.indices.extend_from_slice(indices);
+}
+
+/// Append a copy of vertex row `gi` to every per-vertex Scene table; returns the new row's index.
+fn clone_vertex_row(scene: &mut Scene, gi: usize) -> usize {
+    let next = scene.raw_pos.len();
+    scene.raw_pos.push(scene.raw_pos[gi]);
+    scene.raw_st.push(scene.raw_st[gi]);
+    scene.mtx_index.push(scene.mtx_index[gi]);
+    scene.viewport_index.push(scene.viewport_index[gi]);
+    scene.texcoord_index.push(scene.texcoord_index[gi]);
+    scene.cn.push(scene.cn[gi]);
+    scene.light_index.push(scene.light_index[gi]);
+    scene.light_count.push(scene.light_count[gi]);
+    scene.texgen_mode.push(scene.texgen_mode[gi]);
+    scene.fog.push(scene.fog[gi]);
+    scene.lookat_index.push(scene.lookat_index[gi]);
+    scene.modify_flags.push(scene.modify_flags[gi]);
+    scene.modify_screen.push(scene.modify_screen[gi]);
+    scene.line_offset.push(scene.line_offset[gi]);
+    scene.line_other.push(scene.line_other[gi]);
+    next
+}
+
 /// Record a triangle through the pair recorder. When a CIMG has been seen the tri is routed into the
 /// current `FramebufferPair`'s ordered op-stream (opening a pair / emitting a `SetScissor` as needed);
 /// otherwise it falls through to the flat `draw_runs` path SILENTLY — a pure-3D DL never emits a CIMG,
@@ -894,6 +1313,81 @@ pub(crate) fn record_tri(
     }
 }
 
+/// Record a raw RDP triangle (already in screen space) into the current pair. The RDP does no
+/// culling, so the run is never culled. The caller has already dropped triangles drawn before
+/// the first CIMG.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_screen_tri(
+    rsp: &mut Rsp,
+    rdp: &mut crate::hle::rdp::Rdp,
+    scene: &mut Scene,
+    rec: &mut PairRec,
+    verts: &[ScreenVertex; 3],
+    material_index: u32,
+    render_mode_index: u32,
+) {
+    ensure_pair_open(scene, rdp, rec);
+    record_scissor_if_changed(scene, rdp, rec);
+    let indices = verts.map(|v| rsp.push_screen_vertex(&v, scene));
+    push_run_indices(
+        scene,
+        Some(rec.cur_pair),
+        CullKind::None,
+        false,
+        false,
+        material_index,
+        render_mode_index,
+        &indices,
+    );
+}
+
+/// Record a fill rectangle into the current pair with the current fill color. The caller has
+/// already dropped rects drawn before the first CIMG.
+pub(crate) fn record_fillrect(
+    scene: &mut Scene,
+    rdp: &mut crate::hle::rdp::Rdp,
+    rec: &mut PairRec,
+    rect: Rect,
+) {
+    ensure_pair_open(scene, rdp, rec);
+    record_scissor_if_changed(scene, rdp, rec);
+    let color_raw = rdp.fill_color_raw;
+    scene.framebuffer_pairs[rec.cur_pair]
+        .ops
+        .push(SceneOp::FillRect { rect, color_raw });
+}
+
+/// Record a G_LINE3D segment through the pair recorder, routed exactly like `record_tri`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_line(
+    rsp: &mut Rsp,
+    rdp: &mut crate::hle::rdp::Rdp,
+    scene: &mut Scene,
+    rec: &mut PairRec,
+    a: u32,
+    b: u32,
+    width: f32,
+    material_index: u32,
+    render_mode_index: u32,
+) {
+    let pair_target = if rec.have_seen_cimg {
+        ensure_pair_open(scene, rdp, rec);
+        record_scissor_if_changed(scene, rdp, rec);
+        Some(rec.cur_pair)
+    } else {
+        None
+    };
+    rsp.draw_line(
+        a,
+        b,
+        width,
+        material_index,
+        render_mode_index,
+        scene,
+        pair_target,
+    );
+}
+
 #[cfg(test)]
 mod consts_wired_tests {
     use super::*;
@@ -1063,15 +1557,38 @@ mod phase3_state_tests {
 
         assert_eq!(scene.mvp_table.last(), Some(&forced));
     }
+
+    #[test]
+    fn insert_matrix_patches_one_half_and_the_next_load_recomputes() {
+        use crate::hle::consts::{G_MTX_LOAD, G_MWO_MATRIX_XX_XY_F, G_MWO_MATRIX_XX_XY_I};
+        let bytes = mtx_to_bytes(identity());
+        let mem = RdramImage::new(&bytes);
+        let mut rsp = Rsp::default();
+
+        // Word 7 holds elements wz, ww: integer halves 5 and -2, then fractions .5 and .25.
+        rsp.insert_matrix(G_MWO_MATRIX_XX_XY_I as u32 + 0x1C, 0x0005_FFFE);
+        assert_eq!(rsp.mvp[3], [0.0, 0.0, 5.0, -2.0]);
+        rsp.insert_matrix(G_MWO_MATRIX_XX_XY_F as u32 + 0x1C, 0x8000_4000);
+        assert_eq!(rsp.mvp[3], [0.0, 0.0, 5.5, -1.75]);
+        assert_eq!(rsp.mvp_table.len(), 3);
+        assert_eq!(rsp.cur_mvp_index, 2);
+
+        // The stacks were untouched: a modelview load recomputes identity from them.
+        rsp.matrix(&mem, 0, G_MTX_LOAD);
+        assert_eq!(rsp.mvp, identity());
+        assert_eq!(rsp.cur_mvp_index, 3);
+    }
 }
 
 #[cfg(test)]
 mod draw_runs_tests {
     use super::*;
-    use crate::hle::consts::{G_CULL_BACK, G_CULL_FRONT};
+    use crate::hle::consts::{G_CULL_BACK, G_CULL_FRONT, G_SHADING_SMOOTH};
 
+    /// A smooth-shaded F3DEX2 RSP with cache slots 0..3 mapped to global vertices 0..3.
     fn rsp_three() -> (Rsp, Scene) {
         let mut rsp = Rsp::default();
+        rsp.modify_geometry_mode(!0, G_SHADING_SMOOTH);
         for slot in 0..3u32 {
             rsp.cache_global_index[slot as usize] = slot;
         }
@@ -1089,6 +1606,7 @@ mod draw_runs_tests {
                 material_index: 0,
                 render_mode_index: 0,
                 cull: CullKind::None,
+                flat: false,
                 index_count: 3,
                 index_start: 0,
             }]
@@ -1107,6 +1625,7 @@ mod draw_runs_tests {
                 material_index: 0,
                 render_mode_index: 0,
                 cull: CullKind::Cull,
+                flat: false,
                 index_count: 3,
                 index_start: 0,
             }]
@@ -1127,6 +1646,7 @@ mod draw_runs_tests {
                     material_index: 0,
                     render_mode_index: 0,
                     cull: CullKind::None,
+                    flat: false,
                     index_count: 3,
                     index_start: 0,
                 },
@@ -1134,6 +1654,7 @@ mod draw_runs_tests {
                     material_index: 0,
                     render_mode_index: 0,
                     cull: CullKind::Cull,
+                    flat: false,
                     index_count: 6,
                     index_start: 3,
                 },
@@ -1151,12 +1672,51 @@ mod draw_runs_tests {
                 material_index: 5,
                 render_mode_index: 7,
                 cull: CullKind::None,
+                flat: false,
                 index_count: 3,
                 index_start: 0,
             }]
         );
     }
 
+    #[test]
+    fn flat_shading_leads_with_the_ucodes_provoking_vertex() {
+        use crate::hle::gbi::GbiUcode;
+        // (ucode, cull-front, expected indices): F3DEX2 provokes from the first slot, F3D from
+        // the last; the cull-front swap must not move the provoking vertex off the front.
+        for (ucode, front, expected) in [
+            (GbiUcode::F3dex2, false, [0, 1, 2]),
+            (GbiUcode::F3dex2, true, [0, 2, 1]),
+            (GbiUcode::F3d, false, [2, 0, 1]),
+            (GbiUcode::F3d, true, [2, 1, 0]),
+        ] {
+            let consts = ucode.constants();
+            let mut rsp = Rsp::new(consts, crate::hle::mem::GbiDataFormat::Fixed);
+            for slot in 0..3u32 {
+                rsp.cache_global_index[slot as usize] = slot;
+            }
+            if front {
+                rsp.modify_geometry_mode(!0, consts.g_cull_front);
+            }
+            let mut scene = Scene::default();
+            rsp.draw_tri(0, 1, 2, 0, 0, &mut scene, None);
+            assert_eq!(scene.indices, expected, "{ucode:?} cull_front={front}");
+            assert!(scene.draw_runs[0].flat);
+        }
+    }
+
+    #[test]
+    fn run_splits_on_flat_shading_change() {
+        let (mut rsp, mut scene) = rsp_three();
+        rsp.draw_tri(0, 1, 2, 0, 0, &mut scene, None);
+        rsp.modify_geometry_mode(!G_SHADING_SMOOTH, 0);
+        rsp.draw_tri(0, 1, 2, 0, 0, &mut scene, None); // flat -> new run
+        assert_eq!(
+            scene.draw_runs.iter().map(|r| r.flat).collect::<Vec<_>>(),
+            vec![false, true]
+        );
+    }
+
     #[test]
     fn run_splits_on_material_index_change() {
         let (mut rsp, mut scene) = rsp_three();
@@ -1297,6 +1857,43 @@ mod lights_table_tests {
         assert_eq!(scene.lights_table[1].1, [0.2, 0.2, 0.2]);
     }
 
+    #[test]
+    fn point_light_position_is_brought_into_object_space() {
+        // Modelview: uniform scale 2 then translate +10 X. A point light at (30,0,0) sits at
+        // object-space (10,0,0), and object distances are doubled back by the scale term.
+        let mv: crate::hle::math::Mat4 = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0],
+            [10.0, 0.0, 0.0, 1.0],
+        ];
+        let mut bytes = n64_gbi::encode::mtx_to_bytes(mv).to_vec();
+        bytes.extend([0u8; 16]);
+        let rdram = RdramImage::new(&bytes);
+        let consts = crate::hle::gbi::GbiUcode::F3dzex2.constants();
+        let mut rsp = Rsp::new(consts, crate::hle::mem::GbiDataFormat::Fixed);
+        rsp.matrix(&rdram, 0, crate::hle::consts::G_MTX_LOAD);
+        rsp.lights[0] = ([0.0, 1.0, 0.0], [1.0, 1.0, 1.0]);
+        rsp.point_lights[0] = PointLight {
+            pos: [30.0, 0.0, 0.0],
+            kc: 8,
+            kl: 0,
+            kq: 0,
+        };
+        rsp.set_num_lights(24);
+        rsp.modify_geometry_mode(!0, consts.g_lighting | consts.g_lighting_positional);
+        let mut scene = Scene::default();
+        rsp.set_vertex(&rdram, 64, 1, 0, &Default::default(), &mut scene);
+        assert_eq!(scene.lights_table[0].0, [10.0, 0.0, 0.0]);
+        assert_eq!(scene.light_atten[0], [0.5, 0.0, 0.0, 2.0]);
+
+        // Clearing the positional bit re-folds the same slot as a directional light.
+        rsp.modify_geometry_mode(!consts.g_lighting_positional, 0);
+        rsp.set_vertex(&rdram, 64, 1, 0, &Default::default(), &mut scene);
+        assert_eq!(scene.lights_table[2].0, [0.0, 1.0, 0.0]);
+        assert_eq!(scene.light_atten[2], [0.0; 4]);
+    }
+
     #[test]
     fn lighting_off_yields_zero_light_count() {
         let mut rsp = Rsp::default(); // G_LIGHTING not set
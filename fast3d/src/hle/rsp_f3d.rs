@@ -162,7 +162,12 @@ fn move_mem<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
             let addr = cx.mem.resolve_masked(c.w1_addr);
             cx.rsp.force_matrix(cx.mem, addr);
         }
-        G_MV_MATRIX_2 | G_MV_MATRIX_3 | G_MV_MATRIX_4 | G_MV_TXTATT => {}
+        G_MV_MATRIX_2 | G_MV_MATRIX_3 | G_MV_MATRIX_4 => {
+            let addr = cx.mem.resolve_masked(c.w1_addr);
+            let quarter = (idx - G_MV_MATRIX_2) as u32 / 2 + 1;
+            cx.rsp.force_matrix_part(cx.mem, addr, quarter);
+        }
+        G_MV_TXTATT => {}
         _ => cx.diags.push(Diagnostic {
             at: cx.pc,
             kind: DiagKind::UnhandledMovemem(idx),
@@ -194,7 +199,8 @@ fn set_other_mode_l<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
 fn move_word<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     let ty = c.p0(0, 8) as u8;
     match ty {
-        G_MW_MATRIX | G_MW_CLIP | G_MW_PERSPNORM => {}
+        G_MW_MATRIX => cx.rsp.insert_matrix(c.p0(8, 16), c.w1),
+        G_MW_CLIP | G_MW_PERSPNORM => {}
         G_MW_POINTS => {
             let offset = c.p0(8, 16);
             cx.rsp
@@ -237,7 +243,9 @@ fn set_texture_image<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
 
 #[cfg(all(test, feature = "asm"))]
 mod phase2_tests {
-    use crate::hle::consts::{G_CULL_FRONT, G_FOG, G_RM_OPA_SURF, G_RM_OPA_SURF2};
+    use crate::hle::consts::{
+        G_CULL_FRONT, G_FOG, G_RM_OPA_SURF, G_RM_OPA_SURF2, G_SHADING_SMOOTH,
+    };
     use crate::hle::gbi::GbiUcode;
     use crate::hle::interp::interpret;
     use crate::hle::mem::RdramImage;
@@ -256,6 +264,7 @@ mod phase2_tests {
     const TEXTURE_ADDR: u32 = 0x100;
     const ENTRY_ADDR: u32 = 0x180;
     const G_CULL_FRONT_F3D: u32 = 0x0000_1000;
+    const G_SHADING_SMOOTH_F3D: u32 = 0x0000_0200;
 
     fn push(buf: &mut Vec<u8>, (w0, w1): (u32, u32)) {
         buf.extend_from_slice(&w0.to_be_bytes());
@@ -389,7 +398,7 @@ mod phase2_tests {
         f3d_commands.extend(gdp_load_texture_block(0, 2, 4, 4, TEXTURE_ADDR, 2, 2, 2, 2));
         f3d_commands.extend([
             gsp_texture_f3d(0x8000, 0x4000, 0, 0, true),
-            gsp_set_geometrymode_f3d(G_CULL_FRONT_F3D | G_FOG),
+            gsp_set_geometrymode_f3d(G_CULL_FRONT_F3D | G_SHADING_SMOOTH_F3D | G_FOG),
             gsp_vertex_f3d(5, 4, VTX_ADDR),
             gsp_quad_f3d(5, 6, 7, 8),
             gsp_enddl_f3d(),
@@ -405,7 +414,7 @@ mod phase2_tests {
         f3dex2_commands.extend(gdp_load_texture_block(0, 2, 4, 4, TEXTURE_ADDR, 2, 2, 2, 2));
         f3dex2_commands.extend([
             gsp_texture(0x8000, 0x4000, 0, 0, true),
-            gsp_set_geometrymode(G_CULL_FRONT | G_FOG),
+            gsp_set_geometrymode(G_CULL_FRONT | G_SHADING_SMOOTH | G_FOG),
             gsp_vertex(5, 4, VTX_ADDR),
             gsp_2triangles(5, 6, 7, 5, 7, 8),
             gsp_enddl(),
@@ -456,6 +465,7 @@ mod phase2_tests {
             scene_data(),
             &[
                 shaded_combine(),
+                gsp_set_geometrymode_f3d(G_SHADING_SMOOTH_F3D),
                 gsp_vertex_f3d(0, 4, VTX_ADDR),
                 gsp_1triangle_f3d(0, 1, 2),
                 gsp_quad_f3d(0, 1, 2, 3),
@@ -470,6 +480,7 @@ mod phase2_tests {
                 material_index: 0,
                 render_mode_index: 0,
                 cull: CullKind::None,
+                flat: false,
                 index_count: 9,
                 index_start: 0,
             }]
@@ -493,7 +504,7 @@ mod phase2_tests {
             &[
                 shaded_combine(),
                 gsp_vertex_f3d(0, 3, VTX_ADDR),
-                gsp_set_geometrymode_f3d(G_CULL_FRONT_F3D),
+                gsp_set_geometrymode_f3d(G_CULL_FRONT_F3D | G_SHADING_SMOOTH_F3D),
                 gsp_1triangle_f3d(0, 1, 2),
                 gsp_clear_geometrymode_f3d(G_CULL_FRONT_F3D),
                 gsp_1triangle_f3d(0, 1, 2),
@@ -509,6 +520,7 @@ mod phase2_tests {
                     material_index: 0,
                     render_mode_index: 0,
                     cull: CullKind::Cull,
+                    flat: false,
                     index_count: 3,
                     index_start: 0,
                 },
@@ -516,6 +528,7 @@ mod phase2_tests {
                     material_index: 0,
                     render_mode_index: 0,
                     cull: CullKind::None,
+                    flat: false,
                     index_count: 3,
                     index_start: 3,
                 },
@@ -528,6 +541,7 @@ mod phase2_tests {
 
 #[cfg(all(test, feature = "asm"))]
 mod phase3_tests {
+    use crate::hle::consts::rdp::G_TP_PERSP;
     use crate::hle::consts::rsp_f3d::{
         G_MOVEWORD, G_MV_MATRIX_2, G_MV_MATRIX_3, G_MV_MATRIX_4, G_MW_PERSPNORM, G_POPMTX,
     };
@@ -539,10 +553,10 @@ mod phase3_tests {
     use n64_gbi::encode::{
         gdp_load_texture_block, gdp_set_combine_lerp, gdp_set_cycle_type_f3d,
         gdp_set_render_mode_f3d, gsp_1triangle_f3d, gsp_enddl_f3d, gsp_forcematrix_f3d,
-        gsp_light_f3d, gsp_lightcolor_f3d, gsp_lookat_f3d, gsp_matrix_f3d, gsp_modifyvertex_f3d,
-        gsp_numlights_f3d, gsp_popmatrix_f3d, gsp_segment_f3d, gsp_set_geometrymode_f3d,
-        gsp_texture_f3d, gsp_vertex_f3d, gsp_viewport_f3d, mtx_to_bytes, CcPass, VtxColored,
-        ZERO_A, ZERO_C,
+        gsp_insert_matrix_f3d, gsp_light_f3d, gsp_lightcolor_f3d, gsp_lookat_f3d, gsp_matrix_f3d,
+        gsp_modifyvertex_f3d, gsp_numlights_f3d, gsp_popmatrix_f3d, gsp_segment_f3d,
+        gsp_set_geometrymode_f3d, gsp_texture_f3d, gsp_vertex_f3d, gsp_viewport_f3d, mtx_to_bytes,
+        CcPass, VtxColored, ZERO_A, ZERO_C,
     };
 
     const ENTRY: usize = 0x200;
@@ -673,7 +687,8 @@ mod phase3_tests {
             result.scene.render_modes[0],
             crate::hle::blender::decode_render_mode(
                 render_mode,
-                G_CYC_2CYCLE << 20,
+                // The list never writes G_MDSFT_TEXTPERSP, so it stays perspective-correct.
+                (G_CYC_2CYCLE << 20) | G_TP_PERSP,
                 result.geometry_mode,
             )
         );
@@ -826,6 +841,56 @@ mod phase3_tests {
         );
     }
 
+    #[test]
+    fn split_force_matrix_and_insert_matrix_patch_the_current_mvp() {
+        const MATRIX_A: usize = 0x00;
+        const MATRIX_B: usize = 0x40;
+        const VTX: usize = 0x80;
+        let a = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 3.0, 0.0, 0.0],
+            [0.0, 0.0, 4.0, 0.0],
+            [5.0, 6.0, 7.0, 1.0],
+        ];
+        let b = [
+            [1.0, 1.0, 1.0, 1.0],
+            [2.0, 2.0, 2.0, 2.0],
+            [0.0, 0.0, -8.0, 0.0],
+            [1.0, 2.0, 3.0, 4.5],
+        ];
+        let mut bytes = vec![0; ENTRY];
+        bytes[MATRIX_A..MATRIX_A + 64].copy_from_slice(&mtx_to_bytes(a));
+        bytes[MATRIX_B..MATRIX_B + 64].copy_from_slice(&mtx_to_bytes(b));
+        bytes[VTX..VTX + 16].copy_from_slice(&vertex().to_bytes());
+        let continuation = |selector: u8, addr: usize| {
+            (
+                ((crate::hle::consts::rsp_f3d::G_MOVEMEM as u32) << 24) | ((selector as u32) << 16),
+                addr as u32,
+            )
+        };
+        let result = run(
+            bytes,
+            &[
+                gsp_forcematrix_f3d(MATRIX_A as u32),
+                // Integer and fraction halves of rows 2-3 come from B; rows 0-1 stay A's.
+                continuation(G_MV_MATRIX_2, MATRIX_B + 0x10),
+                continuation(G_MV_MATRIX_3, MATRIX_A + 0x20),
+                continuation(G_MV_MATRIX_4, MATRIX_B + 0x30),
+                // Integer halves of wx, wy: 9 and -1, keeping wy's zero fraction.
+                gsp_insert_matrix_f3d(0x18, 0x0009_FFFF),
+                gsp_vertex_f3d(0, 1, VTX as u32),
+                gsp_enddl_f3d(),
+            ],
+        );
+
+        let expected = [a[0], a[1], b[2], [9.0, -1.0, 3.0, 4.5]];
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        assert_eq!(
+            result.scene.mvp_table[result.scene.mtx_index[0] as usize],
+            expected
+        );
+    }
+
     #[test]
     fn popmtx_only_pops_when_w1_is_zero() {
         const MATRIX_A: usize = 0x00;
@@ -1032,7 +1097,8 @@ mod phase5_tests {
 
         let result = run(&bytes, ENTRY);
 
-        assert_eq!(result.scene.indices, vec![0, 1, 2, 0, 1, 2]);
+        // No G_SHADING_SMOOTH: each flat triangle leads with F3D's provoking (last) vertex.
+        assert_eq!(result.scene.indices, vec![2, 0, 1, 2, 0, 1]);
         assert_eq!(result.commands, 8);
         assert!(
             result.diags.is_empty(),
@@ -1053,7 +1119,7 @@ mod phase5_tests {
 
         let result = run(&bytes, ENTRY);
 
-        assert_eq!(result.scene.indices, vec![0, 1, 2]);
+        assert_eq!(result.scene.indices, vec![2, 0, 1]);
         assert_eq!(result.commands, 6);
         assert!(result
             .diags
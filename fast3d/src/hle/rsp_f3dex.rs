@@ -0,0 +1,496 @@
+use crate::hle::consts::rsp_f3dex::{
+    G_BRANCH_Z, G_LOAD_UCODE, G_MODIFYVTX, G_QUAD, G_RDPHALF_1, G_TRI1, G_TRI2, G_VTX,
+    VTX_CACHE_SIZE, VTX_DMEM_STRIDE,
+};
+use crate::hle::interp::{Cmd, Ctx, Handler};
+use crate::hle::mem::Rdram;
+use crate::{DiagKind, Diagnostic};
+
+/// F3DEX 1.x is F3D plus a 32-entry vertex cache, `*2` vertex indices, G_TRI2 and the
+/// G_MODIFYVTX/G_BRANCH_Z/G_LOAD_UCODE commands. Everything else decodes exactly like F3D.
+pub(crate) fn install_overrides<M: Rdram>(t: &mut [Handler<M>; 256]) {
+    crate::hle::rsp_f3d::install_overrides(t);
+    t[G_VTX as usize] = vtx::<M>;
+    t[G_TRI1 as usize] = tri1::<M>;
+    t[G_TRI2 as usize] = tri2::<M>;
+    t[G_QUAD as usize] = quad::<M>;
+    t[G_MODIFYVTX as usize] = modify_vtx::<M>;
+    t[G_RDPHALF_1 as usize] = crate::hle::rdp::rdphalf_1::<M>;
+    t[G_BRANCH_Z as usize] = branch_z::<M>;
+    t[G_LOAD_UCODE as usize] = crate::hle::interp::load_ucode::<M>;
+}
+
+fn vtx<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let count = c.p0(10, 6);
+    let dst = c.p0(16, 8) / 2;
+    let end = dst + count;
+    if end <= VTX_CACHE_SIZE {
+        let addr = cx.mem.resolve_masked(c.w1_addr);
+        cx.rsp
+            .set_vertex(cx.mem, addr, count, dst, cx.rdp, cx.scene);
+    } else {
+        cx.diags.push(Diagnostic {
+            at: cx.pc,
+            kind: DiagKind::VtxOutOfRange { count, end },
+        });
+    }
+}
+
+fn tri1<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    if let Some((material_index, render_mode_index)) =
+        crate::hle::rsp::snapshot_run(cx.rsp, cx.rdp, cx.diags, cx.scene, cx.pc)
+    {
+        crate::hle::rsp::record_tri(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            c.p1(16, 8) / 2,
+            c.p1(8, 8) / 2,
+            c.p1(0, 8) / 2,
+            material_index,
+            render_mode_index,
+        );
+    } else {
+        *cx.dropped_runs += 1;
+    }
+}
+
+/// G_TRI2: the first triangle rides in w0's low 24 bits, the second in w1 (gsSP2Triangles and
+/// gsSP1Quadrangle both encode to this).
+fn tri2<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    if let Some((material_index, render_mode_index)) =
+        crate::hle::rsp::snapshot_run(cx.rsp, cx.rdp, cx.diags, cx.scene, cx.pc)
+    {
+        crate::hle::rsp::record_tri(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            c.p0(16, 8) / 2,
+            c.p0(8, 8) / 2,
+            c.p0(0, 8) / 2,
+            material_index,
+            render_mode_index,
+        );
+        crate::hle::rsp::record_tri(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            c.p1(16, 8) / 2,
+            c.p1(8, 8) / 2,
+            c.p1(0, 8) / 2,
+            material_index,
+            render_mode_index,
+        );
+    } else {
+        *cx.dropped_runs += 1;
+    }
+}
+
+fn quad<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    if let Some((material_index, render_mode_index)) =
+        crate::hle::rsp::snapshot_run(cx.rsp, cx.rdp, cx.diags, cx.scene, cx.pc)
+    {
+        let v0 = c.p1(24, 8) / 2;
+        let v1 = c.p1(16, 8) / 2;
+        let v2 = c.p1(8, 8) / 2;
+        let v3 = c.p1(0, 8) / 2;
+        crate::hle::rsp::record_tri(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            v0,
+            v1,
+            v2,
+            material_index,
+            render_mode_index,
+        );
+        crate::hle::rsp::record_tri(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            v0,
+            v2,
+            v3,
+            material_index,
+            render_mode_index,
+        );
+    } else {
+        *cx.dropped_runs += 1;
+    }
+}
+
+/// gSPModifyVertex: `where` at p0[16,8], `vtx*2` at p0[0,16]. Same attribute semantics as F3D's
+/// G_MW_POINTS.
+fn modify_vtx<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    cx.rsp
+        .modify_vertex(c.p0(0, 16) / 2, c.p0(16, 8), c.w1, cx.scene);
+}
+
+/// gsSPBranchLessZ: branch to the preceding G_RDPHALF_1 target when the vertex's screen Z is at
+/// or in front of `zval` (w1, 16.16 in viewport Z units). F3DEX addresses the vertex by its
+/// 40-byte DMEM offset in 8-byte units (`vtx*5` at p0[12,12]). The test is inclusive, as the
+/// libultra gSPBranchLessZ reference specifies ("less than or equal to") and GLideN64's
+/// `gSPBranchLessZ` implements; the F3DEX2-family handlers share it.
+fn branch_z<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let slot = c.p0(12, 12) * 8 / VTX_DMEM_STRIDE;
+    let zval = c.w1 as i32 as f32 / 65536.0;
+    let target = cx.rsp.take_rdphalf_1();
+    if let Some(z) = cx.rsp.vertex_screen_z(slot, cx.scene) {
+        if z <= zval {
+            cx.branch = Some(cx.mem.resolve_masked(target));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "asm"))]
+mod tests {
+    use crate::hle::consts::rsp_f3dex::{G_MWO_POINT_RGBA, G_MWO_POINT_ST};
+    use crate::hle::consts::{
+        G_CULL_FRONT, G_FOG, G_RM_OPA_SURF, G_RM_OPA_SURF2, G_SHADING_SMOOTH,
+    };
+    use crate::hle::gbi::GbiUcode;
+    use crate::hle::interp::interpret;
+    use crate::hle::mem::RdramImage;
+    use crate::hle::InterpResult;
+    use crate::DiagKind;
+    use n64_gbi::encode::{
+        gdp_load_texture_block, gdp_set_combine_lerp, gdp_set_cycle_type, gdp_set_cycle_type_f3d,
+        gdp_set_render_mode, gdp_set_render_mode_f3d, gsp_1triangle_f3d, gsp_1triangle_f3dex,
+        gsp_2triangles, gsp_2triangles_f3dex, gsp_branch_less_z_raw_f3dex, gsp_enddl,
+        gsp_enddl_f3d, gsp_load_ucode_f3dex, gsp_matrix, gsp_matrix_f3d, gsp_modifyvertex_f3d,
+        gsp_modifyvertex_f3dex, gsp_set_geometrymode, gsp_set_geometrymode_f3d, gsp_texture,
+        gsp_texture_f3d, gsp_vertex, gsp_vertex_f3d, gsp_vertex_f3dex, gsp_viewport,
+        gsp_viewport_f3d, mtx_to_bytes, CcPass, VtxColored, ZERO_A, ZERO_C,
+    };
+
+    const VTX_ADDR: u32 = 0x40;
+    const VIEWPORT_ADDR: u32 = 0x80;
+    const TEXTURE_ADDR: u32 = 0x100;
+    const BRANCH_ADDR: u32 = 0x140;
+    const ENTRY_ADDR: u32 = 0x180;
+    const G_CULL_FRONT_F3D: u32 = 0x0000_1000;
+    const G_SHADING_SMOOTH_F3D: u32 = 0x0000_0200;
+
+    fn push(buf: &mut Vec<u8>, (w0, w1): (u32, u32)) {
+        buf.extend_from_slice(&w0.to_be_bytes());
+        buf.extend_from_slice(&w1.to_be_bytes());
+    }
+
+    fn shaded_combine() -> (u32, u32) {
+        let color = CcPass {
+            a: ZERO_C,
+            b: ZERO_C,
+            c: ZERO_C,
+            d: 4,
+        };
+        let alpha = CcPass {
+            a: ZERO_A,
+            b: ZERO_A,
+            c: ZERO_A,
+            d: 4,
+        };
+        gdp_set_combine_lerp(color, alpha, color, alpha)
+    }
+
+    fn textured_combine() -> (u32, u32) {
+        let color = CcPass {
+            a: 1,
+            b: ZERO_C,
+            c: 4,
+            d: ZERO_C,
+        };
+        let alpha = CcPass {
+            a: ZERO_A,
+            b: ZERO_A,
+            c: ZERO_A,
+            d: 4,
+        };
+        gdp_set_combine_lerp(color, alpha, color, alpha)
+    }
+
+    /// Matrix at 0, four colored vertices at VTX_ADDR (z = 0, 0, -64, -64 before the matrix),
+    /// a viewport at VIEWPORT_ADDR and a white texture at TEXTURE_ADDR.
+    fn scene_data() -> Vec<u8> {
+        let mut bytes = vec![0; ENTRY_ADDR as usize];
+        let matrix = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 3.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0 / 128.0, 0.0],
+            [4.0, 5.0, 0.0, 1.0],
+        ];
+        bytes[..0x40].copy_from_slice(&mtx_to_bytes(matrix));
+        let corner = |x: i16, y: i16, z: i16, s: i16, t: i16, rgb: [u8; 3]| VtxColored {
+            x,
+            y,
+            z,
+            flag: 0,
+            s,
+            t,
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+            a: 255,
+        };
+        let vertices = [
+            corner(-48, -48, 0, 0, 0, [255, 0, 0]),
+            corner(48, -48, 0, 32, 0, [0, 255, 0]),
+            corner(48, 48, -64, 32, 32, [0, 0, 255]),
+            corner(-48, 48, -64, 0, 32, [255, 255, 255]),
+        ];
+        for (i, vertex) in vertices.iter().enumerate() {
+            let start = VTX_ADDR as usize + i * 16;
+            bytes[start..start + 16].copy_from_slice(&vertex.to_bytes());
+        }
+        for (i, value) in [640i16, 480, 511, 0, 320, 240, 511, 0].iter().enumerate() {
+            let start = VIEWPORT_ADDR as usize + i * 2;
+            bytes[start..start + 2].copy_from_slice(&value.to_be_bytes());
+        }
+        bytes[TEXTURE_ADDR as usize..TEXTURE_ADDR as usize + 32].fill(0xFF);
+        bytes
+    }
+
+    fn run(mut bytes: Vec<u8>, commands: &[(u32, u32)], ucode: GbiUcode) -> InterpResult {
+        for &command in commands {
+            push(&mut bytes, command);
+        }
+        interpret(
+            RdramImage::new(&bytes),
+            ENTRY_ADDR as u64,
+            ucode,
+            crate::hle::mem::GbiDataFormat::Fixed,
+        )
+    }
+
+    #[test]
+    fn f3dex_textured_cull_front_quad_outputs_match_f3dex2() {
+        // Load into the upper half of the 32-entry cache — out of F3D's 16-slot reach.
+        let mut f3dex_commands = vec![
+            gsp_matrix_f3d(0, true, true, false),
+            gsp_viewport_f3d(VIEWPORT_ADDR),
+            gdp_set_cycle_type_f3d(0),
+            gdp_set_render_mode_f3d(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            textured_combine(),
+        ];
+        f3dex_commands.extend(gdp_load_texture_block(0, 2, 4, 4, TEXTURE_ADDR, 2, 2, 2, 2));
+        f3dex_commands.extend([
+            gsp_texture_f3d(0x8000, 0x4000, 0, 0, true),
+            gsp_set_geometrymode_f3d(G_CULL_FRONT_F3D | G_SHADING_SMOOTH_F3D | G_FOG),
+            gsp_vertex_f3dex(28, 4, VTX_ADDR),
+            gsp_2triangles_f3dex(28, 29, 30, 28, 30, 31),
+            gsp_enddl_f3d(),
+        ]);
+
+        let mut f3dex2_commands = vec![
+            gsp_matrix(0, true, true, false),
+            gsp_viewport(VIEWPORT_ADDR),
+            gdp_set_cycle_type(0),
+            gdp_set_render_mode(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            textured_combine(),
+        ];
+        f3dex2_commands.extend(gdp_load_texture_block(0, 2, 4, 4, TEXTURE_ADDR, 2, 2, 2, 2));
+        f3dex2_commands.extend([
+            gsp_texture(0x8000, 0x4000, 0, 0, true),
+            gsp_set_geometrymode(G_CULL_FRONT | G_SHADING_SMOOTH | G_FOG),
+            gsp_vertex(28, 4, VTX_ADDR),
+            gsp_2triangles(28, 29, 30, 28, 30, 31),
+            gsp_enddl(),
+        ]);
+
+        let f3dex = run(scene_data(), &f3dex_commands, GbiUcode::F3dex);
+        let f3dex2 = run(scene_data(), &f3dex2_commands, GbiUcode::F3dex2);
+
+        assert!(f3dex.diags.is_empty(), "{:?}", f3dex.diags);
+        assert!(f3dex.scene.materials[0].tex_enable);
+        assert_eq!(f3dex.scene.indices, vec![2, 1, 0, 3, 2, 0]);
+        assert_eq!(f3dex.scene.fog, vec![1; 4]);
+
+        assert_eq!(
+            (
+                &f3dex.scene.draw_runs,
+                &f3dex.scene.indices,
+                &f3dex.scene.raw_pos,
+                &f3dex.scene.mvp_table,
+                &f3dex.scene.materials,
+                &f3dex.scene.render_modes,
+                &f3dex.scene.viewport_table,
+                &f3dex.scene.texcoord_table,
+                &f3dex.scene.mtx_index,
+                &f3dex.scene.light_index,
+                &f3dex.scene.fog,
+            ),
+            (
+                &f3dex2.scene.draw_runs,
+                &f3dex2.scene.indices,
+                &f3dex2.scene.raw_pos,
+                &f3dex2.scene.mvp_table,
+                &f3dex2.scene.materials,
+                &f3dex2.scene.render_modes,
+                &f3dex2.scene.viewport_table,
+                &f3dex2.scene.texcoord_table,
+                &f3dex2.scene.mtx_index,
+                &f3dex2.scene.light_index,
+                &f3dex2.scene.fog,
+            )
+        );
+    }
+
+    #[test]
+    fn f3dex_vertex_load_past_32_entries_is_rejected() {
+        let result = run(
+            scene_data(),
+            &[gsp_vertex_f3dex(30, 4, VTX_ADDR), gsp_enddl_f3d()],
+            GbiUcode::F3dex,
+        );
+        assert!(result.scene.raw_pos.is_empty());
+        assert_eq!(
+            result.diags.iter().map(|d| d.kind).collect::<Vec<_>>(),
+            vec![DiagKind::VtxOutOfRange { count: 4, end: 34 }]
+        );
+    }
+
+    #[test]
+    fn f3dex_modify_vertex_matches_f3d_moveword_points() {
+        let f3dex = run(
+            scene_data(),
+            &[
+                shaded_combine(),
+                gsp_vertex_f3dex(0, 3, VTX_ADDR),
+                gsp_modifyvertex_f3dex(2, G_MWO_POINT_RGBA, 0x1122_33FF),
+                gsp_modifyvertex_f3dex(1, G_MWO_POINT_ST, 0xFFE0_0020),
+                gsp_1triangle_f3dex(0, 1, 2),
+                gsp_enddl_f3d(),
+            ],
+            GbiUcode::F3dex,
+        );
+        let f3d = run(
+            scene_data(),
+            &[
+                shaded_combine(),
+                gsp_vertex_f3d(0, 3, VTX_ADDR),
+                gsp_modifyvertex_f3d(2, G_MWO_POINT_RGBA as u16, 0x1122_33FF),
+                gsp_modifyvertex_f3d(1, G_MWO_POINT_ST as u16, 0xFFE0_0020),
+                gsp_1triangle_f3d(0, 1, 2),
+                gsp_enddl_f3d(),
+            ],
+            GbiUcode::F3d,
+        );
+        assert_eq!(
+            f3dex.scene.cn[2],
+            u32::from_le_bytes([0x11, 0x22, 0x33, 0xFF])
+        );
+        assert_eq!(f3dex.scene.raw_st[1], [-1.0, 1.0]);
+        assert_eq!(
+            (
+                &f3dex.scene.cn,
+                &f3dex.scene.raw_st,
+                &f3dex.scene.texcoord_index
+            ),
+            (&f3d.scene.cn, &f3d.scene.raw_st, &f3d.scene.texcoord_index)
+        );
+    }
+
+    /// Vertex 0 sits at screen Z 511 and vertex 2 at 255.5 (viewport Z units); the branch target
+    /// draws one triangle, the fall-through draws nothing.
+    fn branch_z_walk(vtx: u8, zval: f32) -> InterpResult {
+        let mut bytes = scene_data();
+        let mut target = Vec::new();
+        push(&mut target, gsp_1triangle_f3dex(0, 1, 2));
+        push(&mut target, gsp_enddl_f3d());
+        bytes[BRANCH_ADDR as usize..BRANCH_ADDR as usize + target.len()].copy_from_slice(&target);
+        let mut commands = vec![
+            gsp_matrix_f3d(0, true, true, false),
+            gsp_viewport_f3d(VIEWPORT_ADDR),
+            gdp_set_render_mode_f3d(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            shaded_combine(),
+            gsp_vertex_f3dex(0, 4, VTX_ADDR),
+        ];
+        commands.extend(gsp_branch_less_z_raw_f3dex(
+            BRANCH_ADDR,
+            vtx,
+            (zval * 65536.0) as u32,
+        ));
+        commands.push(gsp_enddl_f3d());
+        run(bytes, &commands, GbiUcode::F3dex)
+    }
+
+    #[test]
+    fn f3dex_branch_z_follows_rdphalf_target_when_vertex_is_near() {
+        let taken = branch_z_walk(2, 300.0);
+        assert!(taken.diags.is_empty(), "{:?}", taken.diags);
+        // Flat-shaded (no G_SHADING_SMOOTH): F3DEX leads with its provoking (last) vertex.
+        assert_eq!(taken.scene.indices, vec![2, 0, 1]);
+
+        let not_taken = branch_z_walk(0, 300.0);
+        assert!(not_taken.diags.is_empty(), "{:?}", not_taken.diags);
+        assert!(not_taken.scene.indices.is_empty());
+    }
+
+    #[test]
+    fn f3dex_branch_z_is_taken_when_vertex_z_equals_zval() {
+        let equal = branch_z_walk(2, 255.5);
+        assert!(equal.diags.is_empty(), "{:?}", equal.diags);
+        assert_eq!(equal.scene.indices, vec![2, 0, 1]);
+
+        let behind = branch_z_walk(2, 255.0);
+        assert!(behind.scene.indices.is_empty());
+    }
+
+    #[test]
+    fn f3dex_unconsumed_rdphalf_1_is_reported_as_stray() {
+        let [half, _] = gsp_branch_less_z_raw_f3dex(BRANCH_ADDR, 0, 0);
+        let result = run(scene_data(), &[half, gsp_enddl_f3d()], GbiUcode::F3dex);
+        assert!(
+            matches!(result.diags[..], [d] if d.kind == DiagKind::StrayRdphalf && d.at == ENTRY_ADDR as u64),
+            "{:?}",
+            result.diags
+        );
+    }
+
+    #[test]
+    fn f3dex_load_ucode_of_unknown_text_is_reported() {
+        let mut commands = gsp_load_ucode_f3dex(0x0010_0000, 0x0020_0000, 0x800).to_vec();
+        commands.push(gsp_enddl_f3d());
+        let result = run(scene_data(), &commands, GbiUcode::F3dex);
+        assert_eq!(result.commands, 3);
+        assert!(
+            matches!(result.diags[..], [d] if matches!(d.kind, DiagKind::UnknownMicrocode { .. })),
+            "{:?}",
+            result.diags
+        );
+    }
+
+    #[test]
+    fn f3dex_load_ucode_switches_to_f3dex2_and_keeps_rsp_state() {
+        // Vertices and matrices loaded under F3DEX survive the switch; the triangle and the end
+        // of the DL then use F3DEX2 encodings.
+        let mut bytes = scene_data();
+        let text = bytes.len() as u32;
+        bytes.extend(crate::hle::gbi::fixture_ucode(GbiUcode::F3dex2));
+        let entry = bytes.len() as u64;
+        let mut commands = vec![
+            gsp_matrix_f3d(0, true, true, false),
+            gsp_viewport_f3d(VIEWPORT_ADDR),
+            gdp_set_render_mode_f3d(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            shaded_combine(),
+            gsp_vertex_f3dex(0, 4, VTX_ADDR),
+        ];
+        commands.extend(gsp_load_ucode_f3dex(text, text + 0x1000, 0x800));
+        commands.extend([gsp_2triangles(0, 1, 2, 0, 2, 3), gsp_enddl()]);
+        for command in commands {
+            push(&mut bytes, command);
+        }
+        let result = interpret(
+            RdramImage::new(&bytes),
+            entry,
+            GbiUcode::F3dex,
+            crate::hle::mem::GbiDataFormat::Fixed,
+        );
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        assert_eq!(result.scene.indices, vec![0, 1, 2, 0, 2, 3]);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::diag::{DiagKind, Diagnostic};
 use crate::hle::consts::rdp::G_SETTIMG;
 use crate::hle::consts::rsp_f3dex2::{
-    G_GEOMETRYMODE, G_MOVEMEM, G_MOVEWORD, G_MTX, G_POPMTX, G_SETOTHERMODE_H, G_SETOTHERMODE_L,
-    G_TEXTURE, G_TRI1, G_TRI2, G_VTX,
+    G_BRANCH_Z, G_CULLDL, G_GEOMETRYMODE, G_LOAD_UCODE, G_MOVEMEM, G_MOVEWORD, G_MTX, G_POPMTX,
+    G_SETOTHERMODE_H, G_SETOTHERMODE_L, G_TEXTURE, G_TRI1, G_TRI2, G_VTX,
 };
 use crate::hle::interp::{Cmd, Ctx, Handler};
 use crate::hle::mem::Rdram;
@@ -21,6 +21,9 @@ pub(crate) fn install_overrides<M: Rdram>(t: &mut [Handler<M>; 256]) {
     t[G_SETTIMG as usize] = set_texture_image::<M>;
     t[G_MOVEWORD as usize] = move_word::<M>;
     t[G_POPMTX as usize] = pop_matrix::<M>;
+    t[G_CULLDL as usize] = cull_dl::<M>;
+    t[G_BRANCH_Z as usize] = branch_z::<M>;
+    t[G_LOAD_UCODE as usize] = crate::hle::interp::load_ucode::<M>;
 }
 
 fn vtx<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
@@ -88,6 +91,39 @@ fn tri2<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     }
 }
 
+/// gsSPCullDisplayList: end the current DL when every vertex in `vstart..=vend` (p0[0,16] and
+/// w1, both `vtx*2`) shares an outside clip plane under the MVP it was loaded with. An empty slot
+/// keeps the DL, as does any vertex inside the frustum.
+fn cull_dl<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let (vstart, vend) = (c.p0(0, 16) / 2, c.p1(0, 16) / 2);
+    let mut shared = 0x3Fu8;
+    for slot in vstart..=vend {
+        match cx.rsp.vertex_clip_codes(slot, cx.scene) {
+            Some(codes) => shared &= codes,
+            None => return,
+        }
+        if shared == 0 {
+            return;
+        }
+    }
+    cx.end_dl = true;
+    *cx.culled_dls += 1;
+}
+
+/// gsSPBranchLessZ: branch to the preceding G_RDPHALF_1 target when the vertex's screen Z is at
+/// or in front of `zval` (w1, 16.16 in viewport Z units). F3DEX2 reads the vertex from the
+/// `vtx*2` field at p0[0,12]. The test is inclusive, as in `rsp_f3dex::branch_z`.
+fn branch_z<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let slot = c.p0(1, 11);
+    let zval = c.w1 as i32 as f32 / 65536.0;
+    let target = cx.rsp.take_rdphalf_1();
+    if let Some(z) = cx.rsp.vertex_screen_z(slot, cx.scene) {
+        if z <= zval {
+            cx.branch = Some(cx.mem.resolve_masked(target));
+        }
+    }
+}
+
 fn pop_matrix<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
     cx.rsp.pop_matrix(c.w1 >> 6);
 }
@@ -174,6 +210,8 @@ fn move_word<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
         // fidelity + the future Fog milestone). (spec §2)
     } else if ty == cx.gbi_consts.g_mw_clip as u32 {
         // clip ratio — RSP viewport state, no scene effect
+    } else if ty == cx.gbi_consts.g_mw_matrix as u32 {
+        cx.rsp.insert_matrix(c.p0(0, 16), c.w1);
     } else if ty == cx.gbi_consts.g_mw_numlight as u32 {
         cx.rsp.set_num_lights(c.w1);
     } else if ty == cx.gbi_consts.g_mw_fog as u32 {
@@ -219,6 +257,7 @@ mod wired_tests {
         let mut rec = crate::hle::rsp::PairRec::default();
         let mut dropped = 0u32;
         let mut seen = [false; 256];
+        let mut culled = 0u32;
         let mut cx = Ctx {
             rsp: &mut rsp,
             rdp: &mut rdp,
@@ -230,6 +269,10 @@ mod wired_tests {
             rec: &mut rec,
             dropped_runs: &mut dropped,
             unknown_seen: &mut seen,
+            branch: None,
+            end_dl: false,
+            culled_dls: &mut culled,
+            switch_ucode: None,
         };
         move_word(&cmd, &mut cx);
         rdp.fog_mul
@@ -273,3 +316,261 @@ mod fog_tests {
         );
     }
 }
+
+#[cfg(all(test, feature = "asm"))]
+pub(crate) mod cull_branch_tests {
+    use crate::hle::consts::{G_RM_OPA_SURF, G_RM_OPA_SURF2};
+    use crate::hle::gbi::GbiUcode;
+    use crate::hle::interp::interpret;
+    use crate::hle::mem::RdramImage;
+    use crate::hle::InterpResult;
+    use n64_gbi::encode::{
+        gdp_set_combine_lerp, gdp_set_render_mode, gsp_1triangle, gsp_branch_less_z_raw,
+        gsp_cull_displaylist, gsp_displaylist, gsp_enddl, gsp_matrix, gsp_vertex, gsp_viewport,
+        mtx_to_bytes, CcPass, VtxColored, ZERO_A, ZERO_C,
+    };
+
+    pub(crate) const VTX_ADDR: u32 = 0x40;
+    const VIEWPORT_ADDR: u32 = 0x80;
+    pub(crate) const SUB_ADDR: u32 = 0x100;
+    const ENTRY_ADDR: u32 = 0x180;
+
+    fn push(buf: &mut Vec<u8>, (w0, w1): (u32, u32)) {
+        buf.extend_from_slice(&w0.to_be_bytes());
+        buf.extend_from_slice(&w1.to_be_bytes());
+    }
+
+    /// A projection that maps the ±48 quad into the unit cube, shifted by `tx` clip units in X.
+    /// Vertices 0/1 sit at screen Z 511 and 2/3 at 255.5 (viewport Z units).
+    pub(crate) fn scene_data(tx: f32, sub: &[(u32, u32)]) -> Vec<u8> {
+        let mut bytes = vec![0; ENTRY_ADDR as usize];
+        let matrix = [
+            [1.0 / 64.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0 / 64.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0 / 128.0, 0.0],
+            [tx, 0.0, 0.0, 1.0],
+        ];
+        bytes[..0x40].copy_from_slice(&mtx_to_bytes(matrix));
+        let corner = |x: i16, y: i16, z: i16| VtxColored {
+            x,
+            y,
+            z,
+            flag: 0,
+            s: 0,
+            t: 0,
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        };
+        let vertices = [
+            corner(-48, -48, 0),
+            corner(48, -48, 0),
+            corner(48, 48, -64),
+            corner(-48, 48, -64),
+        ];
+        for (i, vertex) in vertices.iter().enumerate() {
+            let start = VTX_ADDR as usize + i * 16;
+            bytes[start..start + 16].copy_from_slice(&vertex.to_bytes());
+        }
+        for (i, value) in [640i16, 480, 511, 0, 320, 240, 511, 0].iter().enumerate() {
+            let start = VIEWPORT_ADDR as usize + i * 2;
+            bytes[start..start + 2].copy_from_slice(&value.to_be_bytes());
+        }
+        let mut sub_bytes = Vec::new();
+        for &command in sub {
+            push(&mut sub_bytes, command);
+        }
+        bytes[SUB_ADDR as usize..SUB_ADDR as usize + sub_bytes.len()].copy_from_slice(&sub_bytes);
+        bytes
+    }
+
+    fn run(bytes: Vec<u8>, tail: &[(u32, u32)]) -> InterpResult {
+        run_as(bytes, tail, GbiUcode::F3dex2)
+    }
+
+    /// Set up the quad from `scene_data` under `ucode`, then walk `tail` and end the list.
+    pub(crate) fn run_as(mut bytes: Vec<u8>, tail: &[(u32, u32)], ucode: GbiUcode) -> InterpResult {
+        let pass = CcPass {
+            a: ZERO_C,
+            b: ZERO_C,
+            c: ZERO_C,
+            d: 4,
+        };
+        let alpha = CcPass {
+            a: ZERO_A,
+            b: ZERO_A,
+            c: ZERO_A,
+            d: 4,
+        };
+        let mut commands = vec![
+            gsp_matrix(0, true, true, false),
+            gsp_viewport(VIEWPORT_ADDR),
+            gdp_set_render_mode(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            gdp_set_combine_lerp(pass, alpha, pass, alpha),
+            gsp_vertex(0, 4, VTX_ADDR),
+        ];
+        commands.extend_from_slice(tail);
+        commands.push(gsp_enddl());
+        for command in commands {
+            push(&mut bytes, command);
+        }
+        interpret(
+            RdramImage::new(&bytes),
+            ENTRY_ADDR as u64,
+            ucode,
+            crate::hle::mem::GbiDataFormat::Fixed,
+        )
+    }
+
+    /// Calls a sub-DL that culls on the whole quad and then draws one triangle; the caller draws
+    /// another triangle after the return.
+    fn cull_walk(tx: f32) -> InterpResult {
+        let sub = [
+            gsp_cull_displaylist(0, 3),
+            gsp_1triangle(0, 1, 2),
+            gsp_enddl(),
+        ];
+        run(
+            scene_data(tx, &sub),
+            &[gsp_displaylist(SUB_ADDR), gsp_1triangle(0, 2, 3)],
+        )
+    }
+
+    #[test]
+    fn cull_dl_keeps_a_visible_range() {
+        let result = cull_walk(0.0);
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        assert_eq!(result.culled_dls, 0);
+        assert_eq!(result.scene.indices.len(), 6);
+    }
+
+    #[test]
+    fn cull_dl_ends_the_sub_dl_when_every_vertex_is_past_one_plane() {
+        // All four vertices land at clip X > W; the caller still resumes after the call.
+        let result = cull_walk(4.0);
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        assert_eq!(result.culled_dls, 1);
+        assert_eq!(result.scene.indices.len(), 3);
+    }
+
+    #[test]
+    fn cull_dl_keeps_a_range_straddling_different_planes() {
+        // Shifted past +X only halfway: the left vertices stay inside, so nothing shares a plane.
+        let result = cull_walk(1.5);
+        assert_eq!(result.culled_dls, 0);
+        assert_eq!(result.scene.indices.len(), 6);
+    }
+
+    #[test]
+    fn cull_dl_at_the_top_level_ends_the_walk() {
+        let result = run(
+            scene_data(-4.0, &[]),
+            &[gsp_cull_displaylist(0, 3), gsp_1triangle(0, 1, 2)],
+        );
+        assert_eq!(result.culled_dls, 1);
+        assert!(result.scene.indices.is_empty());
+        assert_eq!(result.commands, 6);
+    }
+
+    /// The branch target draws one triangle; the fall-through draws nothing.
+    fn branch_z_walk(vtx: u8, zval: f32) -> InterpResult {
+        let sub = [gsp_1triangle(0, 1, 2), gsp_enddl()];
+        let tail = gsp_branch_less_z_raw(SUB_ADDR, vtx, (zval * 65536.0) as u32);
+        run(scene_data(0.0, &sub), &tail)
+    }
+
+    #[test]
+    fn branch_z_follows_rdphalf_target_when_vertex_is_near() {
+        let taken = branch_z_walk(2, 300.0);
+        assert!(taken.diags.is_empty(), "{:?}", taken.diags);
+        assert_eq!(taken.scene.indices, vec![0, 1, 2]);
+
+        let not_taken = branch_z_walk(0, 300.0);
+        assert!(not_taken.diags.is_empty(), "{:?}", not_taken.diags);
+        assert!(not_taken.scene.indices.is_empty());
+    }
+
+    #[test]
+    fn load_ucode_remaps_the_geometry_mode_onto_f3d_bits() {
+        use crate::hle::consts::{rsp_f3d, G_CLIPPING, G_CULL_BACK, G_SHADING_SMOOTH, G_ZBUFFER};
+        use n64_gbi::encode::{gsp_enddl_f3d, gsp_load_ucode, gsp_set_geometrymode};
+
+        let mut bytes = scene_data(0.0, &[]);
+        let text = bytes.len() as u32;
+        bytes.extend(crate::hle::gbi::fixture_ucode(GbiUcode::F3d));
+        let entry = bytes.len() as u64;
+        let mut commands = vec![gsp_set_geometrymode(
+            G_ZBUFFER | G_CULL_BACK | G_SHADING_SMOOTH,
+        )];
+        commands.extend(gsp_load_ucode(text, text + 0x1000, 0x800));
+        commands.push(gsp_enddl_f3d());
+        for command in commands {
+            push(&mut bytes, command);
+        }
+        let result = interpret(
+            RdramImage::new(&bytes),
+            entry,
+            GbiUcode::F3dex2,
+            crate::hle::mem::GbiDataFormat::Fixed,
+        );
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        assert_eq!(
+            result.geometry_mode,
+            G_ZBUFFER | G_CLIPPING | rsp_f3d::G_CULL_BACK | rsp_f3d::G_SHADING_SMOOTH
+        );
+    }
+
+    #[test]
+    fn rdphalf_1_no_command_consumes_is_reported_as_stray() {
+        let [half, _] = gsp_branch_less_z_raw(SUB_ADDR, 0, 0);
+        let result = run(scene_data(0.0, &[]), &[half, gsp_1triangle(0, 1, 2)]);
+        let stray = |r: &InterpResult| {
+            r.diags
+                .iter()
+                .filter(|d| d.kind == crate::diag::DiagKind::StrayRdphalf)
+                .count()
+        };
+        assert_eq!(stray(&result), 1, "{:?}", result.diags);
+        assert_eq!(result.scene.indices, vec![0, 1, 2]);
+
+        assert_eq!(stray(&branch_z_walk(2, 300.0)), 0);
+    }
+
+    #[test]
+    fn branch_z_is_taken_when_vertex_z_equals_zval() {
+        let equal = branch_z_walk(2, 255.5);
+        assert!(equal.diags.is_empty(), "{:?}", equal.diags);
+        assert_eq!(equal.scene.indices, vec![0, 1, 2]);
+
+        assert!(branch_z_walk(2, 255.0).scene.indices.is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "asm"))]
+mod insert_matrix_tests {
+    use super::cull_branch_tests::{run_as, scene_data, VTX_ADDR};
+    use crate::hle::consts::G_MWO_MATRIX_XX_XY_I;
+    use crate::hle::gbi::GbiUcode;
+    use n64_gbi::encode::{gsp_insert_matrix, gsp_vertex};
+
+    #[test]
+    fn insert_matrix_patches_the_mvp_later_vertices_use() {
+        // Word 7 holds the integer halves of wz and ww.
+        let result = run_as(
+            scene_data(0.0, &[]),
+            &[
+                gsp_insert_matrix(G_MWO_MATRIX_XX_XY_I + 0x1C, 0x0000_0002),
+                gsp_vertex(0, 4, VTX_ADDR),
+            ],
+            GbiUcode::F3dex2,
+        );
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        let scene = &result.scene;
+        let before = scene.mvp_table[scene.mtx_index[0] as usize];
+        let after = scene.mvp_table[*scene.mtx_index.last().unwrap() as usize];
+        let mut expected = before;
+        expected[3][3] = 2.0;
+        assert_eq!(after, expected);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::hle::consts::rsp_f3dzex2::{
+    G_BRANCH_Z, G_DMA_IO, G_SPECIAL_1, G_SPECIAL_2, G_SPECIAL_3, VTX_DMEM_STRIDE,
+};
+use crate::hle::interp::{Cmd, Ctx, Handler};
+use crate::hle::mem::Rdram;
+
+/// F3DZEX2 is F3DEX2 plus G_DMA_IO and the G_SPECIAL_* hooks, with G_BRANCH_Z reading its
+/// vertex from the `vtx*5` field. Point lights ride the shared light path, gated by the
+/// `g_lighting_positional` constant.
+pub(crate) fn install_overrides<M: Rdram>(t: &mut [Handler<M>; 256]) {
+    crate::hle::rsp_f3dex2::install_overrides(t);
+    // G_DMA_IO moves data between DMEM and DRAM for debugging; DMEM is not modelled, and the
+    // G_SPECIAL_* hooks do nothing in the stock microcode.
+    for op in [G_DMA_IO, G_SPECIAL_1, G_SPECIAL_2, G_SPECIAL_3] {
+        t[op as usize] = no_op::<M>;
+    }
+    t[G_BRANCH_Z as usize] = branch_z::<M>;
+}
+
+fn no_op<M: Rdram>(_c: &Cmd, _cx: &mut Ctx<M>) {}
+
+/// gsSPBranchLessZraw under F3DZEX2: the vertex is the DMEM offset in the `vtx*5` field at
+/// p0[12,12] (8-byte units of the 40-byte vertex), not F3DEX2's `vtx*2`. Inclusive like the
+/// other G_BRANCH_Z handlers.
+fn branch_z<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let slot = c.p0(12, 12) * 8 / VTX_DMEM_STRIDE;
+    let zval = c.w1 as i32 as f32 / 65536.0;
+    let target = cx.rsp.take_rdphalf_1();
+    if let Some(z) = cx.rsp.vertex_screen_z(slot, cx.scene) {
+        if z <= zval {
+            cx.branch = Some(cx.mem.resolve_masked(target));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "asm"))]
+mod tests {
+    use crate::hle::consts::rsp_f3dzex2::{
+        G_DMA_IO, G_LIGHTING, G_LIGHTING_POSITIONAL, G_SPECIAL_1, G_SPECIAL_2, G_SPECIAL_3,
+    };
+    use crate::hle::gbi::GbiUcode;
+    use crate::hle::rsp_f3dex2::cull_branch_tests::{run_as, scene_data, SUB_ADDR, VTX_ADDR};
+    use crate::DiagKind;
+    use n64_gbi::encode::{
+        gsp_1triangle, gsp_branch_less_z_raw, gsp_enddl, gsp_light, gsp_numlights,
+        gsp_set_geometrymode, gsp_vertex,
+    };
+
+    const SPECIALS: [u8; 4] = [G_DMA_IO, G_SPECIAL_1, G_SPECIAL_2, G_SPECIAL_3];
+
+    #[test]
+    fn dma_io_and_specials_are_unknown_only_under_f3dex2() {
+        let tail: Vec<_> = SPECIALS
+            .iter()
+            .map(|&op| ((op as u32) << 24, 0x0010_0000))
+            .collect();
+        let f3dex2 = run_as(scene_data(0.0, &[]), &tail, GbiUcode::F3dex2);
+        let unknown = f3dex2
+            .diags
+            .iter()
+            .filter(|d| matches!(d.kind, DiagKind::UnknownOpcode(_)))
+            .count();
+        assert_eq!(unknown, SPECIALS.len(), "{:?}", f3dex2.diags);
+
+        let f3dzex2 = run_as(scene_data(0.0, &[]), &tail, GbiUcode::F3dzex2);
+        assert!(f3dzex2.diags.is_empty(), "{:?}", f3dzex2.diags);
+    }
+
+    #[test]
+    fn branch_z_reads_the_vtx5_field() {
+        let sub = [gsp_1triangle(0, 1, 2), gsp_enddl()];
+        // `vtx*5` names the near vertex 2 while `vtx*2` names the far vertex 0.
+        let [half, branch] = gsp_branch_less_z_raw(SUB_ADDR, 2, (300.0 * 65536.0) as u32);
+        let branch = (branch.0 & !0xFFF, branch.1);
+
+        let f3dzex2 = run_as(scene_data(0.0, &sub), &[half, branch], GbiUcode::F3dzex2);
+        assert!(f3dzex2.diags.is_empty(), "{:?}", f3dzex2.diags);
+        assert_eq!(f3dzex2.scene.indices, vec![0, 1, 2]);
+
+        let f3dex2 = run_as(scene_data(0.0, &sub), &[half, branch], GbiUcode::F3dex2);
+        assert!(f3dex2.scene.indices.is_empty());
+    }
+
+    #[test]
+    fn branch_z_is_taken_when_vertex_z_equals_zval() {
+        let sub = [gsp_1triangle(0, 1, 2), gsp_enddl()];
+        let [half, branch] = gsp_branch_less_z_raw(SUB_ADDR, 2, (255.5 * 65536.0) as u32);
+        let branch = (branch.0 & !0xFFF, branch.1);
+        let result = run_as(scene_data(0.0, &sub), &[half, branch], GbiUcode::F3dzex2);
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        assert_eq!(result.scene.indices, vec![0, 1, 2]);
+    }
+
+    /// Load one light from a PointLight_t (kc 16, kl 2, kq 3 at (100, -20, 7)) and reload the
+    /// quad with `geom` lighting bits set.
+    fn point_light_walk(ucode: GbiUcode, geom: u32) -> crate::hle::InterpResult {
+        let mut bytes = scene_data(0.0, &[]);
+        let light = [
+            0xFF, 0x80, 0x40, 16, 0xFF, 0x80, 0x40, 2, 0x00, 100, 0xFF, 0xEC, 0x00, 7, 3, 0,
+        ];
+        bytes[SUB_ADDR as usize..SUB_ADDR as usize + 16].copy_from_slice(&light);
+        run_as(
+            bytes,
+            &[
+                gsp_set_geometrymode(geom),
+                gsp_numlights(1),
+                gsp_light(1, SUB_ADDR),
+                gsp_vertex(0, 4, VTX_ADDR),
+            ],
+            ucode,
+        )
+    }
+
+    #[test]
+    fn positional_lighting_folds_a_point_light_into_the_scene_tables() {
+        let result = point_light_walk(GbiUcode::F3dzex2, G_LIGHTING | G_LIGHTING_POSITIONAL);
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        let scene = &result.scene;
+        let li = *scene.light_index.last().unwrap() as usize;
+        assert_eq!(*scene.light_count.last().unwrap(), 2);
+        // Identity modelview: the object-space position is the light's own.
+        assert_eq!(scene.lights_table[li].0, [100.0, -20.0, 7.0]);
+        assert_eq!(scene.lights_table[li].1, [1.0, 128.0 / 255.0, 64.0 / 255.0]);
+        assert_eq!(
+            scene.light_atten[li],
+            [16.0 / 16.0, 2.0 / 2048.0, 3.0 / 524288.0, 1.0]
+        );
+        assert_eq!(scene.light_atten[li + 1], [0.0; 4]);
+        assert_eq!(scene.light_atten.len(), scene.lights_table.len());
+    }
+
+    #[test]
+    fn point_lights_need_the_positional_bit_and_f3dzex2() {
+        for (ucode, geom) in [
+            (GbiUcode::F3dzex2, G_LIGHTING),
+            (GbiUcode::F3dex2, G_LIGHTING | G_LIGHTING_POSITIONAL),
+        ] {
+            let result = point_light_walk(ucode, geom);
+            let scene = &result.scene;
+            let li = *scene.light_index.last().unwrap() as usize;
+            assert_eq!(scene.light_atten[li], [0.0; 4], "{ucode:?}");
+            // The bytes read as a Light_t: a unit direction from the s8 (0, 100, -1).
+            let dir = scene.lights_table[li].0;
+            assert!((dir[0]).abs() < 1e-6 && dir[1] > 0.99, "{ucode:?} {dir:?}");
+        }
+    }
+}
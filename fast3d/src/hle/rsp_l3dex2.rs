@@ -0,0 +1,97 @@
+use crate::hle::consts::rsp_l3dex2::{G_LINE3D, G_TRI1, G_TRI2};
+use crate::hle::interp::{unknown, Cmd, Ctx, Handler};
+use crate::hle::mem::Rdram;
+
+/// Pixel width of a width-0 line; each unit of the command's `wd` field adds half a pixel.
+const LINE_BASE_WIDTH: f32 = 1.5;
+
+/// L3DEX2 is F3DEX2 with the triangle commands replaced by G_LINE3D: G_TRI1 and G_TRI2 are
+/// gone, everything else (vertices, matrices, lighting, the RDP side) is shared.
+pub(crate) fn install_overrides<M: Rdram>(t: &mut [Handler<M>; 256]) {
+    crate::hle::rsp_f3dex2::install_overrides(t);
+    t[G_TRI1 as usize] = unknown::<M>;
+    t[G_TRI2 as usize] = unknown::<M>;
+    t[G_LINE3D as usize] = line3d::<M>;
+}
+
+/// gsSPLineW3D(v0, v1, wd): p0[16,8] and p0[8,8] are the endpoints as `v*2`, p0[0,8] the
+/// width step.
+fn line3d<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    if let Some((mi, ri)) = crate::hle::rsp::snapshot_run(cx.rsp, cx.rdp, cx.diags, cx.scene, cx.pc)
+    {
+        crate::hle::rsp::record_line(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            c.p0(16, 8) / 2,
+            c.p0(8, 8) / 2,
+            LINE_BASE_WIDTH + c.p0(0, 8) as f32 * 0.5,
+            mi,
+            ri,
+        );
+    } else {
+        *cx.dropped_runs += 1;
+    }
+}
+
+#[cfg(all(test, feature = "asm"))]
+mod tests {
+    use crate::hle::gbi::GbiUcode;
+    use crate::hle::rsp_f3dex2::cull_branch_tests::{run_as, scene_data};
+    use crate::scene::CullKind;
+    use crate::DiagKind;
+    use n64_gbi::encode::{gsp_1triangle, gsp_line_w3d, gsp_set_geometrymode};
+
+    fn unknown_count(result: &crate::hle::InterpResult) -> usize {
+        result
+            .diags
+            .iter()
+            .filter(|d| matches!(d.kind, DiagKind::UnknownOpcode(_)))
+            .count()
+    }
+
+    #[test]
+    fn line_becomes_a_quad_of_offset_corners() {
+        // Cull-both would drop a triangle; lines are never culled.
+        let result = run_as(
+            scene_data(0.0, &[]),
+            &[gsp_set_geometrymode(0x0000_0600), gsp_line_w3d(0, 2, 3)],
+            GbiUcode::L3dex2,
+        );
+        assert!(result.diags.is_empty(), "{:?}", result.diags);
+        let scene = &result.scene;
+        assert_eq!(scene.draw_runs.len(), 1);
+        assert_eq!(scene.draw_runs[0].cull, CullKind::None);
+        assert_eq!(scene.draw_runs[0].index_count, 6);
+
+        // Four loaded vertices, then two corners per endpoint.
+        let corners: Vec<_> = (4..8)
+            .map(|i| (scene.line_offset[i], scene.line_other[i]))
+            .collect();
+        assert_eq!(corners, [(1.5, 2), (-1.5, 2), (1.5, 0), (-1.5, 0)]);
+        assert_eq!(scene.raw_pos[4], scene.raw_pos[0]);
+        assert_eq!(scene.raw_pos[6], scene.raw_pos[2]);
+        assert_eq!(scene.indices, vec![4, 5, 6, 4, 6, 7]);
+        assert!(scene.line_offset[..4].iter().all(|&h| h == 0.0));
+    }
+
+    #[test]
+    fn lines_and_triangles_are_exclusive_to_their_microcode() {
+        let l3dex2 = run_as(
+            scene_data(0.0, &[]),
+            &[gsp_1triangle(0, 1, 2)],
+            GbiUcode::L3dex2,
+        );
+        assert_eq!(unknown_count(&l3dex2), 1, "{:?}", l3dex2.diags);
+        assert!(l3dex2.scene.indices.is_empty());
+
+        // F3DEX2 has no 0x08 command.
+        let f3dex2 = run_as(
+            scene_data(0.0, &[]),
+            &[gsp_line_w3d(0, 1, 0)],
+            GbiUcode::F3dex2,
+        );
+        assert_eq!(unknown_count(&f3dex2), 1, "{:?}", f3dex2.diags);
+    }
+}
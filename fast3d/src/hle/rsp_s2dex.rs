@@ -0,0 +1,1150 @@
+//! S2DEX / S2DEX2 sprite microcode. Each object/background command decodes its gs2dex.h
+//! structure from RDRAM, loads TMEM and programs the render tile the way the ucode's internal RDP
+//! stream would, then records a `SceneOp::TexRect` through the shared 2D rect path.
+use crate::hle::consts::s2dex_types::{
+    G_BG_FLAG_FLIPS, G_OBJLT_TLUT, G_OBJLT_TXTRBLOCK, G_OBJLT_TXTRTILE, G_OBJ_FLAG_FLIPS,
+    G_OBJ_FLAG_FLIPT, OBJ_BG_SIZE, OBJ_MTX_SIZE, OBJ_SPRITE_SIZE, OBJ_SUBMTX_SIZE, OBJ_TXTR_SIZE,
+};
+use crate::hle::consts::{rsp_f3d, rsp_f3dex2, rsp_s2dex, rsp_s2dex2};
+use crate::hle::interp::{unknown, Cmd, Ctx, Handler};
+use crate::hle::mem::Rdram;
+use crate::hle::rdp::{Rdp, TileDescriptor};
+use crate::hle::rsp::Rect;
+use crate::{DiagKind, Diagnostic};
+
+/// The tile the ucode renders through (G_TX_RENDERTILE).
+const RENDER_TILE: usize = 0;
+/// Tile cms/cmt modes: 0=WRAP 2=CLAMP.
+const TX_WRAP: u8 = 0;
+const TX_CLAMP: u8 = 2;
+const G_IM_FMT_CI: u8 = 2;
+/// S2DEX loads textures through TMEM as 16-bit words regardless of the texel size.
+const LOAD_SIZ: u8 = 2;
+
+/// The 2D object matrix (uObjMtx) loaded by gSPObjMatrix / gSPObjSubMatrix: `a..d` from s15.16,
+/// `x`/`y` from s10.2 and the base scales from u5.10.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjMtx {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub x: f32,
+    pub y: f32,
+    pub base_scale_x: f32,
+    pub base_scale_y: f32,
+}
+
+impl Default for ObjMtx {
+    fn default() -> Self {
+        ObjMtx {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            x: 0.0,
+            y: 0.0,
+            base_scale_x: 1.0,
+            base_scale_y: 1.0,
+        }
+    }
+}
+
+impl ObjMtx {
+    fn load(&mut self, b: &[u8]) {
+        self.a = be32(b, 0) as i32 as f32 / 65536.0;
+        self.b = be32(b, 4) as i32 as f32 / 65536.0;
+        self.c = be32(b, 8) as i32 as f32 / 65536.0;
+        self.d = be32(b, 12) as i32 as f32 / 65536.0;
+        self.load_sub(&b[16..]);
+    }
+
+    /// uObjSubMtx: only the translation and base scales.
+    fn load_sub(&mut self, b: &[u8]) {
+        self.x = be16(b, 0) as i16 as f32 / 4.0;
+        self.y = be16(b, 2) as i16 as f32 / 4.0;
+        self.base_scale_x = be16(b, 4) as f32 / 1024.0;
+        self.base_scale_y = be16(b, 6) as f32 / 1024.0;
+    }
+}
+
+/// S2DEX 1.x: the F3D opcode map with the 3D commands replaced by the object commands.
+pub(crate) fn install_overrides_s2dex<M: Rdram>(t: &mut [Handler<M>; 256]) {
+    crate::hle::rsp_f3d::install_overrides(t);
+    // No vertex pipeline: F3D's geometry commands that S2DEX does not reuse are unknown here.
+    for op in [
+        rsp_f3d::G_SPRITE2D_BASE,
+        rsp_f3d::G_QUAD,
+        rsp_f3d::G_CLEARGEOMETRYMODE,
+        rsp_f3d::G_SETGEOMETRYMODE,
+        rsp_f3d::G_TEXTURE,
+        rsp_f3d::G_POPMTX,
+        rsp_f3d::G_CULLDL,
+        rsp_f3d::G_TRI1,
+        rsp_s2dex::G_SELECT_DL,
+    ] {
+        t[op as usize] = unknown::<M>;
+    }
+    t[rsp_s2dex::G_BG_1CYC as usize] = bg_1cyc::<M>;
+    t[rsp_s2dex::G_BG_COPY as usize] = bg_copy::<M>;
+    t[rsp_s2dex::G_OBJ_RECTANGLE as usize] = obj_rectangle::<M>;
+    t[rsp_s2dex::G_OBJ_SPRITE as usize] = obj_sprite::<M>;
+    t[rsp_s2dex::G_OBJ_MOVEMEM as usize] = obj_movemem_s2dex::<M>;
+    t[rsp_s2dex::G_OBJ_RENDERMODE as usize] = obj_render_mode::<M>;
+    t[rsp_s2dex::G_OBJ_RECTANGLE_R as usize] = obj_rectangle_r::<M>;
+    t[rsp_s2dex::G_OBJ_LOADTXTR as usize] = obj_load_txtr::<M>;
+    t[rsp_s2dex::G_OBJ_LDTX_SPRITE as usize] = obj_ldtx_sprite::<M>;
+    t[rsp_s2dex::G_OBJ_LDTX_RECT as usize] = obj_ldtx_rect::<M>;
+    t[rsp_s2dex::G_OBJ_LDTX_RECT_R as usize] = obj_ldtx_rect_r::<M>;
+    t[rsp_s2dex::G_RDPHALF_1 as usize] = crate::hle::rdp::rdphalf_1::<M>;
+    t[rsp_s2dex::G_LOAD_UCODE as usize] = crate::hle::interp::load_ucode::<M>;
+}
+
+/// S2DEX2: the F3DEX2 opcode map with the 3D commands replaced by the object commands.
+pub(crate) fn install_overrides_s2dex2<M: Rdram>(t: &mut [Handler<M>; 256]) {
+    crate::hle::rsp_f3dex2::install_overrides(t);
+    for op in [
+        rsp_f3dex2::G_GEOMETRYMODE,
+        rsp_f3dex2::G_TEXTURE,
+        rsp_f3dex2::G_POPMTX,
+        rsp_f3dex2::G_CULLDL,
+        rsp_s2dex2::G_SELECT_DL,
+    ] {
+        t[op as usize] = unknown::<M>;
+    }
+    t[rsp_s2dex2::G_OBJ_RECTANGLE as usize] = obj_rectangle::<M>;
+    t[rsp_s2dex2::G_OBJ_SPRITE as usize] = obj_sprite::<M>;
+    t[rsp_s2dex2::G_OBJ_LOADTXTR as usize] = obj_load_txtr::<M>;
+    t[rsp_s2dex2::G_OBJ_LDTX_SPRITE as usize] = obj_ldtx_sprite::<M>;
+    t[rsp_s2dex2::G_OBJ_LDTX_RECT as usize] = obj_ldtx_rect::<M>;
+    t[rsp_s2dex2::G_OBJ_LDTX_RECT_R as usize] = obj_ldtx_rect_r::<M>;
+    t[rsp_s2dex2::G_BG_1CYC as usize] = bg_1cyc::<M>;
+    t[rsp_s2dex2::G_BG_COPY as usize] = bg_copy::<M>;
+    t[rsp_s2dex2::G_OBJ_RENDERMODE as usize] = obj_render_mode::<M>;
+    t[rsp_s2dex2::G_OBJ_RECTANGLE_R as usize] = obj_rectangle_r::<M>;
+    t[rsp_s2dex2::G_OBJ_MOVEMEM as usize] = obj_movemem_s2dex2::<M>;
+}
+
+fn be16(b: &[u8], off: usize) -> u16 {
+    u16::from_be_bytes([b[off], b[off + 1]])
+}
+
+fn be32(b: &[u8], off: usize) -> u32 {
+    u32::from_be_bytes([b[off], b[off + 1], b[off + 2], b[off + 3]])
+}
+
+/// Decoded uObjSprite (24 bytes).
+struct ObjSprite {
+    obj_x: i16,
+    scale_w: u16,
+    image_w: u16,
+    obj_y: i16,
+    scale_h: u16,
+    image_h: u16,
+    image_stride: u16,
+    image_adrs: u16,
+    image_fmt: u8,
+    image_siz: u8,
+    image_pal: u8,
+    image_flags: u8,
+}
+
+impl ObjSprite {
+    fn read<M: Rdram>(mem: &M, addr: u64) -> Self {
+        let b = mem.read_bytes(addr, OBJ_SPRITE_SIZE as usize);
+        ObjSprite {
+            obj_x: be16(&b, 0) as i16,
+            scale_w: be16(&b, 2),
+            image_w: be16(&b, 4),
+            obj_y: be16(&b, 8) as i16,
+            scale_h: be16(&b, 10),
+            image_h: be16(&b, 12),
+            image_stride: be16(&b, 16),
+            image_adrs: be16(&b, 18),
+            image_fmt: b[20],
+            image_siz: b[21],
+            image_pal: b[22],
+            image_flags: b[23],
+        }
+    }
+}
+
+/// Decoded uObjBg / uObjScaleBg (40 bytes). The scale words are only read by gSPBgRect1Cyc.
+struct ObjBg {
+    image_x: u16,
+    image_w: u16,
+    frame_x: i16,
+    frame_w: u16,
+    image_y: u16,
+    image_h: u16,
+    frame_y: i16,
+    frame_h: u16,
+    image_ptr: u32,
+    image_fmt: u8,
+    image_siz: u8,
+    image_pal: u16,
+    image_flip: u16,
+    scale_w: u16,
+    scale_h: u16,
+}
+
+impl ObjBg {
+    fn read<M: Rdram>(mem: &M, addr: u64) -> Self {
+        let b = mem.read_bytes(addr, OBJ_BG_SIZE as usize);
+        ObjBg {
+            image_x: be16(&b, 0),
+            image_w: be16(&b, 2),
+            frame_x: be16(&b, 4) as i16,
+            frame_w: be16(&b, 6),
+            image_y: be16(&b, 8),
+            image_h: be16(&b, 10),
+            frame_y: be16(&b, 12) as i16,
+            frame_h: be16(&b, 14),
+            image_ptr: be32(&b, 16),
+            image_fmt: b[22],
+            image_siz: b[23],
+            image_pal: be16(&b, 24),
+            image_flip: be16(&b, 26),
+            scale_w: be16(&b, 28),
+            scale_h: be16(&b, 30),
+        }
+    }
+}
+
+/// Object space → screen: `screen = local * scale + offset`, per axis. A negative scale mirrors.
+#[derive(Clone, Copy)]
+struct Placement {
+    scale: [f32; 2],
+    offset: [f32; 2],
+}
+
+const SCREEN: Placement = Placement {
+    scale: [1.0, 1.0],
+    offset: [0.0, 0.0],
+};
+
+/// Program the render tile as the ucode's SetTile + SetTileSize pair would.
+#[allow(clippy::too_many_arguments)]
+fn set_render_tile(
+    rdp: &mut Rdp,
+    fmt: u8,
+    siz: u8,
+    line: u16,
+    tmem_addr: u16,
+    palette: u8,
+    (w, h): (u32, u32),
+    (cms, cmt): (u8, u8),
+) {
+    rdp.tiles[RENDER_TILE] = TileDescriptor {
+        uls: 0,
+        ult: 0,
+        lrs: ((w - 1) << 2) as u16,
+        lrt: ((h - 1) << 2) as u16,
+        width: w as u16,
+        height: h as u16,
+        fmt,
+        siz,
+        palette,
+        cms,
+        cmt,
+        line,
+        tmem_addr,
+        ..TileDescriptor::default()
+    };
+}
+
+/// One texrect axis: the S10.5 start coordinate and S5.10 step for `len` (u10.5 texels) sampled at
+/// `texels_per_px`, walking backwards from the far edge when `flip`. COPY cycle steps 4 texels per
+/// pixel (the renderer undoes it with `dsdx >> 2`), so the S step is pre-scaled there.
+fn texrect_axis(len: u16, texels_per_px: f32, flip: bool, copy_scale: f32) -> (i16, i16) {
+    let step = (texels_per_px * copy_scale * 1024.0)
+        .round()
+        .clamp(-(i16::MAX as f32), i16::MAX as f32) as i16;
+    if flip {
+        ((len as i32 - 1).max(0) as i16, -step)
+    } else {
+        (0, step)
+    }
+}
+
+fn copy_scale(rdp: &Rdp) -> f32 {
+    if ((rdp.other_mode_h >> 20) & 3) == crate::hle::consts::G_CYC_COPY {
+        4.0
+    } else {
+        1.0
+    }
+}
+
+/// Drop a 2D op issued before the first CIMG, mirroring the inline TEXRECT path.
+fn drop_before_cimg<M: Rdram>(cx: &mut Ctx<M>) -> bool {
+    if cx.rec.have_seen_cimg {
+        return false;
+    }
+    *cx.dropped_runs += 1;
+    cx.diags.push(Diagnostic {
+        at: cx.pc,
+        kind: DiagKind::DrawBeforeCimg,
+    });
+    true
+}
+
+fn draw_sprite<M: Rdram>(cx: &mut Ctx<M>, addr: u64, place: Placement) {
+    if drop_before_cimg(cx) {
+        return;
+    }
+    let s = ObjSprite::read(cx.mem, addr);
+    let tex_w = (s.image_w as u32 >> 5).max(1);
+    let tex_h = (s.image_h as u32 >> 5).max(1);
+    set_render_tile(
+        cx.rdp,
+        s.image_fmt,
+        s.image_siz,
+        s.image_stride,
+        s.image_adrs,
+        s.image_pal,
+        (tex_w, tex_h),
+        (TX_CLAMP, TX_CLAMP),
+    );
+    cx.rsp.material_dirty = true;
+
+    // Object-space extent: imageW texels drawn at scaleW texels per pixel.
+    let texels_per_px = [
+        s.scale_w.max(1) as f32 / 1024.0,
+        s.scale_h.max(1) as f32 / 1024.0,
+    ];
+    let origin = [s.obj_x as f32 / 4.0, s.obj_y as f32 / 4.0];
+    let size = [
+        s.image_w as f32 / 32.0 / texels_per_px[0],
+        s.image_h as f32 / 32.0 / texels_per_px[1],
+    ];
+    let mut lo = [0i32; 2];
+    let mut hi = [0i32; 2];
+    for i in 0..2 {
+        let a = origin[i] * place.scale[i] + place.offset[i];
+        let b = (origin[i] + size[i]) * place.scale[i] + place.offset[i];
+        lo[i] = a.min(b).floor() as i32;
+        hi[i] = a.max(b).floor() as i32;
+    }
+    let rect = Rect {
+        ulx: lo[0],
+        uly: lo[1],
+        lrx: hi[0],
+        lry: hi[1],
+    };
+
+    let flip_s = (s.image_flags & G_OBJ_FLAG_FLIPS != 0) ^ (place.scale[0] < 0.0);
+    let flip_t = (s.image_flags & G_OBJ_FLAG_FLIPT != 0) ^ (place.scale[1] < 0.0);
+    let (uls, dsdx) = texrect_axis(
+        s.image_w,
+        texels_per_px[0] / place.scale[0].abs(),
+        flip_s,
+        copy_scale(cx.rdp),
+    );
+    let (ult, dtdy) = texrect_axis(
+        s.image_h,
+        texels_per_px[1] / place.scale[1].abs(),
+        flip_t,
+        1.0,
+    );
+    crate::hle::rsp::record_texrect(
+        cx.rsp,
+        cx.rdp,
+        cx.scene,
+        cx.rec,
+        cx.diags,
+        cx.pc,
+        rect,
+        (uls, ult),
+        (dsdx, dtdy),
+        false,
+    );
+}
+
+/// gSPObjRectangle: the sprite at its own screen position and scale.
+fn obj_rectangle<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    draw_sprite(cx, addr, SCREEN);
+}
+
+/// gSPObjRectangleR: the sprite placed by the object matrix's translation and base scale.
+fn obj_rectangle_r<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    let m = cx.rsp.obj_mtx;
+    let place = Placement {
+        scale: [1.0 / m.base_scale_x, 1.0 / m.base_scale_y],
+        offset: [m.x, m.y],
+    };
+    draw_sprite(cx, addr, place);
+}
+
+/// gSPObjSprite: the sprite through the full object matrix. Only scale/mirror matrices keep it a
+/// rectangle; a rotation or shear is dropped with a diagnostic.
+fn obj_sprite<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let m = cx.rsp.obj_mtx;
+    if m.b != 0.0 || m.c != 0.0 || m.a == 0.0 || m.d == 0.0 {
+        *cx.dropped_runs += 1;
+        cx.diags.push(Diagnostic {
+            at: cx.pc,
+            kind: DiagKind::RotatedObjSprite,
+        });
+        return;
+    }
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    let place = Placement {
+        scale: [m.a, m.d],
+        offset: [m.x, m.y],
+    };
+    draw_sprite(cx, addr, place);
+}
+
+/// gSPObjLoadTxtr: run the uObjTxtr's LoadBlock / LoadTile / LoadTLUT into TMEM. The
+/// `sid`/`flag`/`mask` load-skip test is not modelled; every load is performed.
+fn load_txtr<M: Rdram>(cx: &mut Ctx<M>, addr: u64) {
+    let b = cx.mem.read_bytes(addr, OBJ_TXTR_SIZE as usize).into_owned();
+    let image = cx.mem.resolve_masked(be32(&b, 4) as u64);
+    let (h0, h1, h2) = (
+        be16(&b, 8) as usize,
+        be16(&b, 10) as usize,
+        be16(&b, 12) as usize,
+    );
+    match be32(&b, 0) {
+        G_OBJLT_TXTRBLOCK => {
+            // tmem, tsize (words - 1), tline (the DXT increment).
+            let words = h1 + 1;
+            let src = cx.mem.read_bytes(image, words << 3).into_owned();
+            cx.rdp
+                .tmem_bank
+                .write_block(&src, h0, 0, h2 as u32, words, 0, LOAD_SIZ);
+            cx.rdp.tex_image = (0, LOAD_SIZ, 0, image);
+            cx.rdp.tmem = src;
+            cx.rdp.load_via_tile = false;
+        }
+        G_OBJLT_TXTRTILE => {
+            // tmem, twidth (line words * 4 - 1), theight (rows * 4 - 1).
+            let line_words = (h1 + 1) >> 2;
+            let rows = (h2 + 1) >> 2;
+            let src = cx
+                .mem
+                .read_bytes(image, rows * (line_words << 3))
+                .into_owned();
+            cx.rdp.tmem_bank.write_tile(
+                &src,
+                h0,
+                line_words,
+                rows,
+                line_words,
+                line_words << 3,
+                0,
+                LOAD_SIZ,
+            );
+            cx.rdp.tex_image = (0, LOAD_SIZ, ((line_words << 2) - 1) as u16, image);
+            cx.rdp.tmem = src;
+            cx.rdp.load_via_tile = true;
+        }
+        G_OBJLT_TLUT => {
+            // phead (first entry + 256, i.e. a palette-region TMEM word), pnum (entries - 1).
+            let count = h1 + 1;
+            let packed = cx.mem.read_bytes(image, count * 2);
+            cx.rdp.tmem_bank.write_tlut(&packed, count, h0);
+        }
+        _ => return,
+    }
+    cx.rsp.material_dirty = true;
+}
+
+fn obj_load_txtr<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    load_txtr(cx, addr);
+}
+
+/// uObjTxSprite = uObjTxtr followed by uObjSprite: load, then draw.
+fn obj_ldtx_sprite<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    obj_load_txtr(c, cx);
+    let next = Cmd {
+        w1_addr: c.w1_addr + OBJ_TXTR_SIZE as u64,
+        ..*c
+    };
+    obj_sprite(&next, cx);
+}
+
+fn obj_ldtx_rect<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    obj_load_txtr(c, cx);
+    let next = Cmd {
+        w1_addr: c.w1_addr + OBJ_TXTR_SIZE as u64,
+        ..*c
+    };
+    obj_rectangle(&next, cx);
+}
+
+fn obj_ldtx_rect_r<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    obj_load_txtr(c, cx);
+    let next = Cmd {
+        w1_addr: c.w1_addr + OBJ_TXTR_SIZE as u64,
+        ..*c
+    };
+    obj_rectangle_r(&next, cx);
+}
+
+/// S2DEX2 G_OBJ_MOVEMEM (gDma2p): the `(len-1)/8` field tells uObjMtx (2) from uObjSubMtx (0).
+fn obj_movemem_s2dex2<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    if c.p0(19, 5) == 0 {
+        let b = cx.mem.read_bytes(addr, OBJ_SUBMTX_SIZE as usize);
+        cx.rsp.obj_mtx.load_sub(&b);
+    } else {
+        let b = cx.mem.read_bytes(addr, OBJ_MTX_SIZE as usize);
+        cx.rsp.obj_mtx.load(&b);
+    }
+}
+
+/// S2DEX 1.x G_OBJ_MOVEMEM (gDma1p): the `p` field selects the matrix or sub-matrix.
+fn obj_movemem_s2dex<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    match c.p0(16, 8) as u8 {
+        rsp_s2dex::G_OBJ_MV_MATRIX => {
+            let b = cx.mem.read_bytes(addr, OBJ_MTX_SIZE as usize);
+            cx.rsp.obj_mtx.load(&b);
+        }
+        rsp_s2dex::G_OBJ_MV_SUBMATRIX => {
+            let b = cx.mem.read_bytes(addr, OBJ_SUBMTX_SIZE as usize);
+            cx.rsp.obj_mtx.load_sub(&b);
+        }
+        p => cx.diags.push(Diagnostic {
+            at: cx.pc,
+            kind: DiagKind::UnhandledMovemem(p),
+        }),
+    }
+}
+
+/// G_OBJ_RENDERMODE tunes the ucode's own sprite rasterization (shrink/bilerp bias); the texrect
+/// path draws without it.
+fn obj_render_mode<M: Rdram>(_c: &Cmd, _cx: &mut Ctx<M>) {}
+
+/// gSPBgRectCopy: an unscaled copy-mode blit.
+fn bg_copy<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    let bg = ObjBg::read(cx.mem, addr);
+    draw_bg(cx, &bg, (1 << 10, 1 << 10));
+}
+
+/// gSPBgRect1Cyc: a scaled 1-cycle blit (uObjScaleBg).
+fn bg_1cyc<M: Rdram>(c: &Cmd, cx: &mut Ctx<M>) {
+    let addr = cx.mem.resolve_masked(c.w1_addr);
+    let bg = ObjBg::read(cx.mem, addr);
+    draw_bg(cx, &bg, (bg.scale_w, bg.scale_h));
+}
+
+/// Blit a background image into its frame. The image rarely fits TMEM, so, like the ucode, it is
+/// streamed in horizontal strips: each strip loads the whole-width texture rows its screen rows
+/// sample (wrapping vertically past the image bottom) and records one texrect.
+fn draw_bg<M: Rdram>(cx: &mut Ctx<M>, bg: &ObjBg, (scale_w, scale_h): (u16, u16)) {
+    if drop_before_cimg(cx) {
+        return;
+    }
+    let img_w = (bg.image_w as u32 >> 2).max(1);
+    let img_h = (bg.image_h as u32 >> 2).max(1);
+    let ptr = cx.mem.resolve_masked(bg.image_ptr as u64);
+    cx.rdp.tex_image = (bg.image_fmt, bg.image_siz, (img_w - 1) as u16, ptr);
+
+    let ulx = bg.frame_x as i32 >> 2;
+    let uly = bg.frame_y as i32 >> 2;
+    let lrx = (bg.frame_x as i32 + bg.frame_w as i32) >> 2;
+    let lry = (bg.frame_y as i32 + bg.frame_h as i32) >> 2;
+
+    let texels_per_px = scale_w.max(1) as f32 / 1024.0;
+    let flip = bg.image_flip & G_BG_FLAG_FLIPS != 0;
+    let (_, dsdx) = texrect_axis(0, texels_per_px, flip, copy_scale(cx.rdp));
+    let uls = if flip {
+        ((img_w << 5) as i32 - 1 - bg.image_x as i32) as i16
+    } else {
+        bg.image_x as i16
+    };
+    let dtdy = scale_h.min(i16::MAX as u16) as i16;
+
+    let row_bytes = ((img_w as usize) << bg.image_siz) >> 1;
+    let line_words = row_bytes.div_ceil(8).max(1);
+
+    // A framebuffer read-back binds the source framebuffer instead of TMEM: one rect, image coords.
+    if crate::hle::rsp::fb_source_for(cx.scene, cx.rec, ptr).is_some() {
+        set_render_tile(
+            cx.rdp,
+            bg.image_fmt,
+            bg.image_siz,
+            line_words as u16,
+            0,
+            bg.image_pal as u8,
+            (img_w, img_h),
+            (TX_WRAP, TX_WRAP),
+        );
+        cx.rsp.material_dirty = true;
+        crate::hle::rsp::record_texrect(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            cx.diags,
+            cx.pc,
+            Rect { ulx, uly, lrx, lry },
+            (uls, bg.image_y as i16),
+            (dsdx, dtdy),
+            false,
+        );
+        return;
+    }
+
+    // CI images share TMEM with the palette: only the low half holds texels.
+    let tmem_bytes = if bg.image_fmt == G_IM_FMT_CI {
+        crate::hle::tmem::PALETTE_BASE
+    } else {
+        crate::hle::tmem::TMEM_BYTES
+    };
+    let rows_fit = (tmem_bytes / (line_words << 3)).max(1) as i32;
+    let sh = scale_h.max(1) as i32;
+    // n screen rows sample (n-1)*sh/1024 texture rows plus a partial row at each end and the
+    // bilinear neighbour below.
+    let strip_rows = if rows_fit > 3 {
+        (rows_fit - 3) * 1024 / sh + 1
+    } else {
+        1
+    };
+
+    let mut y = uly;
+    while y < lry {
+        let y_end = (y + strip_rows).min(lry);
+        let t_fx = bg.image_y as i32 + (((y - uly) * sh) >> 5); // S10.5
+        let t0 = (t_fx >> 5) as u32;
+        let rows = ((((y_end - 1 - y) * sh) >> 10) + 3).min(rows_fit) as usize;
+
+        let mut src = Vec::with_capacity(rows * (line_words << 3));
+        for r in 0..rows {
+            let row = (t0 + r as u32) % img_h;
+            let bytes = cx
+                .mem
+                .read_bytes(ptr + row as u64 * row_bytes as u64, row_bytes);
+            src.extend_from_slice(&bytes);
+            src.resize((r + 1) * (line_words << 3), 0);
+        }
+        cx.rdp.tmem_bank.write_tile(
+            &src,
+            0,
+            line_words,
+            rows,
+            line_words,
+            line_words << 3,
+            bg.image_fmt,
+            bg.image_siz,
+        );
+        cx.rdp.tmem = src;
+        cx.rdp.load_via_tile = true;
+        set_render_tile(
+            cx.rdp,
+            bg.image_fmt,
+            bg.image_siz,
+            line_words as u16,
+            0,
+            bg.image_pal as u8,
+            (img_w, rows as u32),
+            (TX_WRAP, TX_CLAMP),
+        );
+        cx.rsp.material_dirty = true;
+
+        crate::hle::rsp::record_texrect(
+            cx.rsp,
+            cx.rdp,
+            cx.scene,
+            cx.rec,
+            cx.diags,
+            cx.pc,
+            Rect {
+                ulx,
+                uly: y,
+                lrx,
+                lry: y_end,
+            },
+            (uls, (t_fx - ((t0 as i32) << 5)) as i16),
+            (dsdx, dtdy),
+            false,
+        );
+        y = y_end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hle::combiner::decode_rgba16;
+    use crate::hle::consts::G_CYC_COPY;
+    use crate::hle::gbi::{fixture_ucode, GbiUcode, UCODE_TEXT_SIZE};
+    use crate::hle::interp::interpret;
+    use crate::hle::mem::{GbiDataFormat, RdramImage};
+    use crate::hle::rsp::{Rect, SceneOp};
+    use crate::hle::InterpResult;
+    use crate::DiagKind;
+    use n64_gbi::consts::s2dex_types::{G_BGLT_LOADTILE, G_OBJ_FLAG_FLIPS};
+    use n64_gbi::encode::{
+        gdp_set_color_image, gdp_set_cycle_type, gdp_set_cycle_type_f3d, gdp_set_other_mode_h,
+        gs_tb_tline, gs_tb_tsize, gsp_bg_rect_1cyc_s2dex2, gsp_bg_rect_copy_s2dex2, gsp_enddl,
+        gsp_enddl_f3d, gsp_load_ucode, gsp_obj_load_txtr_s2dex, gsp_obj_load_txtr_s2dex2,
+        gsp_obj_matrix_s2dex, gsp_obj_matrix_s2dex2, gsp_obj_rectangle_s2dex,
+        gsp_obj_rectangle_s2dex2, gsp_obj_sprite_s2dex, gsp_obj_sprite_s2dex2, gsp_segment, ObjBg,
+        ObjMtx, ObjSprite, ObjTxtr,
+    };
+
+    const TXTR_ADDR: usize = 0x100;
+    const TLUT_TXTR_ADDR: usize = 0x120;
+    const SPRITE_ADDR: usize = 0x140;
+    const MTX_ADDR: usize = 0x180;
+    const BG_ADDR: usize = 0x1C0;
+    const PAL_ADDR: usize = 0x200;
+    const TEX_ADDR: usize = 0x400;
+    const UCODE_TEXT_ADDR: usize = 0x800;
+    const ENTRY_ADDR: usize = 0x2000;
+    const FB_ADDR: u32 = 0x10_0000;
+
+    fn push(buf: &mut Vec<u8>, (w0, w1): (u32, u32)) {
+        buf.extend_from_slice(&w0.to_be_bytes());
+        buf.extend_from_slice(&w1.to_be_bytes());
+    }
+
+    fn put(buf: &mut [u8], at: usize, bytes: &[u8]) {
+        buf[at..at + bytes.len()].copy_from_slice(bytes);
+    }
+
+    fn run(mut bytes: Vec<u8>, commands: &[(u32, u32)], ucode: GbiUcode) -> InterpResult {
+        assert_eq!(bytes.len(), ENTRY_ADDR);
+        for &command in commands {
+            push(&mut bytes, command);
+        }
+        interpret(
+            RdramImage::new(&bytes),
+            ENTRY_ADDR as u64,
+            ucode,
+            GbiDataFormat::Fixed,
+        )
+    }
+
+    /// (rect, uls, ult, dsdx, dtdy, material_index) for every recorded TexRect.
+    fn texrects(r: &InterpResult) -> Vec<(Rect, i16, i16, i16, i16, u32)> {
+        r.scene
+            .framebuffer_pairs
+            .iter()
+            .flat_map(|p| &p.ops)
+            .filter_map(|op| match *op {
+                SceneOp::TexRect {
+                    rect,
+                    uls,
+                    ult,
+                    dsdx,
+                    dtdy,
+                    material_index,
+                    ..
+                } => Some((rect, uls, ult, dsdx, dtdy, material_index)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// RGBA16 texels that differ per column and row.
+    fn rgba16_texture(w: usize, h: usize) -> Vec<u8> {
+        let mut out = Vec::with_capacity(w * h * 2);
+        for y in 0..h {
+            for x in 0..w {
+                let texel = (((x & 31) as u16) << 11) | (((y & 31) as u16) << 6) | 1;
+                out.extend_from_slice(&texel.to_be_bytes());
+            }
+        }
+        out
+    }
+
+    fn sprite(obj_x: i16, obj_y: i16, scale_w: u16, flags: u8) -> ObjSprite {
+        ObjSprite {
+            obj_x: obj_x << 2,
+            scale_w,
+            image_w: 8 << 5,
+            obj_y: obj_y << 2,
+            scale_h: 1 << 10,
+            image_h: 8 << 5,
+            image_stride: 2,
+            image_adrs: 0,
+            image_fmt: 0,
+            image_siz: 2,
+            image_pal: 0,
+            image_flags: flags,
+        }
+    }
+
+    /// An 8x8 RGBA16 texture, its block-load uObjTxtr and `sprite` in RDRAM.
+    fn sprite_scene(sprite: ObjSprite) -> (Vec<u8>, Vec<u8>) {
+        let mut bytes = vec![0u8; ENTRY_ADDR];
+        let tex = rgba16_texture(8, 8);
+        put(&mut bytes, TEX_ADDR, &tex);
+        let txtr = ObjTxtr::Block {
+            image: TEX_ADDR as u32,
+            tmem: 0,
+            tsize: gs_tb_tsize(64, 2),
+            tline: gs_tb_tline(8, 2),
+        };
+        put(&mut bytes, TXTR_ADDR, &txtr.to_bytes());
+        put(&mut bytes, SPRITE_ADDR, &sprite.to_bytes());
+        (bytes, tex)
+    }
+
+    #[test]
+    fn obj_rectangle_loads_txtr_and_records_texrect() {
+        let (bytes, tex) = sprite_scene(sprite(10, 20, 1 << 10, 0));
+        let r = run(
+            bytes,
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gsp_obj_load_txtr_s2dex2(TXTR_ADDR as u32),
+                gsp_obj_rectangle_s2dex2(SPRITE_ADDR as u32),
+                gsp_enddl(),
+            ],
+            GbiUcode::S2dex2,
+        );
+        assert!(r.diags.is_empty(), "{:?}", r.diags);
+        let rects = texrects(&r);
+        assert_eq!(rects.len(), 1);
+        let (rect, uls, ult, dsdx, dtdy, mi) = rects[0];
+        assert_eq!(
+            rect,
+            Rect {
+                ulx: 10,
+                uly: 20,
+                lrx: 18,
+                lry: 28
+            }
+        );
+        assert_eq!((uls, ult, dsdx, dtdy), (0, 0, 1 << 10, 1 << 10));
+        let m = &r.scene.materials[mi as usize];
+        assert_eq!((m.tex_w, m.tex_h), (8, 8));
+        assert_eq!(m.texture, decode_rgba16(&tex));
+    }
+
+    #[test]
+    fn obj_rectangle_scales_and_flips() {
+        // scaleW = 0.5 texels/pixel doubles the width; FLIPS walks S back from the right edge.
+        let (bytes, _) = sprite_scene(sprite(0, 0, 1 << 9, G_OBJ_FLAG_FLIPS));
+        let r = run(
+            bytes,
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gsp_obj_load_txtr_s2dex2(TXTR_ADDR as u32),
+                gsp_obj_rectangle_s2dex2(SPRITE_ADDR as u32),
+                gsp_enddl(),
+            ],
+            GbiUcode::S2dex2,
+        );
+        let (rect, uls, _, dsdx, _, _) = texrects(&r)[0];
+        assert_eq!((rect.ulx, rect.lrx, rect.lry), (0, 16, 8));
+        assert_eq!((uls, dsdx), ((8 << 5) - 1, -(1 << 9)));
+    }
+
+    #[test]
+    fn obj_sprite_applies_axis_aligned_matrix_and_drops_rotation() {
+        let (mut bytes, _) = sprite_scene(sprite(10, 0, 1 << 10, 0));
+        let scale_x2 = ObjMtx {
+            a: 2 << 16,
+            b: 0,
+            c: 0,
+            d: 1 << 16,
+            x: 100 << 2,
+            y: 4 << 2,
+            base_scale_x: 1 << 10,
+            base_scale_y: 1 << 10,
+        };
+        put(&mut bytes, MTX_ADDR, &scale_x2.to_bytes());
+        let commands = [
+            gdp_set_color_image(0, 2, 320, FB_ADDR),
+            gsp_obj_load_txtr_s2dex2(TXTR_ADDR as u32),
+            gsp_obj_matrix_s2dex2(MTX_ADDR as u32),
+            gsp_obj_sprite_s2dex2(SPRITE_ADDR as u32),
+            gsp_enddl(),
+        ];
+        let r = run(bytes.clone(), &commands, GbiUcode::S2dex2);
+        let (rect, _, _, dsdx, dtdy, _) = texrects(&r)[0];
+        assert_eq!(
+            rect,
+            Rect {
+                ulx: 120,
+                uly: 4,
+                lrx: 136,
+                lry: 12
+            }
+        );
+        assert_eq!((dsdx, dtdy), (1 << 9, 1 << 10));
+
+        let rotated = ObjMtx {
+            b: 1 << 16,
+            ..scale_x2
+        };
+        put(&mut bytes, MTX_ADDR, &rotated.to_bytes());
+        let r = run(bytes, &commands, GbiUcode::S2dex2);
+        assert!(texrects(&r).is_empty());
+        assert_eq!(r.dropped_runs, 1);
+        assert!(r.diags.iter().any(|d| d.kind == DiagKind::RotatedObjSprite));
+    }
+
+    #[test]
+    fn s2dex_1x_matches_s2dex2() {
+        let (mut bytes, _) = sprite_scene(sprite(10, 0, 1 << 10, 0));
+        let mtx = ObjMtx {
+            a: 1 << 16,
+            b: 0,
+            c: 0,
+            d: 2 << 16,
+            x: 8 << 2,
+            y: 0,
+            base_scale_x: 1 << 10,
+            base_scale_y: 1 << 10,
+        };
+        put(&mut bytes, MTX_ADDR, &mtx.to_bytes());
+        let s2dex2 = run(
+            bytes.clone(),
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gsp_obj_load_txtr_s2dex2(TXTR_ADDR as u32),
+                gsp_obj_rectangle_s2dex2(SPRITE_ADDR as u32),
+                gsp_obj_matrix_s2dex2(MTX_ADDR as u32),
+                gsp_obj_sprite_s2dex2(SPRITE_ADDR as u32),
+                gsp_enddl(),
+            ],
+            GbiUcode::S2dex2,
+        );
+        let s2dex = run(
+            bytes,
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gsp_obj_load_txtr_s2dex(TXTR_ADDR as u32),
+                gsp_obj_rectangle_s2dex(SPRITE_ADDR as u32),
+                gsp_obj_matrix_s2dex(MTX_ADDR as u32),
+                gsp_obj_sprite_s2dex(SPRITE_ADDR as u32),
+                gsp_enddl_f3d(),
+            ],
+            GbiUcode::S2dex,
+        );
+        assert!(s2dex.diags.is_empty(), "{:?}", s2dex.diags);
+        assert_eq!(texrects(&s2dex).len(), 2);
+        assert_eq!(
+            s2dex.scene.framebuffer_pairs,
+            s2dex2.scene.framebuffer_pairs
+        );
+        assert_eq!(s2dex.scene.materials, s2dex2.scene.materials);
+    }
+
+    #[test]
+    fn obj_load_txtr_tlut_feeds_ci4_sprite() {
+        let mut bytes = vec![0u8; ENTRY_ADDR];
+        // 16x8 CI4: index (x + y) & 15; palette entry i is a distinct RGBA16 color.
+        let mut ci = Vec::new();
+        for y in 0..8u8 {
+            for x in (0..16u8).step_by(2) {
+                ci.push((((x + y) & 15) << 4) | ((x + 1 + y) & 15));
+            }
+        }
+        put(&mut bytes, TEX_ADDR, &ci);
+        let palette: Vec<u8> = (0..16u16)
+            .flat_map(|i| ((i << 11) | (i << 1) | 1).to_be_bytes())
+            .collect();
+        put(&mut bytes, PAL_ADDR, &palette);
+        let block = ObjTxtr::Block {
+            image: TEX_ADDR as u32,
+            tmem: 0,
+            tsize: gs_tb_tsize(128, 0),
+            tline: gs_tb_tline(16, 0),
+        };
+        put(&mut bytes, TXTR_ADDR, &block.to_bytes());
+        let tlut = ObjTxtr::Tlut {
+            image: PAL_ADDR as u32,
+            phead: 256,
+            pnum: 15,
+        };
+        put(&mut bytes, TLUT_TXTR_ADDR, &tlut.to_bytes());
+        let ci4 = ObjSprite {
+            image_w: 16 << 5,
+            image_stride: 1,
+            image_fmt: 2,
+            image_siz: 0,
+            ..sprite(0, 0, 1 << 10, 0)
+        };
+        put(&mut bytes, SPRITE_ADDR, &ci4.to_bytes());
+
+        let r = run(
+            bytes,
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gdp_set_other_mode_h(14, 2, 2 << 14), // G_TT_RGBA16
+                gsp_obj_load_txtr_s2dex2(TLUT_TXTR_ADDR as u32),
+                gsp_obj_load_txtr_s2dex2(TXTR_ADDR as u32),
+                gsp_obj_rectangle_s2dex2(SPRITE_ADDR as u32),
+                gsp_enddl(),
+            ],
+            GbiUcode::S2dex2,
+        );
+        let (rect, _, _, _, _, mi) = texrects(&r)[0];
+        assert_eq!((rect.lrx, rect.lry), (16, 8));
+        let m = &r.scene.materials[mi as usize];
+        let decoded_palette = decode_rgba16(&palette);
+        for (i, texel) in m.texture.as_chunks::<4>().0.iter().enumerate() {
+            let idx = ((i % 16) + (i / 16)) & 15;
+            assert_eq!(
+                &texel[..],
+                &decoded_palette[idx * 4..idx * 4 + 4],
+                "texel {i}"
+            );
+        }
+    }
+
+    fn bg(w: u16, h: u16, frame_h: u16, scale_h: u16) -> ObjBg {
+        ObjBg {
+            image_x: 0,
+            image_w: w << 2,
+            frame_x: 0,
+            frame_w: w << 2,
+            image_y: 0,
+            image_h: h << 2,
+            frame_y: 0,
+            frame_h: frame_h << 2,
+            image_ptr: TEX_ADDR as u32,
+            image_load: G_BGLT_LOADTILE,
+            image_fmt: 0,
+            image_siz: 2,
+            image_pal: 0,
+            image_flip: 0,
+            scale_w: 1 << 10,
+            scale_h,
+        }
+    }
+
+    #[test]
+    fn bg_rect_copy_streams_strips_through_tmem() {
+        // 64x48 RGBA16 = 6 KiB: more than TMEM holds, so the blit splits into strips.
+        let mut bytes = vec![0u8; ENTRY_ADDR];
+        let tex = rgba16_texture(64, 48);
+        put(&mut bytes, TEX_ADDR, &tex);
+        put(&mut bytes, BG_ADDR, &bg(64, 48, 48, 1 << 10).to_bytes());
+        let r = run(
+            bytes,
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gdp_set_cycle_type(G_CYC_COPY),
+                gsp_bg_rect_copy_s2dex2(BG_ADDR as u32),
+                gsp_enddl(),
+            ],
+            GbiUcode::S2dex2,
+        );
+        let rects = texrects(&r);
+        assert_eq!(rects.len(), 2);
+        let mut y = 0;
+        for &(rect, uls, ult, dsdx, dtdy, mi) in &rects {
+            assert_eq!((rect.ulx, rect.uly, rect.lrx), (0, y, 64));
+            // COPY steps 4 texels per pixel; the renderer undoes it.
+            assert_eq!((uls, ult, dsdx, dtdy), (0, 0, 4 << 10, 1 << 10));
+            let m = &r.scene.materials[mi as usize];
+            assert_eq!(m.tex_w, 64);
+            let row = y as usize * 128;
+            assert_eq!(
+                m.texture[..64 * 4],
+                decode_rgba16(&tex[row..row + 128])[..],
+                "strip at y={y}"
+            );
+            y = rect.lry;
+        }
+        assert_eq!(y, 48);
+    }
+
+    #[test]
+    fn bg_rect_1cyc_scales_strips() {
+        // scaleH = 0.5 texels/pixel: the 48-row image fills 96 screen rows; strip 2 starts halfway
+        // into texture row 29.
+        let mut bytes = vec![0u8; ENTRY_ADDR];
+        let tex = rgba16_texture(64, 48);
+        put(&mut bytes, TEX_ADDR, &tex);
+        put(&mut bytes, BG_ADDR, &bg(64, 48, 96, 1 << 9).to_bytes());
+        let r = run(
+            bytes,
+            &[
+                gdp_set_color_image(0, 2, 320, FB_ADDR),
+                gdp_set_cycle_type(0),
+                gsp_bg_rect_1cyc_s2dex2(BG_ADDR as u32),
+                gsp_enddl(),
+            ],
+            GbiUcode::S2dex2,
+        );
+        let rects = texrects(&r);
+        assert_eq!(rects.len(), 2);
+        assert_eq!((rects[0].0.uly, rects[0].0.lry), (0, 59));
+        assert_eq!((rects[1].0.uly, rects[1].0.lry), (59, 96));
+        let (_, _, ult, dsdx, dtdy, mi) = rects[1];
+        assert_eq!((ult, dsdx, dtdy), (16, 1 << 10, 1 << 9));
+        let m = &r.scene.materials[mi as usize];
+        let row = 29 * 128;
+        assert_eq!(m.texture[..64 * 4], decode_rgba16(&tex[row..row + 128])[..]);
+    }
+
+    #[test]
+    fn obj_draw_before_cimg_is_dropped() {
+        let (bytes, _) = sprite_scene(sprite(0, 0, 1 << 10, 0));
+        let r = run(
+            bytes,
+            &[
+                gdp_set_cycle_type_f3d(0),
+                gsp_obj_load_txtr_s2dex(TXTR_ADDR as u32),
+                gsp_obj_rectangle_s2dex(SPRITE_ADDR as u32),
+                gsp_enddl_f3d(),
+            ],
+            GbiUcode::S2dex,
+        );
+        assert!(texrects(&r).is_empty());
+        assert_eq!(r.dropped_runs, 1);
+        assert_eq!(r.diags[0].kind, DiagKind::DrawBeforeCimg);
+    }
+
+    #[test]
+    fn load_ucode_switches_from_f3dex2_to_s2dex_mid_walk() {
+        // 3D microcode sets the segment and color image, then hands the HUD to S2DEX 1.x, whose
+        // commands decode through the F3D opcode map from the next command on.
+        let (mut bytes, _) = sprite_scene(sprite(10, 20, 1 << 10, 0));
+        put(&mut bytes, UCODE_TEXT_ADDR, &fixture_ucode(GbiUcode::S2dex));
+        let mut commands = vec![gsp_segment(6, 0), gdp_set_color_image(0, 2, 320, FB_ADDR)];
+        commands.extend(gsp_load_ucode(
+            UCODE_TEXT_ADDR as u32,
+            (UCODE_TEXT_ADDR + UCODE_TEXT_SIZE as usize) as u32,
+            0x800,
+        ));
+        commands.extend([
+            gsp_obj_load_txtr_s2dex(0x0600_0000 | TXTR_ADDR as u32),
+            gsp_obj_rectangle_s2dex(0x0600_0000 | SPRITE_ADDR as u32),
+            gsp_enddl_f3d(),
+        ]);
+        let r = run(bytes, &commands, GbiUcode::F3dex2);
+        assert!(r.diags.is_empty(), "{:?}", r.diags);
+        assert_eq!(r.commands, 7);
+        let rects = texrects(&r);
+        assert_eq!(rects.len(), 1);
+        assert_eq!(
+            rects[0].0,
+            Rect {
+                ulx: 10,
+                uly: 20,
+                lrx: 18,
+                lry: 28
+            }
+        );
+    }
+
+    #[test]
+    fn load_ucode_of_unknown_text_reports_and_keeps_the_current_table() {
+        let (bytes, _) = sprite_scene(sprite(0, 0, 1 << 10, 0));
+        let mut commands = gsp_load_ucode(UCODE_TEXT_ADDR as u32, 0, 0x800).to_vec();
+        // Still F3DEX2: 0xDF ends the walk (under S2DEX 1.x it would be unknown).
+        commands.push(gsp_enddl());
+        let r = run(bytes, &commands, GbiUcode::F3dex2);
+        assert_eq!(r.commands, 3);
+        assert_eq!(r.diags.len(), 1);
+        assert!(
+            matches!(r.diags[0].kind, DiagKind::UnknownMicrocode { .. }),
+            "{:?}",
+            r.diags
+        );
+    }
+}
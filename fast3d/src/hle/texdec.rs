@@ -203,6 +203,23 @@ pub(crate) fn decode_ia16_entry(v: u16) -> [u8; 4] {
     [i, i, i, a]
 }
 
+/// Apply the G_SETCONVERT YUV->RGB conversion in place to a raw YUV16 sample buffer (`[U, V, Y, Y]`
+/// per texel, as `Tmem::sample_tile` returns it). With the coefficients in 1/128 units and the
+/// chroma re-centered (`u = U - 128`, `v = V - 128`): `R = Y + K0*v`, `G = Y + K1*u + K2*v`,
+/// `B = Y + K3*u`, each clamped to 0..=255. Alpha keeps Y. K4/K5 are combiner inputs, unused here.
+pub(crate) fn convert_yuv(texels: &mut [u8], k: [i16; 6]) {
+    let [k0, k1, k2, k3, _, _] = k.map(i32::from);
+    for px in texels.as_chunks_mut::<4>().0 {
+        let u = px[0] as i32 - 128;
+        let v = px[1] as i32 - 128;
+        let y = px[2] as i32;
+        px[0] = (y + ((k0 * v) >> 7)).clamp(0, 255) as u8;
+        px[1] = (y + ((k1 * u + k2 * v) >> 7)).clamp(0, 255) as u8;
+        px[2] = (y + ((k3 * u) >> 7)).clamp(0, 255) as u8;
+        px[3] = y as u8;
+    }
+}
+
 /// Decode CI8 (8-bit index per texel) via TLUT to RGBA8.
 ///
 /// Each source byte is a palette index. `tlut_offset = index << 3`. Entry is a
@@ -301,6 +318,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn convert_yuv_applies_k0_to_k3_and_keeps_luma_alpha() {
+        let k = [175, -43, -89, 222, 114, 42];
+        // Neutral chroma: RGB = Y regardless of K.
+        let mut grey = vec![128, 128, 90, 90];
+        convert_yuv(&mut grey, k);
+        assert_eq!(grey, [90, 90, 90, 90]);
+        // u = -28, v = +72 (a red-ish sample): R = 100 + 98, G = 100 - 41, B = 100 - 49.
+        let mut red = vec![100, 200, 100, 100];
+        convert_yuv(&mut red, k);
+        assert_eq!(red, [198, 59, 51, 100]);
+        // Out-of-range sums clamp.
+        let mut hot = vec![255, 255, 250, 250];
+        convert_yuv(&mut hot, k);
+        assert_eq!(hot, [255, 119, 255, 250]);
+    }
+
     #[test]
     fn format_info_tmem_bytes() {
         assert_eq!(FormatInfo { fmt: 4, siz: 1 }.tmem_bytes(4, 4), 16); // I8:  w*h
@@ -11,8 +11,10 @@
 //!
 //! Supported formats: the six non-paletted RGBA16 (0,2), I8 (4,1), I4 (4,0), IA16 (3,2),
 //! IA8 (3,1), IA4 (3,0); the paletted CI4 (2,0)/CI8 (2,1) whose palette (TLUT) lives in the upper
-//! 2 KiB of this same array (loaded via [`Tmem::write_tlut`]); and RGBA32 (0,3), the dual-bank
-//! format whose 32-bit texel is split R,G → low bank / B,A → high bank across the two 2 KiB halves.
+//! 2 KiB of this same array (loaded via [`Tmem::write_tlut`]); RGBA32 (0,3), the dual-bank
+//! format whose 32-bit texel is split R,G → low bank / B,A → high bank across the two 2 KiB halves;
+//! and YUV16 (1,2), the other dual-bank format (U,V chroma → low bank / Y luma → high bank),
+//! sampled raw as `[U, V, Y, Y]` — the `G_SETCONVERT` color conversion runs after the sample.
 
 use crate::hle::rdp::TileDescriptor;
 use crate::hle::texdec::{decode_ia16_entry, decode_rgba16_entry};
@@ -36,6 +38,22 @@ pub const DXT_SWAP: u32 = 0x800;
 /// `^ 0x4` on the byte address). Keeping both on one bit is what makes the swaps cancel.
 const SWAP_BIT: usize = 0x4;
 
+/// The dual-bank byte routing for one 8-byte RDRAM word, as `(low, high)` source byte offsets,
+/// or `None` for the formats copied verbatim.
+///
+/// RGBA32 (`siz == 3`) holds two texels per word: R,G of each feed the low bank, B,A the high bank.
+/// YUV16 (`fmt == 1`) holds four texels per word as `U0 Y0 V0 Y1 U1 Y2 V1 Y3`: the shared U,V
+/// chroma of each texel pair feed the low bank and the four Y samples the high bank.
+fn bank_split(fmt: u8, siz: u8) -> Option<([usize; 4], [usize; 4])> {
+    if siz == 3 {
+        Some(([0, 1, 4, 5], [2, 3, 6, 7]))
+    } else if fmt == 1 && siz == 2 {
+        Some(([0, 2, 4, 6], [1, 3, 5, 7]))
+    } else {
+        None
+    }
+}
+
 /// Byte-addressable RDP texture memory (4 KiB).
 #[derive(Clone, Debug, PartialEq)]
 pub struct Tmem {
@@ -76,8 +94,10 @@ impl Tmem {
     /// RGBA32 (`siz == 3`) splits its 32-bit texel across the two 2 KiB banks: each 8-byte word
     /// holds two texels; R,G land in the low bank and
     /// B,A in the high bank (`| `[`PALETTE_BASE`]), so `tmem_addr` advances 4 (not 8) and is masked
-    /// with [`MASK16`]. The swap runs through the same helper BEFORE the high-bank OR. Every other
-    /// format is 8-bytes-per-word and takes the verbatim copy path.
+    /// with [`MASK16`]. The swap runs through the same helper BEFORE the high-bank OR. YUV16
+    /// (`fmt == 1`) takes the same split with its own byte routing (see [`bank_split`]). Every
+    /// other format is 8-bytes-per-word and takes the verbatim copy path.
+    #[allow(clippy::too_many_arguments)]
     pub fn write_block(
         &mut self,
         src: &[u8],
@@ -85,15 +105,12 @@ impl Tmem {
         line_words: usize,
         dxt: u32,
         word_count: usize,
+        fmt: u8,
         siz: u8,
     ) {
-        let rgba32 = siz == 3;
-        let mask = if rgba32 { MASK16 } else { MASK8 };
-        let advance = if rgba32 { 4 } else { 8 };
-        // Per-texel RDRAM byte offsets feeding the low bank (R,G of each of the two texels) and the
-        // high bank (B,A of each).
-        const LOW_SRC: [usize; 4] = [0, 1, 4, 5];
-        const HIGH_SRC: [usize; 4] = [2, 3, 6, 7];
+        let split = bank_split(fmt, siz);
+        let mask = if split.is_some() { MASK16 } else { MASK8 };
+        let advance = if split.is_some() { 4 } else { 8 };
 
         let mut tmem_addr = (dst_tmem_addr_words << 3) & mask;
         let stride = line_words << 3;
@@ -102,13 +119,14 @@ impl Tmem {
         let mut tex = 0usize;
 
         for _ in 0..word_count {
-            if rgba32 {
-                // loadWord<true, false>: split R,G → low bank, B,A → high bank (dst | 0x800).
+            if let Some((low_src, high_src)) = split {
+                // loadWord<true, false>: split the word across the low bank and the high bank
+                // (dst | 0x800).
                 for i in 0..4 {
                     let dst = Self::swap_odd_line(tmem_addr + i, odd) & mask;
-                    self.bytes[dst] = src.get(tex + LOW_SRC[i]).copied().unwrap_or(0);
+                    self.bytes[dst] = src.get(tex + low_src[i]).copied().unwrap_or(0);
                     self.bytes[dst | PALETTE_BASE] =
-                        src.get(tex + HIGH_SRC[i]).copied().unwrap_or(0);
+                        src.get(tex + high_src[i]).copied().unwrap_or(0);
                 }
             } else {
                 // loadWord<false, false>: copy the whole 8-byte word, applying the odd-line swap.
@@ -142,7 +160,8 @@ impl Tmem {
     /// `(dst_tmem_word << 3) + r * (line_words << 3)`. That padded `line_words << 3` is a GENUINE
     /// per-row stride (unlike LoadBlock's contiguous rows), which lets [`sample_tile`] read a
     /// LoadTile tile correctly even for sub-word widths via the render tile's `line`. RGBA32
-    /// (`siz == 3`) uses the same dual-bank split as [`write_block`], with the per-row swap.
+    /// (`siz == 3`) and YUV16 (`fmt == 1`) use the same dual-bank split as [`write_block`], with
+    /// the per-row swap.
     #[allow(clippy::too_many_arguments)]
     pub fn write_tile(
         &mut self,
@@ -152,13 +171,12 @@ impl Tmem {
         row_count: usize,
         words_per_row: usize,
         src_stride_bytes: usize,
+        fmt: u8,
         siz: u8,
     ) {
-        let rgba32 = siz == 3;
-        let mask = if rgba32 { MASK16 } else { MASK8 };
-        let advance = if rgba32 { 4 } else { 8 };
-        const LOW_SRC: [usize; 4] = [0, 1, 4, 5];
-        const HIGH_SRC: [usize; 4] = [2, 3, 6, 7];
+        let split = bank_split(fmt, siz);
+        let mask = if split.is_some() { MASK16 } else { MASK8 };
+        let advance = if split.is_some() { 4 } else { 8 };
 
         let tmem_start = (dst_tmem_word << 3) & mask;
         let tmem_stride = line_words << 3;
@@ -169,13 +187,13 @@ impl Tmem {
             let src_row = r * src_stride_bytes;
             for w in 0..words_per_row {
                 let tex = src_row + w * 8;
-                if rgba32 {
-                    // loadWord<true, false>: split R,G → low bank, B,A → high bank (dst | 0x800).
+                if let Some((low_src, high_src)) = split {
+                    // loadWord<true, false>: split the word across the two banks (dst | 0x800).
                     for i in 0..4 {
                         let dst = Self::swap_odd_line(tmem_addr + i, odd) & mask;
-                        self.bytes[dst] = src.get(tex + LOW_SRC[i]).copied().unwrap_or(0);
+                        self.bytes[dst] = src.get(tex + low_src[i]).copied().unwrap_or(0);
                         self.bytes[dst | PALETTE_BASE] =
-                            src.get(tex + HIGH_SRC[i]).copied().unwrap_or(0);
+                            src.get(tex + high_src[i]).copied().unwrap_or(0);
                     }
                 } else {
                     // loadWord<false, false>: copy the whole 8-byte word, applying the odd-line swap.
@@ -236,12 +254,12 @@ impl Tmem {
         self.load_byte_masked(base, rel, odd_row, MASK8)
     }
 
-    /// The RGBA32 dual-bank byte read. Mirrors `implLoadTMEM(..., MASK16, orAddress)`: the address
-    /// is confined to one 2 KiB bank ([`MASK16`]) after the odd-line swap, then the high-bank read
-    /// ORs [`PALETTE_BASE`] (`orAddress = RDP_TMEM_BYTES >> 1`). `or_addr` is 0 for the R,G bytes in
-    /// the low bank and [`PALETTE_BASE`] for the B,A bytes in the high bank.
+    /// The dual-bank (RGBA32 / YUV16) byte read. Mirrors `implLoadTMEM(..., MASK16, orAddress)`:
+    /// the address is confined to one 2 KiB bank ([`MASK16`]) after the odd-line swap, then the
+    /// high-bank read ORs [`PALETTE_BASE`] (`orAddress = RDP_TMEM_BYTES >> 1`). `or_addr` is 0 for
+    /// the low bank (RGBA32 R,G / YUV16 U,V) and [`PALETTE_BASE`] for the high bank (B,A / Y).
     #[inline]
-    fn load_byte_rgba32(&self, base: usize, rel: usize, odd_row: bool, or_addr: usize) -> u8 {
+    fn load_byte_banked(&self, base: usize, rel: usize, odd_row: bool, or_addr: usize) -> u8 {
         let addr = ((base + Self::swap_odd_line(rel, odd_row)) & MASK16) | or_addr;
         self.bytes[addr]
     }
@@ -280,9 +298,10 @@ impl Tmem {
         let palette = tile.palette as usize;
         // log2 of the pixel stride in half-bytes: 4b->0, 8b->1, 16b->2. RGBA32 (siz 3) is also 2,
         // not 3: a 32-bit RGBA texel occupies only 16 bits per bank, so its pixel stride is 16 bits.
-        let tmem_shift = match tile.siz {
-            0 => 0,
-            1 => 1,
+        // YUV16 is 1: each texel owns one Y byte in the high bank (its U,V pair is shared).
+        let tmem_shift = match (tile.fmt, tile.siz) {
+            (_, 0) => 0,
+            (_, 1) | (1, 2) => 1,
             _ => 2,
         };
 
@@ -328,12 +347,22 @@ impl Tmem {
             // these are already 8-bit channels. Assembles the 32-bit texel as
             // `(r<<24)|(g<<16)|(b<<8)|a`, i.e. the byte order [r, g, b, a].
             (0, 3) => {
-                let r = self.load_byte_rgba32(base, rel, odd_row, 0);
-                let g = self.load_byte_rgba32(base, rel + 1, odd_row, 0);
-                let b = self.load_byte_rgba32(base, rel, odd_row, PALETTE_BASE);
-                let a = self.load_byte_rgba32(base, rel + 1, odd_row, PALETTE_BASE);
+                let r = self.load_byte_banked(base, rel, odd_row, 0);
+                let g = self.load_byte_banked(base, rel + 1, odd_row, 0);
+                let b = self.load_byte_banked(base, rel, odd_row, PALETTE_BASE);
+                let a = self.load_byte_banked(base, rel + 1, odd_row, PALETTE_BASE);
                 [r, g, b, a]
             }
+            // YUV16: dual-bank. Y is this texel's own byte at `rel` in the HIGH bank; U,V are the
+            // chroma pair shared by the even/odd texel pair, at `rel & !1` / `rel | 1` in the LOW
+            // bank. Returned raw as [U, V, Y, Y] — the G_SETCONVERT conversion to RGB is applied by
+            // the caller (`texdec::convert_yuv`), since TEXT_CONV can also leave it unconverted.
+            (1, 2) => {
+                let y = self.load_byte_banked(base, rel, odd_row, PALETTE_BASE);
+                let u = self.load_byte_banked(base, rel & !1, odd_row, 0);
+                let v = self.load_byte_banked(base, rel | 1, odd_row, 0);
+                [u, v, y, y]
+            }
             // CI4: 4-bit palette index (even column = high nibble). The index read honors the
             // odd-line swap and is confined to the low 2 KiB bank (MASK16); the palette entry is
             // then read directly (no swap) from a 32-entry sub-palette selected by `palette<<7`.
@@ -386,8 +415,8 @@ impl Tmem {
                 let a = if nib & 1 != 0 { 255 } else { 0 };
                 [i8, i8, i8, a]
             }
-            // Formats outside this phase (RGBA32, YUV, ...) are handled elsewhere; decode as
-            // RGBA16 rather than fail silently, matching texdec's fallback.
+            // Any other (fmt, siz) pair is not a real RDP format; decode as RGBA16 rather than
+            // fail silently, matching texdec's fallback.
             (f, s) => {
                 eprintln!("tmem::sample_tile: unimplemented format (fmt={f}, siz={s}); decoding as RGBA16");
                 let hi = self.load_byte(base, rel, odd_row);
@@ -480,7 +509,7 @@ mod tests {
         let src = pattern(w * h * 2); // 256 bytes, contiguous RGBA16
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, siz);
+        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, 0, siz);
         let got = tmem.sample_tile(&tile(0, siz, w, h, line, 0), 0);
 
         let expected = crate::hle::combiner::decode_rgba16(&src);
@@ -499,7 +528,7 @@ mod tests {
             0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, // row 1 (odd)
         ];
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0, 2048, 2, 2);
+        tmem.write_block(&src, 0, 0, 2048, 2, 0, 2);
 
         // Row 0 verbatim.
         for (i, &b) in src[..8].iter().enumerate() {
@@ -541,7 +570,7 @@ mod tests {
         }
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0, dxt, word_count, siz);
+        tmem.write_block(&src, 0, 0, dxt, word_count, 4, siz);
         let got = tmem.sample_tile(&tile(4, siz, w, h, line, 0), 0);
 
         // Independently expand every texel from the source nibbles (even column = high nibble).
@@ -572,7 +601,7 @@ mod tests {
         let src = pattern(w * h * 2);
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0, dxt, word_count, siz);
+        tmem.write_block(&src, 0, 0, dxt, word_count, 3, siz);
         let got = tmem.sample_tile(&tile(3, siz, w, h, line, 0), 0);
 
         // IA16: [i, i, i, a] with i = src[2k], a = src[2k+1] in contiguous order.
@@ -599,7 +628,7 @@ mod tests {
         let src = pattern(w * h);
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, base_words, 0, dxt, word_count, siz);
+        tmem.write_block(&src, base_words, 0, dxt, word_count, 4, siz);
         let got = tmem.sample_tile(&tile(4, siz, w, h, line, base_words), 0);
 
         for k in 0..w * h {
@@ -631,7 +660,7 @@ mod tests {
         let src = pattern(w * h * 2); // 96 bytes, contiguous RGBA16
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, siz);
+        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, 0, siz);
         let got = tmem.sample_tile(&tile(0, siz, w, h, line, 0), 0);
 
         // The drifting accumulator must still land the swap on every row boundary, so the result is
@@ -665,7 +694,7 @@ mod tests {
         }
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&indices, 0, 0, dxt, word_count, siz);
+        tmem.write_block(&indices, 0, 0, dxt, word_count, 2, siz);
         tmem.write_tlut(&pal, w * h, PALETTE_BASE >> 3); // dst_word 0x100 → base 0x800
 
         let got = tmem.sample_tile(&tile(2, siz, w, h, line, 0), 2 /* RGBA16 TLUT */);
@@ -700,7 +729,7 @@ mod tests {
             0x20, 0x21, 0x22, 0x23, // texel1: R=20 G=21 B=22 A=23
         ];
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0, 0, 1, 3); // 1 word, siz=3, dxt=0 (no swap)
+        tmem.write_block(&src, 0, 0, 0, 1, 0, 3); // 1 word, siz=3, dxt=0 (no swap)
 
         // Low bank: R0 G0 R1 G1.
         assert_eq!(tmem.raw(0), 0x10, "R0 → low[0]");
@@ -771,14 +800,14 @@ mod tests {
 
         // Distinct per-channel bytes across all 12 texels (48 bytes 10..=57).
         let mut src = vec![0u8; w * h * 4];
-        for (t, chunk) in src.chunks_exact_mut(4).enumerate() {
+        for (t, chunk) in src.as_chunks_mut::<4>().0.iter_mut().enumerate() {
             for (c, b) in chunk.iter_mut().enumerate() {
                 *b = (10 + t * 4 + c) as u8;
             }
         }
 
         let mut tmem = Tmem::default();
-        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, 3);
+        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, 0, 3);
         let got = tmem.sample_tile(&tile(0, 3, w, h, render_line, 0), 0);
 
         assert_eq!(
@@ -797,7 +826,38 @@ mod tests {
         }
     }
 
-    // ── LoadTile / write_tile ─────────────────────────────────────────────────────────────────
+    #[test]
+    fn roundtrip_yuv16_multirow_splits_chroma_and_luma() {
+        // width 8 (2 source words/row), height 3. Each 4-byte RDRAM group is `U Y0 V Y1`; the split
+        // sends U,V to the low bank and Y0,Y1 to the high bank, so each bank row is w bytes (render
+        // line = 1). A texel samples raw as [U, V, Y, Y] with its pair's shared chroma.
+        let (w, h) = (8usize, 3usize);
+        let render_line = 1usize; // w bytes per bank row / 8
+        let word_count = w * h * 2 / 8; // 6 words (4 texels/word)
+        let dxt = DXT_SWAP / 2; // 1024: 2-word rows → swap toggles every row
+
+        let src: Vec<u8> = (0..w * h * 2).map(|i| (20 + i) as u8).collect();
+
+        let mut tmem = Tmem::default();
+        tmem.write_block(&src, 0, 0 /* load line */, dxt, word_count, 1, 2);
+        let got = tmem.sample_tile(&tile(1, 2, w, h, render_line, 0), 0);
+
+        for y in 0..h {
+            for x in 0..w {
+                let pair = y * w * 2 + (x >> 1) * 4;
+                let (u, v) = (src[pair], src[pair + 2]);
+                let luma = src[pair + 1 + (x & 1) * 2];
+                let o = (y * w + x) * 4;
+                assert_eq!(
+                    got[o..o + 4],
+                    [u, v, luma, luma],
+                    "YUV16 texel ({x},{y}) mismatch"
+                );
+            }
+        }
+    }
+
+    // ── LoadTile / write_tile─────────────────────────────────────────────────────────────────
 
     #[test]
     fn write_tile_roundtrip_strided_source_padded_dest_per_row_swap() {
@@ -825,7 +885,7 @@ mod tests {
         }
 
         let mut tmem = Tmem::default();
-        tmem.write_tile(&src, 0, line_words, h, words_per_row, src_stride, 2);
+        tmem.write_tile(&src, 0, line_words, h, words_per_row, src_stride, 0, 2);
 
         // Physical TMEM: row 0 (even) verbatim at 0..8; row 1 (odd) with its two 4-byte halves
         // swapped at 16..24 (padded stride 16); row 2 (even) verbatim at 32..40.
@@ -878,14 +938,14 @@ mod tests {
 
         // Distinct per-channel bytes across all 6 texels.
         let mut src = vec![0u8; h * src_stride];
-        for (t, chunk) in src.chunks_exact_mut(4).enumerate() {
+        for (t, chunk) in src.as_chunks_mut::<4>().0.iter_mut().enumerate() {
             for (c, b) in chunk.iter_mut().enumerate() {
                 *b = (0x10 + t * 4 + c) as u8;
             }
         }
 
         let mut tmem = Tmem::default();
-        tmem.write_tile(&src, 0, line_words, h, words_per_row, src_stride, 3);
+        tmem.write_tile(&src, 0, line_words, h, words_per_row, src_stride, 0, 3);
         let got = tmem.sample_tile(&tile(0, 3, w, h, line_words, 0), 0);
 
         assert_eq!(
@@ -14,6 +14,7 @@ pub mod hooks;
 pub mod microcode;
 pub(crate) mod render;
 pub(crate) mod scene;
+pub mod task;
 
 use crate::render::SceneRenderer;
 use crate::scene::Scene;
@@ -21,7 +22,13 @@ use crate::scene::Scene;
 // ── New vNext public API (spec §3.6): structured diagnostics ──
 pub use diag::{DiagKind, DiagSink, Diagnostic, DlSummary, LogSink, NopSink, Severity};
 // ── New vNext public API (spec §3.6): microcode selector ──
-pub use microcode::{detect_microcode, Microcode};
+#[allow(deprecated)]
+pub use microcode::detect_microcode;
+pub use microcode::{
+    guess_microcode, identify_microcode, microcode_hash, Microcode, MicrocodeInfo,
+};
+// ── libultra OSTask decode behind `Renderer::process_task` ──
+pub use task::OsTask;
 // ── Vertex/matrix data layout (`Fixed` N64 / `Float` GBI_FLOATS), orthogonal to the microcode ──
 pub use crate::hle::mem::GbiDataFormat as DataFormat;
 // ── New vNext public API (spec §3.5): render hooks ──
@@ -45,11 +52,17 @@ pub enum ClearPolicy {
 }
 
 /// Renderer configuration. All fields are `Copy`; the `Renderer` stores it by value.
+/// `#[non_exhaustive]`: options may grow, so start from `RendererConfig::default()` and set the
+/// fields you need.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct RendererConfig {
-    /// Internal framebuffer scale. **v1: values > 1 are clamped to 1 with a `log::warn!`**.
+    /// Integer scale of the internal framebuffers over the game's native resolution (1 = native,
+    /// 0 is treated as 1). Triangles rasterize at the higher resolution; 2D rects and scissors stay
+    /// on native pixel boundaries.
     pub resolution_multiplier: u32,
-    /// MSAA sample count (1 = off). **v1: clamped to 1**.
+    /// MSAA sample count for the internal framebuffers (1 = off). 4 is the one multisampled count
+    /// WebGPU guarantees; other counts above 1 use 4 with a `log::warn!`.
     pub sample_count: u32,
     pub present_mode: wgpu::PresentMode,
     /// Surface format. `None` = pick deterministically (`pick_surface_format`); NEVER
@@ -57,6 +70,35 @@ pub struct RendererConfig {
     pub format: Option<wgpu::TextureFormat>,
     pub clear_policy: ClearPolicy,
     pub power_preference: wgpu::PowerPreference,
+    /// Round 16-bit color images to RGBA5551 through the RDP's color dither (magic square, Bayer
+    /// or noise, per the run's othermode) — authentic 16-bit banding and dither patterns. `false`
+    /// keeps full 8-bit precision.
+    pub quantize_5551: bool,
+    /// Emulate RDP coverage: runs with AA_EN, CLR_ON_CVG, ALPHA_CVG_SEL, CVG_X_ALPHA or a
+    /// non-clamp CVG_DST go through the framebuffer-read blender, which keeps coverage in the
+    /// color image's alpha and blends only on AA edges. `false` treats every pixel as fully
+    /// covered.
+    pub emulate_coverage: bool,
+    /// Microcode `process_task` assumes when the task's text segment is not recognized.
+    pub default_microcode: Microcode,
+}
+
+impl Default for RendererConfig {
+    /// Native resolution, no MSAA, vsync'd `Fifo`, a picked surface format, `PerFrame` clears and
+    /// every emulation option off — the 1.0 behavior.
+    fn default() -> Self {
+        RendererConfig {
+            resolution_multiplier: 1,
+            sample_count: 1,
+            present_mode: wgpu::PresentMode::Fifo,
+            format: None,
+            clear_policy: ClearPolicy::PerFrame,
+            power_preference: wgpu::PowerPreference::default(),
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
+        }
+    }
 }
 
 /// Where a `Renderer` scans out. `Surface` is an owned swapchain (built by `Renderer::new`, or
@@ -140,13 +182,23 @@ fn render_format_of(config: &wgpu::SurfaceConfiguration) -> wgpu::TextureFormat
         .unwrap_or(config.format)
 }
 
-/// v1 does not support supersampling or MSAA; warn (values are ignored, not normalized).
+/// MSAA is supported only at 4 samples; warn (the config keeps the value as given,
+/// `sample_count_of` normalizes).
 fn warn_unsupported(config: &RendererConfig) {
-    if config.resolution_multiplier > 1 {
-        log::warn!("resolution_multiplier > 1 is unsupported in v1; treated as 1");
+    if !matches!(config.sample_count, 0 | 1 | 4) {
+        log::warn!(
+            "sample_count {} is not portable; using 4",
+            config.sample_count
+        );
     }
+}
+
+/// The MSAA sample count the internal framebuffers use for `config`: 1 (off) or 4.
+fn sample_count_of(config: &RendererConfig) -> u32 {
     if config.sample_count > 1 {
-        log::warn!("sample_count > 1 (MSAA) is unsupported in v1; treated as 1");
+        4
+    } else {
+        1
     }
 }
 
@@ -223,7 +275,11 @@ impl Renderer {
         let dual_source = device
             .features()
             .contains(wgpu::Features::DUAL_SOURCE_BLENDING);
-        let inner = SceneRenderer::new(&device, render_fmt, w, h, dual_source);
+        let mut inner = SceneRenderer::new(&device, render_fmt, w, h, dual_source);
+        inner.set_quantize_5551(config.quantize_5551);
+        inner.set_emulate_coverage(config.emulate_coverage);
+        inner.set_sample_count(&device, sample_count_of(&config));
+        inner.set_resolution_multiplier(&device, config.resolution_multiplier);
         Self {
             target,
             inner,
@@ -297,7 +353,11 @@ impl Renderer {
         };
         surface.configure(&device, &surface_config);
 
-        let inner = SceneRenderer::new(&device, render_fmt, w, h, dual_source);
+        let mut inner = SceneRenderer::new(&device, render_fmt, w, h, dual_source);
+        inner.set_quantize_5551(config.quantize_5551);
+        inner.set_emulate_coverage(config.emulate_coverage);
+        inner.set_sample_count(&device, sample_count_of(&config));
+        inner.set_resolution_multiplier(&device, config.resolution_multiplier);
         Ok(Self {
             target: PresentTarget::Surface {
                 surface,
@@ -378,6 +438,12 @@ impl Renderer {
             .features()
             .contains(wgpu::Features::DUAL_SOURCE_BLENDING);
         self.inner = SceneRenderer::new(&self.device, render_fmt, w, h, dual_source);
+        self.inner.set_quantize_5551(config.quantize_5551);
+        self.inner.set_emulate_coverage(config.emulate_coverage);
+        self.inner
+            .set_sample_count(&self.device, sample_count_of(&config));
+        self.inner
+            .set_resolution_multiplier(&self.device, config.resolution_multiplier);
         self.surface_format = render_fmt;
         self.config = config;
         // The store was just dropped with the old `inner`; drop dangling scanout state too.
@@ -405,13 +471,61 @@ impl Renderer {
         ucode: Microcode,
         diags: &mut dyn DiagSink,
     ) -> DlSummary {
+        self.walk_dl(hw, entry, ucode, self.data_format, diags)
+    }
+
+    /// `process_dl` with an explicit data format (`process_task` takes it from the identified
+    /// microcode rather than `set_data_format`).
+    fn walk_dl(
+        &mut self,
+        hw: &impl Hardware,
+        entry: u64,
+        ucode: Microcode,
+        data_format: DataFormat,
+        diags: &mut dyn DiagSink,
+    ) -> DlSummary {
+        let result = self.interpret_dl(hw, entry, ucode, data_format);
+        self.render_walk(result, diags)
+    }
+
+    /// The HLE walk behind `walk_dl`, before its diagnostics are streamed and its scene rasterized.
+    fn interpret_dl(
+        &mut self,
+        hw: &impl Hardware,
+        entry: u64,
+        ucode: Microcode,
+        data_format: DataFormat,
+    ) -> crate::hle::InterpResult {
         let mem = hw.rdram();
         // Contract #1/#3 (spec §3.2): record the backend kind BEFORE the reader is moved into the
         // walk — `present` gates VI-origin selection on this. RdramImage ⇒ true, HostRam ⇒ false.
         self.last_backend_was_image = mem.is_rdram_image();
+        crate::hle::interpret(mem, entry, ucode.into(), data_format)
+    }
 
-        let result = crate::hle::interpret(mem, entry, ucode.into(), self.data_format);
+    /// RDP: walk a raw RDP command list (the `[start, end)` span of 64-bit commands the RDP
+    /// fetches from DPC_START..DPC_END) and rasterize it into the same store `process_dl` uses.
+    /// Edge-walker triangles, texture/fill rectangles and every RDP state command are decoded; the
+    /// list runs under whatever render state it sets itself. INFALLIBLE, like `process_dl`.
+    pub fn process_rdp_list(
+        &mut self,
+        hw: &impl Hardware,
+        start: u64,
+        end: u64,
+        diags: &mut dyn DiagSink,
+    ) -> DlSummary {
+        let mem = hw.rdram();
+        self.last_backend_was_image = mem.is_rdram_image();
+        let result = crate::hle::rdp_list::interpret_rdp_list(mem, start, end);
+        self.render_walk(result, diags)
+    }
 
+    /// Stream a finished walk's diagnostics, rasterize its scene into the store and roll it up.
+    fn render_walk(
+        &mut self,
+        result: crate::hle::InterpResult,
+        diags: &mut dyn DiagSink,
+    ) -> DlSummary {
         // Stream structured diags into the caller's sink, tallying severity for the rollup.
         let (mut warns, mut errors) = (0u32, 0u32);
         for &d in &result.diags {
@@ -445,10 +559,56 @@ impl Renderer {
             warns,
             errors,
             dropped_runs: result.dropped_runs,
+            culled_dls: result.culled_dls,
             renderable: scanout.is_some(),
         }
     }
 
+    /// RSP-task entry point: parse the libultra `OSTask` at `ostask_addr`, identify its microcode
+    /// from the text segment and walk its `data_ptr` display list exactly like `process_dl`, in the
+    /// identified build's data format. An unrecognized image reports `UnknownTaskMicrocode` and
+    /// falls back to `RendererConfig::default_microcode` and the `set_data_format` format.
+    /// Non-graphics tasks are skipped with an empty summary; an `OSTask` that runs past RDRAM
+    /// reports `DlPastRdram`.
+    pub fn process_task(
+        &mut self,
+        hw: &impl Hardware,
+        ostask_addr: u64,
+        diags: &mut dyn DiagSink,
+    ) -> DlSummary {
+        let mem = hw.rdram();
+        let Some(task) = OsTask::read(&mem, ostask_addr) else {
+            diags.emit(Diagnostic {
+                at: ostask_addr,
+                kind: DiagKind::DlPastRdram,
+            });
+            return DlSummary {
+                errors: 1,
+                ..DlSummary::default()
+            };
+        };
+        if task.task_type != task::M_GFXTASK {
+            return DlSummary::default();
+        }
+        let entry = task.entry(&mem);
+        match task.microcode(&mem) {
+            Some(info) => self.walk_dl(hw, entry, info.microcode, info.data_format, diags),
+            None => {
+                let unknown = Diagnostic {
+                    at: mem.resolve_masked(task.ucode as u64),
+                    kind: DiagKind::UnknownTaskMicrocode {
+                        hash: task.microcode_hash(&mem),
+                    },
+                };
+                let mut result =
+                    self.interpret_dl(hw, entry, self.config.default_microcode, self.data_format);
+                // Lead the walk's own diagnostics so `render_walk` streams and tallies it with them.
+                result.diags.insert(0, unknown);
+                self.render_walk(result, diags)
+            }
+        }
+    }
+
     /// Explicit frame boundary. Resets per-frame accumulation: the inner store's first-touch clear
     /// set (ClearPolicy::PerFrame) and the retained `frame_scenes`.
     pub fn begin_frame(&mut self) {
@@ -684,6 +844,9 @@ mod present_headless_tests {
                 format: Some(wgpu::TextureFormat::Rgba8Unorm),
                 clear_policy: ClearPolicy::PerFrame,
                 power_preference: wgpu::PowerPreference::LowPower,
+                quantize_5551: false,
+                emulate_coverage: false,
+                default_microcode: Microcode::F3dex2,
             },
         );
         let hw = ImgHw { rdram: Vec::new() };
@@ -761,6 +924,9 @@ mod begin_frame_tests {
             format: Some(wgpu::TextureFormat::Rgba8Unorm),
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::LowPower,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         }
     }
 
@@ -859,6 +1025,41 @@ mod begin_frame_tests {
             "draw-nothing must keep the last good frame (spec §4 step 4)"
         );
     }
+
+    #[test]
+    fn process_rdp_list_renders_a_raw_fill_into_the_store() {
+        let mut rdram = Vec::new();
+        for (w0, w1) in [
+            (0xFF10_003Fu32, 0x0000_1000u32), // SET_COLOR_IMAGE 64-wide RGBA16 at 0x1000
+            (0xF700_0000, 0xF801_F801),       // SET_FILL_COLOR
+            (0xF60F_C0FC, 0x0000_0000),       // FILL_RECTANGLE (0,0)-(63,63)
+        ] {
+            rdram.extend_from_slice(&w0.to_be_bytes());
+            rdram.extend_from_slice(&w1.to_be_bytes());
+        }
+        rdram.resize(0x4000, 0);
+        let hw = ImgHw { rdram };
+
+        let (device, queue, _dual) = crate::render::headless_device();
+        let mut r = Renderer::with_device(
+            device,
+            queue,
+            PresentTarget::Headless {
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                width: 64,
+                height: 64,
+            },
+            cfg(),
+        );
+
+        r.begin_frame();
+        let summary = r.process_rdp_list(&hw, 0, 24, &mut crate::diag::NopSink);
+        assert_eq!(summary.commands, 3);
+        assert_eq!(summary.errors, 0);
+        assert!(summary.renderable, "a raw fill produces a framebuffer");
+        assert_eq!(r.last_scanout_addr, Some(0x1000));
+        assert_eq!(r.frame_scenes.len(), 1);
+    }
 }
 
 #[cfg(test)]
@@ -874,6 +1075,9 @@ mod config_construction_tests {
             format: None,
             clear_policy: ClearPolicy::Persist,
             power_preference: wgpu::PowerPreference::HighPerformance,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         };
         assert_eq!(cfg.clear_policy, ClearPolicy::Persist);
         assert_eq!(cfg.resolution_multiplier, 1);
@@ -882,6 +1086,38 @@ mod config_construction_tests {
         assert_ne!(ClearPolicy::PerFrame, ClearPolicy::Persist);
     }
 
+    #[test]
+    fn default_config_keeps_the_1_0_behavior() {
+        let mut cfg = RendererConfig::default();
+        assert_eq!((cfg.resolution_multiplier, cfg.sample_count), (1, 1));
+        assert_eq!(cfg.format, None);
+        assert_eq!(cfg.clear_policy, ClearPolicy::PerFrame);
+        assert!(!cfg.quantize_5551 && !cfg.emulate_coverage);
+        assert_eq!(cfg.default_microcode, Microcode::F3dex2);
+        cfg.sample_count = 4;
+        assert_ne!(cfg, RendererConfig::default());
+    }
+
+    #[test]
+    fn sample_count_normalizes_to_off_or_4x() {
+        let with = |sample_count| RendererConfig {
+            resolution_multiplier: 1,
+            sample_count,
+            present_mode: wgpu::PresentMode::Fifo,
+            format: None,
+            clear_policy: ClearPolicy::PerFrame,
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
+        };
+        let counts: Vec<u32> = [0, 1, 2, 4, 8]
+            .into_iter()
+            .map(|n| sample_count_of(&with(n)))
+            .collect();
+        assert_eq!(counts, [1, 1, 4, 4, 4]);
+    }
+
     #[test]
     fn present_target_headless_variant_fields() {
         let t = PresentTarget::Headless {
@@ -933,6 +1169,9 @@ mod config_construction_tests {
             format: None,
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::HighPerformance,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         };
         let r = Renderer::with_device(
             device,
@@ -964,6 +1203,9 @@ mod config_construction_tests {
             format: None,
             clear_policy: ClearPolicy::Persist,
             power_preference: wgpu::PowerPreference::HighPerformance,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         };
         let r = Renderer::with_device(
             device,
@@ -1012,6 +1254,9 @@ mod config_construction_tests {
             format: None,
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::HighPerformance,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         };
         let mut r = Renderer::with_device(
             device,
@@ -1039,6 +1284,9 @@ mod config_construction_tests {
             format: None,
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::HighPerformance,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         };
         let mut r = Renderer::with_device(
             device,
@@ -1078,6 +1326,9 @@ mod config_construction_tests {
                 format: None,
                 clear_policy: ClearPolicy::PerFrame,
                 power_preference: wgpu::PowerPreference::HighPerformance,
+                quantize_5551: false,
+                emulate_coverage: false,
+                default_microcode: Microcode::F3dex2,
             },
         )
         .await
@@ -1101,6 +1352,9 @@ mod debugger_toggle_tests {
             format: Some(wgpu::TextureFormat::Rgba8Unorm),
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::LowPower,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         }
     }
 
@@ -1167,6 +1421,9 @@ mod hook_lifecycle_tests {
             format: Some(wgpu::TextureFormat::Rgba8Unorm),
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::LowPower,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         }
     }
 
@@ -1286,6 +1543,9 @@ mod debugger_present_tests {
             format: Some(wgpu::TextureFormat::Rgba8Unorm),
             clear_policy: ClearPolicy::PerFrame,
             power_preference: wgpu::PowerPreference::LowPower,
+            quantize_5551: false,
+            emulate_coverage: false,
+            default_microcode: Microcode::F3dex2,
         }
     }
 
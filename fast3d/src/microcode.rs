@@ -1,6 +1,10 @@
 //! Public microcode selector (spec §3.6). Mirrors the crate-internal `crate::hle::gbi::GbiUcode`.
 
-use crate::hle::gbi::{detect_from_ucode_hash, GbiUcode};
+use crate::diag::DiagKind;
+use crate::hle::gbi::{identify, ucode_hash, GbiUcode, UcodeBuild};
+use crate::hle::math::mul_row_vec4;
+use crate::hle::mem::{GbiDataFormat, Rdram};
+use crate::hle::rsp::Scene;
 
 /// Which N64 graphics microcode a display list targets. An explicit per-`process_dl` argument.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -11,6 +15,18 @@ pub enum Microcode {
     F3dex2,
     /// Original F3D microcode.
     F3d,
+    /// F3DEX 1.x (F3DEX/F3DLX/F3DLP): F3D's opcode map with a 32-entry vertex cache.
+    F3dex,
+    /// F3DZEX2 (OoT/MM): F3DEX2 plus `G_DMA_IO`/`G_SPECIAL_*`, a `vtx*5` `G_BRANCH_Z` and
+    /// positional point lights under `G_LIGHTING_POSITIONAL`.
+    F3dzex2,
+    /// L3DEX2 line microcode: F3DEX2's GBI with `G_LINE3D` lines instead of triangles.
+    L3dex2,
+    /// S2DEX 1.x sprite microcode (gs2dex.h over the F3D GBI): object rectangles/sprites and
+    /// background blits.
+    S2dex,
+    /// S2DEX2 sprite microcode (gs2dex.h over the F3DEX2 GBI).
+    S2dex2,
 }
 
 impl From<Microcode> for GbiUcode {
@@ -18,6 +34,11 @@ impl From<Microcode> for GbiUcode {
         match m {
             Microcode::F3dex2 => GbiUcode::F3dex2,
             Microcode::F3d => GbiUcode::F3d,
+            Microcode::F3dex => GbiUcode::F3dex,
+            Microcode::F3dzex2 => GbiUcode::F3dzex2,
+            Microcode::L3dex2 => GbiUcode::L3dex2,
+            Microcode::S2dex => GbiUcode::S2dex,
+            Microcode::S2dex2 => GbiUcode::S2dex2,
         }
     }
 }
@@ -27,14 +48,160 @@ impl From<GbiUcode> for Microcode {
         match u {
             GbiUcode::F3dex2 => Microcode::F3dex2,
             GbiUcode::F3d => Microcode::F3d,
+            GbiUcode::F3dex => Microcode::F3dex,
+            GbiUcode::F3dzex2 => Microcode::F3dzex2,
+            GbiUcode::L3dex2 => Microcode::L3dex2,
+            GbiUcode::S2dex => Microcode::S2dex,
+            GbiUcode::S2dex2 => Microcode::S2dex2,
         }
     }
 }
 
-/// Table lookup for a real-ROM consumer that has already hashed a microcode image (spec §3.6/§8.4;
-/// no live caller today — reuses the internal `detect_from_ucode_hash` fixture table).
-pub fn detect_microcode(ucode_hash: u64) -> Option<Microcode> {
-    detect_from_ucode_hash(ucode_hash).map(Microcode::from)
+/// A recognized microcode image: the variant, the data format its display lists use and the
+/// build name (e.g. `"F3DEX2.fifo 2.08"`) for diagnostics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MicrocodeInfo {
+    pub microcode: Microcode,
+    pub data_format: GbiDataFormat,
+    pub build: String,
+}
+
+impl From<UcodeBuild> for MicrocodeInfo {
+    fn from(b: UcodeBuild) -> Self {
+        MicrocodeInfo {
+            microcode: b.ucode.into(),
+            data_format: b.data_format,
+            build: b.build.into_owned(),
+        }
+    }
+}
+
+/// Always `None`: detection is string-based and no image hashes are pinned. Kept for 1.x
+/// callers; use `identify_microcode`, which reads the ident string in the image's data segment.
+#[deprecated(note = "detection is string-based; use `identify_microcode`")]
+pub fn detect_microcode(_ucode_hash: u64) -> Option<Microcode> {
+    None
+}
+
+/// The detection hash of the microcode image at physical `text_addr`/`data_addr`: FNV-1a over
+/// the 4 KiB text segment followed by the 2 KiB data segment (libultra's `SP_UCODE_SIZE` and
+/// `SP_UCODE_DATA_SIZE`). A segment that runs past RDRAM contributes no bytes. Names an image in
+/// diagnostics; it does not identify one.
+pub fn microcode_hash(mem: &impl Rdram, text_addr: u64, data_addr: u64) -> u64 {
+    ucode_hash(mem, text_addr, data_addr)
+}
+
+/// Identify the microcode image at physical `text_addr`/`data_addr` by the ident string in its
+/// data segment. `None` when it names no supported build.
+pub fn identify_microcode(
+    mem: &impl Rdram,
+    text_addr: u64,
+    data_addr: u64,
+) -> Option<MicrocodeInfo> {
+    identify(mem, text_addr, data_addr)
+        .ok()
+        .map(MicrocodeInfo::from)
+}
+
+/// Microcodes `guess_microcode` tries on a bounds-checked backend, in tie-break order. The sprite
+/// microcodes are left out: they share most of their base table's opcodes, so only an image
+/// (`identify_microcode`) tells them apart.
+const GUESS_CANDIDATES: [Microcode; 5] = [
+    Microcode::F3dex2,
+    Microcode::F3dzex2,
+    Microcode::L3dex2,
+    Microcode::F3dex,
+    Microcode::F3d,
+];
+
+/// Guard band of a plausible vertex: clip-space |x| and |y| within this many |w|, i.e. at most
+/// ~64 screen widths off-axis. Float data misread as fixed lands far outside it (s16 halves of
+/// float bit patterns through a matrix of the same), while fixed data misread as float decodes to
+/// denormal positions.
+const PLAUSIBLE_GUARD: f32 = 64.0;
+
+/// Rank the microcode/data-format pairs a display list at `entry` could target, most likely
+/// first, with a confidence in `(0, 1]` (1 = a clean walk). For a raw DL pointer with no
+/// microcode image to `identify_microcode`, e.g. from a PC port.
+///
+/// Each candidate is a dry `interpret` of the list; its diagnostics are the score. Distinct unknown
+/// opcodes, `VtxOutOfRange`, a walk that never ends (`DlPastRdram`/`RunawayDl`), implausible
+/// vertex data and triangles over never-loaded vertices all lower it. Ties keep `F3dex2` →
+/// `F3dzex2` → `L3dex2` → `F3dex` → `F3d`, `Fixed` before `Float`.
+///
+/// The candidates follow the backend. `RdramImage` bounds-checks every read, so each 3D
+/// microcode is tried, fixed-point only (the image has no float decode). A host-pointer backend
+/// dereferences every operand unchecked, and a table the list was not written for reads
+/// non-pointer words as pointers (F3D's `G_VTX` opcode is F3DEX2's `G_BRANCH_Z`, whose w1 is a
+/// depth), so only `F3dex2` (the PC-port GBI) is tried there, under both data formats. Its float
+/// walk reads each `G_VTX` no further than the fixed-point array would reach, so a fixed-point
+/// list stays within the memory `HostRam::new` vouches for.
+pub fn guess_microcode(
+    mem: &(impl Rdram + Clone),
+    entry: u64,
+) -> Vec<(Microcode, GbiDataFormat, f32)> {
+    let (ucodes, formats): (&[Microcode], &[GbiDataFormat]) = if mem.is_rdram_image() {
+        (&GUESS_CANDIDATES, &[GbiDataFormat::Fixed])
+    } else {
+        (
+            &[Microcode::F3dex2],
+            &[GbiDataFormat::Fixed, GbiDataFormat::Float],
+        )
+    };
+    let mut ranked = Vec::new();
+    for &data_format in formats {
+        for &ucode in ucodes {
+            let result =
+                crate::hle::interp::interpret_dry(mem.clone(), entry, ucode.into(), data_format);
+            let mut penalty = 0.0;
+            for d in &result.diags {
+                penalty += match d.kind {
+                    DiagKind::UnknownOpcode(_) => 1.0,
+                    DiagKind::VtxOutOfRange { .. } => 0.5,
+                    DiagKind::DlPastRdram | DiagKind::RunawayDl { .. } => 4.0,
+                    _ => 0.0,
+                };
+            }
+            penalty += 4.0 * implausible_vertices(&result.scene);
+            penalty += 4.0 * dangling_indices(&result.scene);
+            ranked.push((ucode, data_format, 1.0 / (1.0 + penalty)));
+        }
+    }
+    // Stable: equal confidences keep candidate order.
+    ranked.sort_by(|a, b| b.2.total_cmp(&a.2));
+    ranked
+}
+
+/// Fraction of the walk's loaded vertices with a denormal/non-finite position or a clip-space
+/// transform outside `PLAUSIBLE_GUARD`. 0 when no vertex was loaded.
+fn implausible_vertices(scene: &Scene) -> f32 {
+    if scene.raw_pos.is_empty() {
+        return 0.0;
+    }
+    let bad = scene
+        .raw_pos
+        .iter()
+        .zip(&scene.mtx_index)
+        .filter(|&(&[x, y, z], &mtx)| {
+            let [cx, cy, cz, cw] = mul_row_vec4([x, y, z, 1.0], scene.mvp_table[mtx as usize]);
+            let object = [x, y, z].iter().all(|v| *v == 0.0 || v.is_normal());
+            let clip = [cx, cy, cz, cw].iter().all(|v| v.is_finite())
+                && cx.abs().max(cy.abs()) <= PLAUSIBLE_GUARD * cw.abs();
+            !(object && clip)
+        })
+        .count();
+    bad as f32 / scene.raw_pos.len() as f32
+}
+
+/// Fraction of triangle corners naming a vertex the walk never loaded: a table that misdecodes
+/// `G_VTX` still draws, from cache slots nothing filled. 0 when nothing was drawn.
+fn dangling_indices(scene: &Scene) -> f32 {
+    if scene.indices.is_empty() {
+        return 0.0;
+    }
+    let loaded = scene.raw_pos.len() as u32;
+    let bad = scene.indices.iter().filter(|&&i| i >= loaded).count();
+    bad as f32 / scene.indices.len() as f32
 }
 
 #[cfg(test)]
@@ -47,20 +214,124 @@ mod tests {
         assert_eq!(GbiUcode::from(Microcode::F3d), GbiUcode::F3d);
         assert_eq!(Microcode::from(GbiUcode::F3dex2), Microcode::F3dex2);
         assert_eq!(Microcode::from(GbiUcode::F3d), Microcode::F3d);
+        assert_eq!(GbiUcode::from(Microcode::F3dex), GbiUcode::F3dex);
+        assert_eq!(Microcode::from(GbiUcode::F3dex), Microcode::F3dex);
+        assert_eq!(GbiUcode::from(Microcode::S2dex), GbiUcode::S2dex);
+        assert_eq!(Microcode::from(GbiUcode::S2dex2), Microcode::S2dex2);
         assert_eq!(Microcode::default(), Microcode::F3dex2);
     }
 
     #[test]
-    fn detect_resolves_known_and_rejects_unknown() {
-        // Reuses the populated fixture table (hle/gbi/detect.rs), NOT an always-None stub.
-        assert_eq!(
-            detect_microcode(0xF3D2_0000_0000_0001),
-            Some(Microcode::F3dex2)
+    #[allow(deprecated)]
+    fn identify_reads_the_ident_and_detect_is_retired() {
+        // Detection is string-based: the hash only names the image, no table maps it back.
+        let image = crate::hle::gbi::fixture_ucode(GbiUcode::S2dex2);
+        let mem = crate::hle::mem::RdramImage::new(&image);
+        let data = crate::hle::gbi::UCODE_TEXT_SIZE;
+        let info = identify_microcode(&mem, 0, data).expect("S2DEX2 ident");
+        assert_eq!(info.microcode, Microcode::S2dex2);
+        assert_eq!(info.build, "S2DEX2.fifo 2.05");
+        assert_eq!(detect_microcode(microcode_hash(&mem, 0, data)), None);
+    }
+
+    /// Three BE fixed-point vertices at 0, then `commands` at the returned entry.
+    fn dl_image(commands: &[(u32, u32)]) -> (Vec<u8>, u64) {
+        let mut bytes = Vec::new();
+        for [x, y] in [[10i16, 10], [50, 10], [10, 50]] {
+            for v in [x, y, 0, 0, 0, 0] {
+                bytes.extend_from_slice(&v.to_be_bytes());
+            }
+            bytes.extend_from_slice(&[255; 4]);
+        }
+        let entry = bytes.len() as u64;
+        for (w0, w1) in commands {
+            bytes.extend_from_slice(&w0.to_be_bytes());
+            bytes.extend_from_slice(&w1.to_be_bytes());
+        }
+        (bytes, entry)
+    }
+
+    /// G_SETCOMBINE passing shade through (an RDP command, the same under every table).
+    fn shade_combine() -> (u32, u32) {
+        use n64_gbi::encode::*;
+        let (color, alpha) = (
+            CcPass {
+                a: ZERO_C,
+                b: ZERO_C,
+                c: ZERO_C,
+                d: 4,
+            },
+            CcPass {
+                a: ZERO_A,
+                b: ZERO_A,
+                c: ZERO_A,
+                d: 4,
+            },
         );
+        gdp_set_combine_lerp(color, alpha, color, alpha)
+    }
+
+    #[test]
+    fn guess_ranks_the_table_that_walks_cleanly_first() {
+        use crate::hle::consts::{G_RM_OPA_SURF, G_RM_OPA_SURF2};
+        use n64_gbi::encode::*;
+        let (f3dex2, entry) = dl_image(&[
+            shade_combine(),
+            gdp_set_render_mode(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            gsp_vertex(0, 3, 0),
+            gsp_1triangle(0, 1, 2),
+            gsp_enddl(),
+        ]);
+        let ranked = guess_microcode(&crate::hle::mem::RdramImage::new(&f3dex2), entry);
+        // RdramImage is fixed-only: no Float candidates.
+        assert_eq!(ranked.len(), GUESS_CANDIDATES.len());
+        assert_eq!(ranked[0], (Microcode::F3dex2, GbiDataFormat::Fixed, 1.0));
+        // F3DZEX2 shares the F3DEX2 opcode map, so it ties and ranks right behind.
+        assert_eq!(ranked[1], (Microcode::F3dzex2, GbiDataFormat::Fixed, 1.0));
+        assert!(ranked[2].2 < 1.0, "{ranked:?}");
+
+        // Only F3DZEX2 handles G_SPECIAL_1, breaking the tie.
+        let (f3dzex2, entry) = dl_image(&[
+            shade_combine(),
+            gdp_set_render_mode(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            (
+                (crate::hle::consts::rsp_f3dzex2::G_SPECIAL_1 as u32) << 24,
+                0,
+            ),
+            gsp_vertex(0, 3, 0),
+            gsp_1triangle(0, 1, 2),
+            gsp_enddl(),
+        ]);
+        let ranked = guess_microcode(&crate::hle::mem::RdramImage::new(&f3dzex2), entry);
+        assert_eq!(ranked[0], (Microcode::F3dzex2, GbiDataFormat::Fixed, 1.0));
+        assert!(ranked[1].2 < 1.0, "{ranked:?}");
+
+        let (f3d, entry) = dl_image(&[
+            shade_combine(),
+            gdp_set_render_mode_f3d(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+            gsp_vertex_f3d(13, 3, 0),
+            gsp_1triangle_f3d(13, 14, 15),
+            gsp_enddl_f3d(),
+        ]);
+        let ranked = guess_microcode(&crate::hle::mem::RdramImage::new(&f3d), entry);
+        assert_eq!(ranked[0], (Microcode::F3d, GbiDataFormat::Fixed, 1.0));
+        assert!(ranked[1].2 < 1.0, "{ranked:?}");
+    }
+
+    #[test]
+    fn identify_returns_the_build_string() {
+        let mut image = vec![0u8; 0x1800];
+        let ident = b"RSP Gfx ucode F3DEX       fifo 2.08  Yoshitaka Yasumoto 1999 Nintendo.";
+        image[0x1100..0x1100 + ident.len()].copy_from_slice(ident);
+        let mem = crate::hle::mem::RdramImage::new(&image);
         assert_eq!(
-            detect_microcode(0xF3D0_0000_0000_0003),
-            Some(Microcode::F3d)
+            identify_microcode(&mem, 0, 0x1000),
+            Some(MicrocodeInfo {
+                microcode: Microcode::F3dex2,
+                data_format: GbiDataFormat::Fixed,
+                build: "F3DEX2.fifo 2.08".into(),
+            })
         );
-        assert_eq!(detect_microcode(0xDEAD_BEEF), None);
+        assert_eq!(identify_microcode(&mem, 0, 0x2000), None);
     }
 }
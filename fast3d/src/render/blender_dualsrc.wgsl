@@ -51,12 +51,16 @@ fn fs_main(in: VsOut) -> FragOut {
     let p1 = (combiner.blender_mux >> 14u) & 3u;
     var comb3 = r.rgb;
     if (p1 == 3u) {
-        comb3 = mix(comb3, combiner.fog_color.rgb, in.color.a);
+        comb3 = mix(comb3, combiner.fog_color.rgb, shade_color(in).a);
     }
     let out_a = r.alpha;
-    // Phase D: alpha-test discard. Only active when alpha_mode != 0 (CVG_X_ALPHA or THRESHOLD).
-    // alpha_mode == 0 → NO discard → non-cutout runs are byte-identical (zero regression risk).
-    if (combiner.alpha_mode != 0u && out_a < combiner.alpha_threshold) {
+    // Phase D: alpha-test discard. Only active when alpha_mode != 0 (CVG_X_ALPHA, THRESHOLD or
+    // DITHER). alpha_mode == 0 → NO discard → non-cutout runs are byte-identical.
+    if (alpha_rejected(out_a, in.clip_position.xy)) {
+        discard;
+    }
+    // G_CK_KEY: drop pixels whose combined color falls inside the chroma-key range.
+    if (chroma_keyed(r.rgb)) {
         discard;
     }
 
@@ -68,8 +72,12 @@ fn fs_main(in: VsOut) -> FragOut {
     let a_sel = select((bi >> 10u) & 3u, (bi >> 8u) & 3u, cc2);
     let b_sel = select((bi >> 2u) & 3u, bi & 3u, cc2);
 
-    let pcol = blend_p(p_sel, comb3, combiner.blend_color.rgb, combiner.fog_color.rgb);
-    let a = blend_a(a_sel, out_a, combiner.fog_color.a, in.color.a);
+    // RGBA5551 output quantizes the blender's P input; M is the already-quantized framebuffer.
+    let pcol = quantize_5551(
+        blend_p(p_sel, comb3, combiner.blend_color.rgb, combiner.fog_color.rgb),
+        in.clip_position.xy,
+    );
+    let a = blend_a(a_sel, out_a, combiner.fog_color.a, shade_color(in).a);
     let b = blend_b(b_sel, a);
     let denom = max(a + b, 1.0 / 255.0);
 
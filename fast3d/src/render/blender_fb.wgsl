@@ -0,0 +1,139 @@
+// blender_fb.wgsl — the programmable (framebuffer-read) blender fragment entry.
+//
+// Assembled in `TexturedPipeline::new` as its own module: combiner_prelude.wgsl + THIS file.
+// Bound to the `fb_blend` pipelines (layout g0+g1+g2), which draw with blending OFF: the shader
+// evaluates the N64 blender itself. `@group(2)` is a copy of the color image taken just before the
+// draw, so CLR_MEM is the framebuffer as it stood when the run started (a run's own overlapping
+// triangles do not see each other, unlike the RDP's per-pixel read-modify-write).
+//
+// Used for the render modes the fixed-function paths clamp (`RenderMode::fb_blend`): additive and
+// other non-canonical blends, CLR_BL mixes, fog into memory, and 2-cycle blender chains. Each
+// cycle is (P·A + M·B), divided by (A + B) unless FORCE_BL is set; the framebuffer cycle without
+// FORCE_BL passes P through.
+//
+// With coverage emulated (`combiner.coverage.x`, RendererConfig::emulate_coverage) every run that
+// uses coverage is drawn here too. A pixel's own coverage is full (under MSAA, the share of its
+// samples the primitive covers), scaled by alpha under CVG_X_ALPHA, and counted in the RDP's
+// eighths; the color image's alpha channel holds memory coverage, which A_MEM reads under IM_RD
+// and CVG_DST updates. The framebuffer cycle then blends only on AA edges. Without the model every
+// pixel is fully covered and A_MEM reads 1.0 as in blender_dualsrc.wgsl.
+
+@group(2) @binding(0) var fb_dst: texture_2d<f32>;
+
+// `combiner.coverage.z` bits.
+const CVG_AA_EN: u32 = 1u;
+const CVG_IM_RD: u32 = 2u;
+const CVG_CLR_ON_CVG: u32 = 4u;
+const CVG_ALPHA_CVG_SEL: u32 = 8u;
+const CVG_X_ALPHA: u32 = 16u;
+
+fn cvg_bit(bit: u32) -> bool {
+    return (combiner.coverage.z & bit) != 0u;
+}
+
+// Blender P/M selector: 0 CLR_IN, 1 CLR_MEM, 2 CLR_BL, 3 CLR_FOG.
+fn fb_blend_color(sel: u32, comb: vec3<f32>, mem: vec3<f32>) -> vec3<f32> {
+    if sel == 0u { return comb; }
+    if sel == 1u { return mem; }
+    if sel == 2u { return combiner.blend_color.rgb; }
+    return combiner.fog_color.rgb;
+}
+// Blender A selector: 0 A_IN, 1 A_FOG, 2 A_SHADE, 3 A_0.
+fn fb_blend_a(sel: u32, comb_a: f32, shade_a: f32) -> f32 {
+    if sel == 0u { return comb_a; }
+    if sel == 1u { return combiner.fog_color.a; }
+    if sel == 2u { return shade_a; }
+    return 0.0;
+}
+// Blender B selector: 0 1MA, 1 A_MEM (memory coverage), 2 ONE, 3 ZERO.
+fn fb_blend_b(sel: u32, a: f32, mem_cvg: f32) -> f32 {
+    if sel == 0u { return 1.0 - a; }
+    if sel == 1u { return mem_cvg; }
+    if sel == 3u { return 0.0; }
+    return 1.0;
+}
+
+// One blender cycle. `sel` packs the cycle's P/A/M/B fields as the mux stores them for cycle 2
+// (P [13:12], A [9:8], M [5:4], B [1:0]); cycle 1's fields sit two bits higher. Without FORCE_BL
+// the cycle passes P through unless `blend` (the first of two cycles, or an AA edge).
+fn fb_blend_cycle(sel: u32, comb: vec3<f32>, comb_a: f32, mem: vec3<f32>, mem_cvg: f32, shade_a: f32, blend: bool) -> vec3<f32> {
+    let p = fb_blend_color(bits(sel, 12u, 2u), comb, mem);
+    let a = fb_blend_a(bits(sel, 8u, 2u), comb_a, shade_a);
+    let m = fb_blend_color(bits(sel, 4u, 2u), comb, mem);
+    let b = fb_blend_b(bits(sel, 0u, 2u), a, mem_cvg);
+    if (combiner.force_blend != 0u) {
+        return clamp(p * a + m * b, vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+    if (!blend) {
+        return p;
+    }
+    return (p * a + m * b) / max(a + b, 1.0 / 255.0);
+}
+
+@fragment
+fn fs_blend_fb(in: VsOut) -> @location(0) vec4<f32> {
+    let r = eval_combiner(in);
+    let pos = in.clip_position.xy;
+    let mem = textureLoad(fb_dst, vec2<i32>(pos), 0);
+    let coverage = combiner.coverage.x != 0u;
+
+    // Coverage in eighths. ALPHA_CVG_SEL feeds it to the blender (and the alpha compare) as alpha;
+    // a pixel left with no coverage is never written.
+    var cvg = 8u;
+    var alpha = r.alpha;
+    var mem_cvg = 1.0;
+    if (coverage) {
+        if (cvg_bit(CVG_X_ALPHA)) {
+            cvg = u32(f32(cvg) * clamp(r.alpha, 0.0, 1.0));
+        }
+        if (cvg_bit(CVG_ALPHA_CVG_SEL)) {
+            alpha = f32(cvg) / 8.0;
+        }
+        if (cvg == 0u) {
+            discard;
+        }
+        if (cvg_bit(CVG_IM_RD)) {
+            mem_cvg = mem.a;
+        }
+    }
+    if (alpha_rejected(alpha, pos)) {
+        discard;
+    }
+    // G_CK_KEY: drop pixels whose combined color falls inside the chroma-key range.
+    if (chroma_keyed(r.rgb)) {
+        discard;
+    }
+
+    // An AA edge: the pixel's and memory's coverage together fit in one pixel.
+    let mem_cvg8 = u32(round(mem_cvg * 8.0));
+    let overflow = cvg + mem_cvg8 > 8u;
+    let edge = coverage && cvg_bit(CVG_AA_EN) && !overflow;
+
+    let shade_a = shade_color(in).a;
+    let bi = combiner.blender_mux;
+    var rgb = r.rgb;
+    if (combiner.cycle_type == 1u) {
+        // 2-cycle: cycle 1's output is cycle 2's CLR_IN.
+        rgb = fb_blend_cycle(bi >> 2u, rgb, alpha, mem.rgb, mem_cvg, shade_a, true);
+        rgb = fb_blend_cycle(bi, rgb, alpha, mem.rgb, mem_cvg, shade_a, edge);
+    } else {
+        rgb = fb_blend_cycle(bi >> 2u, rgb, alpha, mem.rgb, mem_cvg, shade_a, edge);
+    }
+    if (!coverage) {
+        return vec4<f32>(quantize_5551(rgb, pos), r.alpha);
+    }
+
+    // CLR_ON_CVG: the color is written only when the coverage overflows.
+    if (cvg_bit(CVG_CLR_ON_CVG) && !overflow) {
+        rgb = mem.rgb;
+    }
+    // CVG_DST: 0 clamp, 1 wrap, 2 full, 3 save.
+    var new_cvg: u32;
+    switch combiner.coverage.y {
+        case 0u: { new_cvg = min(cvg + mem_cvg8, 8u); }
+        case 1u: { new_cvg = (cvg + mem_cvg8) & 7u; }
+        case 2u: { new_cvg = 8u; }
+        default: { new_cvg = mem_cvg8; }
+    }
+    return vec4<f32>(quantize_5551(rgb, pos), f32(new_cvg) / 8.0);
+}
@@ -14,14 +14,24 @@ struct VsIn {
 struct VsOut {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
+    // The G_TP_NONE pipeline twin rewrites this declaration to `@interpolate(linear)` (Rust
+    // `combiner_prelude`), so keep it on one line.
     @location(1) uv:    vec2<f32>,
+    // The provoking vertex's color, for flat-shaded runs (see `shade_color`).
+    @location(2) @interpolate(flat) flat_color: vec4<f32>,
 };
 
 @vertex
 fn vs_main(in: VsIn) -> VsOut {
     var out: VsOut;
     out.clip_position = in.position; // GPU does the perspective divide
+    // G_ZS_PRIM: the whole primitive takes the constant G_SETPRIMDEPTH depth (scaled by w so the
+    // divide lands exactly on it), for the depth test and the depth write alike.
+    if (combiner.prim_depth.y != 0.0) {
+        out.clip_position.z = combiner.prim_depth.x * in.position.w;
+    }
     out.color = in.color;
+    out.flat_color = in.color;
     out.uv = in.uv;
     return out;
 }
@@ -48,12 +58,39 @@ struct Combiner {
     lod_params:      vec4<f32>, // LOD params: .x = lod_enable (1.0 when the mip chain is active),
                                 // .y = num_levels (declared; re-clamped to the real uploaded count
                                 // in eval_combiner), .z = prim_lod_frac (the primitive LOD fraction), .w =
-                                // lod_scale (1.0, native res). In LOCKSTEP with the Rust CombinerUniform.
+                                // the resolution multiplier (1.0, native res): it scales the LOD and
+                                // maps fragments back to native dither pixels. In LOCKSTEP with the
+                                // Rust CombinerUniform.
     inv_detail_size: vec4<f32>, // DETAIL params: .xy = 1/(detail_w, detail_h) when a DETAIL tile is
                                 // present, else (1,1). .z = prim_lod_min (the primitive LOD minimum), consumed
                                 // by compute_lod under DETAIL/SHARPEN. .w = detail_mode bits (bit0 =
                                 // SHARPEN, bit1 = DETAIL — DETAIL set only when a real tile was
                                 // decoded). In LOCKSTEP with the Rust CombinerUniform.
+    key_center:      vec4<f32>, // .rgb = G_SETKEYR/GB center (KEY_CENTER), .a = K4 (signed, /255).
+                                // In LOCKSTEP with the Rust CombinerUniform.
+    key_scale:       vec4<f32>, // .rgb = G_SETKEYR/GB scale (KEY_SCALE), .a = K5 (signed, /255).
+                                // In LOCKSTEP with the Rust CombinerUniform.
+    key_width:       vec4<f32>, // .rgb = G_SETKEYR/GB width / 255², .a = 1.0 when G_CK_KEY is set.
+                                // In LOCKSTEP with the Rust CombinerUniform.
+    prim_depth:      vec4<f32>, // .x = G_SETPRIMDEPTH Z as a [0,1) depth, .y = 1.0 under G_ZS_PRIM.
+                                // In LOCKSTEP with the Rust CombinerUniform.
+    raster:          vec4<f32>, // .x = 1.0 for a flat-shaded run (G_SHADING_SMOOTH clear), .y = the
+                                // texture filter (0 point, 1 G_TF_BILERP 3-point, 2 G_TF_AVERAGE
+                                // bilinear); .z = bound TEXEL0 texels per native texel (the
+                                // resolution multiplier on an fb_source view, 0 = 1); .w pad. In
+                                // LOCKSTEP with the Rust CombinerUniform.
+    tile:            vec4<u32>, // Tile addressing words (Rust `tile_words`): .xy TEXEL0, .zw TEXEL1.
+                                // In LOCKSTEP with the Rust CombinerUniform.
+    tile_origin:     vec4<f32>, // G_SETTILESIZE (uls, ult) in texels: .xy TEXEL0, .zw TEXEL1. Zero
+                                // for rects/texgen. In LOCKSTEP with the Rust CombinerUniform.
+    dither:          vec4<u32>, // .x = color dither (0 magic square, 1 Bayer, 2 noise, 3 off), .y =
+                                // alpha dither (0 pattern, 1 inverted, 2 noise, 3 off), .z = 1 to
+                                // quantize to RGBA5551, .w = per-frame NOISE seed. In LOCKSTEP
+                                // with the Rust CombinerUniform.
+    coverage:        vec4<u32>, // .x = 1 to emulate coverage, .y = CVG_DST (0 clamp, 1 wrap, 2 full,
+                                // 3 save), .z = bits (1 AA_EN, 2 IM_RD, 4 CLR_ON_CVG,
+                                // 8 ALPHA_CVG_SEL, 16 CVG_X_ALPHA). In LOCKSTEP with the Rust
+                                // CombinerUniform.
 };
 
 @group(0) @binding(0) var tex0:  texture_2d<f32>;
@@ -94,7 +131,7 @@ fn bits(v: u32, pos: u32, n: u32) -> u32 {
 // `eval_combiner` (C_TEXEL0 -> secondCycle ? texVal1 : texVal0).
 
 // color_a: 4-bit. 0=COMBINED,1=TEXEL0,2=TEXEL1,3=PRIMITIVE,4=SHADE,5=ENVIRONMENT,6=ONE,7=NOISE,else ZERO
-fn color_a_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, combined: vec3<f32>, prim: vec3<f32>, env: vec3<f32>) -> vec3<f32> {
+fn color_a_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, combined: vec3<f32>, prim: vec3<f32>, env: vec3<f32>, noise: f32) -> vec3<f32> {
     let i = idx & 0xFu;
     if i == 0u { return combined; }
     if i == 1u { return t0.rgb; }
@@ -103,7 +140,7 @@ fn color_a_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, combine
     if i == 4u { return shade; }
     if i == 5u { return env; }
     if i == 6u { return vec3<f32>(1.0); } // ONE
-    // 7=NOISE, else ZERO
+    if i == 7u { return vec3<f32>(noise); } // NOISE
     return vec3<f32>(0.0);
 }
 
@@ -116,14 +153,15 @@ fn color_b_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, combine
     if i == 3u { return prim; }
     if i == 4u { return shade; }
     if i == 5u { return env; }
-    // 6=KEY_CENTER, 7=K4 -> unwired -> ZERO
+    if i == 6u { return combiner.key_center.rgb; }
+    if i == 7u { return vec3<f32>(combiner.key_center.a); } // K4
     return vec3<f32>(0.0);
 }
 
 // color_c: 5-bit. 0=COMBINED,1=TEXEL0,2=TEXEL1,3=PRIMITIVE,4=SHADE,5=ENVIRONMENT,
 //   6=KEY_SCALE,7=COMBINED_ALPHA,8=TEXEL0_ALPHA,9=TEXEL1_ALPHA,10=PRIM_ALPHA,
 //   11=SHADE_ALPHA,12=ENV_ALPHA,13=LOD_FRAC,14=PRIM_LOD_FRAC,15=K5,else ZERO
-fn color_c_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, shade_a: f32, combined: vec3<f32>, prim: vec3<f32>, env: vec3<f32>, prim_a: f32, env_a: f32, lod_fraction: f32, prim_lod_frac: f32) -> vec3<f32> {
+fn color_c_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, shade_a: f32, combined: vec3<f32>, combined_a: f32, prim: vec3<f32>, env: vec3<f32>, prim_a: f32, env_a: f32, lod_fraction: f32, prim_lod_frac: f32) -> vec3<f32> {
     let i = idx & 0x1Fu;
     if i == 0u { return combined; }
     if i == 1u { return t0.rgb; }
@@ -131,8 +169,9 @@ fn color_c_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, shade_a
     if i == 3u { return prim; }
     if i == 4u { return shade; }
     if i == 5u { return env; }
-    // 6..15 -> wired subset: 8=TEXEL0_ALPHA, 9=TEXEL1_ALPHA, 10=PRIM_ALPHA, 11=SHADE_ALPHA,
-    // 12=ENV_ALPHA, 13=LOD_FRACTION, 14=PRIM_LOD_FRAC (color-C mux slots).
+    // 6..15: the color-C-only selectors; the *_ALPHA ones broadcast an alpha to all three channels.
+    if i == 6u  { return combiner.key_scale.rgb; }
+    if i == 7u  { return vec3<f32>(combined_a); }
     if i == 8u  { return vec3<f32>(t0.a); }
     if i == 9u  { return vec3<f32>(t1.a); }
     if i == 10u { return vec3<f32>(prim_a); }
@@ -140,6 +179,7 @@ fn color_c_rgb(idx: u32, t0: vec4<f32>, t1: vec4<f32>, shade: vec3<f32>, shade_a
     if i == 12u { return vec3<f32>(env_a); }
     if i == 13u { return vec3<f32>(lod_fraction); }
     if i == 14u { return vec3<f32>(prim_lod_frac); }
+    if i == 15u { return vec3<f32>(combiner.key_scale.a); } // K5
     return vec3<f32>(0.0);
 }
 
@@ -183,6 +223,90 @@ fn alpha_c(idx: u32, t0_a: f32, t1_a: f32, shade_a: f32, combined_a: f32, prim_a
     return 0.0;
 }
 
+// G_CK_KEY chroma-key test on the combined color: a pixel is keyed (and discarded by the fragment
+// entries) when |rgb - center| * scale <= width on every channel. Always false with G_CK_NONE.
+fn chroma_keyed(rgb: vec3<f32>) -> bool {
+    if (combiner.key_width.a == 0.0) {
+        return false;
+    }
+    let d = abs(rgb - combiner.key_center.rgb) * combiner.key_scale.rgb;
+    return all(d <= combiner.key_width.rgb);
+}
+
+// The native pixel a fragment at `pos` falls in: at a resolution multiplier above 1 each native
+// pixel spans a block of fragments, which share its dither and noise values.
+fn native_pixel(pos: vec2<f32>) -> vec2<u32> {
+    return vec2<u32>(pos / combiner.lod_params.w);
+}
+
+// RDP dither values (0..7) for the pixel at `pos`. `sel` 0 = magic square, 1 = Bayer, 2 = noise;
+// anything else is 7, which never rounds up (plain truncation).
+fn dither_hash(pos: vec2<f32>) -> u32 {
+    let p = native_pixel(pos);
+    var h = p.x * 0x27d4eb2du ^ p.y * 0x165667b1u;
+    h = (h ^ (h >> 15u)) * 0x85ebca6bu;
+    return h ^ (h >> 13u);
+}
+// Combiner NOISE for the pixel at `pos`: a 3-bit random value re-rolled every frame by the
+// `dither.w` seed, scaled as the RDP does ((rand << 6) | 0x20 on its 9-bit 0x100 = 1.0 scale, so
+// it can exceed 1.0) and shared by all three channels.
+fn combiner_noise(pos: vec2<f32>) -> f32 {
+    var h = dither_hash(pos) ^ (combiner.dither.w * 0x9e3779b9u);
+    h = (h ^ (h >> 16u)) * 0x7feb352du;
+    h = h ^ (h >> 15u);
+    return f32(((h & 7u) << 6u) | 0x20u) / 256.0;
+}
+fn dither_value(sel: u32, pos: vec2<f32>) -> u32 {
+    let p = native_pixel(pos);
+    let i = (p.y & 3u) * 4u + (p.x & 3u);
+    var magic_square = array<u32, 16>(0u, 6u, 1u, 7u, 4u, 2u, 5u, 3u, 3u, 5u, 2u, 4u, 7u, 1u, 6u, 0u);
+    var bayer = array<u32, 16>(0u, 4u, 1u, 5u, 6u, 2u, 7u, 3u, 1u, 5u, 0u, 4u, 7u, 3u, 6u, 2u);
+    if sel == 0u { return magic_square[i]; }
+    if sel == 1u { return bayer[i]; }
+    if sel == 2u { return dither_hash(pos) & 7u; }
+    return 7u;
+}
+
+// Alpha-test discard shared by every fragment entry. alpha_mode 1/2 compare against the fixed
+// `alpha_threshold`; 3 (G_AC_DITHER) against a per-pixel threshold from the alpha dither: the
+// color dither's matrix (G_AD_PATTERN), its inverse (G_AD_NOTPATTERN), or noise (G_AD_NOISE and
+// G_AD_DISABLE — the compare itself is a random-threshold test). That is the "screen-door"
+// transparency: a pixel survives with probability ≈ alpha.
+fn alpha_rejected(alpha: f32, pos: vec2<f32>) -> bool {
+    if (combiner.alpha_mode == 0u) {
+        return false;
+    }
+    if (combiner.alpha_mode != 3u) {
+        return alpha < combiner.alpha_threshold;
+    }
+    var threshold: f32;
+    let sel = combiner.dither.y;
+    if (sel == 0u || sel == 1u) {
+        var d = dither_value(select(0u, 1u, combiner.dither.x == 1u), pos);
+        if (sel == 1u) {
+            d = 7u - d;
+        }
+        threshold = (f32(d) + 0.5) / 8.0;
+    } else {
+        threshold = (f32(dither_hash(pos) & 0xFFu) + 0.5) / 256.0;
+    }
+    return alpha < threshold;
+}
+
+// RGBA5551 output: round each channel to 5 bits the way the RDP does for a 16-bit color image,
+// bumping the truncated value up when its low 3 bits exceed the pixel's color dither value, then
+// bit-replicate back to 8 bits. Alpha is left to the blend stage, which still consumes it.
+fn quantize_5551(rgb: vec3<f32>, pos: vec2<f32>) -> vec3<f32> {
+    if (combiner.dither.z == 0u) {
+        return rgb;
+    }
+    let d = f32(dither_value(combiner.dither.x, pos));
+    let c8 = floor(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)) * 255.0 + 0.5);
+    let c5 = floor(c8 / 8.0);
+    let up = select(c5, min(c5 + 1.0, vec3<f32>(31.0)), c8 - c5 * 8.0 > vec3<f32>(d));
+    return (up * 8.0 + floor(up / 4.0)) / 255.0;
+}
+
 // (a-b)*c+d combiner cycle
 struct CycleResult {
     rgb:   vec3<f32>,
@@ -197,16 +321,16 @@ fn run_cycle(
     aa_idx: u32, ab_idx: u32, ac_idx: u32, ad_idx: u32,
     t0: vec4<f32>, t1: vec4<f32>, shade: vec4<f32>, combined: vec4<f32>,
     prim: vec4<f32>, env: vec4<f32>,
-    lod_fraction: f32, prim_lod_frac: f32,
+    lod_fraction: f32, prim_lod_frac: f32, noise: f32,
 ) -> CycleResult {
     let shade3  = shade.rgb;
     let comb3   = combined.rgb;
     let prim3   = prim.rgb;
     let env3    = env.rgb;
 
-    let a_rgb = color_a_rgb(ca_idx, t0, t1, shade3, comb3, prim3, env3);
+    let a_rgb = color_a_rgb(ca_idx, t0, t1, shade3, comb3, prim3, env3, noise);
     let b_rgb = color_b_rgb(cb_idx, t0, t1, shade3, comb3, prim3, env3);
-    let c_rgb = color_c_rgb(cc_idx, t0, t1, shade3, shade.a, comb3, prim3, env3, prim.a, env.a, lod_fraction, prim_lod_frac);
+    let c_rgb = color_c_rgb(cc_idx, t0, t1, shade3, shade.a, comb3, combined.a, prim3, env3, prim.a, env.a, lod_fraction, prim_lod_frac);
     let d_rgb = color_d_rgb(cd_idx, t0, t1, shade3, comb3, prim3, env3);
     let out_rgb = clamp((a_rgb - b_rgb) * c_rgb + d_rgb, vec3<f32>(0.0), vec3<f32>(1.0));
 
@@ -325,6 +449,136 @@ fn sample_tile(idx: f32, uv: vec2<f32>, detail_active: bool) -> vec4<f32> {
     return sample_level(level, uv * combiner.inv_tex_size.xy);
 }
 
+// The SHADE input (and the fog factor in its alpha): the interpolated vertex color, or the
+// provoking vertex's for a flat-shaded run. The CPU rotates the microcode's provoking vertex to the
+// front of each triangle, matching WebGPU's first-vertex flat interpolation.
+fn shade_color(in: VsOut) -> vec4<f32> {
+    return select(in.color, in.flat_color, combiner.raster.x != 0.0);
+}
+
+// `tile_words` flag: fetch through the shift/mask/clamp emulation (the sampler is inexact).
+const TILE_EMULATE: u32 = 0x80000000u;
+
+// The RDP 3-point filter over the 2x2 quad t00/t10/t01/t11 at fraction `f`: blend the upper-left
+// or the lower-right triangle of the quad.
+fn three_point(t00: vec4<f32>, t10: vec4<f32>, t01: vec4<f32>, t11: vec4<f32>, f: vec2<f32>) -> vec4<f32> {
+    if f.x + f.y < 1.0 {
+        return t00 + f.x * (t10 - t00) + f.y * (t01 - t00);
+    }
+    return t11 + (1.0 - f.x) * (t01 - t11) + (1.0 - f.y) * (t10 - t11);
+}
+
+// Apply one axis's G_SETTILE shift: 1..=10 shift right, 11..=15 shift left by 16 - shift.
+fn tile_shift(c: f32, shift: u32) -> f32 {
+    if shift > 10u {
+        return c * f32(1u << (16u - shift));
+    }
+    return c / f32(1u << shift);
+}
+
+// Wrap one axis's integer texel index by the tile mask, mirroring odd periods under the cm mirror
+// bit. Mask 0 wraps at the texture's own size `n` instead (a clamped index never leaves it).
+fn tile_mask(i: i32, mask: u32, cm: u32, n: i32) -> i32 {
+    if mask == 0u {
+        let k = select(i / n, (i + 1) / n - 1, i < 0);
+        let m = i - k * n;
+        if (cm & 1u) != 0u && (k & 1) != 0 {
+            return n - 1 - m;
+        }
+        return m;
+    }
+    let period = 1 << min(mask, 10u);
+    var m = i;
+    if (cm & 1u) != 0u && (m & period) != 0 {
+        m = ~m;
+    }
+    return m & (period - 1);
+}
+
+// Fetch the texel at tile-space integer coordinate `i` (already masked), guarding the texture edge.
+// A view bound at `k` texels per native texel reads texel `sub` of the native texel's k×k block.
+fn tile_load(t: texture_2d<f32>, i: vec2<i32>, k: i32, sub: vec2<i32>) -> vec4<f32> {
+    let last = vec2<i32>(textureDimensions(t)) - 1;
+    return textureLoad(t, clamp(i * k + sub, vec2<i32>(0), last), 0);
+}
+
+// Shift/clamp/mask emulation of one tile's texcoord addressing (words from `tile_words`). The
+// texel-space coordinate is shifted, moved to the tile's `origin` (uls, ult), clamped to [0, clamp >> 2] when the cm clamp bit is set
+// (the fraction drops to 0 at a clamped edge, as on the RDP), and then every tap the filter reads
+// is wrapped/mirrored by the mask before the fetch. `k` is the bound texels per native texel: all
+// addressing runs on the native grid, and each tap reads the block texel under the sample point.
+fn sample_emulated(t: texture_2d<f32>, uv: vec2<f32>, origin: vec2<f32>, w0: u32, w1: u32, k: f32) -> vec4<f32> {
+    let shift = vec2<u32>(w0 & 0xFu, (w0 >> 4u) & 0xFu);
+    let mask = vec2<u32>((w0 >> 8u) & 0xFu, (w0 >> 12u) & 0xFu);
+    let cm = vec2<u32>((w0 >> 16u) & 3u, (w0 >> 18u) & 3u);
+    let bound = vec2<f32>(f32((w1 & 0xFFFFu) >> 2u), f32(w1 >> 18u));
+    let dims = vec2<f32>(textureDimensions(t)) / k;
+    var st = uv * dims;
+    st = vec2<f32>(tile_shift(st.x, shift.x), tile_shift(st.y, shift.y)) - origin;
+    // G_TF_AVERAGE keeps the sampler approximation's texel-center convention.
+    if combiner.raster.y == 2.0 {
+        st = st - 0.5;
+    }
+    if (cm.x & 2u) != 0u {
+        st.x = clamp(st.x, 0.0, bound.x);
+    }
+    if (cm.y & 2u) != 0u {
+        st.y = clamp(st.y, 0.0, bound.y);
+    }
+    let n = vec2<i32>(dims);
+    let base = vec2<i32>(floor(st));
+    let f = fract(st);
+    let ki = i32(k);
+    let sub = min(vec2<i32>(f * k), vec2<i32>(ki - 1));
+    let s0 = tile_mask(base.x, mask.x, cm.x, n.x);
+    let t0 = tile_mask(base.y, mask.y, cm.y, n.y);
+    let t00 = tile_load(t, vec2<i32>(s0, t0), ki, sub);
+    if combiner.raster.y == 0.0 {
+        return t00;
+    }
+    let s1 = tile_mask(base.x + 1, mask.x, cm.x, n.x);
+    let t1 = tile_mask(base.y + 1, mask.y, cm.y, n.y);
+    let t10 = tile_load(t, vec2<i32>(s1, t0), ki, sub);
+    let t01 = tile_load(t, vec2<i32>(s0, t1), ki, sub);
+    let t11 = tile_load(t, vec2<i32>(s1, t1), ki, sub);
+    if combiner.raster.y == 1.0 {
+        return three_point(t00, t10, t01, t11, f);
+    }
+    return mix(mix(t00, t10, f.x), mix(t01, t11, f.x), f.y);
+}
+
+// Sample a TEXEL0/TEXEL1 texture at normalized `uv` through its tile words, measured from the
+// tile's G_SETTILESIZE `origin` (texels; see `tile_origin`). A tile the hardware
+// sampler addresses exactly takes the sampler; under G_TF_BILERP (`raster.y` = 1) that sampler
+// is NEAREST (so each tap honors the wrap mode) and the three taps nearest the sample point run the
+// RDP's 3-point filter. Like the RDP, the fraction is taken from the texel-space coordinate
+// directly — texel k spans [k, k+1), with no half-texel recentring. (LOD levels and the DETAIL
+// tile are not routed through here; they keep `samp0` / `samp_detail`.) `k` is the bound texels
+// per native texel (see `raster.z`); texel coordinates are native.
+fn sample_tex(t: texture_2d<f32>, s: sampler, uv: vec2<f32>, origin: vec2<f32>, w0: u32, w1: u32, k: f32) -> vec4<f32> {
+    if (w0 & TILE_EMULATE) != 0u {
+        return sample_emulated(t, uv, origin, w0, w1, k);
+    }
+    // An exact-sampler tile has no shift, so the origin comes straight off the texcoord.
+    let dims = vec2<f32>(textureDimensions(t)) / k;
+    let at = uv - origin / dims;
+    if combiner.raster.y != 1.0 {
+        return textureSampleLevel(t, s, at, 0.0);
+    }
+    let st = at * dims;
+    // Each tap is the block texel under the sample point (the native texel's center at k = 1).
+    let base = (floor(st) + (floor(fract(st) * k) + 0.5) / k) / dims;
+    let dx = vec2<f32>(1.0 / dims.x, 0.0);
+    let dy = vec2<f32>(0.0, 1.0 / dims.y);
+    return three_point(
+        textureSampleLevel(t, s, base, 0.0),
+        textureSampleLevel(t, s, base + dx, 0.0),
+        textureSampleLevel(t, s, base + dy, 0.0),
+        textureSampleLevel(t, s, base + dx + dy, 0.0),
+        fract(st),
+    );
+}
+
 // Evaluate the full color combiner for a fragment, returning RGB + alpha.
 // Shared by the base ubershader (skeleton.wgsl) and the dual-source blender (blender_dualsrc.wgsl).
 fn eval_combiner(in: VsOut) -> CycleResult {
@@ -342,7 +596,10 @@ fn eval_combiner(in: VsOut) -> CycleResult {
     if combiner.tex_enable != 0u {
         // Normalize the TEXEL-space triangle texcoord by the draw-time tile dims. inv_tex_size =
         // (1,1) leaves already-normalized rect / texgen uv untouched.
-        texel = textureSample(tex0, samp0, in.uv * combiner.inv_tex_size.xy);
+        texel = sample_tex(
+            tex0, samp0, in.uv * combiner.inv_tex_size.xy, combiner.tile_origin.xy,
+            combiner.tile.x, combiner.tile.y, max(combiner.raster.z, 1.0),
+        );
     } else {
         texel = vec4<f32>(1.0);
     }
@@ -352,15 +609,19 @@ fn eval_combiner(in: VsOut) -> CycleResult {
     // (tex1 is the 1×1 dummy): TEXEL1 selectors resolve to the magenta sentinel and NO role swap
     // occurs, so single-texture goldens stay byte-identical.
     var use_tex1 = combiner.inv_tex1_size.z != 0.0;
-    let texel1 = textureSample(tex1, samp1, in.uv * combiner.inv_tex1_size.xy);
+    let texel1 = sample_tex(
+        tex1, samp1, in.uv * combiner.inv_tex1_size.xy, combiner.tile_origin.zw,
+        combiner.tile.z, combiner.tile.w, 1.0,
+    );
     let sentinel1 = vec4<f32>(1.0, 0.0, 1.0, 1.0); // unwired-TEXEL1 sentinel (never read when gated)
     // The value a TEXEL1 selector reads in CYCLE 0 (no swap yet): the tex1 sample when present, else
     // the sentinel.
     var t1_cyc0 = select(sentinel1, texel1, use_tex1);
 
-    let shade  = in.color;
+    let shade  = shade_color(in);
     let prim   = combiner.prim;
     let env    = combiner.env;
+    let noise  = combiner_noise(in.clip_position.xy);
 
     // LOD_FRACTION selector value. The non-LOD default is 1.0.
     // PRIM_LOD_FRAC is the primitive LOD fraction, carried in lod_params.z.
@@ -409,7 +670,7 @@ fn eval_combiner(in: VsOut) -> CycleResult {
     if combiner.cycle_type == 0u {
         // 1-cycle: no pipeline swap. TEXEL0 -> tex0, TEXEL1 -> t1_cyc0 (sentinel unless enabled).
         let zero4 = vec4<f32>(0.0);
-        result = run_cycle(ca1, cb1, cc1, cd1, aa1, ab1, ac1, ad1, texel, t1_cyc0, shade, zero4, prim, env, lod_fraction, prim_lod_frac);
+        result = run_cycle(ca1, cb1, cc1, cd1, aa1, ab1, ac1, ad1, texel, t1_cyc0, shade, zero4, prim, env, lod_fraction, prim_lod_frac, noise);
     } else {
         let ca0 = bits(l, 20u, 4u);
         let cb0 = bits(h, 28u, 4u);
@@ -421,7 +682,7 @@ fn eval_combiner(in: VsOut) -> CycleResult {
         let ad0 = bits(h, 9u, 3u);
         let zero4 = vec4<f32>(0.0);
         // Cycle 0 (secondCycle=false): TEXEL0 -> tex0, TEXEL1 -> tex1.
-        let r0 = run_cycle(ca0, cb0, cc0, cd0, aa0, ab0, ac0, ad0, texel, t1_cyc0, shade, zero4, prim, env, lod_fraction, prim_lod_frac);
+        let r0 = run_cycle(ca0, cb0, cc0, cd0, aa0, ab0, ac0, ad0, texel, t1_cyc0, shade, zero4, prim, env, lod_fraction, prim_lod_frac, noise);
         let combined0 = vec4<f32>(r0.rgb, r0.alpha);
         // Cycle 1 (secondCycle=true): the TEXEL0<->TEXEL1 role swap — a TEXEL0 selector reads
         // the tex1 sample, a TEXEL1 selector reads the tex0 sample
@@ -432,7 +693,7 @@ fn eval_combiner(in: VsOut) -> CycleResult {
         // `use_tex1` is true on the non-LOD path, so this is byte-identical there.
         let c1_t0 = select(texel, t1_cyc0, use_tex1);
         let c1_t1 = select(t1_cyc0, texel, use_tex1);
-        result = run_cycle(ca1, cb1, cc1, cd1, aa1, ab1, ac1, ad1, c1_t0, c1_t1, shade, combined0, prim, env, lod_fraction, prim_lod_frac);
+        result = run_cycle(ca1, cb1, cc1, cd1, aa1, ab1, ac1, ad1, c1_t0, c1_t1, shade, combined0, prim, env, lod_fraction, prim_lod_frac, noise);
     }
 
     return result;
@@ -18,10 +18,14 @@ fn fs_decal(in: VsOut) -> @location(0) vec4<f32> {
     // C3: fog mix — applied when cyc1 blender P == CLR_FOG (3). Matches skeleton.wgsl/fs_main.
     let p1 = (combiner.blender_mux >> 14u) & 3u;
     if (p1 == 3u) {
-        rgb = mix(rgb, combiner.fog_color.rgb, in.color.a);
+        rgb = mix(rgb, combiner.fog_color.rgb, shade_color(in).a);
     }
     // Phase D: alpha-test discard (only active when alpha_mode != 0).
-    if (combiner.alpha_mode != 0u && r.alpha < combiner.alpha_threshold) {
+    if (alpha_rejected(r.alpha, in.clip_position.xy)) {
+        discard;
+    }
+    // G_CK_KEY: drop pixels whose combined color falls inside the chroma-key range.
+    if (chroma_keyed(r.rgb)) {
         discard;
     }
 
@@ -42,5 +46,5 @@ fn fs_decal(in: VsOut) -> @location(0) vec4<f32> {
     if (abs(pixel_z - sampled) > max(dz, eps)) {
         discard;
     }
-    return vec4<f32>(rgb, r.alpha);
+    return vec4<f32>(quantize_5551(rgb, in.clip_position.xy), r.alpha);
 }
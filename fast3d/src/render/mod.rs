@@ -1,5 +1,6 @@
 //! wgpu pass-through renderer for the walking skeleton, extended with texture support.
 use bytemuck::{Pod, Zeroable};
+use std::borrow::Cow;
 
 /// The depth format the Z-buffer uses. `Depth32Float` is WebGL2-core (`DEPTH_COMPONENT32F`) and
 /// matches `D32_FLOAT`. Callers that own the depth texture must use this format.
@@ -74,8 +75,8 @@ fn lod_level_entries(view: &wgpu::TextureView) -> impl Iterator<Item = wgpu::Bin
 
 /// The combiner uniform passed to the shader.
 /// Carries raw combine words + cycle type + tex_enable flag + blender fields + prim/env/blend/fog colors.
-/// 160 bytes total; must be ≤ 256 (A8b slot stride). Field order matches `combiner_prelude.wgsl
-/// struct Combiner` (std140): 8 scalar u32/f32 fields (32 bytes), then seven vec4<f32> fields (112 bytes).
+/// 304 bytes total; must be ≤ `UNIFORM_STRIDE` (A8b slot stride). Field order matches `combiner_prelude.wgsl
+/// struct Combiner` (std140): 8 scalar u32/f32 fields (32 bytes), then twelve vec4<f32> fields (192 bytes).
 /// The blender fields (blender_mux/force_blend/alpha_mode/alpha_threshold) and color registers
 /// (blend_color/fog_color) drive the dual-source blender, fog mix, and alpha-test discard in the
 /// shaders (wired in B3/Phase C/D).
@@ -88,7 +89,7 @@ pub struct CombinerUniform {
     pub tex_enable: u32,       // 1 if texture is enabled, 0 otherwise
     pub blender_mux: u32,      // raw blender mux (other_mode_l bits [31:16])
     pub force_blend: u32,      // 1 if FORCE_BL is set, 0 otherwise
-    pub alpha_mode: u32,       // 0=off, 1=CVG_X_ALPHA, 2=THRESHOLD (Phase D)
+    pub alpha_mode: u32,       // 0=off, 1=CVG_X_ALPHA, 2=THRESHOLD (Phase D), 3=DITHER
     pub alpha_threshold: f32, // alpha discard threshold (Phase D: 0.125 for CVG_X_ALPHA, blend_color.a for THRESHOLD)
     pub prim: [f32; 4],       // primitive color RGBA, normalized 0..1
     pub env: [f32; 4],        // environment color RGBA, normalized 0..1
@@ -110,7 +111,9 @@ pub struct CombinerUniform {
     /// `.y` = num_levels (declared mip level count; the shader independently re-clamps this to the
     /// REAL uploaded mip count — see `compute_lod`'s caller in `combiner_prelude.wgsl`),
     /// `.z` = prim_lod_frac (the primitive LOD fraction = lodFrac/256; drives the PRIM_LOD_FRAC selector),
-    /// `.w` = lod_scale (resolution LOD scale; 1.0, native res).
+    /// `.w` = the internal resolution multiplier (1.0, native res; set by the draw sites from
+    /// `SceneRenderer::set_resolution_multiplier`): it scales the LOD back to native texel density
+    /// and maps each fragment to the native pixel whose dither and noise values it takes.
     /// Grows the struct by exactly one std140 row (128 -> 144). Must stay in LOCKSTEP with the WGSL
     /// `Combiner.lod_params`.
     pub lod_params: [f32; 4],
@@ -123,8 +126,73 @@ pub struct CombinerUniform {
     /// this `[1, 1, 0, 0]`, byte-identical to the prior tail. Grows the struct by exactly one
     /// std140 row (144 -> 160). Must stay in LOCKSTEP with the WGSL `Combiner.inv_detail_size`.
     pub inv_detail_size: [f32; 4],
+    /// Combiner KEY_CENTER / K4 inputs. `.rgb` = the G_SETKEYR/GB center per channel (/255), `.a` =
+    /// K4 (the 9-bit signed G_SETCONVERT coefficient, /255). Read by the WGSL color_b slot (idx 6 /
+    /// 7). Must stay in LOCKSTEP with the WGSL `Combiner.key_center`.
+    pub key_center: [f32; 4],
+    /// Combiner KEY_SCALE / K5 inputs. `.rgb` = the key scale per channel (/255), `.a` = K5 (/255).
+    /// Read by the WGSL color_c slot (idx 6 / 15). Must stay in LOCKSTEP with the WGSL
+    /// `Combiner.key_scale`.
+    pub key_scale: [f32; 4],
+    /// Chroma-key test. `.rgb` = the key width per channel / 255², so the shader's normalized
+    /// `|color - center| * scale` compares against it in the RDP's integer units; `.a` = 1.0 when
+    /// the material has G_CK_KEY set (0.0 disables the test). Must stay in LOCKSTEP with the WGSL
+    /// `Combiner.key_width`.
+    pub key_width: [f32; 4],
+    /// G_ZS_PRIM constant depth. `.x` = the G_SETPRIMDEPTH Z as a `[0, 1)` depth (15-bit screen Z
+    /// / 0x8000, the same scale the viewport maps vertex Z to), `.y` = 1.0 when the run's render
+    /// mode selects G_ZS_PRIM (the vertex stage then pins every vertex to `.x`), else 0.0. Must
+    /// stay in LOCKSTEP with the WGSL `Combiner.prim_depth`.
+    pub prim_depth: [f32; 4],
+    /// Per-run rasterizer state. `.x` = 1.0 for a flat-shaded run (G_SHADING_SMOOTH clear): the
+    /// combiner's SHADE inputs read the provoking vertex's color instead of the interpolated one;
+    /// only triangle runs set it. `.y` = the texture filter: 0.0 point, 1.0 G_TF_BILERP (TEXEL0/
+    /// TEXEL1 run the RDP 3-point filter over nearest-sampled taps), 2.0 G_TF_AVERAGE (bilinear).
+    /// `.z` = bound TEXEL0 texels per native texel: the resolution multiplier for an `fb_source`
+    /// TexRect, whose view is the prior pair at the internal size; 0.0 (treated as 1) otherwise.
+    /// `.w` = pad. Must stay in LOCKSTEP with the WGSL `Combiner.raster`.
+    pub raster: [f32; 4],
+    /// Tile addressing words from `tile_words`: `.xy` for TEXEL0's tile, `.zw` for TEXEL1's. A
+    /// texture whose word has `TILE_EMULATE` set is fetched with the shader's shift/mask/clamp
+    /// emulation; otherwise its bound sampler already addresses it exactly. Must stay in LOCKSTEP
+    /// with the WGSL `Combiner.tile`.
+    pub tile: [u32; 4],
+    /// The G_SETTILESIZE upper-left corner each triangle texcoord is measured from, in texels at
+    /// the hardware's 10.2 precision: `.xy` = TEXEL0's (uls, ult) / 4, `.zw` = TEXEL1's. The
+    /// shader subtracts it after the tile shift, so animating uls/ult scrolls the texture. Only
+    /// triangle runs set it (rects fold their own origin into `texrect_uv`). Must stay in
+    /// LOCKSTEP with the WGSL `Combiner.tile_origin`.
+    pub tile_origin: [f32; 4],
+    /// Dither state. `.x` = the othermode_H color dither (0 magic square, 1 Bayer, 2 noise,
+    /// 3 disabled), `.y` = the alpha dither (0 pattern, 1 inverted pattern, 2 noise, 3 disabled)
+    /// that `alpha_mode` 3 (G_AC_DITHER) thresholds against, `.z` = 1 when the output is
+    /// quantized to RGBA5551 (set by the draw sites from `SceneRenderer::set_quantize_5551` and
+    /// the color image size), `.w` = the per-frame NOISE seed (set by the draw sites from
+    /// `SceneRenderer`'s frame counter). Must stay in LOCKSTEP with the WGSL `Combiner.dither`.
+    pub dither: [u32; 4],
+    /// Coverage model state, read only by the framebuffer-read blender. `.x` = 1 when the
+    /// renderer emulates coverage (set by the paired draw sites from
+    /// `SceneRenderer::set_emulate_coverage`), `.y` = the `CvgDest` (0 clamp, 1 wrap, 2 full,
+    /// 3 save), `.z` = the coverage bits (1 AA_EN, 2 IM_RD, 4 CLR_ON_CVG, 8 ALPHA_CVG_SEL,
+    /// 16 CVG_X_ALPHA), `.w` = pad. Must stay in LOCKSTEP with the WGSL `Combiner.coverage`.
+    pub coverage: [u32; 4],
 }
-const _: () = assert!(std::mem::size_of::<CombinerUniform>() == 160);
+const _: () = assert!(std::mem::size_of::<CombinerUniform>() == 304);
+
+/// The `CombinerUniform.coverage.z` bits for a render mode.
+fn coverage_bits(rm: &crate::hle::RenderMode) -> u32 {
+    rm.aa_en as u32
+        | (rm.im_rd as u32) << 1
+        | (rm.clr_on_cvg as u32) << 2
+        | (rm.alpha_cvg_sel as u32) << 3
+        | (rm.cvg_x_alpha as u32) << 4
+}
+
+/// Byte stride between per-run `CombinerUniform` slots in the pooled uniform buffer: the
+/// struct rounded up to `min_uniform_buffer_offset_alignment` (256), so every slot's dynamic
+/// offset stays aligned.
+pub const UNIFORM_STRIDE: u32 = 512;
+const _: () = assert!(std::mem::size_of::<CombinerUniform>() <= UNIFORM_STRIDE as usize);
 
 impl CombinerUniform {
     /// Build a `CombinerUniform` from a material + run's render mode + per-frame fog color.
@@ -134,7 +202,8 @@ impl CombinerUniform {
     /// is threaded through from the caller (C3: scene.fog_color normalized).
     ///
     /// Phase D: `alpha_mode` is derived from `rm.cvg_x_alpha` (1 = CVG_X_ALPHA, threshold 0.125)
-    /// and `rm.alpha_compare` (2 = THRESHOLD, threshold = mat.blend_color[3] / 255.0).
+    /// and `rm.alpha_compare` (2 = THRESHOLD, threshold = mat.blend_color[3] / 255.0; 3 = DITHER,
+    /// a per-pixel threshold from `rm.alpha_dither`).
     pub fn from_run(
         mat: &crate::hle::Material,
         rm: &crate::hle::RenderMode,
@@ -161,11 +230,14 @@ impl CombinerUniform {
         // Phase D: derive alpha_mode and alpha_threshold from the render mode.
         // CVG_X_ALPHA (TEX_EDGE): threshold = 8/255 ≈ 0.125.
         // THRESHOLD: threshold = blend_color.a (gsDPSetBlendColor alpha channel).
+        // DITHER: the shader draws the threshold per pixel from the alpha dither pattern.
         // Neither set: alpha_mode = 0 → no discard.
         let (alpha_mode, alpha_threshold) = if rm.cvg_x_alpha {
             (1u32, 0.125f32)
         } else if rm.alpha_compare == crate::hle::AlphaCompare::Threshold {
             (2u32, mat.blend_color[3] as f32 / 255.0)
+        } else if rm.alpha_compare == crate::hle::AlphaCompare::Dither {
+            (3u32, 0.0f32)
         } else {
             (0u32, 0.0f32)
         };
@@ -200,9 +272,9 @@ impl CombinerUniform {
             // (`mat.lod`), else 0.0; num_levels is the REAL uploaded level count (clamped to MAX_LOD
             // the same way `build_tex_entry` clamps its uploads, so the shader never selects a level
             // past what was actually bound); prim_lod_frac from the material (the primitive LOD fraction);
-            // lod_scale = 1. A non-LOD material keeps `[0, 1, prim_lod_frac, 1]` — byte-identical to
-            // before (the shader gates all new consumption on `.x != 0`). No non-LOD golden sets
-            // `mat.lod`.
+            // resolution multiplier = 1 (the draw sites set the real one). A non-LOD material keeps
+            // `[0, 1, prim_lod_frac, 1]` — byte-identical to before (the shader gates all new
+            // consumption on `.x != 0`). No non-LOD golden sets `mat.lod`.
             lod_params: [
                 if mat.lod { 1.0 } else { 0.0 },
                 uploaded_level_count(mat.num_levels) as f32,
@@ -227,6 +299,39 @@ impl CombinerUniform {
                 let detail_bit = if mat.detail_tex.is_some() { 2.0 } else { 0.0 };
                 [dw, dh, mat.prim_min_level, sharpen_bit + detail_bit]
             },
+            key_center: [
+                mat.key_center[0] as f32 / 255.0,
+                mat.key_center[1] as f32 / 255.0,
+                mat.key_center[2] as f32 / 255.0,
+                mat.k4_k5[0] as f32 / 255.0,
+            ],
+            key_scale: [
+                mat.key_scale[0] as f32 / 255.0,
+                mat.key_scale[1] as f32 / 255.0,
+                mat.key_scale[2] as f32 / 255.0,
+                mat.k4_k5[1] as f32 / 255.0,
+            ],
+            key_width: [
+                mat.key_width[0] as f32 / (255.0 * 255.0),
+                mat.key_width[1] as f32 / (255.0 * 255.0),
+                mat.key_width[2] as f32 / (255.0 * 255.0),
+                if mat.chroma_key { 1.0 } else { 0.0 },
+            ],
+            prim_depth: prim_depth_row(rm),
+            raster: [
+                0.0,
+                match mat.tex_filter {
+                    crate::hle::TextureFilter::Point => 0.0,
+                    crate::hle::TextureFilter::Bilerp => 1.0,
+                    crate::hle::TextureFilter::Average => 2.0,
+                },
+                0.0,
+                0.0,
+            ],
+            tile: material_tile_row(mat),
+            tile_origin: [0.0; 4],
+            dither: [rm.color_dither as u32, rm.alpha_dither as u32, 0, 0],
+            coverage: [0, rm.cvg_dst as u32, coverage_bits(rm), 0],
         }
     }
 
@@ -299,6 +404,17 @@ impl CombinerUniform {
             lod_params: [0.0, 1.0, 0.0, 1.0],
             // No DETAIL tile on the fill path.
             inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+            // The flat-PRIM combine reads no key / K4 / K5 input.
+            key_center: [0.0; 4],
+            key_scale: [0.0; 4],
+            key_width: [0.0; 4],
+            prim_depth: [0.0; 4],
+            raster: [0.0; 4],
+            tile: [0; 4],
+            tile_origin: [0.0; 4],
+            // FILL/COPY bypass the dither; only the caller's quantize flag applies.
+            dither: [3, 3, 0, 0],
+            coverage: [0; 4],
         }
     }
 
@@ -328,7 +444,8 @@ impl CombinerUniform {
     /// When alpha-compare is NOT enabled (`rm == None`, or a render mode with no AC — e.g. the
     /// `offscreen-then-sample` opaque FB-as-texture scratch blit), `alpha_mode` stays 0: an opaque
     /// copy, byte-identical to before.
-    pub fn tex_copy(rm: Option<&crate::hle::RenderMode>, fmt: u8) -> Self {
+    pub fn tex_copy(rm: Option<&crate::hle::RenderMode>, mat: &crate::hle::Material) -> Self {
+        let fmt = mat.fmt;
         const CC_TEXEL0: u32 = 1; // G_CCMUX_TEXEL0 (color d slot, 3-bit)
         const AC_TEXEL0: u32 = 1; // G_ACMUX_TEXEL0 (alpha d slot, 3-bit)
         let combine_h = ((CC_TEXEL0 & 0x7) << 6) | (AC_TEXEL0 & 0x7);
@@ -361,6 +478,18 @@ impl CombinerUniform {
             lod_params: [0.0, 1.0, 0.0, 1.0],
             // No DETAIL tile on the COPY-mode TexRect path.
             inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+            // COPY mode bypasses the combiner — no key / K4 / K5 input.
+            key_center: [0.0; 4],
+            key_scale: [0.0; 4],
+            key_width: [0.0; 4],
+            prim_depth: [0.0; 4],
+            raster: [0.0; 4],
+            // The copy pipeline still addresses the tile through its shift/mask/clamp.
+            tile: material_tile_row(mat),
+            tile_origin: [0.0; 4],
+            // FILL/COPY bypass the dither; only the caller's quantize flag applies.
+            dither: [3, 3, 0, 0],
+            coverage: [0; 4],
         }
     }
 }
@@ -379,9 +508,16 @@ mod tests {
             prim: [0, 0, 0, 255],
             env: [0, 0, 0, 255],
             blend_color: [0, 0, 0, 255],
+            k4_k5: [0, 0],
+            key_center: [0; 3],
+            key_scale: [0; 3],
+            key_width: [0; 3],
+            chroma_key: false,
+            tex_filter: crate::hle::TextureFilter::Average,
             tex_enable: false,
             wrap_s: 2,
             wrap_t: 2,
+            tile_addr: crate::hle::TileAddress::whole(1, 1),
             fmt: 0,
             siz: 0,
             tile_count: 0,
@@ -405,7 +541,198 @@ mod tests {
         let u = CombinerUniform::from_run(&mat, &rm, [0.0; 4]);
         assert_eq!(u.blender_mux, rm.blender_mux as u32);
         assert_eq!(u.force_blend, 1);
-        assert!(std::mem::size_of::<CombinerUniform>() <= 256);
+        assert!(std::mem::size_of::<CombinerUniform>() <= UNIFORM_STRIDE as usize);
+    }
+
+    #[test]
+    fn combiner_uniform_from_run_carries_key_and_k4_k5() {
+        let mut mat = test_material();
+        mat.key_center = [255, 0, 51];
+        mat.key_scale = [0, 255, 102];
+        mat.k4_k5 = [114, -51];
+        let rm = crate::hle::decode_render_mode(0, 0, 0);
+        let u = CombinerUniform::from_run(&mat, &rm, [0.0; 4]);
+        assert_eq!(u.key_center, [1.0, 0.0, 0.2, 114.0 / 255.0]);
+        assert_eq!(u.key_scale, [0.0, 1.0, 0.4, -0.2]);
+    }
+
+    #[test]
+    fn combiner_uniform_prim_depth_row_only_under_zs_prim() {
+        let mat = test_material();
+        let mut rm = crate::hle::decode_render_mode(0, 0, 0);
+        rm.prim_depth = 0x4000;
+        assert_eq!(
+            CombinerUniform::from_run(&mat, &rm, [0.0; 4]).prim_depth,
+            [0.0; 4]
+        );
+        rm.z_source_prim = true;
+        let u = CombinerUniform::from_run(&mat, &rm, [0.0; 4]);
+        assert_eq!(u.prim_depth, [0.5, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn combiner_uniform_dither_compare_uses_alpha_mode_3() {
+        use crate::hle::consts::rdp::{AC_DITHER, G_AD_NOTPATTERN, G_CD_NOISE};
+        let mat = test_material();
+        let rm = crate::hle::decode_render_mode(AC_DITHER, G_CD_NOISE | G_AD_NOTPATTERN, 0);
+        let u = CombinerUniform::from_run(&mat, &rm, [0.0; 4]);
+        assert_eq!(u.alpha_mode, 3);
+        assert_eq!(u.dither, [2, 1, 0, 0]);
+        // The fill path never dithers.
+        assert_eq!(CombinerUniform::fill_rect(0, 2).dither, [3, 3, 0, 0]);
+    }
+
+    #[test]
+    fn triangle_flat_lane_flags_flat_runs() {
+        let mut run = crate::hle::DrawRun {
+            material_index: 0,
+            render_mode_index: 0,
+            cull: crate::hle::CullKind::None,
+            flat: false,
+            index_count: 3,
+            index_start: 0,
+        };
+        assert_eq!(triangle_flat_lane(&run), 0.0);
+        run.flat = true;
+        assert_eq!(triangle_flat_lane(&run), 1.0);
+    }
+
+    #[test]
+    fn triangle_indices_rotate_flat_runs_for_last_vertex_backends() {
+        let run = |flat, index_start| crate::hle::DrawRun {
+            material_index: 0,
+            render_mode_index: 0,
+            cull: crate::hle::CullKind::None,
+            flat,
+            index_count: 6,
+            index_start,
+        };
+        let scene = crate::hle::Scene {
+            indices: (0..12).collect(),
+            draw_runs: vec![run(true, 0), run(false, 6)],
+            ..Default::default()
+        };
+        assert_eq!(*triangle_indices(&scene, false), scene.indices[..]);
+        assert_eq!(
+            *triangle_indices(&scene, true),
+            [1, 2, 0, 4, 5, 3, 6, 7, 8, 9, 10, 11]
+        );
+    }
+
+    #[test]
+    fn sampler_wrap_is_exact_only_when_hardware_matches() {
+        // A 32-texel axis: mask 5 wraps/mirrors exactly where Repeat/MirrorRepeat do.
+        assert_eq!(sampler_wrap(0, 0, 5, 31 << 2, 32), Some(0));
+        assert_eq!(sampler_wrap(1, 0, 5, 31 << 2, 32), Some(1));
+        // Mask 0 wraps/mirrors at the texture size unless cm clamps; a clamp bound must be the
+        // last texel.
+        assert_eq!(sampler_wrap(0, 0, 0, 15 << 2, 32), Some(0));
+        assert_eq!(sampler_wrap(1, 0, 0, 31 << 2, 32), Some(1));
+        assert_eq!(sampler_wrap(2, 0, 0, 31 << 2, 32), Some(2));
+        assert_eq!(sampler_wrap(2, 0, 0, 15 << 2, 32), None);
+        // Clamp with a mask that never folds inside the bound is still ClampToEdge.
+        assert_eq!(sampler_wrap(3, 0, 5, 31 << 2, 32), Some(2));
+        assert_eq!(sampler_wrap(2, 0, 4, 31 << 2, 32), None);
+        // A mask narrower than the texture, or any shift, needs the shader.
+        assert_eq!(sampler_wrap(0, 0, 4, 31 << 2, 32), None);
+        assert_eq!(sampler_wrap(0, 1, 5, 31 << 2, 32), None);
+    }
+
+    #[test]
+    fn tile_words_pack_addressing_and_flag_emulation() {
+        let whole = crate::hle::TileAddress::whole(4, 4);
+        let [w0, w1] = tile_words([0, 1], &whole, [4, 4]);
+        assert_eq!(w0 & TILE_EMULATE, 0, "an exact sampler keeps the fast path");
+        assert_eq!((w0 >> 8) & 0xFF, 0x22);
+        assert_eq!((w0 >> 16) & 0xF, 0b0100);
+        assert_eq!(w1, 12 | 12 << 16);
+        let shifted = crate::hle::TileAddress {
+            shift: [15, 0],
+            ..whole
+        };
+        let [w0, _] = tile_words([0, 0], &shifted, [4, 4]);
+        assert_ne!(w0 & TILE_EMULATE, 0);
+        assert_eq!(w0 & 0xFF, 15);
+        assert_eq!(tile_sampler([0, 2], &shifted, [4, 4]), [0, 2]);
+    }
+
+    #[test]
+    fn bilerp_filter_sets_the_three_point_lane() {
+        let mut mat = test_material();
+        let rm = crate::hle::decode_render_mode(0, 0, 0);
+        mat.tex_filter = crate::hle::TextureFilter::Bilerp;
+        assert_eq!(
+            CombinerUniform::from_run(&mat, &rm, [0.0; 4]).raster[1],
+            1.0
+        );
+        assert_eq!(sampler_filter(mat.tex_filter), wgpu::FilterMode::Nearest);
+        mat.tex_filter = crate::hle::TextureFilter::Average;
+        assert_eq!(
+            CombinerUniform::from_run(&mat, &rm, [0.0; 4]).raster[1],
+            2.0
+        );
+        assert_eq!(sampler_filter(mat.tex_filter), wgpu::FilterMode::Linear);
+    }
+
+    #[test]
+    fn combiner_uniform_key_width_row_gates_on_chroma_key() {
+        let mut mat = test_material();
+        mat.key_width = [65025, 0, 0xFFF];
+        let rm = crate::hle::decode_render_mode(0, 0, 0);
+        let off = CombinerUniform::from_run(&mat, &rm, [0.0; 4]);
+        assert_eq!(off.key_width[3], 0.0, "G_CK_NONE disables the key test");
+        mat.chroma_key = true;
+        let on = CombinerUniform::from_run(&mat, &rm, [0.0; 4]);
+        assert_eq!(on.key_width[..2], [1.0, 0.0]);
+        assert_eq!(on.key_width[3], 1.0);
+    }
+
+    #[test]
+    fn clamp_scissor_clamps_natively_then_scales() {
+        let s = crate::hle::Scissor {
+            ulx: -4,
+            uly: 10,
+            lrx: 400,
+            lry: 20,
+            mode: 0,
+        };
+        assert_eq!(clamp_scissor(&s, 320, 240, 1), (0, 10, 320, 10));
+        assert_eq!(clamp_scissor(&s, 320, 240, 3), (0, 30, 960, 30));
+    }
+
+    #[test]
+    fn combiner_ubershader_parses_and_validates() {
+        // The base module exactly as `TexturedPipeline::build` assembles it, perspective and
+        // affine, single-sampled and at 4x (per-sample decal depth, sample-mask coverage).
+        for (affine, sample_count) in [(false, 1), (true, 1), (false, 4), (true, 4)] {
+            let src = format!(
+                "{}\n{}\n{}",
+                combiner_prelude(affine),
+                include_str!("skeleton.wgsl"),
+                decal_source(include_str!("decal.wgsl"), sample_count)
+            );
+            let module = wgpu::naga::front::wgsl::parse_str(&src).expect("ubershader must parse");
+            wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::all(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .expect("ubershader must validate");
+            // The framebuffer-read blender module: prelude + blender_fb.wgsl.
+            let src = format!(
+                "{}\n{}",
+                combiner_prelude(affine),
+                fb_blend_source(sample_count)
+            );
+            let module = wgpu::naga::front::wgsl::parse_str(&src).expect("fb blender must parse");
+            wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::all(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .expect("fb blender must validate");
+        }
+        assert!(combiner_prelude(true).contains("@interpolate(linear) uv"));
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -485,6 +812,136 @@ fn rect_quad(
     [tl, bl, br, tl, br, tr]
 }
 
+/// The `CombinerUniform.prim_depth` row for a run: G_ZS_PRIM pins the whole primitive to the
+/// G_SETPRIMDEPTH Z (15-bit screen Z → `[0, 1)` depth); any other Z source leaves it zeroed.
+fn prim_depth_row(rm: &crate::hle::RenderMode) -> [f32; 4] {
+    if rm.z_source_prim {
+        [rm.prim_depth as f32 / 32768.0, 1.0, 0.0, 0.0]
+    } else {
+        [0.0; 4]
+    }
+}
+
+/// The `CombinerUniform.raster.x` lane for a TRIANGLE run: flags flat shading, whose provoking
+/// vertex `draw_tri` already rotated to the front of every triangle (see `triangle_indices`).
+pub fn triangle_flat_lane(run: &crate::hle::DrawRun) -> f32 {
+    if run.flat {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// The index buffer contents for `scene`. `draw_tri` leads every flat triangle with its provoking
+/// vertex, which is where WebGPU takes `@interpolate(flat)` varyings from; OpenGL takes them from
+/// the last vertex instead, so with `last_vertex_flat` each flat run's triangles rotate left by
+/// one (same winding, provoking vertex last).
+pub fn triangle_indices(scene: &crate::hle::Scene, last_vertex_flat: bool) -> Cow<'_, [u32]> {
+    if !last_vertex_flat {
+        return Cow::Borrowed(&scene.indices);
+    }
+    let mut indices = scene.indices.clone();
+    let pair_runs = scene.framebuffer_pairs.iter().flat_map(|pair| {
+        pair.ops.iter().filter_map(|op| match op {
+            crate::hle::SceneOp::Tris(run) => Some(run),
+            _ => None,
+        })
+    });
+    for run in scene
+        .draw_runs
+        .iter()
+        .chain(pair_runs)
+        .filter(|run| run.flat)
+    {
+        let start = run.index_start as usize;
+        for tri in indices[start..start + run.index_count as usize]
+            .as_chunks_mut::<3>()
+            .0
+        {
+            tri.rotate_left(1);
+        }
+    }
+    Cow::Owned(indices)
+}
+
+/// `tile_words` flag: the texture's addressing differs from every hardware sampler mode, so the
+/// shader fetches it through the shift/mask/clamp emulation. Mirrored in `combiner_prelude.wgsl`.
+pub const TILE_EMULATE: u32 = 1 << 31;
+
+/// The sampler wrap index (0=Repeat, 1=MirrorRepeat, 2=ClampToEdge) that reproduces one tile
+/// axis's hardware addressing exactly over an `n`-texel texture, or `None` when no sampler does.
+///
+/// The RDP shifts the texcoord, clamps it to `[0, clamp >> 2]` when `cm` has the clamp bit, then
+/// wraps it (mirroring on odd periods under the mirror bit) every `1 << mask` texels. Mask 0 on an
+/// unclamped axis wraps at the texture's own size, as the sampler always did before the tile was
+/// emulated. A sampler matches only an unshifted axis whose clamp bound is the texture's last texel
+/// and whose mask either folds nothing inside that bound or wraps at exactly `n`.
+pub fn sampler_wrap(cm: u8, shift: u8, mask: u8, clamp: u16, n: u32) -> Option<u8> {
+    if shift != 0 {
+        return None;
+    }
+    if cm & 2 != 0 {
+        let last = (clamp >> 2) as u32;
+        let mask_inert = mask == 0 || last < 1 << mask.min(10);
+        return (last + 1 == n && mask_inert).then_some(2);
+    }
+    (mask == 0 || 1u32 << mask.min(10) == n).then_some(cm & 1)
+}
+
+/// The sampler-pool index `[s][t]` for a texture sampled through `wrap`/`addr` at `dims`: the
+/// exact hardware mode when `sampler_wrap` finds one, else the raw cms/cmt (the shader emulates
+/// the addressing then, and the sampler only serves as the bound fallback).
+pub fn tile_sampler(wrap: [u8; 2], addr: &crate::hle::TileAddress, dims: [u32; 2]) -> [usize; 2] {
+    std::array::from_fn(|i| {
+        sampler_wrap(wrap[i], addr.shift[i], addr.mask[i], addr.clamp[i], dims[i])
+            .unwrap_or(wrap[i])
+            .min(2) as usize
+    })
+}
+
+/// Pack one texture's addressing into the two `CombinerUniform.tile` words. Word 0: `shift`
+/// bits [3:0]/[7:4], `mask` [11:8]/[15:12], cm [17:16]/[19:18] (S then T), plus `TILE_EMULATE`
+/// when `sampler_wrap` finds no exact sampler on either axis. Word 1: the 10.2 clamp bounds,
+/// S in [15:0] and T in [31:16].
+pub fn tile_words(wrap: [u8; 2], addr: &crate::hle::TileAddress, dims: [u32; 2]) -> [u32; 2] {
+    let exact = (0..2).all(|i| {
+        sampler_wrap(wrap[i], addr.shift[i], addr.mask[i], addr.clamp[i], dims[i]).is_some()
+    });
+    let mut w0 = if exact { 0 } else { TILE_EMULATE };
+    for (i, &cm) in wrap.iter().enumerate() {
+        w0 |= (addr.shift[i] as u32 & 0xF) << (4 * i);
+        w0 |= (addr.mask[i] as u32 & 0xF) << (8 + 4 * i);
+        w0 |= (cm as u32 & 3) << (16 + 2 * i);
+    }
+    [w0, addr.clamp[0] as u32 | (addr.clamp[1] as u32) << 16]
+}
+
+/// The `CombinerUniform.tile` row for `mat`: TEXEL0's words, then TEXEL1's (zero without one).
+fn material_tile_row(mat: &crate::hle::Material) -> [u32; 4] {
+    let [a, b] = tile_words(
+        [mat.wrap_s, mat.wrap_t],
+        &mat.tile_addr,
+        [mat.tex_w, mat.tex_h],
+    );
+    let [c, d] = match &mat.tex1 {
+        Some(t) => tile_words([t.wrap_s, t.wrap_t], &t.tile_addr, [t.tex_w, t.tex_h]),
+        None => [0; 2],
+    };
+    [a, b, c, d]
+}
+
+/// Sampler filter for a material's `TextureFilter`. G_TF_POINT and G_TF_BILERP both sample
+/// NEAREST — the 3-point filter blends nearest-sampled taps in the shader (`raster.y`) — and
+/// G_TF_AVERAGE approximates the RDP box filter with the sampler's bilinear filter.
+pub fn sampler_filter(filter: crate::hle::TextureFilter) -> wgpu::FilterMode {
+    match filter {
+        crate::hle::TextureFilter::Average => wgpu::FilterMode::Linear,
+        crate::hle::TextureFilter::Point | crate::hle::TextureFilter::Bilerp => {
+            wgpu::FilterMode::Nearest
+        }
+    }
+}
+
 /// Draw-time tile-size normalization for a TRIANGLE run's texcoord (`CombinerUniform.inv_tex_size`).
 ///
 /// The RSP emits TEXEL-space triangle texcoords (no tile division), so the fragment shader must
@@ -497,13 +954,7 @@ pub fn triangle_inv_tex_size(
     mat: &crate::hle::Material,
     run: &crate::hle::DrawRun,
 ) -> [f32; 4] {
-    let first = scene
-        .indices
-        .get(run.index_start as usize)
-        .copied()
-        .unwrap_or(0) as usize;
-    let texgen = scene.texgen_mode.get(first).copied().unwrap_or(0) != 0;
-    if mat.tex_enable && !texgen {
+    if mat.tex_enable && !run_is_texgen(scene, run) {
         [
             1.0 / mat.tex_w.max(1) as f32,
             1.0 / mat.tex_h.max(1) as f32,
@@ -515,6 +966,35 @@ pub fn triangle_inv_tex_size(
     }
 }
 
+/// Draw-time tile origin for a TRIANGLE run's texcoord (`CombinerUniform.tile_origin`): each
+/// texture's G_SETTILESIZE (uls, ult) as recorded with the triangle, in texels (10.2 / 4, exact in
+/// `f32`). The texcoord table stays tile-independent, so it still dedupes across tile changes and
+/// a scrolling tile costs no new texcoords. Zero for untextured or texgen runs, like
+/// `triangle_inv_tex_size`.
+pub fn triangle_tile_origin(
+    scene: &crate::hle::Scene,
+    mat: &crate::hle::Material,
+    run: &crate::hle::DrawRun,
+) -> [f32; 4] {
+    if !mat.tex_enable || run_is_texgen(scene, run) {
+        return [0.0; 4];
+    }
+    let origin = |a: &crate::hle::TileAddress| a.origin.map(|c| c as f32 / 4.0);
+    let [s0, t0] = origin(&mat.tile_addr);
+    let [s1, t1] = mat.tex1.as_ref().map_or([0.0; 2], |t| origin(&t.tile_addr));
+    [s0, t0, s1, t1]
+}
+
+/// Whether `run`'s triangles were texgen-mapped (their texcoords are already normalized).
+fn run_is_texgen(scene: &crate::hle::Scene, run: &crate::hle::DrawRun) -> bool {
+    let first = scene
+        .indices
+        .get(run.index_start as usize)
+        .copied()
+        .unwrap_or(0) as usize;
+    scene.texgen_mode.get(first).copied().unwrap_or(0) != 0
+}
+
 /// Compute the four normalized-UV corners (TL, TR, BR, BL) for a `TexRect`.
 ///
 /// The texel coordinate at pixel offset `o` from the rect's upper-left is `uls/32 + o·dsdx/1024`
@@ -560,14 +1040,25 @@ fn texrect_uv(
 }
 
 /// Clamp an N64 `Scissor` (pixel coords, possibly negative or larger than the FB) to a
-/// `(x, y, w, h)` rect that fits inside `fb_w × fb_h`. wgpu's `set_scissor_rect` PANICS if
+/// `(x, y, w, h)` rect that fits inside `fb_w × fb_h`, then scale it by the resolution multiplier
+/// `scale` into the internal framebuffer's pixels. wgpu's `set_scissor_rect` PANICS if
 /// `x + w > attachment_width` (or the Y analog), so every field is saturated into range first.
-fn clamp_scissor(s: &crate::hle::Scissor, fb_w: u32, fb_h: u32) -> (u32, u32, u32, u32) {
+fn clamp_scissor(
+    s: &crate::hle::Scissor,
+    fb_w: u32,
+    fb_h: u32,
+    scale: u32,
+) -> (u32, u32, u32, u32) {
     let x = (s.ulx.max(0) as u32).min(fb_w);
     let y = (s.uly.max(0) as u32).min(fb_h);
     let right = (s.lrx.max(0) as u32).min(fb_w);
     let bottom = (s.lry.max(0) as u32).min(fb_h);
-    (x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    (
+        x * scale,
+        y * scale,
+        right.saturating_sub(x) * scale,
+        bottom.saturating_sub(y) * scale,
+    )
 }
 
 /// The textured rendering pipeline with split bind groups:
@@ -576,6 +1067,8 @@ fn clamp_scissor(s: &crate::hle::Scissor, fb_w: u32, fb_h: u32) -> (u32, u32, u3
 ///
 /// Each depth×cull combination has TWO pipeline variants — Replace and AlphaOver — selected
 /// at draw time by `run.render_mode.fallback_class` (B3: fixed-function fallback path).
+/// The whole matrix exists twice, perspective-correct and affine (`for_texcoords`), keyed on the
+/// run's G_MDSFT_TEXTPERSP bit.
 pub struct TexturedPipeline {
     // ── Replace blend (src_factor=One, dst_factor=Zero) ──────────────────────────────────────
     pipeline_no_depth_nocull: wgpu::RenderPipeline,
@@ -602,16 +1095,26 @@ pub struct TexturedPipeline {
     /// device with `DUAL_SOURCE_BLENDING` enabled — `None` on the fallback device, where DualSrc
     /// runs take the AlphaOver fallback path instead. Selected for runs whose `blend_class` is
     /// `DualSrc` (B4 primary path).
-    dual: Option<DualSrcSet>,
+    dual: Option<DepthCullSet>,
     /// Decal pipelines, built for the distinct decal pipeline layout `(group0, group1, group2)`.
     /// Used for ZMODE_DEC runs in the SECOND (decal) render pass, which has NO depth attachment
     /// (so only no-depth variants exist) and binds the prior pass's depth as a sampled texture at
     /// `@group(2)` (E1). group0/group1 BGLs are byte-identical to the non-decal layout's, so
     /// group0/group1 bindings survive `set_pipeline` across the two passes.
     decal: DecalSet,
+    /// Framebuffer-read blender pipelines (`blender_fb.wgsl`, layout `g0+g1+g2`) for runs whose
+    /// `RenderMode::fb_blend` is set, or that use coverage while it is emulated. Blending is off;
+    /// the shader reads the pre-draw copy of the color image bound at `@group(2)` and evaluates the
+    /// full two-cycle blender.
+    fb_blend: DepthCullSet,
     group0_bgl: wgpu::BindGroupLayout,
     group1_bgl: wgpu::BindGroupLayout,
     group2_depth_bgl: wgpu::BindGroupLayout,
+    group2_fb_bgl: wgpu::BindGroupLayout,
+    /// The G_TP_NONE twin: the same pipeline matrix over the affine-texcoord shader modules
+    /// (`combiner_prelude(true)`), sharing this set's bind group layouts. Selected per run by
+    /// `for_texcoords`; `None` on the twin itself.
+    affine: Option<Box<TexturedPipeline>>,
 }
 
 /// The no-depth decal pipelines (decal layout `g0+g1+g2`). The decal pass carries no depth
@@ -626,9 +1129,10 @@ struct DecalSet {
     dual: Option<(wgpu::RenderPipeline, wgpu::RenderPipeline)>,
 }
 
-/// The eight depth×cull dual-source primary blender pipelines (mirrors the Replace/AlphaOver
-/// matrices). Built only on a `DUAL_SOURCE_BLENDING` device; see `TexturedPipeline::dual`.
-struct DualSrcSet {
+/// The eight depth×cull variants of one blend path (mirrors the Replace/AlphaOver matrices): the
+/// dual-source primary blender (`TexturedPipeline::dual`, built only on a `DUAL_SOURCE_BLENDING`
+/// device) and the framebuffer-read blender (`TexturedPipeline::fb_blend`).
+struct DepthCullSet {
     no_depth_nocull: wgpu::RenderPipeline,
     no_depth_cull: wgpu::RenderPipeline,
     depth_compat_nocull: wgpu::RenderPipeline,
@@ -639,9 +1143,9 @@ struct DualSrcSet {
     depth_test_nowrite_cull: wgpu::RenderPipeline,
 }
 
-impl DualSrcSet {
-    /// Pick the dual-source pipeline variant for a run's cull/depth state (same matrix as
-    /// `TexturedPipeline::select`, but always the dual-source blend).
+impl DepthCullSet {
+    /// Pick the variant for a run's cull/depth state (same matrix as `TexturedPipeline::select`,
+    /// but always this set's blend).
     fn select(
         &self,
         cull: crate::hle::CullKind,
@@ -672,15 +1176,116 @@ impl DualSrcSet {
     }
 }
 
+/// The combiner prelude (structs, bindings, helpers, eval_combiner) shared by the base ubershader
+/// and the dual-source blender; each fragment entry is concatenated after it. `affine` builds the
+/// G_TP_NONE variant: the texcoord varying is `@interpolate(linear)` (WGSL's noperspective), so it
+/// steps linearly in screen space and textures warp across a triangle like the RDP's with
+/// perspective correction off.
+fn combiner_prelude(affine: bool) -> String {
+    const PRELUDE: &str = include_str!("combiner_prelude.wgsl");
+    const UV: &str = "@location(1) uv:    vec2<f32>,";
+    if affine {
+        debug_assert!(PRELUDE.contains(UV), "VsOut.uv declaration moved");
+        PRELUDE.replace(UV, "@location(1) @interpolate(linear) uv: vec2<f32>,")
+    } else {
+        PRELUDE.to_owned()
+    }
+}
+
+/// A decal entry (decal.wgsl or decal_dual.wgsl) for a pipeline matrix of `sample_count` samples.
+/// Multisampled, the stored depth is a `texture_depth_multisampled_2d` — a depth buffer cannot be
+/// resolved — and `fs_decal` takes `@builtin(sample_index)`, so it shades per sample and tests each
+/// sample against its own stored depth.
+fn decal_source(src: &str, sample_count: u32) -> String {
+    const DEPTH: &str = ": texture_depth_2d;";
+    const ENTRY: &str = "fn fs_decal(in: VsOut)";
+    const LOAD: &str = ", coord, 0);";
+    if sample_count > 1 {
+        debug_assert!(
+            src.contains(DEPTH) && src.contains(ENTRY) && src.contains(LOAD),
+            "decal depth binding, entry or load moved"
+        );
+        src.replace(DEPTH, ": texture_depth_multisampled_2d;")
+            .replace(
+                ENTRY,
+                "fn fs_decal(in: VsOut, @builtin(sample_index) sample: u32)",
+            )
+            .replace(LOAD, ", coord, i32(sample));")
+    } else {
+        src.to_owned()
+    }
+}
+
+/// The framebuffer-read blender entry (blender_fb.wgsl) for a pipeline matrix of `sample_count`
+/// samples. Multisampled, a pixel's geometric coverage is the share of its samples in
+/// `@builtin(sample_mask)`, in eighths; single-sampled (and so on WebGL, which lacks the builtin)
+/// every pixel is fully covered.
+fn fb_blend_source(sample_count: u32) -> String {
+    const SRC: &str = include_str!("blender_fb.wgsl");
+    const ENTRY: &str = "fn fs_blend_fb(in: VsOut)";
+    const CVG: &str = "var cvg = 8u;";
+    if sample_count > 1 {
+        debug_assert!(
+            SRC.contains(ENTRY) && SRC.contains(CVG),
+            "fb blender entry or coverage moved"
+        );
+        SRC.replace(
+            ENTRY,
+            "fn fs_blend_fb(in: VsOut, @builtin(sample_mask) mask: u32)",
+        )
+        .replace(
+            CVG,
+            &format!("var cvg = countOneBits(mask) * 8u / {sample_count}u;"),
+        )
+    } else {
+        SRC.to_owned()
+    }
+}
+
 impl TexturedPipeline {
     pub fn new(
         device: &wgpu::Device,
         target_format: wgpu::TextureFormat,
         depth_format: wgpu::TextureFormat,
     ) -> Self {
-        // The combiner prelude (structs, bindings, helpers, eval_combiner) is shared by the base
-        // ubershader and the dual-source blender; each fragment entry is concatenated after it.
-        const COMBINER_PRELUDE: &str = include_str!("combiner_prelude.wgsl");
+        Self::multisampled(device, target_format, depth_format, 1)
+    }
+
+    /// `new` for targets of `sample_count` samples (MSAA). Every pipeline is built at that count,
+    /// and when it is above 1 the decal `@group(2)` depth is multisampled (see `decal_source`).
+    pub fn multisampled(
+        device: &wgpu::Device,
+        target_format: wgpu::TextureFormat,
+        depth_format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let mut persp = Self::build(device, target_format, depth_format, sample_count, None);
+        let affine = Self::build(
+            device,
+            target_format,
+            depth_format,
+            sample_count,
+            Some(&persp),
+        );
+        persp.affine = Some(Box::new(affine));
+        persp
+    }
+
+    /// Build one pipeline matrix. `base` is `None` for the perspective-correct set, which creates
+    /// the bind group layouts; the affine twin passes that set to reuse them and switch its
+    /// modules to `combiner_prelude(true)`.
+    fn build(
+        device: &wgpu::Device,
+        target_format: wgpu::TextureFormat,
+        depth_format: wgpu::TextureFormat,
+        sample_count: u32,
+        base: Option<&TexturedPipeline>,
+    ) -> Self {
+        let prelude = combiner_prelude(base.is_some());
+        let multisample = wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        };
         // The base module carries skeleton's non-decal `fs_main` AND decal.wgsl's `fs_decal`
         // (E2: combiner + in-shader Z occlusion/coplanar). decal.wgsl declares `@group(2)` depth,
         // which `fs_main` never references — so non-decal pipelines (layout g0+g1) stay valid while
@@ -690,9 +1295,9 @@ impl TexturedPipeline {
             source: wgpu::ShaderSource::Wgsl(
                 format!(
                     "{}\n{}\n{}",
-                    COMBINER_PRELUDE,
+                    prelude,
                     include_str!("skeleton.wgsl"),
-                    include_str!("decal.wgsl")
+                    decal_source(include_str!("decal.wgsl"), sample_count)
                 )
                 .into(),
             ),
@@ -781,17 +1386,21 @@ impl TexturedPipeline {
             },
             count: None,
         }));
-        let group0_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("textured-group0-bgl"),
-            entries: &group0_entries,
-        });
+        let group0_bgl = match base {
+            Some(b) => b.group0_bgl.clone(),
+            None => device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("textured-group0-bgl"),
+                entries: &group0_entries,
+            }),
+        };
 
         // group1_bgl: combiner uniform (@group(1)), dynamic offset for A8b per-run stride.
-        let group1_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        // VERTEX too: `vs_main` reads `prim_depth` to pin G_ZS_PRIM runs to the constant depth.
+        let group1_desc = wgpu::BindGroupLayoutDescriptor {
             label: Some("textured-group1-bgl"),
             entries: &[wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::FRAGMENT,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: true,
@@ -801,12 +1410,17 @@ impl TexturedPipeline {
                 },
                 count: None,
             }],
-        });
+        };
+        let group1_bgl = match base {
+            Some(b) => b.group1_bgl.clone(),
+            None => device.create_bind_group_layout(&group1_desc),
+        };
 
-        // group2_depth_bgl: the scene depth buffer bound as a SAMPLED `texture_depth_2d` (E1).
-        // sample_type Depth, no sampler entry (depth is read via `textureLoad` in E2). Used only by
-        // the decal pipeline layout; the decal pass binds the prior depth pass's output here.
-        let group2_depth_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        // group2_depth_bgl: the scene depth buffer bound as a SAMPLED `texture_depth_2d` (E1), or
+        // `texture_depth_multisampled_2d` under MSAA. sample_type Depth, no sampler entry (depth is
+        // read via `textureLoad` in E2). Used only by the decal pipeline layout; the decal pass
+        // binds the prior depth pass's output here.
+        let group2_desc = wgpu::BindGroupLayoutDescriptor {
             label: Some("textured-group2-depth-bgl"),
             entries: &[wgpu::BindGroupLayoutEntry {
                 binding: 0,
@@ -814,11 +1428,34 @@ impl TexturedPipeline {
                 ty: wgpu::BindingType::Texture {
                     sample_type: wgpu::TextureSampleType::Depth,
                     view_dimension: wgpu::TextureViewDimension::D2,
-                    multisampled: false,
+                    multisampled: sample_count > 1,
                 },
                 count: None,
             }],
-        });
+        };
+        let group2_depth_bgl = match base {
+            Some(b) => b.group2_depth_bgl.clone(),
+            None => device.create_bind_group_layout(&group2_desc),
+        };
+
+        // group2_fb_bgl: the pre-draw copy of the color image (`fb_dst`), read with `textureLoad`
+        // by the framebuffer-read blender. Used only by `fb_blend_layout`.
+        let group2_fb_bgl = match base {
+            Some(b) => b.group2_fb_bgl.clone(),
+            None => device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("textured-group2-fb-bgl"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            }),
+        };
 
         let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("skeleton-layout"),
@@ -837,6 +1474,11 @@ impl TexturedPipeline {
             ],
             immediate_size: 0,
         });
+        let fb_blend_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("fb-blender-layout"),
+            bind_group_layouts: &[Some(&group0_bgl), Some(&group1_bgl), Some(&group2_fb_bgl)],
+            immediate_size: 0,
+        });
         let make = |label: &str,
                     depth_stencil: Option<wgpu::DepthStencilState>,
                     cull_mode: Option<wgpu::Face>,
@@ -869,7 +1511,7 @@ impl TexturedPipeline {
                     ..Default::default()
                 },
                 depth_stencil,
-                multisample: wgpu::MultisampleState::default(),
+                multisample,
                 multiview_mask: None,
                 cache: None,
             })
@@ -1000,7 +1642,7 @@ impl TexturedPipeline {
                     ..Default::default()
                 },
                 depth_stencil: None,
-                multisample: wgpu::MultisampleState::default(),
+                multisample,
                 multiview_mask: None,
                 cache: None,
             })
@@ -1019,9 +1661,9 @@ impl TexturedPipeline {
                 source: wgpu::ShaderSource::Wgsl(
                     format!(
                         "enable dual_source_blending;\n{}\n{}\n{}",
-                        COMBINER_PRELUDE,
+                        prelude,
                         include_str!("blender_dualsrc.wgsl"),
-                        include_str!("decal_dual.wgsl")
+                        decal_source(include_str!("decal_dual.wgsl"), sample_count)
                     )
                     .into(),
                 ),
@@ -1069,7 +1711,7 @@ impl TexturedPipeline {
                         ..Default::default()
                     },
                     depth_stencil,
-                    multisample: wgpu::MultisampleState::default(),
+                    multisample,
                     multiview_mask: None,
                     cache: None,
                 })
@@ -1102,7 +1744,7 @@ impl TexturedPipeline {
                         ..Default::default()
                     },
                     depth_stencil: None,
-                    multisample: wgpu::MultisampleState::default(),
+                    multisample,
                     multiview_mask: None,
                     cache: None,
                 })
@@ -1111,7 +1753,7 @@ impl TexturedPipeline {
                 make_dual_decal("tp-ds-decal-nodepth-nocull", None),
                 make_dual_decal("tp-ds-decal-nodepth-cull", back),
             );
-            let dualset = DualSrcSet {
+            let dualset = DepthCullSet {
                 no_depth_nocull: make_dual("tp-ds-nodepth-nocull", None, None),
                 no_depth_cull: make_dual("tp-ds-nodepth-cull", None, back),
                 depth_compat_nocull: make_dual(
@@ -1139,7 +1781,64 @@ impl TexturedPipeline {
             };
             (dualset, decal_dual)
         });
-        // Split the optional (DualSrcSet, decal-dual-pair) into the `dual` field + the decal set's
+        // ── Framebuffer-read blender pipelines (fb_blend_layout g0+g1+g2) ─────────────────────
+        // Their own module: `fb_dst` takes `@group(2) @binding(0)`, which the base module's decal
+        // entry already declares as the sampled depth.
+        let fb_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("fb-blender"),
+            source: wgpu::ShaderSource::Wgsl(
+                format!("{}\n{}", prelude, fb_blend_source(sample_count)).into(),
+            ),
+        });
+        let make_fb = |label: &str,
+                       depth_stencil: Option<wgpu::DepthStencilState>,
+                       cull_mode: Option<wgpu::Face>| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&fb_blend_layout),
+                vertex: wgpu::VertexState {
+                    module: &fb_shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[OutVertex::layout()],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &fb_shader,
+                    entry_point: Some("fs_blend_fb"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: target_format,
+                        blend: Some(replace),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode,
+                    ..Default::default()
+                },
+                depth_stencil,
+                multisample,
+                multiview_mask: None,
+                cache: None,
+            })
+        };
+        let fb_blend = DepthCullSet {
+            no_depth_nocull: make_fb("tp-fb-nodepth-nocull", None, None),
+            no_depth_cull: make_fb("tp-fb-nodepth-cull", None, back),
+            depth_compat_nocull: make_fb("tp-fb-depth-compat-nocull", Some(ds_compat()), None),
+            depth_compat_cull: make_fb("tp-fb-depth-compat-cull", Some(ds_compat()), back),
+            depth_test_write_nocull: make_fb("tp-fb-depth-write-nocull", Some(ds_write()), None),
+            depth_test_write_cull: make_fb("tp-fb-depth-write-cull", Some(ds_write()), back),
+            depth_test_nowrite_nocull: make_fb(
+                "tp-fb-depth-nowrite-nocull",
+                Some(ds_nowrite()),
+                None,
+            ),
+            depth_test_nowrite_cull: make_fb("tp-fb-depth-nowrite-cull", Some(ds_nowrite()), back),
+        };
+        // Split the optional (DepthCullSet, decal-dual-pair) into the `dual` field + the decal set's
         // dual variants (both `Some` together, on a DUAL_SOURCE_BLENDING device, or both `None`).
         let (dual, decal_dual) = match dual {
             Some((ds, dd)) => (Some(ds), Some(dd)),
@@ -1171,9 +1870,21 @@ impl TexturedPipeline {
             pipeline_ao_depth_test_nowrite_cull,
             dual,
             decal,
+            fb_blend,
             group0_bgl,
             group1_bgl,
             group2_depth_bgl,
+            group2_fb_bgl,
+            affine: None,
+        }
+    }
+
+    /// The pipeline set whose texcoord interpolation matches `rm`'s G_MDSFT_TEXTPERSP bit: this
+    /// (perspective-correct) set for G_TP_PERSP, the affine twin for G_TP_NONE.
+    fn for_texcoords(&self, rm: &crate::hle::RenderMode) -> &TexturedPipeline {
+        match &self.affine {
+            Some(affine) if !rm.tex_persp => affine,
+            _ => self,
         }
     }
 
@@ -1188,12 +1899,17 @@ impl TexturedPipeline {
         &self.group1_bgl
     }
 
-    /// `@group(2)` layout: the scene depth buffer as a sampled `texture_depth_2d` (E1). Used by the
-    /// decal pass to build the bind group that exposes the prior depth pass's output to decal runs.
+    /// `@group(2)` layout: the scene depth buffer as a sampled `texture_depth_2d` (E1; multisampled
+    /// in a `multisampled` matrix). Used by the decal pass to build the bind group that exposes the prior depth pass's output to decal runs.
     pub fn depth_bind_group_layout(&self) -> &wgpu::BindGroupLayout {
         &self.group2_depth_bgl
     }
 
+    /// `@group(2)` layout of the framebuffer-read blender: the pre-draw copy of the color image.
+    pub fn fb_bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.group2_fb_bgl
+    }
+
     /// Pick the decal pipeline (decal layout, no depth attachment) for a run's cull/blend state.
     /// Mirrors the no-depth slice of `select`: dual-source primary on a capable device for DualSrc
     /// runs, else the Replace/AlphaOver fallback keyed on `fallback_class`.
@@ -1354,11 +2070,12 @@ impl TexturedPipeline {
             // B4: on a dual-source device, DualSrc runs take the primary blender path; all other
             // runs (and every run on the fallback device) take B3's Replace/AlphaOver fallback by
             // fallback_class. Replace-class runs always use the fallback Replace pipeline.
-            let pipeline = match &self.dual {
+            let tp = self.for_texcoords(rm);
+            let pipeline = match &tp.dual {
                 Some(ds) if rm.blend_class == crate::hle::BlendClass::DualSrc => {
                     ds.select(run.cull, z_test, z_write, any_depth)
                 }
-                _ => self.select(run.cull, z_test, z_write, any_depth, rm.fallback_class),
+                _ => tp.select(run.cull, z_test, z_write, any_depth, rm.fallback_class),
             };
             pass.set_pipeline(pipeline);
             pass.set_bind_group(0, material_bind_groups[run.material_index as usize], &[]);
@@ -1430,11 +2147,12 @@ impl TexturedPipeline {
                 if rm.z_mode != crate::hle::ZMode::Decal {
                     let z_test = any_depth && rm.z_test;
                     let z_write = any_depth && rm.z_write;
-                    let pipeline = match &self.dual {
+                    let tp = self.for_texcoords(rm);
+                    let pipeline = match &tp.dual {
                         Some(ds) if rm.blend_class == crate::hle::BlendClass::DualSrc => {
                             ds.select(run.cull, z_test, z_write, any_depth)
                         }
-                        _ => self.select(run.cull, z_test, z_write, any_depth, rm.fallback_class),
+                        _ => tp.select(run.cull, z_test, z_write, any_depth, rm.fallback_class),
                     };
                     pass.set_pipeline(pipeline);
                     pass.set_bind_group(0, material_bind_groups[run.material_index as usize], &[]);
@@ -1470,7 +2188,11 @@ impl TexturedPipeline {
             for (i, run) in scene.draw_runs.iter().enumerate() {
                 let rm = &scene.render_modes[run.render_mode_index as usize];
                 if rm.z_mode == crate::hle::ZMode::Decal {
-                    let pipeline = self.select_decal(run.cull, rm.fallback_class, rm.blend_class);
+                    let pipeline = self.for_texcoords(rm).select_decal(
+                        run.cull,
+                        rm.fallback_class,
+                        rm.blend_class,
+                    );
                     pass.set_pipeline(pipeline);
                     pass.set_bind_group(0, material_bind_groups[run.material_index as usize], &[]);
                     pass.set_bind_group(1, uniform_bind_group, &[(i as u32) * uniform_stride]);
@@ -1487,12 +2209,14 @@ pub struct RspProcessPipeline {
     bind_group_layout: wgpu::BindGroupLayout,
 }
 
-/// Parameters uniform for the RSP-process compute kernel (binding 0, 16 bytes).
+/// Parameters uniform for the RSP-process compute kernel (binding 0, 32 bytes).
 /// WGSL `struct Params` must mirror this exactly:
-///   `{ vertex_count: u32, fog_enable: u32, fog_mul: f32, fog_offset: f32 }`.
+///   `{ vertex_count: u32, fog_enable: u32, fog_mul: f32, fog_offset: f32, screen: vec4<f32> }`.
 /// fog_enable=0 → kernel skips the fog-factor path; o.color.a comes from cn as before.
 /// fog_enable≠0 → kernel writes `clamp((max(clip.z,0)/clip.w)*fog_mul+fog_offset,0,255)/255`
 /// into o.color.a (raw clip-Z, NOT viewport-folded o.pos.z).
+/// `screen.xy` is the native screen the viewport folds into (`hle::rsp::FB_WIDTH`/`FB_HEIGHT`);
+/// `.zw` pad. The fold lands in NDC, so it is the same at every resolution multiplier.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct RspProcessParams {
@@ -1500,8 +2224,17 @@ pub struct RspProcessParams {
     pub fog_enable: u32,
     pub fog_mul: f32,
     pub fog_offset: f32,
+    pub screen: [f32; 4],
 }
-const _: () = assert!(std::mem::size_of::<RspProcessParams>() == 16);
+const _: () = assert!(std::mem::size_of::<RspProcessParams>() == 32);
+
+/// `RspProcessParams.screen` for the native N64 screen.
+pub const RSP_SCREEN: [f32; 4] = [
+    crate::hle::rsp::FB_WIDTH,
+    crate::hle::rsp::FB_HEIGHT,
+    0.0,
+    0.0,
+];
 
 impl RspProcessPipeline {
     pub fn new(device: &wgpu::Device) -> Self {
@@ -1602,6 +2335,10 @@ struct TexCache {
     bytes: Vec<u8>,
     wrap_s: u8,
     wrap_t: u8,
+    /// The material's tile addressing, part of the cache key because it picks the bound sampler.
+    tile_addr: crate::hle::TileAddress,
+    /// The material's texture filter, part of the cache key because it picks the bound sampler.
+    tex_filter: crate::hle::TextureFilter,
     /// The material's second texture (TEXEL1), part of the cache key so a change to the second
     /// tile's content/dims/wrap rebuilds the bind group. `None` for single-texture materials — the
     /// bind group then points binding 2/3 at the shared dummy.
@@ -1620,15 +2357,26 @@ struct TexCache {
 ///
 /// Standalone (not a `SceneRenderer` method) so callers can hold immutable borrows of
 /// `self.textured` and `self.samplers` while mutably updating `self.tex_caches`, without
-/// triggering a split-borrow conflict.
+/// triggering a split-borrow conflict. TEXEL0/TEXEL1 sample from `point_samplers` or the linear
+/// `samplers` pool per `sampler_filter(mat.tex_filter)`.
 fn build_tex_entry(
     device: &wgpu::Device,
     queue: &wgpu::Queue,
     bgl: &wgpu::BindGroupLayout,
     samplers: &[[wgpu::Sampler; 3]; 3],
+    point_samplers: &[[wgpu::Sampler; 3]; 3],
     dummy_view: &wgpu::TextureView,
     mat: &crate::hle::Material,
 ) -> TexCache {
+    let pool = match sampler_filter(mat.tex_filter) {
+        wgpu::FilterMode::Nearest => point_samplers,
+        wgpu::FilterMode::Linear => samplers,
+    };
+    let samp0 = tile_sampler(
+        [mat.wrap_s, mat.wrap_t],
+        &mat.tile_addr,
+        [mat.tex_w, mat.tex_h],
+    );
     // Upload one decoded RGBA8 texture (tex0, or the tex1 second texture) to a fresh GPU texture,
     // returning its view. The view keeps the texture alive via the bind group's strong ref.
     let upload = |label: &str, w: u32, h: u32, bytes: &[u8]| -> wgpu::TextureView {
@@ -1691,7 +2439,10 @@ fn build_tex_entry(
         None => dummy_view.clone(),
     };
     let samp1 = match &mat.tex1 {
-        Some(t) => &samplers[(t.wrap_s as usize).min(2)][(t.wrap_t as usize).min(2)],
+        Some(t) => {
+            let [ws, wt] = tile_sampler([t.wrap_s, t.wrap_t], &t.tile_addr, [t.tex_w, t.tex_h]);
+            &pool[ws][wt]
+        }
         None => &samplers[2][2],
     };
     // Binding 4/5: the DETAIL tile when present (LOD DETAIL mode), else the shared 1×1 dummy.
@@ -1709,9 +2460,7 @@ fn build_tex_entry(
         },
         wgpu::BindGroupEntry {
             binding: 1,
-            resource: wgpu::BindingResource::Sampler(
-                &samplers[(mat.wrap_s as usize).min(2)][(mat.wrap_t as usize).min(2)],
-            ),
+            resource: wgpu::BindingResource::Sampler(&pool[samp0[0]][samp0[1]]),
         },
         wgpu::BindGroupEntry {
             binding: 2,
@@ -1752,6 +2501,8 @@ fn build_tex_entry(
         bytes: mat.texture.clone(),
         wrap_s: mat.wrap_s,
         wrap_t: mat.wrap_t,
+        tile_addr: mat.tile_addr,
+        tex_filter: mat.tex_filter,
         tex1: mat.tex1.clone(),
         mip_levels: mat.mip_levels.clone(),
         detail_tex: mat.detail_tex.clone(),
@@ -1760,16 +2511,49 @@ fn build_tex_entry(
 }
 
 /// One persisted N64 color framebuffer (D2 store). Depth is TRANSIENT (created per pair inside
-/// render_into_store, never stored / never scanned out). `attach` = write view; `present_bg` is a
-/// prebuilt @group(0) blit bind group so `scanout` needs no device handle (F1). `sampled` (used by
-/// S2's paired path) feeds cross-pair `fb_source` reads.
+/// render_into_store, never stored / never scanned out). `views.attach` = write view; `present_bg`
+/// is a prebuilt @group(0) blit bind group so `scanout` needs no device handle (F1).
+/// `views.sampled` (used by S2's paired path) feeds cross-pair `fb_source` reads. Under MSAA the
+/// multisampled `views.msaa` persists alongside `color`, so a later `LoadOp::Load` keeps its samples.
 struct Framebuffer {
     color: wgpu::Texture,        // Rgba8Unorm | RENDER_ATTACHMENT | TEXTURE_BINDING
-    attach: wgpu::TextureView,   // color attachment for the RCP pass
-    sampled: wgpu::TextureView,  // sampled view (S2 fb_pool + present_bg source)
+    views: FbViews,              // attach/sampled over `color` (+ the MSAA target)
     present_bg: wgpu::BindGroup, // @group(0): sampled(color) + Clamp/Linear sampler, for `scanout`
 }
 
+/// A color image's views as the RCP passes use them. `attach` is the single-sampled color image;
+/// under MSAA every pass draws into `msaa` instead and resolves into `attach` as it ends, so
+/// `sampled` — the `fb_source`, fb-blend snapshot and scanout source — always reads the resolved
+/// image.
+#[derive(Clone)]
+struct FbViews {
+    attach: wgpu::TextureView,
+    msaa: Option<wgpu::TextureView>,
+    sampled: wgpu::TextureView,
+}
+
+impl FbViews {
+    /// The color attachment of a pass into this image: `msaa` resolving into `attach`, or `attach`.
+    fn color_attachment(
+        &self,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPassColorAttachment<'_> {
+        let (view, resolve_target) = match &self.msaa {
+            Some(msaa) => (msaa, Some(&self.attach)),
+            None => (&self.attach, None),
+        };
+        wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        }
+    }
+}
+
 /// Per-frame GPU wiring for drawing one `crate::hle::Scene` to a target view — the reusable facade lifted
 /// verbatim from the web shell's `Renderer::init` + `Renderer::render` GPU sections. It owns the
 /// pipelines, the surface-sized depth buffer, a pool of samplers per wrap-mode combination, and the
@@ -1794,9 +2578,14 @@ pub struct SceneRenderer {
     /// A sampled (`texture_depth_2d`) view over the SAME depth texture, bound at `@group(2)` in the
     /// decal pass so decal runs can read the depth pass 1 wrote (E1 wiring; E2 reads it).
     depth_sample_view: wgpu::TextureView,
+    /// The pair-less path's multisampled color target, sized like `depth_view` and rebuilt with it
+    /// (`None` when MSAA is off).
+    fb_msaa_view: Option<wgpu::TextureView>,
     samplers: [[wgpu::Sampler; 3]; 3],
+    /// The same wrap-mode pool with NEAREST filtering, for G_TF_POINT and G_TF_BILERP materials.
+    point_samplers: [[wgpu::Sampler; 3]; 3],
     /// Content-keyed GPU texture + `@group(0)` bind group — one per `scene.materials[i]`.
-    /// Rebuilt only when a material's texture bytes, dims, or wrap mode change.
+    /// Rebuilt only when a material's texture bytes, dims, wrap mode, or filter change.
     tex_caches: Vec<TexCache>,
     /// A 1×1 white `@group(0)` (tex + sampler) bind group used as the texture binding for
     /// `FillRect` draws (which carry no material, but the pipeline layout still requires group 0).
@@ -1819,6 +2608,28 @@ pub struct SceneRenderer {
     /// touched (cleared-or-loaded) THIS frame, so `ClearPolicy::PerFrame` clears exactly once per
     /// frame per addr. Reset by `begin_frame`. Never dropped/rebuilt otherwise.
     first_touch: std::collections::HashSet<u64>,
+    /// Quantize 16-bit color images to RGBA5551 with the RDP's color dither
+    /// (`RendererConfig::quantize_5551`). Off by default.
+    quantize_5551: bool,
+    /// Emulate RDP coverage in framebuffer pairs (`RendererConfig::emulate_coverage`): runs that
+    /// use coverage draw through the framebuffer-read blender, which keeps each pixel's coverage
+    /// in the color image's alpha channel. Off by default.
+    emulate_coverage: bool,
+    /// Seed for the combiner NOISE input (`CombinerUniform.dither.w`), advanced by `begin_frame`
+    /// so the noise changes every frame.
+    noise_seed: u32,
+    /// MSAA sample count of the internal framebuffers, depth buffers and `textured_fb` pipelines
+    /// (`RendererConfig::sample_count`; 1 = off).
+    sample_count: u32,
+    /// Integer scale of the internal framebuffers and depth buffers over their native size
+    /// (`RendererConfig::resolution_multiplier`; 1 = native). Scissors scale with it; viewports and
+    /// rects are resolution-independent (NDC / normalized UVs). An `fb_source` read binds the
+    /// prior pair at the multiplied size, so its slot passes the multiplier in `raster.z` and the
+    /// shader addresses it in native texels.
+    resolution_multiplier: u32,
+    /// The backend takes flat varyings from a triangle's last vertex (OpenGL / WebGL), so the
+    /// index buffer goes through `triangle_indices` rotated.
+    last_vertex_flat: bool,
 }
 
 impl SceneRenderer {
@@ -1830,6 +2641,7 @@ impl SceneRenderer {
         device: &wgpu::Device,
         w: u32,
         h: u32,
+        sample_count: u32,
     ) -> (wgpu::TextureView, wgpu::TextureView) {
         let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("n64-depth"),
@@ -1839,7 +2651,7 @@ impl SceneRenderer {
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: 1,
+            sample_count,
             dimension: wgpu::TextureDimension::D2,
             format: DEPTH_FORMAT,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
@@ -1910,22 +2722,26 @@ impl SceneRenderer {
             })
         };
         let rsp = RspProcessPipeline::new(device);
-        let (depth_view, depth_sample_view) = Self::make_depth_view(device, w, h);
-        // Build a 3×3 sampler pool indexed [cms][cmt] (0=WRAP, 1=MIRROR, 2=CLAMP).
-        let samplers: [[wgpu::Sampler; 3]; 3] = std::array::from_fn(|s| {
-            std::array::from_fn(|t| {
-                device.create_sampler(&wgpu::SamplerDescriptor {
-                    label: Some("n64-sampler"),
-                    address_mode_u: address_mode(s as u8),
-                    address_mode_v: address_mode(t as u8),
-                    address_mode_w: wgpu::AddressMode::ClampToEdge,
-                    mag_filter: wgpu::FilterMode::Linear,
-                    min_filter: wgpu::FilterMode::Linear,
-                    mipmap_filter: wgpu::MipmapFilterMode::Nearest,
-                    ..Default::default()
+        let (depth_view, depth_sample_view) = Self::make_depth_view(device, w, h, 1);
+        // Build 3×3 sampler pools indexed [cms][cmt] (0=WRAP, 1=MIRROR, 2=CLAMP), one per filter.
+        let sampler_pool = |filter: wgpu::FilterMode| -> [[wgpu::Sampler; 3]; 3] {
+            std::array::from_fn(|s| {
+                std::array::from_fn(|t| {
+                    device.create_sampler(&wgpu::SamplerDescriptor {
+                        label: Some("n64-sampler"),
+                        address_mode_u: address_mode(s as u8),
+                        address_mode_v: address_mode(t as u8),
+                        address_mode_w: wgpu::AddressMode::ClampToEdge,
+                        mag_filter: filter,
+                        min_filter: filter,
+                        mipmap_filter: wgpu::MipmapFilterMode::Nearest,
+                        ..Default::default()
+                    })
                 })
             })
-        });
+        };
+        let samplers = sampler_pool(wgpu::FilterMode::Linear);
+        let point_samplers = sampler_pool(wgpu::FilterMode::Nearest);
         // Shared 1×1 dummy texture: bound at the TEXEL1 slot (binding 2) of every group(0) bind
         // group that has no real second texture. Also reused as the FillRect texture binding
         // (binding 0) below — the fill combine has `tex_enable = 0`, so it is never sampled.
@@ -1989,7 +2805,9 @@ impl SceneRenderer {
             rsp,
             depth_view,
             depth_sample_view,
+            fb_msaa_view: None,
             samplers,
+            point_samplers,
             tex_caches: Vec::new(),
             fill_bind_group,
             dummy_view,
@@ -1997,15 +2815,125 @@ impl SceneRenderer {
             fb_h: h,
             framebuffers: std::collections::HashMap::new(),
             first_touch: std::collections::HashSet::new(),
+            quantize_5551: false,
+            emulate_coverage: false,
+            noise_seed: 0,
+            sample_count: 1,
+            resolution_multiplier: 1,
+            last_vertex_flat: device.adapter_info().backend == wgpu::Backend::Gl,
         }
     }
 
-    /// Recreate the depth buffer at a new `(w, h)` (the consumer calls this on surface resize).
-    #[cfg_attr(not(all(test, feature = "asm")), allow(dead_code))]
+    /// Turn RGBA5551 output on or off. When on, draws into a 16-bit color image (and the pair-less
+    /// path's internal FB) round their color to 5 bits per channel through the run's color dither.
+    pub fn set_quantize_5551(&mut self, on: bool) {
+        self.quantize_5551 = on;
+    }
+
+    /// Emulate RDP coverage (`RendererConfig::emulate_coverage`): in framebuffer pairs, runs
+    /// whose render mode uses coverage (`RenderMode::uses_coverage`) draw through the
+    /// framebuffer-read blender, which derives each pixel's coverage, feeds it into the alpha
+    /// compare and the AA blend, and stores it in the color image's alpha channel per CVG_DST.
+    pub fn set_emulate_coverage(&mut self, on: bool) {
+        self.emulate_coverage = on;
+    }
+
+    /// Whether a paired run draws through the framebuffer-read blender: its blend needs it, or
+    /// coverage is emulated and the run uses it.
+    fn draws_fb_blend(&self, rm: &crate::hle::RenderMode) -> bool {
+        rm.fb_blend || (self.emulate_coverage && rm.uses_coverage())
+    }
+
+    /// Switch MSAA to `sample_count` samples (`RendererConfig::sample_count`; 1 = off). Rebuilds
+    /// the `textured_fb` pipelines, the depth buffer and the pair-less multisampled color target
+    /// at the new count and drops the framebuffer store, whose multisampled targets no longer
+    /// match. Every pass then draws into a multisampled target that resolves into the color image
+    /// before anything reads it — scanout, `fb_source` and the framebuffer-read blender all see
+    /// resolved pixels. The caller picks a count the device supports for `Rgba8Unorm` and
+    /// `Depth32Float` (WebGPU guarantees 1 and 4).
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        let sample_count = sample_count.max(1);
+        if sample_count == self.sample_count {
+            return;
+        }
+        self.textured_fb = TexturedPipeline::multisampled(
+            device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            DEPTH_FORMAT,
+            sample_count,
+        );
+        self.sample_count = sample_count;
+        self.resize(device, self.fb_w, self.fb_h);
+        self.framebuffers.clear();
+    }
+
+    /// Render the internal framebuffers at `multiplier` times their native size. It is clamped to
+    /// at least 1, and to what keeps a 640-pixel (hi-res) color image — or the pair-less
+    /// framebuffer, when larger — within the device's texture size limit. A change rebuilds the
+    /// depth buffer and drops the framebuffer store, whose images no longer match. Scissors scale
+    /// with the framebuffers; 2D rects stay on native pixel boundaries, each native pixel covering
+    /// a `multiplier`-sized block.
+    pub fn set_resolution_multiplier(&mut self, device: &wgpu::Device, multiplier: u32) {
+        let widest = self.fb_w.max(self.fb_h).max(640);
+        let max = (device.limits().max_texture_dimension_2d / widest).max(1);
+        let multiplier = multiplier.clamp(1, max);
+        if multiplier == self.resolution_multiplier {
+            return;
+        }
+        self.resolution_multiplier = multiplier;
+        self.resize(device, self.fb_w, self.fb_h);
+        self.framebuffers.clear();
+    }
+
+    /// The multisampled color target for a color image of `size` (`None` when MSAA is off).
+    fn msaa_view(&self, device: &wgpu::Device, size: wgpu::Extent3d) -> Option<wgpu::TextureView> {
+        (self.sample_count > 1).then(|| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("fb-msaa-color"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: self.sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        })
+    }
+
+    /// Resolve `views.msaa` into `views.attach` with an empty pass (no-op without MSAA). The
+    /// pair-less path draws through `TexturedPipeline::draw`, whose passes have no resolve target.
+    fn resolve(&self, encoder: &mut wgpu::CommandEncoder, views: &FbViews) {
+        if views.msaa.is_some() {
+            let _ = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("msaa-resolve-pass"),
+                color_attachments: &[Some(views.color_attachment(wgpu::LoadOp::Load))],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+        }
+    }
+
+    /// Recreate the depth buffer (and the pair-less multisampled color target) at a new `(w, h)`
+    /// times the resolution multiplier (the consumer calls this on surface resize).
     pub fn resize(&mut self, device: &wgpu::Device, w: u32, h: u32) {
-        let (depth_view, depth_sample_view) = Self::make_depth_view(device, w, h);
+        let m = self.resolution_multiplier;
+        let (depth_view, depth_sample_view) =
+            Self::make_depth_view(device, w * m, h * m, self.sample_count);
         self.depth_view = depth_view;
         self.depth_sample_view = depth_sample_view;
+        self.fb_msaa_view = self.msaa_view(
+            device,
+            wgpu::Extent3d {
+                width: w * m,
+                height: h * m,
+                depth_or_array_layers: 1,
+            },
+        );
         self.fb_w = w;
         self.fb_h = h;
     }
@@ -2050,7 +2978,7 @@ impl SceneRenderer {
         &self,
         pass: &mut wgpu::RenderPass<'_>,
         device: &wgpu::Device,
-        fb_pool: &std::collections::HashMap<u64, (wgpu::TextureView, wgpu::TextureView)>,
+        fb_pool: &std::collections::HashMap<u64, FbViews>,
         pair: &crate::hle::FramebufferPair,
         pair_idx: usize,
         op: &crate::hle::SceneOp,
@@ -2080,7 +3008,10 @@ impl SceneRenderer {
                 "fb_source {:#x} must be in the pool — consumer pair {pair_idx} references a non-existent prior pair",
                 src_addr
             );
-            let (_, sampled) = fb_pool.get(src_addr).expect("asserted key exists above");
+            let sampled = &fb_pool
+                .get(src_addr)
+                .expect("asserted key exists above")
+                .sampled;
             Some(
                 device.create_bind_group(&wgpu::BindGroupDescriptor {
                     label: Some("fb-source-bg"),
@@ -2143,10 +3074,24 @@ impl SceneRenderer {
             }
             _ => crate::hle::BlendClass::Replace,
         };
+        // A rect has no per-pixel Z, so it normally never z-tests (2D overlay semantics). Under
+        // G_ZS_PRIM its quad sits at the constant prim depth, so it tests/writes per its render
+        // mode — that is how sprites get occluded by (and occlude) geometry.
+        let (z_test, z_write) = match op {
+            crate::hle::SceneOp::TexRect {
+                render_mode_index,
+                copy_mode: false,
+                ..
+            } if any_depth => match scene.render_modes.get(*render_mode_index as usize) {
+                Some(rm) if rm.z_source_prim => (rm.z_test, rm.z_write),
+                _ => (false, false),
+            },
+            _ => (false, false),
+        };
         let pipeline = self.textured_fb.select(
             crate::hle::CullKind::None,
-            false,
-            false,
+            z_test,
+            z_write,
             any_depth,
             blend_class,
         );
@@ -2156,16 +3101,21 @@ impl SceneRenderer {
         }
         pass.set_bind_group(0, group0, &[]);
         if let Some(ubg) = uniform_bg {
-            pass.set_bind_group(1, ubg, &[slot * 256]);
+            pass.set_bind_group(1, ubg, &[slot * UNIFORM_STRIDE]);
         }
         pass.draw((rect_idx * 6)..(rect_idx * 6 + 6), 0..1);
     }
 
-    /// Get-or-create the store framebuffer for `addr` at `(w, h)`. Recreates the texture, its views,
-    /// and the prebuilt present bind group only on size change; otherwise reuses it so a later
-    /// `LoadOp::Load` sees last frame's pixels (N64 persistence). Returns `created_or_resized` (S2's
-    /// ClearPolicy::Persist first-touch decision consumes it; S1 ignores it).
+    /// Get-or-create the store framebuffer for `addr` at native `(w, h)` (its image is that times
+    /// the resolution multiplier). Recreates the texture, its views, and the prebuilt present bind
+    /// group only on size change; otherwise reuses it so a later `LoadOp::Load` sees last frame's
+    /// pixels (N64 persistence). Returns `created_or_resized` (S2's ClearPolicy::Persist
+    /// first-touch decision consumes it; S1 ignores it).
     fn ensure_fb(&mut self, device: &wgpu::Device, addr: u64, w: u32, h: u32) -> bool {
+        let (w, h) = (
+            w * self.resolution_multiplier,
+            h * self.resolution_multiplier,
+        );
         let need = match self.framebuffers.get(&addr) {
             Some(fb) => fb.color.width() != w || fb.color.height() != h,
             None => true,
@@ -2184,11 +3134,14 @@ impl SceneRenderer {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
         let attach = color.create_view(&wgpu::TextureViewDescriptor::default());
         let sampled = color.create_view(&wgpu::TextureViewDescriptor::default());
+        let msaa = self.msaa_view(device, color.size());
         let present_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("fb-store-present-bg"),
             layout: self.textured.bind_group_layout(),
@@ -2229,8 +3182,11 @@ impl SceneRenderer {
             addr,
             Framebuffer {
                 color,
-                attach,
-                sampled,
+                views: FbViews {
+                    attach,
+                    msaa,
+                    sampled,
+                },
                 present_bg,
             },
         );
@@ -2259,10 +3215,12 @@ impl SceneRenderer {
         self.blit_to(encoder, target, &fb.present_bg);
     }
 
-    /// Explicit frame boundary (D2): reset the per-frame first-touch-clear set. Does NOT drop the
-    /// textures (cross-frame persistence). `Renderer::begin_frame` delegates here.
+    /// Explicit frame boundary (D2): reset the per-frame first-touch-clear set and advance the NOISE
+    /// seed. Does NOT drop the textures (cross-frame persistence). `Renderer::begin_frame`
+    /// delegates here.
     pub fn begin_frame(&mut self) {
         self.first_touch.clear();
+        self.noise_seed = self.noise_seed.wrapping_add(1);
     }
 
     /// The color LoadOp for a store FB this frame under `clear_policy`. Mutates the per-frame
@@ -2275,7 +3233,8 @@ impl SceneRenderer {
     ) -> wgpu::LoadOp<wgpu::Color> {
         let first_this_frame = self.first_touch.insert(addr);
         let clear = match clear_policy {
-            crate::ClearPolicy::PerFrame => first_this_frame,
+            // A texture created or resized mid-frame holds nothing to keep.
+            crate::ClearPolicy::PerFrame => first_this_frame || created_or_resized,
             crate::ClearPolicy::Persist => created_or_resized,
         };
         if clear {
@@ -2318,6 +3277,8 @@ impl SceneRenderer {
                         || c.bytes != mat.texture
                         || c.wrap_s != mat.wrap_s
                         || c.wrap_t != mat.wrap_t
+                        || c.tile_addr != mat.tile_addr
+                        || c.tex_filter != mat.tex_filter
                         || c.tex1 != mat.tex1
                         || c.mip_levels != mat.mip_levels
                         || c.detail_tex != mat.detail_tex
@@ -2329,6 +3290,7 @@ impl SceneRenderer {
                     queue,
                     self.textured.bind_group_layout(),
                     &self.samplers,
+                    &self.point_samplers,
                     &self.dummy_view,
                     &scene.materials[i],
                 );
@@ -2410,6 +3372,7 @@ impl SceneRenderer {
                     fog_enable: u32::from(scene.fog_enable),
                     fog_mul: scene.fog_mul as f32,
                     fog_offset: scene.fog_offset as f32,
+                    screen: RSP_SCREEN,
                 }),
                 usage: wgpu::BufferUsages::UNIFORM,
             });
@@ -2455,7 +3418,7 @@ impl SceneRenderer {
 
             let ibuf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("ibuf"),
-                contents: bytemuck::cast_slice(&scene.indices),
+                contents: bytemuck::cast_slice(&triangle_indices(scene, self.last_vertex_flat)),
                 usage: wgpu::BufferUsages::INDEX,
             });
             (Some(dst), Some(ibuf))
@@ -2467,13 +3430,13 @@ impl SceneRenderer {
             let dst = dst.as_ref().expect("pair-less scene has triangles");
             let ibuf = ibuf.as_ref().expect("pair-less scene has triangles");
 
-            // --- Pooled uniform buffer: N_runs × 256 bytes (256 = min_uniform_buffer_offset_alignment).
-            // Each run's CombinerUniform occupies bytes [i*256 .. i*256+48]; the rest of each slot
-            // is zero-padded. The @group(1) bind group uses a BufferBinding with explicit size
+            // --- Pooled uniform buffer: N_runs × UNIFORM_STRIDE bytes (a multiple of the 256-byte
+            // min_uniform_buffer_offset_alignment). Each run's CombinerUniform opens its slot; the
+            // rest of each slot is zero-padded. The @group(1) bind group uses a BufferBinding with explicit size
             // (not as_entire_binding) to stay within WebGL2's 16 KiB max_uniform_buffer_binding_size
-            // even for large run counts [MIN11]. Each draw call's dynamic offset = i * 256.
+            // even for large run counts [MIN11]. Each draw call's dynamic offset = i * UNIFORM_STRIDE.
             let n_runs = scene.draw_runs.len();
-            let mut pool = vec![0u8; n_runs * 256];
+            let mut pool = vec![0u8; n_runs * UNIFORM_STRIDE as usize];
             for (i, run) in scene.draw_runs.iter().enumerate() {
                 let mat = &scene.materials[run.material_index as usize];
                 let rm = &scene.render_modes[run.render_mode_index as usize];
@@ -2487,8 +3450,14 @@ impl SceneRenderer {
                 ];
                 let mut combiner = CombinerUniform::from_run(mat, rm, fog_color);
                 combiner.inv_tex_size = triangle_inv_tex_size(scene, mat, run);
+                combiner.raster[0] = triangle_flat_lane(run);
+                combiner.tile_origin = triangle_tile_origin(scene, mat, run);
+                combiner.dither[2] = self.quantize_5551 as u32;
+                combiner.dither[3] = self.noise_seed;
+                combiner.lod_params[3] = self.resolution_multiplier as f32;
                 let slot = bytemuck::bytes_of(&combiner);
-                pool[i * 256..i * 256 + slot.len()].copy_from_slice(slot);
+                pool[i * UNIFORM_STRIDE as usize..i * UNIFORM_STRIDE as usize + slot.len()]
+                    .copy_from_slice(slot);
             }
             let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("combiner-uniform-pool"),
@@ -2520,7 +3489,9 @@ impl SceneRenderer {
             // Render into the STORE attach view (not a frame-local fb_tex). NB: draw/draw_with_decals
             // hard-code LoadOp::Clear → the pair-less FB always clears (ClearPolicy honored on the
             // paired path only — S2/F2). `clear_policy` is consumed by the paired path (S2).
-            let attach = &self.framebuffers[&scene.color_image.addr].attach;
+            // Under MSAA they draw into the multisampled target, resolved below.
+            let views = &self.framebuffers[&scene.color_image.addr].views;
+            let attach = views.msaa.as_ref().unwrap_or(&views.attach);
             if has_decal {
                 let depth_sample_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
                     label: Some("store-decal-depth-sample-bg"),
@@ -2539,7 +3510,7 @@ impl SceneRenderer {
                     CLEAR_COLOR,
                     &material_bgs,
                     &uniform_bg,
-                    256,
+                    UNIFORM_STRIDE,
                     &self.depth_view,
                     &depth_sample_bg,
                 );
@@ -2553,10 +3524,11 @@ impl SceneRenderer {
                     CLEAR_COLOR,
                     &material_bgs,
                     &uniform_bg,
-                    256,
+                    UNIFORM_STRIDE,
                     depth,
                 );
             }
+            self.resolve(&mut encoder, views);
             Some(scene.color_image.addr)
         } else {
             self.render_pairs_into_store(
@@ -2634,6 +3606,8 @@ impl SceneRenderer {
                         || c.bytes != mat.texture
                         || c.wrap_s != mat.wrap_s
                         || c.wrap_t != mat.wrap_t
+                        || c.tile_addr != mat.tile_addr
+                        || c.tex_filter != mat.tex_filter
                         || c.tex1 != mat.tex1
                         || c.mip_levels != mat.mip_levels
                         || c.detail_tex != mat.detail_tex
@@ -2645,6 +3619,7 @@ impl SceneRenderer {
                     queue,
                     self.textured.bind_group_layout(),
                     &self.samplers,
+                    &self.point_samplers,
                     &self.dummy_view,
                     &scene.materials[i],
                 );
@@ -2697,6 +3672,7 @@ impl SceneRenderer {
                     fog_enable: u32::from(scene.fog_enable),
                     fog_mul: scene.fog_mul as f32,
                     fog_offset: scene.fog_offset as f32,
+                    screen: RSP_SCREEN,
                 }),
                 usage: wgpu::BufferUsages::UNIFORM,
             });
@@ -2742,7 +3718,7 @@ impl SceneRenderer {
 
             let ibuf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("ibuf"),
-                contents: bytemuck::cast_slice(&scene.indices),
+                contents: bytemuck::cast_slice(&triangle_indices(scene, self.last_vertex_flat)),
                 usage: wgpu::BufferUsages::INDEX,
             });
             (Some(dst), Some(ibuf))
@@ -2756,23 +3732,24 @@ impl SceneRenderer {
             // (spec §4): `present` needs an internal FB to scan out. The FB is frame-local here
             // (created, drawn, blitted, dropped); Phase 3 promotes it into the persistent
             // address-keyed store `SceneRenderer::framebuffers[addr]`. It is sized to the surface
-            // (fb_w/fb_h), matching the owned depth buffer, and blitted (1:1 or scaled) to `target`.
+            // (fb_w/fb_h) times the resolution multiplier, matching the owned depth buffer, and
+            // blitted (1:1 or scaled) to `target`.
             // The empty-scene guard above guarantees `has_tris` here, so `dst`/`ibuf` are `Some`.
             let dst = dst.as_ref().expect("pair-less scene has triangles");
             let ibuf = ibuf.as_ref().expect("pair-less scene has triangles");
 
-            // --- Pooled uniform buffer: N_runs × 256 bytes (256 = min_uniform_buffer_offset_alignment).
-            // Each run's CombinerUniform occupies bytes [i*256 .. i*256+48]; the rest of each slot
-            // is zero-padded. The @group(1) bind group uses a BufferBinding with explicit size
+            // --- Pooled uniform buffer: N_runs × UNIFORM_STRIDE bytes (a multiple of the 256-byte
+            // min_uniform_buffer_offset_alignment). Each run's CombinerUniform opens its slot; the
+            // rest of each slot is zero-padded. The @group(1) bind group uses a BufferBinding with explicit size
             // (not as_entire_binding) to stay within WebGL2's 16 KiB max_uniform_buffer_binding_size
-            // even for large run counts [MIN11]. Each draw call's dynamic offset = i * 256.
+            // even for large run counts [MIN11]. Each draw call's dynamic offset = i * UNIFORM_STRIDE.
             //
-            // BLOCKER 3: this VERBATIM `n_runs*256` pool is built ONLY for the pair-less path. The
-            // `draw()`/`draw_with_decals()` calls below bind per-run dynamic offsets `i*256` into it,
-            // so a 0-byte pool (e.g. if it were keyed on op-count for a draw_runs scene) would be an
-            // out-of-range-offset validation error. The op-count pool lives in the paired branch.
+            // BLOCKER 3: this VERBATIM `n_runs*UNIFORM_STRIDE` pool is built ONLY for the pair-less
+            // path. The `draw()`/`draw_with_decals()` calls below bind per-run dynamic offsets
+            // `i*UNIFORM_STRIDE` into it, so a 0-byte pool (e.g. if it were keyed on op-count for a
+            // draw_runs scene) would be an out-of-range-offset validation error. The op-count pool lives in the paired branch.
             let n_runs = scene.draw_runs.len();
-            let mut pool = vec![0u8; n_runs * 256];
+            let mut pool = vec![0u8; n_runs * UNIFORM_STRIDE as usize];
             for (i, run) in scene.draw_runs.iter().enumerate() {
                 let mat = &scene.materials[run.material_index as usize];
                 let rm = &scene.render_modes[run.render_mode_index as usize];
@@ -2786,8 +3763,14 @@ impl SceneRenderer {
                 ];
                 let mut combiner = CombinerUniform::from_run(mat, rm, fog_color);
                 combiner.inv_tex_size = triangle_inv_tex_size(scene, mat, run);
+                combiner.raster[0] = triangle_flat_lane(run);
+                combiner.tile_origin = triangle_tile_origin(scene, mat, run);
+                combiner.dither[2] = self.quantize_5551 as u32;
+                combiner.dither[3] = self.noise_seed;
+                combiner.lod_params[3] = self.resolution_multiplier as f32;
                 let slot = bytemuck::bytes_of(&combiner);
-                pool[i * 256..i * 256 + slot.len()].copy_from_slice(slot);
+                pool[i * UNIFORM_STRIDE as usize..i * UNIFORM_STRIDE as usize + slot.len()]
+                    .copy_from_slice(slot);
             }
             let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("combiner-uniform-pool"),
@@ -2807,13 +3790,14 @@ impl SceneRenderer {
                 }],
             });
 
+            let fb_extent = wgpu::Extent3d {
+                width: self.fb_w * self.resolution_multiplier,
+                height: self.fb_h * self.resolution_multiplier,
+                depth_or_array_layers: 1,
+            };
             let fb_tex = device.create_texture(&wgpu::TextureDescriptor {
                 label: Some("flat3d-fb-color"),
-                size: wgpu::Extent3d {
-                    width: self.fb_w,
-                    height: self.fb_h,
-                    depth_or_array_layers: 1,
-                },
+                size: fb_extent,
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
@@ -2822,8 +3806,12 @@ impl SceneRenderer {
                     | wgpu::TextureUsages::TEXTURE_BINDING,
                 view_formats: &[],
             });
-            let fb_attach = fb_tex.create_view(&wgpu::TextureViewDescriptor::default());
-            let fb_sampled = fb_tex.create_view(&wgpu::TextureViewDescriptor::default());
+            let views = FbViews {
+                attach: fb_tex.create_view(&wgpu::TextureViewDescriptor::default()),
+                msaa: self.fb_msaa_view.clone(),
+                sampled: fb_tex.create_view(&wgpu::TextureViewDescriptor::default()),
+            };
+            let fb_attach = views.msaa.as_ref().unwrap_or(&views.attach);
 
             let depth = if scene.render_modes.iter().any(|r| r.z_test || r.z_write) {
                 Some(&self.depth_view)
@@ -2850,14 +3838,14 @@ impl SceneRenderer {
                 });
                 self.textured_fb.draw_with_decals(
                     &mut encoder,
-                    &fb_attach,
+                    fb_attach,
                     dst,
                     ibuf,
                     scene,
                     CLEAR_COLOR,
                     &material_bgs,
                     &uniform_bg,
-                    256,
+                    UNIFORM_STRIDE,
                     // E2: the decal path always needs depth in pass 1 (so pass 2 can sample it),
                     // regardless of whether any non-decal run sets z_test/z_write.
                     &self.depth_view,
@@ -2866,17 +3854,18 @@ impl SceneRenderer {
             } else {
                 self.textured_fb.draw(
                     &mut encoder,
-                    &fb_attach,
+                    fb_attach,
                     dst,
                     ibuf,
                     scene,
                     CLEAR_COLOR,
                     &material_bgs,
                     &uniform_bg,
-                    256,
+                    UNIFORM_STRIDE,
                     depth,
                 );
             }
+            self.resolve(&mut encoder, &views);
 
             // Blit the internal FB to the caller's target (present.wgsl fullscreen triangle;
             // ClampToEdge+Linear is identity at 1:1). Mirrors render_pairs' scanout blit.
@@ -2886,7 +3875,7 @@ impl SceneRenderer {
                 entries: &[
                     wgpu::BindGroupEntry {
                         binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&fb_sampled),
+                        resource: wgpu::BindingResource::TextureView(&views.sampled),
                     },
                     wgpu::BindGroupEntry {
                         binding: 1,
@@ -2932,33 +3921,33 @@ impl SceneRenderer {
         queue.submit(Some(encoder.finish()));
     }
 
-    /// Three-pass coplanar-decal render for ONE `FramebufferPair` that has depth AND decal Tris.
-    /// Mirrors `TexturedPipeline::draw_with_decals` (the pair-LESS decal path) inside the per-pair
+    /// Multi-pass render for ONE `FramebufferPair` that has depth AND decal Tris, or any Tris that
+    /// draw through the framebuffer-read blender (`draws_fb_blend`). Mirrors
+    /// `TexturedPipeline::draw_with_decals` (the pair-LESS decal path) inside the per-pair
     /// framebuffer pipeline, so paired scenes (sm64's carpet / door overlays / wall cracks) get the
     /// same z-fight-free coplanar decals the pair-less path always had.
     ///
-    /// The pair's drawing ops are partitioned (preserving relative op order within each group) into
-    /// (a) non-decal Tris, (b) decal Tris, (c) rects, each carrying its uniform-pool slot index (and
-    /// rects their quad index). Three passes into the SAME pair color target + a per-pair depth
-    /// texture created with `RENDER_ATTACHMENT | TEXTURE_BINDING` (so it can be both written and
-    /// sampled):
-    ///   1. color `color_load`, depth write+store — non-decal Tris (depth pass).
-    ///   2. color `Load`, NO depth attachment, pair-depth SAMPLED at `@group(2)` — decal Tris via
-    ///      `select_decal` (the decal shader samples the stored depth for the §8.1 coplanar test).
-    ///   3. color `Load`, depth off — the rects (reuses `draw_rect_op`, incl. the HUD alpha-blend
-    ///      `fallback_class` logic and the `fb_source` alias).
+    /// The pair's ops are split into ORDERED segments (see below), each its own pass into the SAME
+    /// pair color target + a per-pair depth texture created with `RENDER_ATTACHMENT |
+    /// TEXTURE_BINDING` (so it can be both written and sampled):
+    ///   - write: color + depth attached — opaque Tris and rects (`draw_rect_op`, incl. the HUD
+    ///     alpha-blend `fallback_class` logic and the `fb_source` alias).
+    ///   - decal read: NO depth attachment, pair depth SAMPLED at `@group(2)` — decal Tris via
+    ///     `select_decal` (the decal shader samples the stored depth for the §8.1 coplanar test).
+    ///   - fb blend: one `draws_fb_blend` run. The color target is first copied into a per-pair
+    ///     snapshot, which the framebuffer-read blender samples at `@group(2)` as CLR_MEM.
     ///
-    /// ORDERING ASSUMPTION (documented known limitation): this is faithful when the pair's ops are
-    /// 3D-opaque < 3D-decal < 2D-rects (true for sm64 + the test scenes). A rect ordered BETWEEN the two
-    /// tri groups would be reordered (drawn after both); decal scenes never interleave that way.
+    /// `want_depth` is false for a pair without a depth image (routed here only for its fb-blend
+    /// runs): the depth texture still backs the passes, but no run tests or writes it and decal
+    /// runs draw as ordinary Tris, exactly as in the single-pass branch.
     /// Each pass sets the scissor per-draw from the op's active scissor, so mid-pair `SetScissor`
     /// still applies to the correct draws regardless of which pass they land in.
     #[allow(clippy::too_many_arguments)]
-    fn render_decal_pair(
+    fn render_segmented_pair(
         &self,
         device: &wgpu::Device,
         encoder: &mut wgpu::CommandEncoder,
-        fb_pool: &std::collections::HashMap<u64, (wgpu::TextureView, wgpu::TextureView)>,
+        fb_pool: &std::collections::HashMap<u64, FbViews>,
         pair: &crate::hle::FramebufferPair,
         pair_idx: usize,
         fb_extent: wgpu::Extent3d,
@@ -2966,6 +3955,7 @@ impl SceneRenderer {
         fb_h: u32,
         addr: u64,
         color_load: wgpu::LoadOp<wgpu::Color>,
+        want_depth: bool,
         scene: &crate::hle::Scene,
         material_bgs: &[&wgpu::BindGroup],
         dst: Option<&wgpu::Buffer>,
@@ -2982,7 +3972,7 @@ impl SceneRenderer {
             label: Some("fb-depth-decal"),
             size: fb_extent,
             mip_level_count: 1,
-            sample_count: 1,
+            sample_count: self.sample_count,
             dimension: wgpu::TextureDimension::D2,
             format: DEPTH_FORMAT,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
@@ -2999,12 +3989,9 @@ impl SceneRenderer {
             }],
         });
 
-        let color_attach = &fb_pool
-            .get(&addr)
-            .expect("fb_pool entry was just inserted")
-            .0;
+        let color = fb_pool.get(&addr).expect("fb_pool entry was just inserted");
         let set_scissor = |pass: &mut wgpu::RenderPass<'_>, s: &crate::hle::Scissor| {
-            let (x, y, w, h) = clamp_scissor(s, fb_w, fb_h);
+            let (x, y, w, h) = clamp_scissor(s, fb_w, fb_h, self.resolution_multiplier);
             pass.set_scissor_rect(x, y, w, h);
         };
 
@@ -3014,54 +4001,67 @@ impl SceneRenderer {
         // depth written so far. Segments render in submission order — so a leading background
         // FILLRECT stays BEHIND the geometry (bucketing it to a trailing pass painted it OVER the
         // scene → black), and each decal group sees only the depth of opaque geometry drawn before
-        // it (bucketing all decals last over-occluded them → clipping/z-fighting). Slots/rect_idx
-        // advance in op order, matching the single-pass branch's totals.
+        // it (bucketing all decals last over-occluded them → clipping/z-fighting). Every fb_blend
+        // run is a segment of its own, so it reads the target as every earlier op left it.
+        // Slots/rect_idx advance in op order, matching the single-pass branch's totals.
         enum Entry<'a> {
             OpaqueTri(&'a crate::hle::DrawRun, u32, crate::hle::Scissor),
             DecalTri(&'a crate::hle::DrawRun, u32, crate::hle::Scissor),
+            FbBlendTri(&'a crate::hle::DrawRun, u32, crate::hle::Scissor),
             Rect(&'a crate::hle::SceneOp, u32, u32, crate::hle::Scissor),
         }
-        // `read` = a depth-sampling (decal) segment; otherwise a depth-writing (opaque/rect) segment.
-        let mut segments: Vec<(bool, Vec<Entry>)> = Vec::new();
+        #[derive(Clone, Copy, PartialEq)]
+        enum Segment {
+            Write,
+            DecalRead,
+            FbBlend,
+        }
+        let mut segments: Vec<(Segment, Vec<Entry>)> = Vec::new();
         let mut cur_scissor = pair.active_scissor;
         for op in &pair.ops {
-            let (read, entry) = match op {
+            let (seg, entry) = match op {
                 crate::hle::SceneOp::Tris(run) => {
-                    let is_decal = scene.render_modes[run.render_mode_index as usize].z_mode
-                        == crate::hle::ZMode::Decal;
+                    let rm = &scene.render_modes[run.render_mode_index as usize];
+                    let is_decal = want_depth && rm.z_mode == crate::hle::ZMode::Decal;
                     let e = if is_decal {
-                        Entry::DecalTri(run, *slot, cur_scissor)
+                        (Segment::DecalRead, Entry::DecalTri(run, *slot, cur_scissor))
+                    } else if self.draws_fb_blend(rm) {
+                        (Segment::FbBlend, Entry::FbBlendTri(run, *slot, cur_scissor))
                     } else {
-                        Entry::OpaqueTri(run, *slot, cur_scissor)
+                        (Segment::Write, Entry::OpaqueTri(run, *slot, cur_scissor))
                     };
                     *slot += 1;
-                    (is_decal, e)
+                    e
                 }
                 crate::hle::SceneOp::FillRect { .. } | crate::hle::SceneOp::TexRect { .. } => {
                     let e = Entry::Rect(op, *slot, *rect_idx, cur_scissor);
                     *slot += 1;
                     *rect_idx += 1;
-                    (false, e)
+                    (Segment::Write, e)
                 }
                 crate::hle::SceneOp::SetScissor(s) => {
                     cur_scissor = *s;
                     continue;
                 }
             };
-            if segments.last().map(|s| s.0) != Some(read) {
-                segments.push((read, Vec::new()));
+            if seg == Segment::FbBlend || segments.last().map(|s| s.0) != Some(seg) {
+                segments.push((seg, Vec::new()));
             }
             segments.last_mut().unwrap().1.push(entry);
         }
 
-        // Render segments in order. Depth is CLEARED on the first write segment and LOADED on later
-        // ones (so a write segment after a decal group preserves the accumulated depth). Color uses
-        // the caller's `color_load` on the first segment and LOAD thereafter. (sm64/test scenes always open
-        // with opaque geometry, so the first segment is a write segment that clears depth before any
-        // decal samples it.)
+        // The fb_blend snapshot: a COPY_DST copy of the pair's color target, created on the first
+        // fb_blend segment and refreshed before each one.
+        let mut fb_snapshot: Option<(wgpu::Texture, wgpu::BindGroup)> = None;
+
+        // Render segments in order. Depth is CLEARED on the first depth-attached segment and LOADED
+        // on later ones (so a write segment after a decal group preserves the accumulated depth).
+        // Color uses the caller's `color_load` on the first segment and LOAD thereafter. (sm64/test
+        // scenes always open with opaque geometry, so the first segment is a write segment that
+        // clears depth before any decal samples it.)
         let mut depth_cleared = false;
         let mut color_first = true;
-        for (read, entries) in &segments {
+        for (seg, entries) in &segments {
             if entries.is_empty() {
                 continue;
             }
@@ -3072,19 +4072,95 @@ impl SceneRenderer {
             };
             color_first = false;
 
-            if *read {
+            if *seg == Segment::FbBlend {
+                let Some(Entry::FbBlendTri(run, run_slot, scissor)) = entries.first() else {
+                    unreachable!("an fb-blend segment holds one fb_blend Tris");
+                };
+                // A pending clear must land before the snapshot copies the target.
+                if let wgpu::LoadOp::Clear(_) = color_op {
+                    let _ = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: Some("fb-blend-clear-pass"),
+                        color_attachments: &[Some(color.color_attachment(color_op))],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                        multiview_mask: None,
+                    });
+                }
+                let (snapshot, snapshot_bg) = fb_snapshot.get_or_insert_with(|| {
+                    let tex = device.create_texture(&wgpu::TextureDescriptor {
+                        label: Some("fb-blend-snapshot"),
+                        size: fb_extent,
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: wgpu::TextureDimension::D2,
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+                        view_formats: &[],
+                    });
+                    let view = tex.create_view(&wgpu::TextureViewDescriptor::default());
+                    let bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: Some("fb-blend-snapshot-bg"),
+                        layout: self.textured_fb.fb_bind_group_layout(),
+                        entries: &[wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&view),
+                        }],
+                    });
+                    (tex, bg)
+                });
+                encoder.copy_texture_to_texture(
+                    color.attach.texture().as_image_copy(),
+                    snapshot.as_image_copy(),
+                    fb_extent,
+                );
+                let depth_op = if depth_cleared {
+                    wgpu::LoadOp::Load
+                } else {
+                    wgpu::LoadOp::Clear(1.0)
+                };
+                depth_cleared = true;
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("fb-blend-pass"),
+                    color_attachments: &[Some(color.color_attachment(wgpu::LoadOp::Load))],
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: &depth_attach,
+                        depth_ops: Some(wgpu::Operations {
+                            load: depth_op,
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    }),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                    multiview_mask: None,
+                });
+                set_scissor(&mut pass, scissor);
+                let rm = &scene.render_modes[run.render_mode_index as usize];
+                let pipeline = self.textured_fb.for_texcoords(rm).fb_blend.select(
+                    run.cull,
+                    want_depth && rm.z_test,
+                    want_depth && rm.z_write,
+                    true,
+                );
+                pass.set_pipeline(pipeline);
+                if let Some(ib) = ibuf {
+                    pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                }
+                if let Some(d) = dst {
+                    pass.set_vertex_buffer(0, d.slice(..));
+                }
+                pass.set_bind_group(0, material_bgs[run.material_index as usize], &[]);
+                if let Some(ubg) = uniform_bg {
+                    pass.set_bind_group(1, ubg, &[run_slot * UNIFORM_STRIDE]);
+                }
+                pass.set_bind_group(2, &*snapshot_bg, &[]);
+                pass.draw_indexed(run.index_start..run.index_start + run.index_count, 0, 0..1);
+            } else if *seg == Segment::DecalRead {
                 // Depth-READ (decal) segment: NO depth attachment, sample depth at @group(2).
                 let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     label: Some("fb-decal-read-pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: color_attach,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: color_op,
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
+                    color_attachments: &[Some(color.color_attachment(color_op))],
                     depth_stencil_attachment: None, // cannot write+sample the same depth in one pass
                     timestamp_writes: None,
                     occlusion_query_set: None,
@@ -3103,13 +4179,15 @@ impl SceneRenderer {
                         pass.set_vertex_buffer(0, d.slice(..));
                     }
                     let rm = &scene.render_modes[run.render_mode_index as usize];
-                    let pipeline =
-                        self.textured_fb
-                            .select_decal(run.cull, rm.fallback_class, rm.blend_class);
+                    let pipeline = self.textured_fb.for_texcoords(rm).select_decal(
+                        run.cull,
+                        rm.fallback_class,
+                        rm.blend_class,
+                    );
                     pass.set_pipeline(pipeline);
                     pass.set_bind_group(0, material_bgs[run.material_index as usize], &[]);
                     if let Some(ubg) = uniform_bg {
-                        pass.set_bind_group(1, ubg, &[run_slot * 256]);
+                        pass.set_bind_group(1, ubg, &[run_slot * UNIFORM_STRIDE]);
                     }
                     pass.draw_indexed(run.index_start..run.index_start + run.index_count, 0, 0..1);
                 }
@@ -3123,15 +4201,7 @@ impl SceneRenderer {
                 depth_cleared = true;
                 let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     label: Some("fb-decal-write-pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: color_attach,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: color_op,
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
+                    color_attachments: &[Some(color.color_attachment(color_op))],
                     depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                         view: &depth_attach,
                         depth_ops: Some(wgpu::Operations {
@@ -3152,19 +4222,14 @@ impl SceneRenderer {
                         Entry::OpaqueTri(run, run_slot, scissor) => {
                             set_scissor(&mut pass, scissor);
                             let rm = &scene.render_modes[run.render_mode_index as usize];
-                            let z_test = rm.z_test;
-                            let z_write = rm.z_write;
-                            let pipeline = match &self.textured_fb.dual {
+                            let z_test = want_depth && rm.z_test;
+                            let z_write = want_depth && rm.z_write;
+                            let tp = self.textured_fb.for_texcoords(rm);
+                            let pipeline = match &tp.dual {
                                 Some(ds) if rm.blend_class == crate::hle::BlendClass::DualSrc => {
                                     ds.select(run.cull, z_test, z_write, true)
                                 }
-                                _ => self.textured_fb.select(
-                                    run.cull,
-                                    z_test,
-                                    z_write,
-                                    true,
-                                    rm.fallback_class,
-                                ),
+                                _ => tp.select(run.cull, z_test, z_write, true, rm.fallback_class),
                             };
                             pass.set_pipeline(pipeline);
                             if let Some(d) = dst {
@@ -3172,7 +4237,7 @@ impl SceneRenderer {
                             }
                             pass.set_bind_group(0, material_bgs[run.material_index as usize], &[]);
                             if let Some(ubg) = uniform_bg {
-                                pass.set_bind_group(1, ubg, &[run_slot * 256]);
+                                pass.set_bind_group(1, ubg, &[run_slot * UNIFORM_STRIDE]);
                             }
                             pass.draw_indexed(
                                 run.index_start..run.index_start + run.index_count,
@@ -3201,7 +4266,9 @@ impl SceneRenderer {
                                 true,
                             );
                         }
-                        Entry::DecalTri(..) => unreachable!("write segment holds no decal Tris"),
+                        Entry::DecalTri(..) | Entry::FbBlendTri(..) => {
+                            unreachable!("write segment holds no decal or fb_blend Tris")
+                        }
                     }
                 }
             }
@@ -3234,12 +4301,12 @@ impl SceneRenderer {
 
         // Frame-local color FB pool keyed by `color_image.addr`: pairs targeting the same CIMG reuse
         // one texture (a later pair accumulates over an earlier one — N64 framebuffer persistence).
-        // Value = (RENDER_ATTACHMENT view, sampled TEXTURE_BINDING view).
-        let mut fb_pool: HashMap<u64, (wgpu::TextureView, wgpu::TextureView)> = HashMap::new();
+        // Value = the color image's attach/sampled views (+ its MSAA target).
+        let mut fb_pool: HashMap<u64, FbViews> = HashMap::new();
         // CIMG addrs already cleared this frame → LoadOp::Load on reuse (Clear only on first use).
         let mut color_cleared: HashSet<u64> = HashSet::new();
 
-        // --- Op-count uniform pool (BLOCKER 3): one 256-byte slot per DRAWING op (Tris/FillRect/
+        // --- Op-count uniform pool (BLOCKER 3): one UNIFORM_STRIDE slot per DRAWING op (Tris/FillRect/
         // TexRect), walked across every pair in order. `SetScissor` carries no slot. ---
         let fc = scene.fog_color;
         let fog_color = [
@@ -3261,10 +4328,16 @@ impl SceneRenderer {
         };
 
         let mut pool: Vec<u8> = Vec::new();
-        let push_slot = |pool: &mut Vec<u8>, u: &CombinerUniform| {
+        // `quantize` is the pair's RGBA5551 flag (`CombinerUniform.dither.z`).
+        let push_slot = |pool: &mut Vec<u8>, u: &CombinerUniform, quantize: bool| {
+            let mut u = *u;
+            u.dither[2] = quantize as u32;
+            u.dither[3] = self.noise_seed;
+            u.lod_params[3] = self.resolution_multiplier as f32;
+            u.coverage[0] = self.emulate_coverage as u32;
             let base = pool.len();
-            pool.resize(base + 256, 0);
-            let bytes = bytemuck::bytes_of(u);
+            pool.resize(base + UNIFORM_STRIDE as usize, 0);
+            let bytes = bytemuck::bytes_of(&u);
             pool[base..base + bytes.len()].copy_from_slice(bytes);
         };
         // Per-frame rect-quad vertex buffer (NEW): 6 CLIP-SPACE OutVertex per FillRect/TexRect, in
@@ -3278,6 +4351,8 @@ impl SceneRenderer {
                 continue;
             }
             let (fb_w, fb_h) = fb_dims(pair);
+            // Only a 16-bit color image is quantized; 32-bit images keep full precision.
+            let quantize = self.quantize_5551 && pair.color_image.siz == 2;
             for op in &pair.ops {
                 match op {
                     crate::hle::SceneOp::Tris(run) => {
@@ -3285,7 +4360,9 @@ impl SceneRenderer {
                         let rm = &scene.render_modes[run.render_mode_index as usize];
                         let mut u = CombinerUniform::from_run(mat, rm, fog_color);
                         u.inv_tex_size = triangle_inv_tex_size(scene, mat, run);
-                        push_slot(&mut pool, &u);
+                        u.raster[0] = triangle_flat_lane(run);
+                        u.tile_origin = triangle_tile_origin(scene, mat, run);
+                        push_slot(&mut pool, &u, quantize);
                     }
                     crate::hle::SceneOp::TexRect {
                         rect,
@@ -3297,23 +4374,27 @@ impl SceneRenderer {
                         copy_mode,
                         material_index,
                         render_mode_index,
-                        ..
+                        fb_source,
                     } => {
                         let mat = &scene.materials[*material_index as usize];
                         // COPY cycle bypasses the combiner: emit a TEXEL0 passthrough. Otherwise use
                         // the material's combine/render-mode (1-/2-cycle).
-                        let u = if *copy_mode {
+                        let mut u = if *copy_mode {
                             // Copy mode bypasses the combiner, but RDP alpha-compare still keys
                             // transparent texels away (alpha-keyed HUD/text glyphs). Derive the
                             // discard from the decoded render mode + tile format. `.get` defends
                             // the synthetic-scene case where `render_modes` may be empty.
                             let rm = scene.render_modes.get(*render_mode_index as usize);
-                            CombinerUniform::tex_copy(rm, mat.fmt)
+                            CombinerUniform::tex_copy(rm, mat)
                         } else {
                             let rm = &scene.render_modes[*render_mode_index as usize];
                             CombinerUniform::from_run(mat, rm, fog_color)
                         };
-                        push_slot(&mut pool, &u);
+                        // An `fb_source` TEXEL0 is the prior pair's view at the multiplied size.
+                        if fb_source.is_some() {
+                            u.raster[2] = self.resolution_multiplier as f32;
+                        }
+                        push_slot(&mut pool, &u, quantize);
                         // COPY cycle scales the horizontal step by 4 (4 px/cycle): dsdx >>= 2.
                         let dsdx_eff = if *copy_mode {
                             (*dsdx as i32) >> 2
@@ -3331,7 +4412,7 @@ impl SceneRenderer {
                     }
                     crate::hle::SceneOp::FillRect { rect, color_raw } => {
                         let u = CombinerUniform::fill_rect(*color_raw, pair.color_image.siz);
-                        push_slot(&mut pool, &u);
+                        push_slot(&mut pool, &u, quantize);
                         rect_verts.extend_from_slice(&rect_quad(
                             rect,
                             fb_w,
@@ -3384,8 +4465,8 @@ impl SceneRenderer {
         for (pair_idx, pair) in scene.framebuffer_pairs.iter().enumerate() {
             let (fb_w, fb_h) = fb_dims(pair);
             let fb_extent = wgpu::Extent3d {
-                width: fb_w,
-                height: fb_h,
+                width: fb_w * self.resolution_multiplier,
+                height: fb_h * self.resolution_multiplier,
                 depth_or_array_layers: 1,
             };
 
@@ -3425,7 +4506,8 @@ impl SceneRenderer {
             }
 
             // Fetch-or-create this pair's color target (Rgba8Unorm, RENDER_ATTACHMENT|TEXTURE_BINDING
-            // — no COPY_SRC). The textured_fb pipeline matrix is built at Rgba8Unorm to match.
+            // |COPY_SRC for the fb_blend snapshot), plus its multisampled target under MSAA. The
+            // textured_fb pipeline matrix is built at Rgba8Unorm and the same sample count to match.
             let addr = pair.color_image.addr;
             fb_pool.entry(addr).or_insert_with(|| {
                 let tex = device.create_texture(&wgpu::TextureDescriptor {
@@ -3436,12 +4518,15 @@ impl SceneRenderer {
                     dimension: wgpu::TextureDimension::D2,
                     format: wgpu::TextureFormat::Rgba8Unorm,
                     usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                        | wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::COPY_SRC,
                     view_formats: &[],
                 });
-                let attach = tex.create_view(&wgpu::TextureViewDescriptor::default());
-                let sampled = tex.create_view(&wgpu::TextureViewDescriptor::default());
-                (attach, sampled)
+                FbViews {
+                    attach: tex.create_view(&wgpu::TextureViewDescriptor::default()),
+                    msaa: self.msaa_view(device, fb_extent),
+                    sampled: tex.create_view(&wgpu::TextureViewDescriptor::default()),
+                }
             });
             let color_load = if color_cleared.insert(addr) {
                 wgpu::LoadOp::Clear(CLEAR_COLOR)
@@ -3465,9 +4550,15 @@ impl SceneRenderer {
                     if scene.render_modes[run.render_mode_index as usize].z_mode
                         == crate::hle::ZMode::Decal)
             });
+            // Does it carry any run the fixed-function blend cannot express? Those draw through
+            // the framebuffer-read blender, which needs the segmented path's per-run snapshots.
+            let has_fb_blend_tris = pair.ops.iter().any(|op| {
+                matches!(op, crate::hle::SceneOp::Tris(run)
+                    if self.draws_fb_blend(&scene.render_modes[run.render_mode_index as usize]))
+            });
 
-            if want_depth && has_decal_tris {
-                self.render_decal_pair(
+            if (want_depth && has_decal_tris) || has_fb_blend_tris {
+                self.render_segmented_pair(
                     device,
                     encoder,
                     &fb_pool,
@@ -3478,6 +4569,7 @@ impl SceneRenderer {
                     fb_h,
                     addr,
                     color_load,
+                    want_depth,
                     scene,
                     material_bgs,
                     dst.as_ref(),
@@ -3497,7 +4589,7 @@ impl SceneRenderer {
                     label: Some("fb-depth"),
                     size: fb_extent,
                     mip_level_count: 1,
-                    sample_count: 1,
+                    sample_count: self.sample_count,
                     dimension: wgpu::TextureDimension::D2,
                     format: DEPTH_FORMAT,
                     usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -3509,8 +4601,7 @@ impl SceneRenderer {
                 .map(|t| t.create_view(&wgpu::TextureViewDescriptor::default()));
             let any_depth = depth_view.is_some();
 
-            let (color_attach, _color_sampled) =
-                fb_pool.get(&addr).expect("fb_pool entry was just inserted");
+            let color = fb_pool.get(&addr).expect("fb_pool entry was just inserted");
             let depth_attachment =
                 depth_view
                     .as_ref()
@@ -3524,15 +4615,7 @@ impl SceneRenderer {
                     });
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("fb-pair-pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: color_attach,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: color_load,
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
+                color_attachments: &[Some(color.color_attachment(color_load))],
                 depth_stencil_attachment: depth_attachment,
                 timestamp_writes: None,
                 occlusion_query_set: None,
@@ -3543,7 +4626,8 @@ impl SceneRenderer {
             // mid-pair `SetScissor` ops update it below. The default (no-op) scissor is the full
             // attachment, so a full-FB scissor is equivalent to not calling this at all.
             {
-                let (x, y, w, h) = clamp_scissor(&pair.active_scissor, fb_w, fb_h);
+                let (x, y, w, h) =
+                    clamp_scissor(&pair.active_scissor, fb_w, fb_h, self.resolution_multiplier);
                 pass.set_scissor_rect(x, y, w, h);
             }
             // The global index buffer is bound once (Tris draws use it); the vertex buffer (slot 0)
@@ -3558,17 +4642,12 @@ impl SceneRenderer {
                         // Gate z by the pass's depth presence, exactly like `TexturedPipeline::draw`.
                         let z_test = any_depth && rm.z_test;
                         let z_write = any_depth && rm.z_write;
-                        let pipeline = match &self.textured_fb.dual {
+                        let tp = self.textured_fb.for_texcoords(rm);
+                        let pipeline = match &tp.dual {
                             Some(ds) if rm.blend_class == crate::hle::BlendClass::DualSrc => {
                                 ds.select(run.cull, z_test, z_write, any_depth)
                             }
-                            _ => self.textured_fb.select(
-                                run.cull,
-                                z_test,
-                                z_write,
-                                any_depth,
-                                rm.fallback_class,
-                            ),
+                            _ => tp.select(run.cull, z_test, z_write, any_depth, rm.fallback_class),
                         };
                         pass.set_pipeline(pipeline);
                         // Tris consume the compute `dst` vertex buffer (rects rebind slot 0).
@@ -3577,7 +4656,7 @@ impl SceneRenderer {
                         }
                         pass.set_bind_group(0, material_bgs[run.material_index as usize], &[]);
                         if let Some(ubg) = &uniform_bg {
-                            pass.set_bind_group(1, ubg, &[slot * 256]);
+                            pass.set_bind_group(1, ubg, &[slot * UNIFORM_STRIDE]);
                         }
                         pass.draw_indexed(
                             run.index_start..run.index_start + run.index_count,
@@ -3614,7 +4693,7 @@ impl SceneRenderer {
                     }
                     crate::hle::SceneOp::SetScissor(s) => {
                         // Mid-pair scissor change: apply the new rect, clamped to the FB extent.
-                        let (x, y, w, h) = clamp_scissor(s, fb_w, fb_h);
+                        let (x, y, w, h) = clamp_scissor(s, fb_w, fb_h, self.resolution_multiplier);
                         pass.set_scissor_rect(x, y, w, h);
                     }
                 }
@@ -3629,7 +4708,7 @@ impl SceneRenderer {
             .rev()
             .find(|p| !p.is_depth_clear)
         {
-            if let Some((_, sampled)) = fb_pool.get(&p.color_image.addr) {
+            if let Some(FbViews { sampled, .. }) = fb_pool.get(&p.color_image.addr) {
                 let src_bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
                     label: Some("scanout-blit-src"),
                     layout: self.textured.bind_group_layout(),
@@ -3689,18 +4768,18 @@ impl SceneRenderer {
         use std::collections::HashMap;
         use wgpu::util::DeviceExt;
         // Local view-map into the PERSISTENT store (FBs pre-created in render_into_store Phase A).
-        // TextureView is Clone (wgpu 29), so this reuses draw_rect_op/render_decal_pair unchanged.
-        let mut fb_pool: HashMap<u64, (wgpu::TextureView, wgpu::TextureView)> = HashMap::new();
+        // TextureView is Clone (wgpu 29), so this reuses draw_rect_op/render_segmented_pair unchanged.
+        let mut fb_pool: HashMap<u64, FbViews> = HashMap::new();
         for pair in scene.framebuffer_pairs.iter().filter(|p| !p.is_depth_clear) {
             let fb = &self.framebuffers[&pair.color_image.addr];
             fb_pool
                 .entry(pair.color_image.addr)
-                .or_insert_with(|| (fb.attach.clone(), fb.sampled.clone()));
+                .or_insert_with(|| fb.views.clone());
         }
 
         // ── Uniform/rect-quad pool build — copied from render_pairs VERBATIM (produces `uniform_bg`,
         //    `rect_vbuf`, and the per-pair `fb_dims` closure). ──
-        // --- Op-count uniform pool (BLOCKER 3): one 256-byte slot per DRAWING op (Tris/FillRect/
+        // --- Op-count uniform pool (BLOCKER 3): one UNIFORM_STRIDE slot per DRAWING op (Tris/FillRect/
         // TexRect), walked across every pair in order. `SetScissor` carries no slot. ---
         let fc = scene.fog_color;
         let fog_color = [
@@ -3722,10 +4801,16 @@ impl SceneRenderer {
         };
 
         let mut pool: Vec<u8> = Vec::new();
-        let push_slot = |pool: &mut Vec<u8>, u: &CombinerUniform| {
+        // `quantize` is the pair's RGBA5551 flag (`CombinerUniform.dither.z`).
+        let push_slot = |pool: &mut Vec<u8>, u: &CombinerUniform, quantize: bool| {
+            let mut u = *u;
+            u.dither[2] = quantize as u32;
+            u.dither[3] = self.noise_seed;
+            u.lod_params[3] = self.resolution_multiplier as f32;
+            u.coverage[0] = self.emulate_coverage as u32;
             let base = pool.len();
-            pool.resize(base + 256, 0);
-            let bytes = bytemuck::bytes_of(u);
+            pool.resize(base + UNIFORM_STRIDE as usize, 0);
+            let bytes = bytemuck::bytes_of(&u);
             pool[base..base + bytes.len()].copy_from_slice(bytes);
         };
         // Per-frame rect-quad vertex buffer (NEW): 6 CLIP-SPACE OutVertex per FillRect/TexRect, in
@@ -3739,6 +4824,8 @@ impl SceneRenderer {
                 continue;
             }
             let (fb_w, fb_h) = fb_dims(pair);
+            // Only a 16-bit color image is quantized; 32-bit images keep full precision.
+            let quantize = self.quantize_5551 && pair.color_image.siz == 2;
             for op in &pair.ops {
                 match op {
                     crate::hle::SceneOp::Tris(run) => {
@@ -3746,7 +4833,9 @@ impl SceneRenderer {
                         let rm = &scene.render_modes[run.render_mode_index as usize];
                         let mut u = CombinerUniform::from_run(mat, rm, fog_color);
                         u.inv_tex_size = triangle_inv_tex_size(scene, mat, run);
-                        push_slot(&mut pool, &u);
+                        u.raster[0] = triangle_flat_lane(run);
+                        u.tile_origin = triangle_tile_origin(scene, mat, run);
+                        push_slot(&mut pool, &u, quantize);
                     }
                     crate::hle::SceneOp::TexRect {
                         rect,
@@ -3758,23 +4847,27 @@ impl SceneRenderer {
                         copy_mode,
                         material_index,
                         render_mode_index,
-                        ..
+                        fb_source,
                     } => {
                         let mat = &scene.materials[*material_index as usize];
                         // COPY cycle bypasses the combiner: emit a TEXEL0 passthrough. Otherwise use
                         // the material's combine/render-mode (1-/2-cycle).
-                        let u = if *copy_mode {
+                        let mut u = if *copy_mode {
                             // Copy mode bypasses the combiner, but RDP alpha-compare still keys
                             // transparent texels away (alpha-keyed HUD/text glyphs). Derive the
                             // discard from the decoded render mode + tile format. `.get` defends
                             // the synthetic-scene case where `render_modes` may be empty.
                             let rm = scene.render_modes.get(*render_mode_index as usize);
-                            CombinerUniform::tex_copy(rm, mat.fmt)
+                            CombinerUniform::tex_copy(rm, mat)
                         } else {
                             let rm = &scene.render_modes[*render_mode_index as usize];
                             CombinerUniform::from_run(mat, rm, fog_color)
                         };
-                        push_slot(&mut pool, &u);
+                        // An `fb_source` TEXEL0 is the prior pair's view at the multiplied size.
+                        if fb_source.is_some() {
+                            u.raster[2] = self.resolution_multiplier as f32;
+                        }
+                        push_slot(&mut pool, &u, quantize);
                         // COPY cycle scales the horizontal step by 4 (4 px/cycle): dsdx >>= 2.
                         let dsdx_eff = if *copy_mode {
                             (*dsdx as i32) >> 2
@@ -3792,7 +4885,7 @@ impl SceneRenderer {
                     }
                     crate::hle::SceneOp::FillRect { rect, color_raw } => {
                         let u = CombinerUniform::fill_rect(*color_raw, pair.color_image.siz);
-                        push_slot(&mut pool, &u);
+                        push_slot(&mut pool, &u, quantize);
                         rect_verts.extend_from_slice(&rect_quad(
                             rect,
                             fb_w,
@@ -3850,8 +4943,8 @@ impl SceneRenderer {
         for (pair_idx, pair) in scene.framebuffer_pairs.iter().enumerate() {
             let (fb_w, fb_h) = fb_dims(pair);
             let fb_extent = wgpu::Extent3d {
-                width: fb_w,
-                height: fb_h,
+                width: fb_w * self.resolution_multiplier,
+                height: fb_h * self.resolution_multiplier,
                 depth_or_array_layers: 1,
             };
 
@@ -3912,9 +5005,15 @@ impl SceneRenderer {
                     if scene.render_modes[run.render_mode_index as usize].z_mode
                         == crate::hle::ZMode::Decal)
             });
+            // Does it carry any run the fixed-function blend cannot express? Those draw through
+            // the framebuffer-read blender, which needs the segmented path's per-run snapshots.
+            let has_fb_blend_tris = pair.ops.iter().any(|op| {
+                matches!(op, crate::hle::SceneOp::Tris(run)
+                    if self.draws_fb_blend(&scene.render_modes[run.render_mode_index as usize]))
+            });
 
-            if want_depth && has_decal_tris {
-                self.render_decal_pair(
+            if (want_depth && has_decal_tris) || has_fb_blend_tris {
+                self.render_segmented_pair(
                     device,
                     encoder,
                     &fb_pool,
@@ -3925,6 +5024,7 @@ impl SceneRenderer {
                     fb_h,
                     addr,
                     color_load,
+                    want_depth,
                     scene,
                     material_bgs,
                     dst.as_ref(),
@@ -3945,7 +5045,7 @@ impl SceneRenderer {
                     label: Some("fb-depth"),
                     size: fb_extent,
                     mip_level_count: 1,
-                    sample_count: 1,
+                    sample_count: self.sample_count,
                     dimension: wgpu::TextureDimension::D2,
                     format: DEPTH_FORMAT,
                     usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -3957,7 +5057,7 @@ impl SceneRenderer {
                 .map(|t| t.create_view(&wgpu::TextureViewDescriptor::default()));
             let any_depth = depth_view.is_some();
 
-            let (color_attach, _color_sampled) = fb_pool
+            let color = fb_pool
                 .get(&addr)
                 .expect("fb_pool entry pre-created by render_into_store Phase A");
             let depth_attachment =
@@ -3973,15 +5073,7 @@ impl SceneRenderer {
                     });
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("fb-pair-pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: color_attach,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: color_load,
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
+                color_attachments: &[Some(color.color_attachment(color_load))],
                 depth_stencil_attachment: depth_attachment,
                 timestamp_writes: None,
                 occlusion_query_set: None,
@@ -3992,7 +5084,8 @@ impl SceneRenderer {
             // mid-pair `SetScissor` ops update it below. The default (no-op) scissor is the full
             // attachment, so a full-FB scissor is equivalent to not calling this at all.
             {
-                let (x, y, w, h) = clamp_scissor(&pair.active_scissor, fb_w, fb_h);
+                let (x, y, w, h) =
+                    clamp_scissor(&pair.active_scissor, fb_w, fb_h, self.resolution_multiplier);
                 pass.set_scissor_rect(x, y, w, h);
             }
             // The global index buffer is bound once (Tris draws use it); the vertex buffer (slot 0)
@@ -4007,17 +5100,12 @@ impl SceneRenderer {
                         // Gate z by the pass's depth presence, exactly like `TexturedPipeline::draw`.
                         let z_test = any_depth && rm.z_test;
                         let z_write = any_depth && rm.z_write;
-                        let pipeline = match &self.textured_fb.dual {
+                        let tp = self.textured_fb.for_texcoords(rm);
+                        let pipeline = match &tp.dual {
                             Some(ds) if rm.blend_class == crate::hle::BlendClass::DualSrc => {
                                 ds.select(run.cull, z_test, z_write, any_depth)
                             }
-                            _ => self.textured_fb.select(
-                                run.cull,
-                                z_test,
-                                z_write,
-                                any_depth,
-                                rm.fallback_class,
-                            ),
+                            _ => tp.select(run.cull, z_test, z_write, any_depth, rm.fallback_class),
                         };
                         pass.set_pipeline(pipeline);
                         // Tris consume the compute `dst` vertex buffer (rects rebind slot 0).
@@ -4026,7 +5114,7 @@ impl SceneRenderer {
                         }
                         pass.set_bind_group(0, material_bgs[run.material_index as usize], &[]);
                         if let Some(ubg) = &uniform_bg {
-                            pass.set_bind_group(1, ubg, &[slot * 256]);
+                            pass.set_bind_group(1, ubg, &[slot * UNIFORM_STRIDE]);
                         }
                         pass.draw_indexed(
                             run.index_start..run.index_start + run.index_count,
@@ -4063,7 +5151,7 @@ impl SceneRenderer {
                     }
                     crate::hle::SceneOp::SetScissor(s) => {
                         // Mid-pair scissor change: apply the new rect, clamped to the FB extent.
-                        let (x, y, w, h) = clamp_scissor(s, fb_w, fb_h);
+                        let (x, y, w, h) = clamp_scissor(s, fb_w, fb_h, self.resolution_multiplier);
                         pass.set_scissor_rect(x, y, w, h);
                     }
                 }
@@ -4160,7 +5248,7 @@ pub mod rsp_buffers {
     #[derive(Clone, Copy, Debug, Pod, Zeroable)]
     pub struct SrcVertex {
         pub pos: [f32; 3],
-        pub _pad0: f32,
+        pub line_offset: f32,
         pub st: [f32; 2],
         pub mtx_index: u32,
         pub viewport_index: u32,
@@ -4173,17 +5261,21 @@ pub mod rsp_buffers {
         pub fog: u32,
         pub modify_flags: u32,
         pub modify_screen: [f32; 4],
+        pub line_other: u32,
+        pub _pad0: [u32; 3],
     }
-    const _: () = assert!(std::mem::size_of::<SrcVertex>() == 80);
+    const _: () = assert!(std::mem::size_of::<SrcVertex>() == 96);
 
-    /// Per-light GPU entry: 32 bytes (dir + col each vec4).
+    /// Per-light GPU entry: 48 bytes (dir + col + point-light attenuation, each vec4). A light
+    /// with `atten[0] > 0` is positional and `dir` holds its object-space position.
     #[repr(C)]
     #[derive(Clone, Copy, Debug, Pod, Zeroable)]
     pub struct GpuLight {
         pub dir: [f32; 4],
         pub col: [f32; 4],
+        pub atten: [f32; 4],
     }
-    const _: () = assert!(std::mem::size_of::<GpuLight>() == 32);
+    const _: () = assert!(std::mem::size_of::<GpuLight>() == 48);
 
     /// Per-lookat GPU entry: 32 bytes (object-space S + T axis, each vec4; only xyz used).
     #[repr(C)]
@@ -4209,7 +5301,7 @@ pub mod rsp_buffers {
         (0..scene.raw_pos.len())
             .map(|i| SrcVertex {
                 pos: scene.raw_pos[i],
-                _pad0: 0.0,
+                line_offset: scene.line_offset.get(i).copied().unwrap_or(0.0),
                 st: scene.raw_st[i],
                 mtx_index: scene.mtx_index[i],
                 viewport_index: scene.viewport_index[i],
@@ -4224,6 +5316,8 @@ pub mod rsp_buffers {
                 fog: scene.fog.get(i).copied().unwrap_or(0),
                 modify_flags: scene.modify_flags.get(i).copied().unwrap_or(0),
                 modify_screen: scene.modify_screen.get(i).copied().unwrap_or([0.0; 4]),
+                line_other: scene.line_other.get(i).copied().unwrap_or(0),
+                _pad0: [0; 3],
             })
             .collect()
     }
@@ -4248,9 +5342,11 @@ pub mod rsp_buffers {
         let v: Vec<GpuLight> = scene
             .lights_table
             .iter()
-            .map(|(d, c)| GpuLight {
+            .enumerate()
+            .map(|(i, (d, c))| GpuLight {
                 dir: [d[0], d[1], d[2], 0.0],
                 col: [c[0], c[1], c[2], 0.0],
+                atten: scene.light_atten.get(i).copied().unwrap_or_default(),
             })
             .collect();
         if v.is_empty() {
@@ -4342,7 +5438,7 @@ pub mod rsp_buffers {
             assert_eq!(sv[0].texcoord_index, 1);
             assert_eq!(sv[0].modify_flags, 3);
             assert_eq!(sv[0].modify_screen, [32.0, 16.0, 0.5, 0.0]);
-            assert_eq!(std::mem::size_of::<SrcVertex>(), 80);
+            assert_eq!(std::mem::size_of::<SrcVertex>(), 96);
             assert_eq!(texcoord_table(&scene)[1].scale_s, 0.25);
             assert_eq!(texcoord_table(&scene)[1].scale_t, 0.5);
             assert_eq!(std::mem::size_of::<GpuViewport>(), 32);
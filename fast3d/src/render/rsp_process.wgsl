@@ -4,14 +4,13 @@
 // normalize is the extra step matching hle set_vertex.
 // color: diffuse lighting (light_count>0) or cn RGBA passthrough (unlit).
 
-const FB_WIDTH:  f32 = 320.0;
-const FB_HEIGHT: f32 = 240.0;
-
-// Must mirror RspProcessParams (lib.rs) exactly — 16 bytes.
-struct Params { vertex_count: u32, fog_enable: u32, fog_mul: f32, fog_offset: f32 };
-// Must mirror rsp_buffers::SrcVertex (render/mod.rs) exactly — 80 bytes.
+// Must mirror RspProcessParams (render/mod.rs) exactly — 32 bytes. `screen.xy` is the native
+// screen the viewport maps into (hle::rsp::FB_WIDTH/FB_HEIGHT); `.zw` pad.
+struct Params { vertex_count: u32, fog_enable: u32, fog_mul: f32, fog_offset: f32, screen: vec4<f32> };
+// Must mirror rsp_buffers::SrcVertex (render/mod.rs) exactly — 96 bytes.
 struct SrcVertex {
     pos: vec3<f32>,
+    line_offset: f32,
     st: vec2<f32>,
     mtx_index: u32,
     viewport_index: u32,
@@ -24,10 +23,11 @@ struct SrcVertex {
     fog: u32,
     modify_flags: u32,
     modify_screen: vec4<f32>,
+    line_other: u32,
 };
 struct GpuViewport { scale: vec4<f32>, trans: vec4<f32> };
 struct GpuTexcoord { scale_s: f32, scale_t: f32, texgen_scale_s: f32, texgen_scale_t: f32 };
-struct GpuLight { dir: vec4<f32>, col: vec4<f32> };
+struct GpuLight { dir: vec4<f32>, col: vec4<f32>, atten: vec4<f32> };
 struct GpuLookAt { axis_s: vec4<f32>, axis_t: vec4<f32> };
 struct OutVertex { pos: vec4<f32>, color: vec4<f32>, uv: vec2<f32> }; // std430 stride 48
 
@@ -40,6 +40,17 @@ struct OutVertex { pos: vec4<f32>, color: vec4<f32>, uv: vec2<f32> }; // std430
 @group(0) @binding(6) var<storage, read> lookat: array<GpuLookAt>;
 @group(0) @binding(7) var<storage, read_write> out: array<OutVertex>;
 
+// Fold the viewport into clip space: the GPU's divide by `w` then lands on the viewport pixel.
+fn viewport_fold(clip: vec4<f32>, w: f32, vp: GpuViewport) -> vec4<f32> {
+    let fb = params.screen.xy;
+    return vec4<f32>(
+        clip.x * (2.0 * vp.scale.x / fb.x) + w * (2.0 * vp.trans.x / fb.x - 1.0),
+        clip.y * (2.0 * vp.scale.y / fb.y) + w * (1.0 - 2.0 * vp.trans.y / fb.y),
+        clip.z * vp.scale.z + w * vp.trans.z,
+        w,
+    );
+}
+
 @compute @workgroup_size(64)
 fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     let vi = gid.x;
@@ -58,21 +69,34 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     if (w == 0.0) { w = 1e-6; } // w==0 guard before the viewport fold
 
     var o: OutVertex;
-    o.pos = vec4<f32>(
-        clip.x * (2.0 * vp.scale.x / FB_WIDTH)  + w * (2.0 * vp.trans.x / FB_WIDTH  - 1.0),
-        clip.y * (2.0 * vp.scale.y / FB_HEIGHT) + w * (1.0 - 2.0 * vp.trans.y / FB_HEIGHT),
-        clip.z * vp.scale.z + w * vp.trans.z,
-        w,
-    );
+    o.pos = viewport_fold(clip, w, vp);
     // gSPModifyVertex screen overrides. Rebuild clip = ndc*w so the GPU's perspective divide
     // lands on the requested pixel/depth while keeping the shader-computed w for correct UVs.
     if ((v.modify_flags & 1u) != 0u) {
-        o.pos.x = (2.0 * v.modify_screen.x / FB_WIDTH - 1.0) * w;
-        o.pos.y = (1.0 - 2.0 * v.modify_screen.y / FB_HEIGHT) * w;
+        o.pos.x = (2.0 * v.modify_screen.x / params.screen.x - 1.0) * w;
+        o.pos.y = (1.0 - 2.0 * v.modify_screen.y / params.screen.y) * w;
     }
     if ((v.modify_flags & 2u) != 0u) {
         o.pos.z = v.modify_screen.z * w;
     }
+    // G_LINE3D corner: push the vertex `line_offset` pixels off the segment towards its partner
+    // endpoint, perpendicular to it in screen space. The partner is folded the same way so both
+    // ends of a quad agree on the direction.
+    if (v.line_offset != 0.0) {
+        let other = src[v.line_other];
+        let other_clip = mvp_table[other.mtx_index] * vec4<f32>(other.pos, 1.0);
+        var other_w = other_clip.w;
+        if (other_w == 0.0) { other_w = 1e-6; }
+        let other_pos = viewport_fold(other_clip, other_w, viewport_table[other.viewport_index]);
+        let half_fb = params.screen.xy * 0.5;
+        var dir = (other_pos.xy / other_w - o.pos.xy / w) * half_fb;
+        if (dot(dir, dir) < 1e-12) {
+            dir = vec2<f32>(1.0, 0.0);
+        }
+        dir = normalize(dir);
+        o.pos.x += -dir.y * v.line_offset / half_fb.x * w;
+        o.pos.y += dir.x * v.line_offset / half_fb.y * w;
+    }
     // Prefolded scale (f64-computed CPU-side): one f32 multiply per axis (spec §2 Precision).
     o.uv = vec2<f32>(v.st.x * tc.scale_s, v.st.y * tc.scale_t);
 
@@ -96,10 +120,21 @@ fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
         let li = v.light_index;
         // Ambient = last light entry
         var c = lights[li + lc - 1u].col.rgb;
-        // Diffuse accumulation
+        // Diffuse accumulation. A point light (atten.x = kc > 0) carries its object-space
+        // position in dir; atten.w rescales object-space distance for the kc + kl*d + kq*d^2 falloff.
         for (var k: u32 = 0u; k < lc - 1u; k++) {
-            let nl = max(dot(n, lights[li + k].dir.xyz), 0.0);
-            c += nl * lights[li + k].col.rgb;
+            let l = lights[li + k];
+            if (l.atten.x > 0.0) {
+                let d = l.dir.xyz - v.pos;
+                let len = length(d);
+                let dist = len * l.atten.w;
+                var nl = 0.0;
+                if (len > 0.0) { nl = max(dot(n, d / len), 0.0); }
+                c += nl / (l.atten.x + l.atten.y * dist + l.atten.z * dist * dist) * l.col.rgb;
+            } else {
+                let nl = max(dot(n, l.dir.xyz), 0.0);
+                c += nl * l.col.rgb;
+            }
         }
         o.color = vec4<f32>(min(c, vec3<f32>(1.0)), alpha);
     } else {
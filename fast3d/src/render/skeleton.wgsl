@@ -12,12 +12,16 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     // Non-fog runs have P != CLR_FOG, so this branch is never taken → zero output change.
     let p1 = (combiner.blender_mux >> 14u) & 3u;
     if (p1 == 3u) {
-        rgb = mix(rgb, combiner.fog_color.rgb, in.color.a);
+        rgb = mix(rgb, combiner.fog_color.rgb, shade_color(in).a);
     }
-    // Phase D: alpha-test discard. Only active when alpha_mode != 0 (CVG_X_ALPHA or THRESHOLD).
-    // alpha_mode == 0 → NO discard → non-cutout runs are byte-identical (zero regression risk).
-    if (combiner.alpha_mode != 0u && r.alpha < combiner.alpha_threshold) {
+    // Phase D: alpha-test discard. Only active when alpha_mode != 0 (CVG_X_ALPHA, THRESHOLD or
+    // DITHER). alpha_mode == 0 → NO discard → non-cutout runs are byte-identical.
+    if (alpha_rejected(r.alpha, in.clip_position.xy)) {
         discard;
     }
-    return vec4<f32>(rgb, r.alpha);
+    // G_CK_KEY: drop pixels whose combined color falls inside the chroma-key range.
+    if (chroma_keyed(r.rgb)) {
+        discard;
+    }
+    return vec4<f32>(quantize_5551(rgb, in.clip_position.xy), r.alpha);
 }
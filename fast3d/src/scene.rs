@@ -17,6 +17,9 @@ pub struct DrawRun {
     pub material_index: u32,
     pub render_mode_index: u32,
     pub cull: CullKind,
+    /// G_SHADING_SMOOTH was clear: shade takes the provoking vertex's color, which `draw_tri`
+    /// rotates to the first index of each triangle.
+    pub flat: bool,
     pub index_count: u32,
     pub index_start: u32,
 }
@@ -92,6 +95,12 @@ pub struct Scene {
     pub modify_flags: Vec<u32>,
     /// gSPModifyVertex final screen target `[x_px, y_px, z_ndc, unused]`, index-parallel to `raw_pos`.
     pub modify_screen: Vec<[f32; 4]>,
+    /// G_LINE3D quad corners, index-parallel to `raw_pos`: the signed half-width in pixels this
+    /// vertex is pushed off its segment by (0 for an ordinary vertex). The renderer offsets the
+    /// corner perpendicular to the segment in screen space, so `indices` draws lines as quads.
+    pub line_offset: Vec<f32>,
+    /// For a line corner, the global vertex index of the segment's other endpoint.
+    pub line_other: Vec<u32>,
     /// Index into `mvp_table` of the MVP active when this vertex was loaded.
     pub mtx_index: Vec<u32>,
     /// Index into `viewport_table` of the viewport active when this vertex was loaded.
@@ -121,6 +130,10 @@ pub struct Scene {
     pub light_count: Vec<u32>,
     /// Concatenated object-space light sets: (dir_obj, col); the last entry of each set is ambient.
     pub lights_table: Vec<([f32; 3], [f32; 3])>,
+    /// Index-parallel to `lights_table`: `[kc, kl, kq, scale]` for a point light, whose
+    /// `lights_table` dir is then its object-space position and `scale` maps object-space
+    /// distance back to the modelview's output space. All zero for directional/ambient entries.
+    pub light_atten: Vec<[f32; 4]>,
     /// Texgen mode per vertex: 0 = off, 1 = spherical, 2 = linear (gated on G_LIGHTING).
     pub texgen_mode: Vec<u32>,
     /// Per-vertex fog flag: 1 when the G_FOG geometry bit was set at THIS vertex's load time, else 0.
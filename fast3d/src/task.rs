@@ -0,0 +1,157 @@
+//! libultra `OSTask` (sptask.h) decode for `Renderer::process_task`: the struct a game hands
+//! `osSpTaskStart`, read straight from guest RDRAM so a front-end can forward what the RSP receives.
+
+use crate::hle::mem::Rdram;
+use crate::microcode::{identify_microcode, microcode_hash, MicrocodeInfo};
+
+/// `OSTask.type` of a graphics task (`M_GFXTASK`). Audio/JPEG/other tasks are not display lists.
+pub const M_GFXTASK: u32 = 1;
+
+/// Byte size of a guest `OSTask_t`: sixteen 32-bit words.
+pub const OS_TASK_SIZE: u64 = 0x40;
+
+/// The fields of a guest `OSTask_t` (big-endian words, KSEG0 pointers as stored by the game).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OsTask {
+    pub task_type: u32,
+    pub flags: u32,
+    pub ucode_boot: u32,
+    pub ucode_boot_size: u32,
+    pub ucode: u32,
+    pub ucode_size: u32,
+    pub ucode_data: u32,
+    pub ucode_data_size: u32,
+    pub dram_stack: u32,
+    pub dram_stack_size: u32,
+    pub output_buff: u32,
+    pub output_buff_size: u32,
+    pub data_ptr: u32,
+    pub data_size: u32,
+    pub yield_data_ptr: u32,
+    pub yield_data_size: u32,
+}
+
+impl OsTask {
+    /// Read the `OSTask` at `addr` (a KSEG0 or physical address). `None` if it runs past RDRAM.
+    /// Only meaningful on a guest-layout backend (`RdramImage`): the words are big-endian u32s.
+    pub fn read(mem: &impl Rdram, addr: u64) -> Option<OsTask> {
+        let addr = mem.resolve_masked(addr);
+        if !mem.in_bounds(addr, OS_TASK_SIZE) {
+            return None;
+        }
+        let bytes = mem.read_bytes(addr, OS_TASK_SIZE as usize);
+        let (words, _) = bytes.as_chunks::<4>();
+        let w = |i: usize| u32::from_be_bytes(words[i]);
+        Some(OsTask {
+            task_type: w(0),
+            flags: w(1),
+            ucode_boot: w(2),
+            ucode_boot_size: w(3),
+            ucode: w(4),
+            ucode_size: w(5),
+            ucode_data: w(6),
+            ucode_data_size: w(7),
+            dram_stack: w(8),
+            dram_stack_size: w(9),
+            output_buff: w(10),
+            output_buff_size: w(11),
+            data_ptr: w(12),
+            data_size: w(13),
+            yield_data_ptr: w(14),
+            yield_data_size: w(15),
+        })
+    }
+
+    /// The microcode this task boots, identified from its text/data images the way
+    /// `G_LOAD_UCODE` does. `None` for an unrecognized image.
+    pub fn microcode(&self, mem: &impl Rdram) -> Option<MicrocodeInfo> {
+        identify_microcode(
+            mem,
+            mem.resolve_masked(self.ucode as u64),
+            mem.resolve_masked(self.ucode_data as u64),
+        )
+    }
+
+    /// `microcode_hash` of the task's text/data images, as an `UnknownMicrocode` diagnostic
+    /// reports it.
+    pub fn microcode_hash(&self, mem: &impl Rdram) -> u64 {
+        microcode_hash(
+            mem,
+            mem.resolve_masked(self.ucode as u64),
+            mem.resolve_masked(self.ucode_data as u64),
+        )
+    }
+
+    /// Physical address of the task's display list (`data_ptr`).
+    pub fn entry(&self, mem: &impl Rdram) -> u64 {
+        mem.resolve_masked(self.data_ptr as u64)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::hle::gbi::{fixture_ucode, GbiUcode, UCODE_TEXT_SIZE};
+    use crate::hle::mem::RdramImage;
+    use crate::Microcode;
+
+    pub(crate) const TASK_ADDR: u32 = 0x40;
+    pub(crate) const TEXT_ADDR: u32 = 0x1000;
+
+    /// A graphics `OSTask` at `TASK_ADDR` whose KSEG0 pointers name the microcode `image` (text,
+    /// then data) at `TEXT_ADDR` and `data_ptr`. `bytes` must already be large enough for `image`.
+    pub(crate) fn write_task(bytes: &mut [u8], image: &[u8], data_ptr: u32) {
+        let task = [
+            M_GFXTASK,
+            0,
+            0,
+            0,
+            0x8000_0000 | TEXT_ADDR,
+            UCODE_TEXT_SIZE as u32,
+            0x8000_0000 | (TEXT_ADDR + UCODE_TEXT_SIZE as u32),
+            0x800,
+            0,
+            0x400,
+            0,
+            0,
+            0x8000_0000 | data_ptr,
+            0x100,
+            0,
+            0,
+        ];
+        for (i, word) in task.iter().enumerate() {
+            let at = TASK_ADDR as usize + i * 4;
+            bytes[at..at + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        let text_at = TEXT_ADDR as usize;
+        bytes[text_at..text_at + image.len()].copy_from_slice(image);
+    }
+
+    #[test]
+    fn reads_kseg0_pointers_and_identifies_the_text() {
+        let mut bytes = vec![0u8; 0x3000];
+        write_task(&mut bytes, &fixture_ucode(GbiUcode::S2dex2), 0x2000);
+        let mem = RdramImage::new(&bytes);
+        let task = OsTask::read(&mem, 0x8000_0000 | TASK_ADDR as u64).expect("in bounds");
+        assert_eq!(task.task_type, M_GFXTASK);
+        assert_eq!(task.ucode_data_size, 0x800);
+        assert_eq!(task.entry(&mem), 0x2000);
+        let info = task.microcode(&mem).expect("S2DEX2 ident");
+        assert_eq!(info.microcode, Microcode::S2dex2);
+        assert_eq!(info.build, "S2DEX2.fifo 2.05");
+    }
+
+    #[test]
+    fn unknown_text_and_truncated_tasks() {
+        let mut bytes = vec![0u8; 0x3000];
+        write_task(&mut bytes, &[0xAA; 0x1800], 0x2000);
+        let mem = RdramImage::new(&bytes);
+        let task = OsTask::read(&mem, TASK_ADDR as u64).expect("in bounds");
+        assert_eq!(task.microcode(&mem), None);
+        assert_eq!(
+            task.microcode_hash(&mem),
+            microcode_hash(&mem, TEXT_ADDR as u64, TEXT_ADDR as u64 + UCODE_TEXT_SIZE)
+        );
+        assert_eq!(OsTask::read(&mem, 0x2FF0), None);
+    }
+}
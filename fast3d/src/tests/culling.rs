@@ -79,6 +79,7 @@ fn cull_back_marks_cull_run_no_swap() {
             material_index: 0,
             render_mode_index: 0,
             cull: CullKind::Cull,
+            flat: true,
             index_count: 3,
             index_start: 0,
         }]
@@ -95,11 +96,26 @@ fn cull_front_marks_cull_run_and_swaps() {
             material_index: 0,
             render_mode_index: 0,
             cull: CullKind::Cull,
+            flat: true,
             index_count: 3,
             index_start: 0,
         }]
     );
-    assert_eq!(s.indices, vec![2, 1, 0]); // front-cull swaps a<->c
+    // Front-cull swaps a<->c to [2, 1, 0]; flat shading then rotates the winding so vertex 0,
+    // F3DEX2's provoking vertex, leads again.
+    assert_eq!(s.indices, vec![0, 2, 1]);
+}
+
+#[test]
+fn shading_smooth_records_smooth_runs_in_swapped_order() {
+    use crate::hle::consts::{G_CULL_FRONT, G_SHADING_SMOOTH};
+    let flat = run_tri(G_CULL_FRONT, 0, 1, 2);
+    let smooth = run_tri(G_CULL_FRONT | G_SHADING_SMOOTH, 0, 1, 2);
+    assert!(flat.draw_runs[0].flat);
+    assert!(!smooth.draw_runs[0].flat);
+    // Only a flat run reorders for its provoking vertex; a smooth one keeps the plain a<->c swap.
+    assert_eq!(smooth.indices, vec![2, 1, 0]);
+    assert_eq!(flat.indices, vec![0, 2, 1]);
 }
 
 #[test]
@@ -118,6 +134,7 @@ fn no_cull_bits_records_none_run() {
             material_index: 0,
             render_mode_index: 0,
             cull: CullKind::None,
+            flat: true,
             index_count: 3,
             index_start: 0,
         }]
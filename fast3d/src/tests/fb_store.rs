@@ -258,3 +258,189 @@ fn clear_policy_persist_keeps_prior_frame_perframe_clears() {
         );
     }
 }
+
+/// Render `scene` through the store at native 64×64, then at `set_resolution_multiplier(2)`
+/// (128×128); returns both scanouts after checking the 2x walk stored under `addr`.
+fn native_and_2x(scene: &crate::hle::Scene, addr: u64) -> (Vec<u8>, Vec<u8>) {
+    let (device, queue, dual) = headless_device();
+    let mut sr = SceneRenderer::new(&device, FORMAT, 64, 64, dual);
+    let (_, native) = store_to_pixels(
+        &device,
+        &queue,
+        &mut sr,
+        scene,
+        ClearPolicy::PerFrame,
+        64,
+        64,
+    );
+    sr.set_resolution_multiplier(&device, 2);
+    let (stored, scaled) = store_to_pixels(
+        &device,
+        &queue,
+        &mut sr,
+        scene,
+        ClearPolicy::PerFrame,
+        128,
+        128,
+    );
+    assert_eq!(stored, Some(addr));
+    (native, scaled)
+}
+
+/// Every 2x pixel equals the native pixel it covers: each native pixel is a 2×2 block.
+fn assert_native_pixel_blocks(native: &[u8], scaled: &[u8]) {
+    for y in 0..128 {
+        for x in 0..128 {
+            assert_eq!(
+                pixel(scaled, 128, x, y),
+                pixel(native, 64, x / 2, y / 2),
+                "2x pixel ({x},{y}) must match native pixel ({},{})",
+                x / 2,
+                y / 2
+            );
+        }
+    }
+}
+
+/// Assemble an inline scene source with `tex_rgba8` as its texture and interpret it.
+fn inline_scene(src: &str, tex_rgba8: &[u8], tex_w: u32, tex_h: u32) -> crate::hle::Scene {
+    let img = crate::asm::assemble_with_texture(src, tex_rgba8, tex_w, tex_h)
+        .expect("inline scene must assemble");
+    let r = crate::hle::interpret_rdram(&img.rdram, img.entry_addr);
+    assert!(r.diags.is_empty(), "unexpected HLE diags: {:?}", r.diags);
+    r.scene
+}
+
+/// `set_resolution_multiplier(2)`: a paired 2D walk renders at twice the size, and every native
+/// pixel becomes a 2×2 block of the native result — the fill rect stays on native pixel
+/// boundaries.
+#[test]
+fn store_scales_2d_by_resolution_multiplier_on_native_pixel_blocks() {
+    let scene = dl_2d_fill_rect(0x0020_0000, 0x003F_003F, 5, 3, 20, 11);
+    let (native, scaled) = native_and_2x(&scene, 0x0020_0000);
+    assert_native_pixel_blocks(&native, &scaled);
+}
+
+/// An offset COPY TexRect of a 4×4 texture: at 2x it lands on the same native pixels with the
+/// same texels.
+const TEXRECT_AT_OFFSET_SRC: &str = r#"
+Texture tex = { 4, 4, RGBA16 }
+gsDPSetColorImage(G_IM_FMT_RGBA, G_IM_SIZ_16b, 64, 0x00100000)
+gsDPSetScissor(0, 0, 0, 256, 256)
+gsDPSetOtherMode_H(G_CYC_COPY)
+gsDPSetTextureFilter(G_TF_POINT)
+gsDPLoadTextureBlock(tex, G_IM_FMT_RGBA, G_IM_SIZ_16b, 4, 4)
+gsSPTextureRectangle(36, 48, 164, 112, 0, 0, 0, 4096, 1024)
+gsSPEndDisplayList()
+"#;
+
+#[test]
+fn texrect_at_2x_keeps_native_placement() {
+    #[rustfmt::skip]
+    let tex: Vec<u8> = [
+        [255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 0],
+        [0, 255, 255], [255, 0, 255], [255, 255, 255], [0, 0, 0],
+    ]
+    .iter()
+    .cycle()
+    .take(16)
+    .flat_map(|&[r, g, b]| [r, g, b, 255])
+    .collect();
+    let scene = inline_scene(TEXRECT_AT_OFFSET_SRC, &tex, 4, 4);
+    let (native, scaled) = native_and_2x(&scene, 0x0010_0000);
+    let clear = common::clear_color_rgb();
+    assert_eq!(pixel(&native, 64, 9, 11)[..3], clear, "above the rect");
+    assert_eq!(pixel(&native, 64, 8, 12)[..3], clear, "left of the rect");
+    assert_eq!(
+        pixel(&native, 64, 9, 12),
+        [255, 0, 0, 255],
+        "the rect's first texel"
+    );
+    assert_native_pixel_blocks(&native, &scaled);
+}
+
+/// A scratch pair filled red for 16 columns and green beyond, read back by the scanout pair's
+/// COPY TexRect through a mask-5 tile: the `fb_source` view wraps at 32 native texels.
+const FB_SOURCE_MASKED_SRC: &str = r#"
+gsDPSetColorImage(G_IM_FMT_RGBA, G_IM_SIZ_16b, 64, 0x00200000)
+gsDPSetScissor(0, 0, 0, 256, 256)
+gsDPSetOtherMode_H(G_CYC_FILL)
+gsDPSetFillColor(0x07C107C1)
+gsDPFillRectangle(0, 0, 256, 256)
+gsDPSetFillColor(0xF801F801)
+gsDPFillRectangle(0, 0, 60, 256)
+gsDPSetColorImage(G_IM_FMT_RGBA, G_IM_SIZ_16b, 64, 0x00100000)
+gsDPSetScissor(0, 0, 0, 256, 256)
+gsDPSetOtherMode_H(G_CYC_COPY)
+gsDPSetTextureImage(G_IM_FMT_RGBA, G_IM_SIZ_16b, 64, 0x00200000)
+gsDPSetTile(G_IM_FMT_RGBA, G_IM_SIZ_16b, 16, 0, G_TX_RENDERTILE, 0, 2, 0, 0, 0, 5, 0)
+gsDPSetTileSize(G_TX_RENDERTILE, 0, 0, 252, 252)
+gsSPTextureRectangle(0, 0, 256, 256, 0, 0, 0, 4096, 1024)
+gsSPEndDisplayList()
+"#;
+
+/// The `fb_source` view is the scratch pair at 128×128, but the tile mask still wraps at 32 native
+/// texels: the 2x readback repeats the native red/green bands.
+#[test]
+fn fb_source_readback_at_2x_addresses_native_texels() {
+    let scene = inline_scene(FB_SOURCE_MASKED_SRC, &[255u8; 4], 1, 1);
+    let (native, scaled) = native_and_2x(&scene, 0x0010_0000);
+    for x in 0..64 {
+        let red = x % 32 < 16;
+        assert_eq!(
+            pixel(&native, 64, x, 32),
+            if red {
+                [255, 0, 0, 255]
+            } else {
+                [0, 255, 0, 255]
+            },
+            "native column {x}"
+        );
+    }
+    assert_native_pixel_blocks(&native, &scaled);
+}
+
+/// An L3DEX2 line 7.5 native pixels wide (the 1.5 base plus 12 half-pixel steps): its width is in
+/// native pixels, so at 2x it covers the same rows (39..41 of the 64×64 target).
+#[test]
+fn line_at_2x_keeps_native_width() {
+    use crate::hle::rsp_f3dex2::cull_branch_tests::{run_as, scene_data};
+    let result = run_as(
+        scene_data(0.0, &[]),
+        &[n64_gbi::encode::gsp_line_w3d(0, 1, 12)],
+        crate::hle::gbi::GbiUcode::L3dex2,
+    );
+    assert!(result.diags.is_empty(), "{:?}", result.diags);
+    let (native, scaled) = native_and_2x(&result.scene, 0);
+    let clear = common::clear_color_rgb();
+    for (y, lit) in [(38, false), (39, true), (40, true), (41, false)] {
+        assert_eq!(pixel(&native, 64, 20, y)[..3] != clear, lit, "row {y}");
+    }
+    assert_native_pixel_blocks(&native, &scaled);
+}
+
+/// A pair-less z-buffered walk at 2x: the store FB and the depth buffer scale together, so the
+/// pass raises no color/depth attachment-size mismatch.
+#[test]
+fn store_scales_depth_with_resolution_multiplier() {
+    let (device, queue, dual) = headless_device();
+    let scene = scene_from_source("perspective-cube.n64", &[255u8; 4], 1, 1);
+    let mut sr = SceneRenderer::new(&device, FORMAT, 64, 64, dual);
+    sr.set_resolution_multiplier(&device, 2);
+    let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let (addr, _buf) = store_to_pixels(
+        &device,
+        &queue,
+        &mut sr,
+        &scene,
+        ClearPolicy::PerFrame,
+        128,
+        128,
+    );
+    let err = pollster::block_on(scope.pop());
+    assert!(addr.is_some(), "the walk must store a framebuffer");
+    assert!(
+        err.is_none(),
+        "the 128² store FB must match the 128² depth view; got {err:?}"
+    );
+}
@@ -15,7 +15,7 @@
 
 use crate::render::{
     headless_device, headless_device_forced_fallback, CombinerUniform, RspProcessParams,
-    RspProcessPipeline, TexturedPipeline, CLEAR_COLOR, DEPTH_FORMAT,
+    RspProcessPipeline, TexturedPipeline, CLEAR_COLOR, DEPTH_FORMAT, RSP_SCREEN, UNIFORM_STRIDE,
 };
 use wgpu::util::DeviceExt;
 
@@ -262,8 +262,8 @@ fn render_scene_with_device(
             address_mode_u: addr_u,
             address_mode_v: addr_v,
             address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
+            mag_filter: crate::render::sampler_filter(mat.tex_filter),
+            min_filter: crate::render::sampler_filter(mat.tex_filter),
             mipmap_filter: wgpu::MipmapFilterMode::Nearest,
             ..Default::default()
         });
@@ -311,12 +311,12 @@ fn render_scene_with_device(
     }
     let material_bg_refs: Vec<&wgpu::BindGroup> = material_bgs.iter().collect();
 
-    // --- Step 7: pooled uniform buffer (N_runs × 256 bytes) + @group(1) bind group. ---
-    // Each run's CombinerUniform sits at byte offset [i*256 .. i*256+48].  The BufferBinding
+    // --- Step 7: pooled uniform buffer (N_runs × UNIFORM_STRIDE bytes) + @group(1) bind group. ---
+    // Each run's CombinerUniform opens its slot at byte offset i*UNIFORM_STRIDE.  The BufferBinding
     // with explicit `size` (not as_entire_binding) keeps the binding within WebGL2's 16 KiB
     // max_uniform_buffer_binding_size for large run counts [MIN11].
     let n_runs = scene.draw_runs.len();
-    let mut pool = vec![0u8; n_runs * 256];
+    let mut pool = vec![0u8; n_runs * UNIFORM_STRIDE as usize];
     for (i, run) in scene.draw_runs.iter().enumerate() {
         let mat = &scene.materials[run.material_index as usize];
         let rm = &scene.render_modes[run.render_mode_index as usize];
@@ -331,8 +331,11 @@ fn render_scene_with_device(
         let mut combiner = CombinerUniform::from_run(mat, rm, fog_color);
         // Texcoord table is TEXEL-space: normalize by draw-time tile dims in the fragment.
         combiner.inv_tex_size = crate::render::triangle_inv_tex_size(scene, mat, run);
+        combiner.raster[0] = crate::render::triangle_flat_lane(run);
+        combiner.tile_origin = crate::render::triangle_tile_origin(scene, mat, run);
         let slot = bytemuck::bytes_of(&combiner);
-        pool[i * 256..i * 256 + slot.len()].copy_from_slice(slot);
+        pool[i * UNIFORM_STRIDE as usize..i * UNIFORM_STRIDE as usize + slot.len()]
+            .copy_from_slice(slot);
     }
     let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
         label: Some("golden-uniform-pool"),
@@ -385,6 +388,7 @@ fn render_scene_with_device(
             fog_enable: u32::from(scene.fog_enable),
             fog_mul: scene.fog_mul as f32,
             fog_offset: scene.fog_offset as f32,
+            screen: RSP_SCREEN,
         }),
         usage: wgpu::BufferUsages::UNIFORM,
     });
@@ -465,7 +469,7 @@ fn render_scene_with_device(
             CLEAR_COLOR,
             &material_bg_refs,
             &group1_bg,
-            256,
+            UNIFORM_STRIDE,
             &depth_view,
             &depth_sample_bg,
         );
@@ -484,7 +488,7 @@ fn render_scene_with_device(
             CLEAR_COLOR,
             &material_bg_refs,
             &group1_bg,
-            256,
+            UNIFORM_STRIDE,
             depth,
         );
     }
@@ -1600,6 +1604,45 @@ fn golden_alpha_threshold() {
     );
 }
 
+// ── Chroma-key scene (G_CK_KEY) ─────────────────────────────────────────────────────────────────
+
+const CHROMA_KEY_SRC: &str = include_str!("../../tests/scenes/chroma-key.n64");
+
+/// Golden test for the `chroma-key` scene — G_CK_KEY discards pixels inside the key range.
+///
+/// A green→red Gouraud quad keyed on pure green (scale 64, width 0xFFF): the leftmost quarter of
+/// the quad (every channel within 64 of the center) shows the BACKGROUND, the rest shows shade.
+#[test]
+fn golden_chroma_key() {
+    let px = render_scene_to_rgba8(CHROMA_KEY_SRC, &[], 64, 64);
+    let [bg_r, bg_g, bg_b] = common::clear_color_rgb();
+    let is_bg =
+        |p: [u8; 4]| p[0].abs_diff(bg_r) < 4 && p[1].abs_diff(bg_g) < 4 && p[2].abs_diff(bg_b) < 4;
+    // The quad spans x 8..56; red ramps 0→255 across it, so the key (red <= 64) ends near x=20.
+    for x in [10, 16] {
+        let keyed = common::pixel(&px, 64, x, 32);
+        assert!(
+            is_bg(keyed),
+            "keyed green edge (x={x}) must show background; got {keyed:?} — G_CK_KEY discard not firing?"
+        );
+    }
+    let past_key = common::pixel(&px, 64, 24, 32);
+    assert!(
+        !is_bg(past_key) && past_key[1] > 120,
+        "x=24 is just past the key range and must draw its green-leaning shade; got {past_key:?}"
+    );
+    let kept = common::pixel(&px, 64, 50, 32);
+    assert!(
+        kept[0] > 180 && kept[1] < 80,
+        "red side (x=50) lies outside the key and must draw shade; got {kept:?}"
+    );
+    for (x, y) in [(4, 32), (32, 4), (60, 32)] {
+        let out = common::pixel(&px, 64, x, y);
+        assert!(is_bg(out), "({x},{y}) lies outside the quad; got {out:?}");
+    }
+    compare_or_write("chroma-key", &px, 64, 64);
+}
+
 // ── Decal scene (Phase E Task E2) ─────────────────────────────────────────────────────────────
 
 const DECAL_SRC: &str = include_str!("../../tests/scenes/decal.n64");
@@ -1839,9 +1882,16 @@ fn tex1x1_material(rgba: [u8; 4]) -> crate::hle::Material {
         tex_enable: true,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 2,
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tile_count: 1,
         tex1: None,
         prim_lod_frac: 0.0,
@@ -2046,6 +2096,836 @@ fn golden_2d_hud_over_3d() {
     compare_or_write("2d-hud-over-3d", &buf, 64, 64);
 }
 
+/// `prim-depth`: a full-screen TEXRECT drawn under G_ZS_PRIM at a far constant primitive depth
+/// after a Z-buffered white quad. The rect shows everywhere but the center, where the nearer quad
+/// wins the depth test — a G_ZS_PIXEL (never-z-testing) rect would paint over it.
+#[test]
+fn golden_2d_prim_depth_sprite_behind_geometry() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("prim-depth.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    // The quad covers screen x/y 20..44; it must win the depth test over its whole interior.
+    for (x, y) in [(32, 32), (24, 24), (40, 40)] {
+        let c = common::pixel(&buf, 64, x, y);
+        assert!(
+            c[0] > 240 && c[1] > 240 && c[2] > 240,
+            "prim-depth: the nearer white quad must occlude the far sprite at ({x},{y}), got {c:?}"
+        );
+    }
+    // Outside it the 1:1 sprite shows the checker's opaque rows: red on even columns, green on
+    // odd ones, repeating every 4 pixels (the mask-0 tile wraps at the 4-texel texture).
+    for (x, y) in [(4, 4), (6, 5), (56, 60), (12, 32)] {
+        let p = common::pixel(&buf, 64, x, y);
+        assert!(
+            p[0] > 200 && p[1] < 60 && p[2] < 60,
+            "prim-depth: sprite texel at ({x},{y}) must be red, got {p:?}"
+        );
+    }
+    for (x, y) in [(5, 4), (7, 5), (57, 60), (13, 32)] {
+        let p = common::pixel(&buf, 64, x, y);
+        assert!(
+            p[1] > 200 && p[0] < 60 && p[2] < 60,
+            "prim-depth: sprite texel at ({x},{y}) must be green, got {p:?}"
+        );
+    }
+    compare_or_write("2d-prim-depth", &buf, 64, 64);
+}
+
+/// CPU half of `prim-depth`: the quad's run keeps per-pixel Z, the sprite's TEXRECT render mode
+/// carries G_ZS_PRIM plus the G_SETPRIMDEPTH Z.
+#[test]
+fn prim_depth_scene_records_zs_prim_on_the_texrect_only() {
+    let scene = common::scene_from_source("prim-depth.n64", RGBA16_QUAD_TEX, 4, 4);
+    let ops = &scene.framebuffer_pairs[0].ops;
+    let rm_of = |op: &crate::hle::SceneOp| match op {
+        crate::hle::SceneOp::Tris(run) => Some(scene.render_modes[run.render_mode_index as usize]),
+        crate::hle::SceneOp::TexRect {
+            render_mode_index, ..
+        } => Some(scene.render_modes[*render_mode_index as usize]),
+        _ => None,
+    };
+    let modes: Vec<_> = ops.iter().filter_map(rm_of).collect();
+    assert_eq!(modes.len(), 2, "one Tris run + one TexRect: {ops:?}");
+    assert!(!modes[0].z_source_prim && modes[0].prim_depth == 0);
+    assert!(modes[1].z_source_prim && modes[1].z_test && modes[1].z_write);
+    assert_eq!(modes[1].prim_depth, 0x7F00);
+}
+
+// ── Flat shading (G_SHADING_SMOOTH cleared) ────────────────────────────────────────────────────
+
+const FLAT_SHADE_SRC: &str = include_str!("../../tests/scenes/flat-shade.n64");
+
+/// `flat-shade` walked by F3D instead of F3DEX2: the assembled data section (viewport + three
+/// verts) is reused, and an F3D-encoded copy of the scene's command list is appended after it.
+fn flat_shade_f3d_scene() -> crate::hle::Scene {
+    use crate::hle::consts::rsp_f3d::G_SHADE;
+    use crate::hle::consts::{G_RM_OPA_SURF, G_RM_OPA_SURF2};
+    use n64_gbi::encode::{
+        gdp_set_color_image, gdp_set_combine_lerp, gdp_set_cycle_type_f3d, gdp_set_render_mode_f3d,
+        gdp_set_scissor, gsp_1triangle_f3d, gsp_enddl_f3d, gsp_set_geometrymode_f3d,
+        gsp_vertex_f3d, gsp_viewport_f3d, CcPass, ZERO_A, ZERO_C,
+    };
+
+    let img = crate::asm::assemble_with_texture(FLAT_SHADE_SRC, &[255u8; 4], 1, 1)
+        .unwrap_or_else(|d| panic!("assembly failed: {d:?}"));
+    let mut rdram = img.rdram;
+    rdram.resize(rdram.len().next_multiple_of(8), 0);
+    let entry = rdram.len() as u64;
+    let shade_c = CcPass {
+        a: ZERO_C,
+        b: ZERO_C,
+        c: ZERO_C,
+        d: 4,
+    };
+    let shade_a = CcPass {
+        a: ZERO_A,
+        b: ZERO_A,
+        c: ZERO_A,
+        d: 4,
+    };
+    for (w0, w1) in [
+        gdp_set_color_image(0, 2, 64, 0x0010_0000), // RGBA16
+        gdp_set_scissor(0, 0, 0, 256, 256),
+        gsp_viewport_f3d(img.vp_addr),
+        gsp_set_geometrymode_f3d(G_SHADE),
+        gdp_set_cycle_type_f3d(0),
+        gdp_set_render_mode_f3d(G_RM_OPA_SURF, G_RM_OPA_SURF2),
+        gdp_set_combine_lerp(shade_c, shade_a, shade_c, shade_a),
+        gsp_vertex_f3d(0, 3, img.vtx_addr),
+        gsp_1triangle_f3d(0, 1, 2),
+        gsp_enddl_f3d(),
+    ] {
+        rdram.extend_from_slice(&w0.to_be_bytes());
+        rdram.extend_from_slice(&w1.to_be_bytes());
+    }
+    let r = crate::hle::interpret(
+        crate::hle::mem::RdramImage::new(&rdram),
+        entry,
+        crate::hle::GbiUcode::F3d,
+        crate::hle::mem::GbiDataFormat::Fixed,
+    );
+    assert!(r.diags.is_empty(), "HLE diags: {:?}", r.diags);
+    r.scene
+}
+
+/// The flat run of a one-triangle paired scene and the RGBA of its leading (provoking) vertex.
+fn flat_run_lead_color(scene: &crate::hle::Scene) -> (bool, [u8; 4]) {
+    let run = scene.framebuffer_pairs[0]
+        .ops
+        .iter()
+        .find_map(|op| match op {
+            crate::hle::SceneOp::Tris(run) => Some(*run),
+            _ => None,
+        })
+        .expect("flat-shade records one Tris run");
+    let lead = scene.indices[run.index_start as usize] as usize;
+    (run.flat, scene.cn[lead].to_le_bytes())
+}
+
+/// CPU half of `flat-shade`: both microcodes mark the run flat, and each leads its triangle with
+/// its own provoking vertex — F3DEX2's first (red), F3D's last (blue).
+#[test]
+fn flat_shade_scene_leads_with_each_ucodes_provoking_vertex() {
+    let f3dex2 = common::scene_from_source("flat-shade.n64", &[255u8; 4], 1, 1);
+    assert_eq!(flat_run_lead_color(&f3dex2), (true, [255, 0, 0, 255]));
+    assert_eq!(
+        flat_run_lead_color(&flat_shade_f3d_scene()),
+        (true, [0, 0, 255, 255])
+    );
+}
+
+/// Render a `flat-shade` scene and check the triangle is one solid `rgb` (no Gouraud blend).
+fn render_flat_shade(scene: &crate::hle::Scene, rgb: [u8; 3], name: &str) {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, scene, 64, 64);
+    // Near each corner and at the center: a smooth triangle would show three different colors.
+    for (x, y) in [(32, 32), (6, 60), (58, 60), (32, 8)] {
+        let px = common::pixel(&buf, 64, x, y);
+        assert!(
+            px[..3].iter().zip(rgb).all(|(&a, b)| a.abs_diff(b) <= 8),
+            "{name}: ({x},{y}) must be the provoking vertex's {rgb:?}, got {px:?}"
+        );
+    }
+    // The apex is at the top center, so both top corners stay background.
+    let [bg_r, bg_g, bg_b] = common::clear_color_rgb();
+    for (x, y) in [(2, 2), (61, 2)] {
+        let px = common::pixel(&buf, 64, x, y);
+        assert!(
+            px[0].abs_diff(bg_r) <= 4 && px[1].abs_diff(bg_g) <= 4 && px[2].abs_diff(bg_b) <= 4,
+            "{name}: ({x},{y}) lies outside the triangle, got {px:?}"
+        );
+    }
+    compare_or_write(name, &buf, 64, 64);
+}
+
+/// `flat-shade` under F3DEX2: the whole triangle takes the FIRST vertex's red.
+#[test]
+fn golden_flat_shade_f3dex2_first_vertex() {
+    let scene = common::scene_from_source("flat-shade.n64", &[255u8; 4], 1, 1);
+    render_flat_shade(&scene, [255, 0, 0], "flat-shade-f3dex2");
+}
+
+/// `flat-shade` under F3D: the whole triangle takes the LAST vertex's blue.
+#[test]
+fn golden_flat_shade_f3d_last_vertex() {
+    render_flat_shade(&flat_shade_f3d_scene(), [0, 0, 255], "flat-shade-f3d");
+}
+
+/// `texture-filter` with its gsDPSetTextureFilter argument replaced by `filter`.
+fn texture_filter_scene(filter: &str) -> crate::hle::Scene {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/scenes/texture-filter.n64");
+    let src = std::fs::read_to_string(path)
+        .expect("texture-filter.n64 must exist")
+        .replace(
+            "gsDPSetTextureFilter(G_TF_POINT)",
+            &format!("gsDPSetTextureFilter({filter})"),
+        );
+    let img = crate::asm::assemble_with_texture(&src, RGBA16_QUAD_TEX, 4, 4)
+        .unwrap_or_else(|_| panic!("texture-filter ({filter}) must assemble"));
+    let r = crate::hle::interpret_rdram(&img.rdram, img.entry_addr);
+    assert!(r.diags.is_empty(), "unexpected HLE diags: {:?}", r.diags);
+    r.scene
+}
+
+/// CPU half of `texture-filter`: the triangle and the TEXRECT materials both carry the decoded
+/// G_MDSFT_TEXTFILT mode.
+#[test]
+fn texture_filter_scene_decodes_filter_on_tris_and_texrect() {
+    use crate::hle::{SceneOp, TextureFilter};
+    for (token, want) in [
+        ("G_TF_POINT", TextureFilter::Point),
+        ("G_TF_BILERP", TextureFilter::Bilerp),
+        ("G_TF_AVERAGE", TextureFilter::Average),
+    ] {
+        let scene = texture_filter_scene(token);
+        let filters: Vec<TextureFilter> = scene.framebuffer_pairs[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                SceneOp::Tris(run) => Some(run.material_index),
+                SceneOp::TexRect { material_index, .. } => Some(*material_index),
+                _ => None,
+            })
+            .map(|i| scene.materials[i as usize].tex_filter)
+            .collect();
+        assert_eq!(filters, vec![want; 2], "{token}");
+    }
+}
+
+/// Render `texture-filter` under `filter`. Pixel x=7 is the last column of texel 0 (red) on the
+/// triangle half and x=39 the same on the TEXRECT half: point sampling keeps it pure red, both
+/// filters pull in texel 1's green.
+fn render_texture_filter(filter: &str, name: &str) {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = texture_filter_scene(filter);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    for x in [7, 39] {
+        let px = common::pixel(&buf, 64, x, 4);
+        if filter == "G_TF_POINT" {
+            assert!(
+                px[0] > 240 && px[1] < 16,
+                "{name}: ({x},4) must be pure red, got {px:?}"
+            );
+        } else {
+            assert!(
+                px[1] > 40,
+                "{name}: ({x},4) must blend in green, got {px:?}"
+            );
+        }
+    }
+    // Inside a texel every filter stays on it: red in the opaque rows 0-1, blue in row 2 (the
+    // combiner forces alpha to 1, so the α=0 rows still draw). Point and average sample the
+    // texel's middle; the 3-point filter has no half-texel offset, so it is pure only at the
+    // texel's top-left corner.
+    let (dx, dy) = if filter == "G_TF_BILERP" {
+        (0, 1)
+    } else {
+        (3, 8)
+    };
+    for x in [dx, 32 + dx] {
+        let red = common::pixel(&buf, 64, x, dy);
+        assert!(
+            red[0] > 200 && red[1] < 60 && red[2] < 60,
+            "{name}: ({x},{dy}) is inside texel (0,0) and must be red, got {red:?}"
+        );
+        let blue = common::pixel(&buf, 64, x, 32 + dy);
+        assert!(
+            blue[2] > 200 && blue[0] < 60 && blue[1] < 60,
+            "{name}: ({x},{}) is inside texel (0,2) and must be blue, got {blue:?}",
+            32 + dy
+        );
+    }
+    compare_or_write(name, &buf, 64, 64);
+}
+
+#[test]
+fn golden_texture_filter_point() {
+    render_texture_filter("G_TF_POINT", "texture-filter-point");
+}
+
+#[test]
+fn golden_texture_filter_bilerp() {
+    render_texture_filter("G_TF_BILERP", "texture-filter-bilerp");
+}
+
+#[test]
+fn golden_texture_filter_average() {
+    render_texture_filter("G_TF_AVERAGE", "texture-filter-average");
+}
+
+/// CPU half of `tile-mask`: the render tile's shift/mask/clamp reach the material, and no
+/// hardware sampler reproduces them, so the renderer flags the tile for shader emulation.
+#[test]
+fn tile_mask_scene_flags_the_tile_for_emulation() {
+    let scene = common::scene_from_source("tile-mask.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mat = &scene.materials[0];
+    assert_eq!((mat.tex_w, mat.tex_h), (4, 2));
+    assert_eq!(mat.tile_addr.shift, [15, 0]);
+    assert_eq!(mat.tile_addr.mask, [1, 0]);
+    assert_eq!(mat.tile_addr.clamp, [12, 4]);
+    let [w0, _] = crate::render::tile_words(
+        [mat.wrap_s, mat.wrap_t],
+        &mat.tile_addr,
+        [mat.tex_w, mat.tex_h],
+    );
+    assert_ne!(w0 & crate::render::TILE_EMULATE, 0);
+}
+
+/// The scenes that load `quad_tex` with the default G_TX_WRAP, mask 0 tile wrap at the 4-texel
+/// texture size, so their TEXRECTs keep the plain Repeat sampler they used before tile emulation
+/// existed and the committed goldens still describe them.
+#[test]
+fn mask_zero_texrect_scenes_keep_the_repeat_sampler() {
+    for file in [
+        "fill-texrect.n64",
+        "hud-over-3d.n64",
+        "texrectflip.n64",
+        "prim-depth.n64",
+    ] {
+        let scene = common::scene_from_source(file, RGBA16_QUAD_TEX, 4, 4);
+        let rects: Vec<_> = scene
+            .framebuffer_pairs
+            .iter()
+            .flat_map(|pair| &pair.ops)
+            .filter_map(|op| match op {
+                crate::hle::SceneOp::TexRect { material_index, .. } => {
+                    Some(&scene.materials[*material_index as usize])
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(!rects.is_empty(), "{file}: no TEXRECT recorded");
+        for mat in rects {
+            let wrap = [mat.wrap_s, mat.wrap_t];
+            let dims = [mat.tex_w, mat.tex_h];
+            assert_eq!(dims, [4, 4], "{file}");
+            let [w0, _] = crate::render::tile_words(wrap, &mat.tile_addr, dims);
+            assert_eq!(w0 & crate::render::TILE_EMULATE, 0, "{file}: tile emulated");
+            assert_eq!(
+                crate::render::tile_sampler(wrap, &mat.tile_addr, dims),
+                [0, 0],
+                "{file}: not the Repeat sampler"
+            );
+        }
+    }
+}
+
+/// `tile-mask`: the shifted S folds into 8-px red/green stripes, and T clamps to texrow 1 so the
+/// blue/yellow rows never appear.
+#[test]
+fn golden_tile_mask() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("tile-mask.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    for (x, y, rgb) in [
+        (4, 4, [255, 0, 0]),
+        (12, 4, [0, 255, 0]),
+        (20, 4, [255, 0, 0]),
+        (4, 60, [255, 0, 0]),
+        (60, 60, [0, 255, 0]),
+    ] {
+        let px = common::pixel(&buf, 64, x, y);
+        assert!(
+            px[..3].iter().zip(rgb).all(|(&a, b)| a.abs_diff(b) <= 8),
+            "tile-mask: ({x},{y}) must be {rgb:?}, got {px:?}"
+        );
+    }
+    compare_or_write("tile-mask", &buf, 64, 64);
+}
+
+/// CPU half of `tile-mirror`: the mask-0 mirrored S axis keeps the MirrorRepeat sampler, and the
+/// 2-texel T mask sends the tile through shader emulation.
+#[test]
+fn tile_mirror_scene_mirrors_mask_zero_at_the_texture_size() {
+    let scene = common::scene_from_source("tile-mirror.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mat = &scene.materials[0];
+    assert_eq!((mat.tex_w, mat.tex_h), (4, 4));
+    assert_eq!([mat.wrap_s, mat.wrap_t], [1, 0]);
+    assert_eq!(mat.tile_addr.mask, [0, 1]);
+    assert_eq!(
+        crate::render::sampler_wrap(mat.wrap_s, 0, 0, mat.tile_addr.clamp[0], 4),
+        Some(1)
+    );
+    let [w0, _] = crate::render::tile_words(
+        [mat.wrap_s, mat.wrap_t],
+        &mat.tile_addr,
+        [mat.tex_w, mat.tex_h],
+    );
+    assert_ne!(w0 & crate::render::TILE_EMULATE, 0);
+}
+
+/// `tile-mirror`: S mirrors at the texture size (the two green texel-3 stripes meet at the
+/// center instead of clamping to green), and T repeats texrows 0–1 so blue/yellow never appear.
+#[test]
+fn golden_tile_mirror() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("tile-mirror.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    const RED: [u8; 3] = [255, 0, 0];
+    const GREEN: [u8; 3] = [0, 255, 0];
+    let stripes = [RED, GREEN, RED, GREEN, GREEN, RED, GREEN, RED];
+    for (i, rgb) in stripes.into_iter().enumerate() {
+        let x = 8 * i as u32 + 4;
+        for y in [4, 20, 44, 60] {
+            let px = common::pixel(&buf, 64, x, y);
+            assert!(
+                px[..3].iter().zip(rgb).all(|(&a, b)| a.abs_diff(b) <= 8),
+                "tile-mirror: ({x},{y}) must be {rgb:?}, got {px:?}"
+            );
+        }
+    }
+    compare_or_write("tile-mirror", &buf, 64, 64);
+}
+
+#[test]
+fn tile_scroll_scene_subtracts_the_tile_origin_at_draw_time() {
+    let scene = common::scene_from_source("tile-scroll.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mat = &scene.materials[0];
+    assert_eq!(mat.tile_addr.origin, [4, 8]);
+    let run = scene.framebuffer_pairs[0]
+        .ops
+        .iter()
+        .find_map(|op| match op {
+            crate::hle::SceneOp::Tris(run) => Some(run),
+            _ => None,
+        })
+        .expect("tile-scroll draws triangles");
+    assert_eq!(
+        crate::render::triangle_tile_origin(&scene, mat, run),
+        [1.0, 2.0, 0.0, 0.0]
+    );
+    // The exact wrap sampler still serves a moved origin.
+    let [w0, _] = crate::render::tile_words(
+        [mat.wrap_s, mat.wrap_t],
+        &mat.tile_addr,
+        [mat.tex_w, mat.tex_h],
+    );
+    assert_eq!((mat.tex_w, mat.tex_h), (4, 4), "the tile spans uls..=lrs");
+    assert_eq!(w0 & crate::render::TILE_EMULATE, 0);
+    // The texcoord table never sees the tile: an unscrolled tile yields the same entries.
+    let path =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/scenes/tile-scroll.n64");
+    let src = std::fs::read_to_string(path)
+        .expect("tile-scroll.n64 must exist")
+        .replace(
+            "gsDPSetTileSize(G_TX_RENDERTILE, 4, 8, 16, 20)",
+            "gsDPSetTileSize(G_TX_RENDERTILE, 0, 0, 12, 12)",
+        );
+    let img = crate::asm::assemble_with_texture(&src, RGBA16_QUAD_TEX, 4, 4)
+        .expect("unscrolled tile-scroll must assemble");
+    let still = crate::hle::interpret_rdram(&img.rdram, img.entry_addr).scene;
+    assert_eq!(still.texcoord_table, scene.texcoord_table);
+    assert_eq!(still.materials[0].tile_addr.origin, [0, 0]);
+}
+
+/// `tile-scroll`: the (1, 2)-texel tile origin rolls the checker one column left and two rows up,
+/// so the blue/yellow rows land on top.
+#[test]
+fn golden_tile_scroll() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("tile-scroll.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    for (x, y, rgb) in [
+        (8, 8, [255, 255, 0]),
+        (24, 8, [0, 0, 255]),
+        (24, 40, [255, 0, 0]),
+        (40, 40, [0, 255, 0]),
+        (40, 8, [255, 255, 0]),
+        (56, 8, [0, 0, 255]),
+        (56, 24, [0, 0, 255]),
+        (8, 56, [0, 255, 0]),
+        (56, 56, [255, 0, 0]),
+    ] {
+        let px = common::pixel(&buf, 64, x, y);
+        assert!(
+            px[..3].iter().zip(rgb).all(|(&a, b)| a.abs_diff(b) <= 8),
+            "tile-scroll: ({x},{y}) must be {rgb:?}, got {px:?}"
+        );
+    }
+    compare_or_write("tile-scroll", &buf, 64, 64);
+}
+
+/// `texture-persp.n64` with its `gsDPSetTexturePersp` argument replaced by `mode`.
+fn texture_persp_scene(mode: &str) -> crate::hle::Scene {
+    let path =
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/scenes/texture-persp.n64");
+    let src = std::fs::read_to_string(path)
+        .expect("texture-persp.n64 must exist")
+        .replace(
+            "gsDPSetTexturePersp(G_TP_NONE)",
+            &format!("gsDPSetTexturePersp({mode})"),
+        );
+    let img = crate::asm::assemble_with_texture(&src, RGBA16_QUAD_TEX, 4, 4)
+        .unwrap_or_else(|_| panic!("texture-persp ({mode}) must assemble"));
+    let r = crate::hle::interpret_rdram(&img.rdram, img.entry_addr);
+    assert!(r.diags.is_empty(), "unexpected HLE diags: {:?}", r.diags);
+    r.scene
+}
+
+#[test]
+fn texture_persp_scene_decodes_textpersp_per_run() {
+    for (mode, want) in [("G_TP_NONE", false), ("G_TP_PERSP", true)] {
+        let scene = texture_persp_scene(mode);
+        let persp: Vec<bool> = scene.framebuffer_pairs[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                crate::hle::SceneOp::Tris(run) => {
+                    Some(scene.render_modes[run.render_mode_index as usize].tex_persp)
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(!persp.is_empty(), "{mode}: the floor must draw");
+        assert!(persp.iter().all(|&p| p == want), "{mode}: {persp:?}");
+    }
+}
+
+/// `texture-persp`: the same receding floor with G_TP_NONE (affine: evenly spaced checker rows
+/// bent at the quad diagonal) and G_TP_PERSP (rows compress toward the horizon).
+#[test]
+fn golden_texture_persp_affine_warp() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let affine = texture_persp_scene("G_TP_NONE");
+    let affine_buf = common::render_to_pixels(&device, &queue, &mut sr, &affine, 64, 64);
+    let persp = texture_persp_scene("G_TP_PERSP");
+    let persp_buf = common::render_to_pixels(&device, &queue, &mut sr, &persp, 64, 64);
+    let differing = affine_buf
+        .as_chunks::<4>()
+        .0
+        .iter()
+        .zip(persp_buf.as_chunks::<4>().0)
+        .filter(|(a, b)| a.iter().zip(b.iter()).any(|(&x, &y)| x.abs_diff(y) > 8))
+        .count();
+    assert!(
+        differing > 64,
+        "affine texcoords must warp the floor visibly, only {differing} pixels differ"
+    );
+    // The floor projects below the horizon (screen y ≈ 35..60): the top of the screen stays
+    // background either way, and the near edge samples the checker's opaque red/green rows.
+    let [bg_r, bg_g, bg_b] = common::clear_color_rgb();
+    for (name, buf) in [("affine", &affine_buf), ("persp", &persp_buf)] {
+        for (x, y) in [(32, 8), (4, 30), (60, 30)] {
+            let px = common::pixel(buf, 64, x, y);
+            assert!(
+                px[0].abs_diff(bg_r) <= 4 && px[1].abs_diff(bg_g) <= 4 && px[2].abs_diff(bg_b) <= 4,
+                "{name}: ({x},{y}) lies above the floor, got {px:?}"
+            );
+        }
+        let near = common::pixel(buf, 64, 20, 58);
+        assert!(
+            near[0].max(near[1]) > 200 && near[2] < 60,
+            "{name}: the near edge (20,58) must show texrow 0's red/green, got {near:?}"
+        );
+    }
+    compare_or_write("texture-persp-none", &affine_buf, 64, 64);
+    compare_or_write("texture-persp", &persp_buf, 64, 64);
+}
+
+#[test]
+fn dither_scene_decodes_dither_compare_and_modes() {
+    let scene = common::scene_from_source("dither.n64", RGBA16_QUAD_TEX, 4, 4);
+    let rm = scene.framebuffer_pairs[0]
+        .ops
+        .iter()
+        .find_map(|op| match op {
+            crate::hle::SceneOp::Tris(run) => {
+                Some(scene.render_modes[run.render_mode_index as usize])
+            }
+            _ => None,
+        })
+        .expect("dither: the quad must draw");
+    assert_eq!(rm.alpha_compare, crate::hle::AlphaCompare::Dither);
+    assert_eq!(rm.color_dither, crate::hle::ColorDither::Bayer);
+    assert_eq!(rm.alpha_dither, crate::hle::AlphaDither::Pattern);
+}
+
+/// `dither`: G_AC_DITHER's screen door thins the quad out toward its transparent left edge, and
+/// with RGBA5551 output every kept pixel lands on a 5-bit level.
+#[test]
+fn golden_dither_screen_door() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("dither.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    sr.set_quantize_5551(true);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    // Kept pixels carry the quad's full blue; discarded ones show the cleared framebuffer.
+    let kept = |x0: u32| {
+        (0..64)
+            .flat_map(|y| (x0..x0 + 16).map(move |x| (x, y)))
+            .filter(|&(x, y)| common::pixel(&buf, 64, x, y)[2] > 200)
+            .count()
+    };
+    let (left, middle, right) = (kept(0), kept(24), kept(48));
+    assert!(
+        left < middle && middle < right,
+        "screen door must thin out toward alpha 0: {left} / {middle} / {right}"
+    );
+    assert!(
+        middle > 128 && middle < 896,
+        "the middle band must be a partial mesh, {middle} of 1024 kept"
+    );
+    let on_5bit_grid = |v: u8| v == ((v >> 3) << 3) | (v >> 5);
+    let [bg_r, bg_g, bg_b] = common::clear_color_rgb();
+    for y in 0..64 {
+        for x in 0..64 {
+            let px = common::pixel(&buf, 64, x, y);
+            if px[2] > 200 {
+                assert!(
+                    px[..3].iter().all(|&c| on_5bit_grid(c)),
+                    "({x},{y}) must be quantized to RGBA5551, got {px:?}"
+                );
+            } else {
+                // The compare discards whole pixels: nothing half-blended shows through.
+                assert!(
+                    px[0].abs_diff(bg_r) <= 4
+                        && px[1].abs_diff(bg_g) <= 4
+                        && px[2].abs_diff(bg_b) <= 4,
+                    "({x},{y}) must be either kept or background, got {px:?}"
+                );
+            }
+        }
+    }
+    compare_or_write("dither", &buf, 64, 64);
+}
+
+/// `blend-2cycle`: a blender chain that blends in its first cycle takes the framebuffer-read
+/// blender, while the opaque quad under it keeps the fixed-function path.
+#[test]
+fn blend_2cycle_scene_routes_chained_run_to_fb_blend() {
+    let scene = common::scene_from_source("blend-2cycle.n64", RGBA16_QUAD_TEX, 4, 4);
+    let fb_blend: Vec<bool> = scene.framebuffer_pairs[0]
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            crate::hle::SceneOp::Tris(run) => {
+                Some(scene.render_modes[run.render_mode_index as usize].fb_blend)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fb_blend, vec![false, true]);
+}
+
+/// `blend-2cycle`: cycle 1 swaps the green input for the blue blend color before cycle 2 lerps it
+/// onto the red framebuffer.
+#[test]
+fn golden_blend_2cycle() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("blend-2cycle.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    for y in [4, 32, 60] {
+        for x in [4, 16, 28] {
+            let [r, g, b, _] = common::pixel(&buf, 64, x, y);
+            assert!(
+                r > 240 && g < 16 && b < 16,
+                "left half stays red at ({x},{y}), got ({r},{g},{b})"
+            );
+        }
+        for x in [36, 48, 60] {
+            let [r, g, b, _] = common::pixel(&buf, 64, x, y);
+            assert!(
+                (112..144).contains(&r) && g < 16 && (112..144).contains(&b),
+                "right half mixes blue over red at ({x},{y}), got ({r},{g},{b})"
+            );
+        }
+    }
+    compare_or_write("blend-2cycle", &buf, 64, 64);
+}
+
+/// The `combine-*` scenes each reference one of the selectors wired last (NOISE and the *_ALPHA
+/// broadcasts): every one decodes to its selector and draws — `scene_from_source` already refuses
+/// any `UnwiredSelector` diagnostic.
+#[test]
+fn combine_selector_scenes_decode_and_draw() {
+    use crate::hle::combiner::ColorIn;
+    for (name, ca, cc) in [
+        ("combine-noise.n64", ColorIn::Noise, ColorIn::Primitive),
+        (
+            "combine-shade-alpha.n64",
+            ColorIn::Shade,
+            ColorIn::ShadeAlpha,
+        ),
+        (
+            "combine-prim-alpha.n64",
+            ColorIn::Primitive,
+            ColorIn::PrimitiveAlpha,
+        ),
+        (
+            "combine-env-alpha.n64",
+            ColorIn::Environment,
+            ColorIn::EnvAlpha,
+        ),
+        (
+            "combine-combined-alpha.n64",
+            ColorIn::Primitive,
+            ColorIn::CombinedAlpha,
+        ),
+    ] {
+        let scene = common::scene_from_source(name, RGBA16_QUAD_TEX, 4, 4);
+        let mat = scene.framebuffer_pairs[0]
+            .ops
+            .iter()
+            .find_map(|op| match op {
+                crate::hle::SceneOp::Tris(run) => {
+                    Some(&scene.materials[run.material_index as usize])
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("{name}: the quad must draw"));
+        assert_eq!(
+            (mat.selectors.cyc1.ca, mat.selectors.cyc1.cc),
+            (ca, cc),
+            "{name}"
+        );
+    }
+}
+
+/// Render a `combine-*` scene and return its pixels.
+fn render_combine_scene(name: &str) -> Vec<u8> {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source(name, RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64)
+}
+
+/// `combine-noise`: NOISE is gray, spread over the RDP's noise levels, and re-rolled by
+/// `begin_frame`. A fresh renderer's first frame always uses seed 0, so it has a golden.
+#[test]
+fn golden_combine_noise() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("combine-noise.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    let mut levels = std::collections::BTreeSet::new();
+    for y in 0..64 {
+        for x in 0..64 {
+            let [r, g, b, _] = common::pixel(&buf, 64, x, y);
+            assert!(
+                r == g && g == b,
+                "({x},{y}) noise must be gray, got ({r},{g},{b})"
+            );
+            levels.insert(r);
+        }
+    }
+    assert!(
+        levels.len() >= 6,
+        "noise must spread over the levels, got {levels:?}"
+    );
+    compare_or_write("combine-noise", &buf, 64, 64);
+    sr.begin_frame();
+    let next = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    assert_ne!(buf, next, "a new frame must re-roll the noise");
+}
+
+/// Check a `combine-*` render is a left-to-right gray ramp: gray on every row, rising across each
+/// row from dark on the left to light on the right.
+fn assert_gray_ramp(buf: &[u8], name: &str) {
+    for y in [4, 32, 60] {
+        let mut prev = 0;
+        for x in (0..64).step_by(4) {
+            let [r, g, b, _] = common::pixel(buf, 64, x, y);
+            assert!(
+                r == g && g == b && r >= prev,
+                "{name}: ({x},{y}) must be gray and no darker than its left neighbor {prev}, \
+                 got ({r},{g},{b})"
+            );
+            prev = r;
+        }
+        let [r, ..] = common::pixel(buf, 64, 8, y);
+        assert!(r < 48, "{name}: left end is dark gray, got {r}");
+        let [r, ..] = common::pixel(buf, 64, 56, y);
+        assert!(r > 208, "{name}: right end is light gray, got {r}");
+    }
+}
+
+/// Check a `combine-*` render is one flat color: within ±4 per channel, exactly 0 where `rgb` is.
+fn assert_flat_color(buf: &[u8], name: &str, rgb: [u8; 3]) {
+    for (x, y) in [(32, 32), (2, 2), (61, 2), (2, 61), (61, 61)] {
+        let px = common::pixel(buf, 64, x, y);
+        assert!(
+            px[..3]
+                .iter()
+                .zip(rgb)
+                .all(|(&a, b)| a.abs_diff(b) <= if b == 0 { 0 } else { 4 }),
+            "{name}: ({x},{y}) expected ≈{rgb:?}, got {px:?}"
+        );
+    }
+}
+
+/// `combine-shade-alpha`: SHADE_ALPHA turns the shade alpha ramp into a gray ramp.
+#[test]
+fn golden_combine_shade_alpha() {
+    let buf = render_combine_scene("combine-shade-alpha.n64");
+    assert_gray_ramp(&buf, "combine-shade-alpha");
+    compare_or_write("combine-shade-alpha", &buf, 64, 64);
+}
+
+/// `combine-prim-alpha`: PRIMITIVE_ALPHA (128) halves the orange primitive color.
+#[test]
+fn golden_combine_prim_alpha() {
+    let buf = render_combine_scene("combine-prim-alpha.n64");
+    assert_flat_color(&buf, "combine-prim-alpha", [128, 64, 0]);
+    compare_or_write("combine-prim-alpha", &buf, 64, 64);
+}
+
+/// `combine-env-alpha`: ENV_ALPHA (192) scales the azure environment color to three quarters.
+#[test]
+fn golden_combine_env_alpha() {
+    let buf = render_combine_scene("combine-env-alpha.n64");
+    assert_flat_color(&buf, "combine-env-alpha", [0, 96, 192]);
+    compare_or_write("combine-env-alpha", &buf, 64, 64);
+}
+
+/// `combine-combined-alpha`: cycle 2's COMBINED_ALPHA reads cycle 1's shade alpha ramp, not the
+/// red shade color.
+#[test]
+fn golden_combine_combined_alpha() {
+    let buf = render_combine_scene("combine-combined-alpha.n64");
+    assert_gray_ramp(&buf, "combine-combined-alpha");
+    compare_or_write("combine-combined-alpha", &buf, 64, 64);
+}
+
 /// Scene 4 — `texrectflip`: COPY TEXRECT with S/T axes swapped (`gsSPTextureRectangleFlip`). The
 /// flipped UVs transpose the checker vs `fill-texrect`'s un-flipped layout.
 #[test]
@@ -2290,9 +3170,16 @@ fn copy_alpha_keyed_scene() -> crate::hle::Scene {
         tex_enable: true,
         wrap_s: 0,
         wrap_t: 0,
+        tile_addr: crate::hle::TileAddress::whole(2, 2),
         fmt: 0,                            // RGBA
         siz: 2,                            // 16b (RGBA5551, 1-bit alpha)
         blend_color: [255, 255, 255, 255], // sm64 sets blend_color.a = 255 → threshold must NOT be blend_a
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tile_count: 1,
         tex1: None,
         prim_lod_frac: 0.0,
@@ -2398,9 +3285,9 @@ fn golden_2d_copy_alpha_keyed_over_bg() {
 /// This is the pair-LESS form (flat `draw_runs`). Mirrors `render.rs::build_decal_smoke_scene`.
 ///
 /// The viewport uses the canonical FB_WIDTH/2, FB_HEIGHT/2 (160, 120) so the RSP-process fold
-/// (`rsp_process.wgsl`, which hardcodes FB_WIDTH=320 / FB_HEIGHT=240) maps the NDC `[-1,1]` quad to
-/// the FULL render target at ANY target size — so both the pair-less render and a paired render
-/// into any-size FB fill the whole target.
+/// (`rsp_process.wgsl`, fed FB_WIDTH=320 / FB_HEIGHT=240 via `RSP_SCREEN`) maps the NDC `[-1,1]`
+/// quad to the FULL render target at ANY target size — so both the pair-less render and a paired
+/// render into any-size FB fill the whole target.
 fn build_decal_scene() -> crate::hle::Scene {
     // PRIM-passthrough combiner (combine_l=0, combine_h=0xC3 → cd1=PRIM, ad1=PRIM).
     let selectors = crate::hle::combiner::decode_combine(0x0000_0000, 0x0000_00C3);
@@ -2413,9 +3300,16 @@ fn build_decal_scene() -> crate::hle::Scene {
         prim,
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -2480,6 +3374,7 @@ fn build_decal_scene() -> crate::hle::Scene {
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 0,
         },
@@ -2487,6 +3382,7 @@ fn build_decal_scene() -> crate::hle::Scene {
             material_index: 1,
             render_mode_index: 1,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 6,
         },
@@ -2877,3 +3773,208 @@ fn one_cycle_material_ignores_a_lod_selector_living_in_the_dead_cyc0_slots() {
         "2-cycle material must flag its live cyc0 LOD reference (sanity check for the gate itself)"
     );
 }
+
+/// `coverage`: both TEX_EDGE runs decode the coverage fields, the first with CVG_DST_WRAP.
+#[test]
+fn coverage_scene_decodes_coverage_fields() {
+    let scene = common::scene_from_source("coverage.n64", RGBA16_QUAD_TEX, 4, 4);
+    let modes: Vec<crate::hle::RenderMode> = scene.framebuffer_pairs[0]
+        .ops
+        .iter()
+        .filter_map(|op| match op {
+            crate::hle::SceneOp::Tris(run) => {
+                Some(scene.render_modes[run.render_mode_index as usize])
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(modes.len(), 2);
+    for rm in &modes {
+        assert!(rm.uses_coverage() && rm.aa_en && rm.im_rd && rm.alpha_cvg_sel);
+        assert!(
+            !rm.fb_blend,
+            "TEX_EDGE is canonical without the coverage model"
+        );
+    }
+    assert_eq!(modes[0].cvg_dst, crate::hle::CvgDest::Wrap);
+    assert_eq!(modes[1].cvg_dst, crate::hle::CvgDest::Clamp);
+}
+
+/// `coverage`: the red quad overflows the cleared framebuffer's coverage and is written as is;
+/// the blue quad fits the four eighths CVG_DST_WRAP left behind and blends as an AA edge.
+#[test]
+fn golden_coverage() {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let scene = common::scene_from_source("coverage.n64", RGBA16_QUAD_TEX, 4, 4);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, 64, 64, dual);
+    sr.set_emulate_coverage(true);
+    let buf = common::render_to_pixels(&device, &queue, &mut sr, &scene, 64, 64);
+    for y in [4, 32, 60] {
+        for x in [4, 16, 28] {
+            let [r, g, b, _] = common::pixel(&buf, 64, x, y);
+            assert!(
+                r > 240 && g < 16 && b < 16,
+                "left half is unblended red at ({x},{y}), got ({r},{g},{b})"
+            );
+        }
+        for x in [36, 48, 60] {
+            let [r, g, b, _] = common::pixel(&buf, 64, x, y);
+            assert!(
+                (112..144).contains(&r) && g < 16 && (112..144).contains(&b),
+                "right half blends blue over red at ({x},{y}), got ({r},{g},{b})"
+            );
+        }
+    }
+    compare_or_write("coverage", &buf, 64, 64);
+}
+
+// ── MSAA (RendererConfig::sample_count) ──────────────────────────────────────────────────────────
+
+/// Render `scene` single-sampled and at 4x on one device, returning `(1x, 4x)` pixels.
+fn render_1x_and_4x(scene: &crate::hle::Scene, w: u32, h: u32) -> (Vec<u8>, Vec<u8>) {
+    use crate::render::SceneRenderer;
+    let (device, queue, dual) = headless_device();
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, w, h, dual);
+    let single = common::render_to_pixels(&device, &queue, &mut sr, scene, w, h);
+    let mut sr = SceneRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm, w, h, dual);
+    sr.set_sample_count(&device, 4);
+    let multi = common::render_to_pixels(&device, &queue, &mut sr, scene, w, h);
+    (single, multi)
+}
+
+/// Pixels whose RGB differs by more than 8 on some channel between two same-sized renders.
+fn differing_pixels(a: &[u8], b: &[u8]) -> usize {
+    a.as_chunks::<4>()
+        .0
+        .iter()
+        .zip(b.as_chunks::<4>().0)
+        .filter(|(p, q)| p[..3].iter().zip(&q[..3]).any(|(&x, &y)| x.abs_diff(y) > 8))
+        .count()
+}
+
+/// Check a 4x render against the same scene single-sampled: every pixel whose 3×3 neighborhood is
+/// one color at 1x lies off every edge and must resolve to that color within the golden tolerance,
+/// and such pixels must make up at least `min_interior` of the image.
+fn assert_4x_matches_1x_off_edges(
+    single: &[u8],
+    multi: &[u8],
+    w: u32,
+    h: u32,
+    min_interior: usize,
+    name: &str,
+) {
+    let close = |a: [u8; 4], b: [u8; 4]| {
+        a[..3]
+            .iter()
+            .zip(&b[..3])
+            .all(|(&x, &y)| x.abs_diff(y) <= 2)
+    };
+    let mut interior = 0;
+    for y in 1..h - 1 {
+        for x in 1..w - 1 {
+            let want = common::pixel(single, w, x, y);
+            let flat = (y - 1..=y + 1)
+                .flat_map(|ny| (x - 1..=x + 1).map(move |nx| (nx, ny)))
+                .all(|(nx, ny)| close(common::pixel(single, w, nx, ny), want));
+            if !flat {
+                continue;
+            }
+            interior += 1;
+            let got = common::pixel(multi, w, x, y);
+            assert!(
+                close(got, want),
+                "{name}: 4x ({x},{y}) lies off every edge and must match 1x {want:?}, got {got:?}"
+            );
+        }
+    }
+    assert!(
+        interior >= min_interior,
+        "{name}: only {interior} pixels lie off an edge at 1x, expected at least {min_interior}"
+    );
+}
+
+/// `msaa-edge` at 4x: a diagonal pixel straddling the hypotenuse resolves to a half tone between
+/// the clear color and the triangle's green, while pixels off the edge stay solid.
+#[test]
+fn golden_msaa_edge_4x() {
+    let scene = common::scene_from_source("msaa-edge.n64", RGBA16_QUAD_TEX, 4, 4);
+    let (single, buf) = render_1x_and_4x(&scene, 64, 64);
+    let [_, g, _, _] = common::pixel(&buf, 64, 8, 56);
+    assert!(g > 240, "inside the triangle is solid green, got g={g}");
+    let [_, g, _, _] = common::pixel(&buf, 64, 56, 8);
+    assert!(g < 40, "outside the triangle is the clear color, got g={g}");
+    // The hypotenuse is the screen diagonal x == y; two pixels off it every sample agrees.
+    for k in (4..60).step_by(8) {
+        let [_, g, _, _] = common::pixel(&buf, 64, k, k);
+        assert!(
+            (96..176).contains(&g),
+            "edge pixel ({k},{k}) resolves to a half tone, got g={g}"
+        );
+        let [_, g, _, _] = common::pixel(&buf, 64, k, k + 2);
+        assert!(
+            g > 240,
+            "({k},{}) is inside and solid green, got g={g}",
+            k + 2
+        );
+        let [_, g, _, _] = common::pixel(&buf, 64, k + 2, k);
+        assert!(
+            g < 40,
+            "({},{k}) is outside and the clear color, got g={g}",
+            k + 2
+        );
+    }
+    // Only the 64 diagonal pixels may differ from the single-sampled render.
+    let differing = differing_pixels(&single, &buf);
+    assert!(
+        differing <= 64,
+        "4x must match 1x off the hypotenuse, {differing} pixels differ"
+    );
+    assert_4x_matches_1x_off_edges(&single, &buf, 64, 64, 62 * 62 * 9 / 10, "msaa-edge-4x");
+    compare_or_write("msaa-edge-4x", &buf, 64, 64);
+}
+
+/// `decal` at 4x: the decal pass tests each sample against that sample's stored depth, so the
+/// coplanar decal still shows on the base and stays occluded under the nearer quad.
+#[test]
+fn golden_decal_4x() {
+    let scene = common::scene_from_source("decal.n64", &[255u8; 4], 1, 1);
+    let (single, buf) = render_1x_and_4x(&scene, 96, 96);
+    for ((x, y), want, what) in [
+        ((70, 20), OCCLUDER_RGB, "occluder"),
+        ((20, 20), DECAL_DECAL_RGB, "decal"),
+        ((20, 76), DECAL_BASE_RGB, "base"),
+    ] {
+        let p = common::pixel(&buf, 96, x, y);
+        for k in 0..3 {
+            assert!(
+                (p[k] as i32 - want[k] as i32).abs() < 24,
+                "pixel ({x},{y}) must show the {what} color {want:?}, got {p:?}"
+            );
+        }
+    }
+    // Resolving only softens the quads' edges.
+    assert_4x_matches_1x_off_edges(&single, &buf, 96, 96, 94 * 94 * 3 / 4, "decal-4x");
+}
+
+/// `offscreen-then-sample` at 4x: the scratch framebuffer is resolved before the scanout pair
+/// samples it through `fb_source`.
+#[test]
+fn golden_2d_offscreen_then_sample_4x() {
+    let scene = common::scene_from_source("offscreen-then-sample.n64", &[255u8; 4], 1, 1);
+    let (single, buf) = render_1x_and_4x(&scene, 64, 64);
+    for &(x, y) in &[(0u32, 0u32), (32, 32), (63, 63), (0, 63), (63, 0)] {
+        let p = common::pixel(&buf, 64, x, y);
+        assert!(
+            p[0] > 200 && p[1] > 80 && p[2] < 60,
+            "pixel ({x},{y}): expected the resolved orange scratch, got {p:?}"
+        );
+    }
+    // Both passes are full-screen, so there is no edge to resolve: 4x matches 1x everywhere.
+    assert_eq!(
+        differing_pixels(&single, &buf),
+        0,
+        "the resolved scratch must match the single-sampled render"
+    );
+    compare_or_write("2d-offscreen-then-sample-4x", &buf, 64, 64);
+}
@@ -395,3 +395,108 @@ fn hostptr_segment_resolve_vs_passthrough() {
         0x1_0000_0000 + 0x0012_3457
     );
 }
+
+/// A native `GBI_FLOATS` list walks cleanly under both data formats (the G_MTX/G_VTX encodings are
+/// shared); only the float decode yields a sane scene, so `guess_microcode` ranks it first.
+#[test]
+fn guess_microcode_prefers_float_for_a_native_float_dl() {
+    use crate::hle::consts::{G_ENDDL, G_RM_OPA_SURF, G_RM_OPA_SURF2};
+    use crate::{guess_microcode, DataFormat, Microcode};
+
+    #[rustfmt::skip]
+    let model: [f32; 16] = [
+        0.015625, 0.0, 0.0, 0.0,
+        0.0, 0.015625, 0.0, 0.0,
+        0.0, 0.0, 0.015625, 0.0,
+        0.25, 0.5, 0.75, 1.0,
+    ];
+    let vtx = |x: f32, y: f32| HostVtx {
+        x,
+        y,
+        z: 0.0,
+        flag: 0,
+        s: 0,
+        t: 0,
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+    let verts = [vtx(-40.0, -30.0), vtx(40.0, -30.0), vtx(0.5, 30.25)];
+    let shade = n64_gbi::encode::CcPass {
+        a: n64_gbi::encode::ZERO_C,
+        b: n64_gbi::encode::ZERO_C,
+        c: n64_gbi::encode::ZERO_C,
+        d: 4,
+    };
+    let shade_alpha = n64_gbi::encode::CcPass {
+        a: n64_gbi::encode::ZERO_A,
+        b: n64_gbi::encode::ZERO_A,
+        c: n64_gbi::encode::ZERO_A,
+        d: 4,
+    };
+    let combine = n64_gbi::encode::gdp_set_combine_lerp(shade, shade_alpha, shade, shade_alpha);
+    let (rm_w0, rm_w1) = n64_gbi::encode::gdp_set_render_mode(G_RM_OPA_SURF, G_RM_OPA_SURF2);
+    let (mtx_w0, _) = n64_gbi::encode::gsp_matrix(0, false, true, false);
+    let (vtx_w0, _) = n64_gbi::encode::gsp_vertex(0, 3, 0);
+    let (tri_w0, tri_w1) = n64_gbi::encode::gsp_1triangle(0, 1, 2);
+    let dl: Vec<[usize; 2]> = vec![
+        cmd_imm(combine.0, combine.1),
+        cmd_imm(rm_w0, rm_w1),
+        cmd_addr(mtx_w0, model.as_ptr() as u64),
+        cmd_addr(vtx_w0, verts.as_ptr() as u64),
+        cmd_imm(tri_w0, tri_w1),
+        cmd_imm((G_ENDDL as u32) << 24, 0),
+    ];
+
+    let frame: &[u8] = unsafe {
+        std::slice::from_raw_parts(
+            dl.as_ptr() as *const u8,
+            dl.len() * core::mem::size_of::<[usize; 2]>(),
+        )
+    };
+    let host = unsafe { HostRam::new(frame) };
+    let ranked = guess_microcode(&host, dl.as_ptr() as u64);
+    // Host pointers: only the F3DEX2 table, under both formats.
+    assert_eq!(ranked.len(), 2, "{ranked:?}");
+    assert_eq!(ranked[0], (Microcode::F3dex2, DataFormat::Float, 1.0));
+    assert_eq!(
+        (ranked[1].0, ranked[1].1),
+        (Microcode::F3dex2, DataFormat::Fixed)
+    );
+    assert!(ranked[1].2 < 1.0, "{ranked:?}");
+
+    std::hint::black_box((&model, &verts, &dl));
+}
+
+/// The float dry walk of a fixed-point list reads only what the 16-byte array covers: a 3-vertex
+/// `G_VTX` over 48 bytes loads two 24-byte float vertices, never the 72 bytes a float array needs.
+#[test]
+fn guess_float_walk_stays_inside_a_fixed_point_vertex_array() {
+    use crate::hle::consts::G_ENDDL;
+    use crate::hle::gbi::GbiUcode;
+    use crate::DataFormat;
+
+    let verts = [[0x0101_0101u32; 4]; 3];
+    let (vtx_w0, _) = n64_gbi::encode::gsp_vertex(0, 3, 0);
+    let dl: Vec<[usize; 2]> = vec![
+        cmd_addr(vtx_w0, verts.as_ptr() as u64),
+        cmd_imm((G_ENDDL as u32) << 24, 0),
+    ];
+
+    let frame: &[u8] = unsafe {
+        std::slice::from_raw_parts(
+            dl.as_ptr() as *const u8,
+            dl.len() * core::mem::size_of::<[usize; 2]>(),
+        )
+    };
+    let host = unsafe { HostRam::new(frame) };
+    let entry = dl.as_ptr() as u64;
+    let dry =
+        crate::hle::interp::interpret_dry(host.clone(), entry, GbiUcode::F3dex2, DataFormat::Float);
+    assert_eq!(dry.scene.raw_pos.len(), 2);
+    let fixed = crate::hle::interp::interpret_dry(host, entry, GbiUcode::F3dex2, DataFormat::Fixed);
+    assert_eq!(fixed.scene.raw_pos.len(), 3);
+
+    std::hint::black_box((&verts, &dl));
+}
@@ -259,6 +259,15 @@ fn render_and_read_center_channel(
         inv_tex1_size: [1.0, 1.0, 0.0, 0.0],
         lod_params: [0.0, 1.0, 0.0, 1.0],
         inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+        key_center: [0.0; 4],
+        key_scale: [0.0; 4],
+        key_width: [0.0; 4],
+        prim_depth: [0.0; 4],
+        raster: [0.0; 4],
+        tile: [0; 4],
+        tile_origin: [0.0; 4],
+        dither: [0; 4],
+        coverage: [0; 4],
     };
 
     let pipeline = TexturedPipeline::new(device, format, DEPTH_FORMAT);
@@ -278,6 +287,7 @@ fn render_and_read_center_channel(
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: indices.len() as u32,
             index_start: 0,
         }],
@@ -363,9 +373,16 @@ fn combiner_uniform_packs_raw_words() {
         prim: [255, 255, 255, 255],
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: true,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -566,6 +583,15 @@ fn renders_red_triangle_center_and_clear_corner() {
         inv_tex1_size: [1.0, 1.0, 0.0, 0.0],
         lod_params: [0.0, 1.0, 0.0, 1.0],
         inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+        key_center: [0.0; 4],
+        key_scale: [0.0; 4],
+        key_width: [0.0; 4],
+        prim_depth: [0.0; 4],
+        raster: [0.0; 4],
+        tile: [0; 4],
+        tile_origin: [0.0; 4],
+        dither: [0; 4],
+        coverage: [0; 4],
     };
 
     // Use a 1x1 white texture as placeholder (tex_enable=0 so it doesn't matter)
@@ -582,6 +608,7 @@ fn renders_red_triangle_center_and_clear_corner() {
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: indices.len() as u32,
             index_start: 0,
         }],
@@ -651,11 +678,13 @@ fn renders_red_triangle_center_and_clear_corner() {
     readback.unmap();
 }
 
-#[test]
-fn depth_test_hides_the_farther_triangle() {
-    // Two full-screen triangles: a NEAR green one (z=0.1) drawn FIRST, then a FAR red one (z=0.9).
-    // With depth_compare=Less + a cleared depth buffer, the near green survives (the later far red is
-    // rejected) — which only holds if depth actually works (paint order alone would make red win).
+/// Draw two full-screen triangles `(z, color)` in order through one depth-test+write run whose
+/// uniform carries `prim_depth`, and return the RGBA8 pixel at (4, 4).
+fn render_two_depth_tris_center(
+    first: (f32, [f32; 4]),
+    second: (f32, [f32; 4]),
+    prim_depth: [f32; 4],
+) -> [u8; 4] {
     let (device, queue, _dual_source) = headless_device();
     const W: u32 = 8;
     const H: u32 = 8;
@@ -700,8 +729,6 @@ fn depth_test_hides_the_farther_triangle() {
         color: [f32; 4],
         uv: [f32; 2],
     }
-    let far = [1.0f32, 0.0, 0.0, 1.0];
-    let near = [0.0f32, 1.0, 0.0, 1.0];
     let mk = |z: f32, c: [f32; 4]| {
         [
             V {
@@ -722,10 +749,8 @@ fn depth_test_hides_the_farther_triangle() {
         ]
     };
     let mut verts = Vec::new();
-    // NEAR drawn FIRST, FAR second: only a working Less depth test keeps the earlier near-green
-    // fragment alive against the later far-red. (If depth were off, paint order would make red win.)
-    verts.extend_from_slice(&mk(0.1, near));
-    verts.extend_from_slice(&mk(0.9, far));
+    verts.extend_from_slice(&mk(first.0, first.1));
+    verts.extend_from_slice(&mk(second.0, second.1));
     let indices: [u32; 6] = [0, 1, 2, 3, 4, 5];
 
     let pos: Vec<OutVertex> = verts
@@ -771,6 +796,15 @@ fn depth_test_hides_the_farther_triangle() {
         inv_tex1_size: [1.0, 1.0, 0.0, 0.0],
         lod_params: [0.0, 1.0, 0.0, 1.0],
         inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+        key_center: [0.0; 4],
+        key_scale: [0.0; 4],
+        key_width: [0.0; 4],
+        prim_depth,
+        raster: [0.0; 4],
+        tile: [0; 4],
+        tile_origin: [0.0; 4],
+        dither: [0; 4],
+        coverage: [0; 4],
     };
     let pipeline = TexturedPipeline::new(&device, format, DEPTH_FORMAT);
     let (group0_bg, group1_bg) = make_bind_groups(
@@ -791,6 +825,7 @@ fn depth_test_hides_the_farther_triangle() {
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: indices.len() as u32,
             index_start: 0,
         }],
@@ -848,13 +883,42 @@ fn depth_test_hides_the_farther_triangle() {
     rx.recv().unwrap().unwrap();
     let data = slice.get_mapped_range();
     let off = (4 * bytes_per_row + 4 * 4) as usize;
+    let px = [data[off], data[off + 1], data[off + 2], data[off + 3]];
+    drop(data);
+    readback.unmap();
+    px
+}
+
+#[test]
+fn depth_test_hides_the_farther_triangle() {
+    // Two full-screen triangles: a NEAR green one (z=0.1) drawn FIRST, then a FAR red one (z=0.9).
+    // With depth_compare=Less + a cleared depth buffer, the near green survives (the later far red is
+    // rejected) — which only holds if depth actually works (paint order alone would make red win).
+    let far = [1.0f32, 0.0, 0.0, 1.0];
+    let near = [0.0f32, 1.0, 0.0, 1.0];
     assert_eq!(
-        &data[off..off + 4],
-        &[0, 255, 0, 255],
+        render_two_depth_tris_center((0.1, near), (0.9, far), [0.0; 4]),
+        [0, 255, 0, 255],
         "near (green) must win the depth test"
     );
-    drop(data);
-    readback.unmap();
+}
+
+#[test]
+fn zs_prim_depth_replaces_the_vertex_depth() {
+    // FAR red drawn first, NEAR green second: per-pixel Z lets green win. Under G_ZS_PRIM both
+    // pin to the same constant prim depth, so the second triangle fails the Less test and the
+    // first (red) survives — only possible if the vertex stage overrode the interpolated Z.
+    let red = [1.0f32, 0.0, 0.0, 1.0];
+    let green = [0.0f32, 1.0, 0.0, 1.0];
+    assert_eq!(
+        render_two_depth_tris_center((0.9, red), (0.1, green), [0.0; 4]),
+        [0, 255, 0, 255]
+    );
+    assert_eq!(
+        render_two_depth_tris_center((0.9, red), (0.1, green), [0.5, 1.0, 0.0, 0.0]),
+        [255, 0, 0, 255],
+        "G_ZS_PRIM must pin both triangles to the prim depth"
+    );
 }
 
 struct GpuOut {
@@ -902,6 +966,7 @@ fn run_compute_outputs(
             fog_enable: u32::from(scene.fog_enable),
             fog_mul: scene.fog_mul as f32,
             fog_offset: scene.fog_offset as f32,
+            screen: crate::render::RSP_SCREEN,
         }),
         wgpu::BufferUsages::UNIFORM,
     );
@@ -962,7 +1027,9 @@ fn run_compute_outputs(
     let data = slice.get_mapped_range();
     // OutVertex = 12 f32 (48B): [0..4]=pos, [4..8]=color, [8..10]=uv, [10..12]=pad
     let res: Vec<GpuOut> = bytemuck::cast_slice::<u8, f32>(&data)
-        .chunks_exact(12)
+        .as_chunks::<12>()
+        .0
+        .iter()
         .map(|c| GpuOut {
             pos: [c[0], c[1], c[2], c[3]],
             color: [c[4], c[5], c[6], c[7]],
@@ -1055,8 +1122,23 @@ fn ref_color(scene: &crate::hle::Scene, i: usize) -> [f32; 4] {
         let mut c = scene.lights_table[li + lc - 1].1; // ambient
         for k in 0..lc - 1 {
             let (d, col) = scene.lights_table[li + k];
-            let nl = (n[0] * d[0] + n[1] * d[1] + n[2] * d[2]).max(0.0);
-            c = [c[0] + nl * col[0], c[1] + nl * col[1], c[2] + nl * col[2]];
+            let at = scene.light_atten.get(li + k).copied().unwrap_or_default();
+            let f = if at[0] > 0.0 {
+                // Point light: d is the object-space position (mirrors the kernel's falloff).
+                let p = scene.raw_pos[i];
+                let r = [d[0] - p[0], d[1] - p[1], d[2] - p[2]];
+                let len = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
+                let dist = len * at[3];
+                let nl = if len > 0.0 {
+                    ((n[0] * r[0] + n[1] * r[1] + n[2] * r[2]) / len).max(0.0)
+                } else {
+                    0.0
+                };
+                nl / (at[0] + at[1] * dist + at[2] * dist * dist)
+            } else {
+                (n[0] * d[0] + n[1] * d[1] + n[2] * d[2]).max(0.0)
+            };
+            c = [c[0] + f * col[0], c[1] + f * col[1], c[2] + f * col[2]];
         }
         [c[0].min(1.0), c[1].min(1.0), c[2].min(1.0), a]
     } else {
@@ -1361,6 +1443,77 @@ fn kernel_lit_color_two_directional_lights_plus_ambient() {
     }
 }
 
+/// Point-light kernel test (F3DZEX2 `G_LIGHTING_POSITIONAL`): a light whose `light_atten.x > 0`
+/// carries its object-space position in the dir slot and falls off as 1/(kc + kl·d + kq·d²).
+///
+///   light: pos = [0,0,10], col = [0.8,0.8,0.8], atten = [kc 1, kl 0.1, kq 0, scale 1]
+///   ambient = [0.1,0.1,0.1]; both vertices have normal (0,0,127) → N·L = 1 toward the light.
+///   Vertex A at [0,0,0]: d = 10 → 1/(1+1)   = 0.5  → 0.1 + 0.8·0.5 = 0.5
+///   Vertex B at [0,0,5]: d = 5  → 1/(1+0.5) = 2/3 → 0.1 + 0.8·2/3 ≈ 0.6333
+///
+/// Hand-computed, like the directional tests above.
+#[test]
+fn kernel_lit_color_point_light_falls_off_with_distance() {
+    let (device, queue, _dual_source) = headless_device();
+    use crate::hle::math::identity;
+
+    let mut scene = crate::hle::Scene::default();
+    scene
+        .lights_table
+        .push(([0.0_f32, 0.0, 10.0], [0.8, 0.8, 0.8])); // position, col
+    scene.light_atten.push([1.0, 0.1, 0.0, 1.0]);
+    scene
+        .lights_table
+        .push(([0.0_f32, 0.0, 0.0], [0.1, 0.1, 0.1])); // ambient
+    scene.light_atten.push([0.0; 4]);
+
+    scene.mvp_table = vec![identity()];
+    scene.viewport_table = vec![(
+        [
+            crate::hle::rsp::FB_WIDTH / 2.0,
+            crate::hle::rsp::FB_HEIGHT / 2.0,
+            511.0 / crate::hle::rsp::DEPTH_RANGE,
+        ],
+        [
+            crate::hle::rsp::FB_WIDTH / 2.0,
+            crate::hle::rsp::FB_HEIGHT / 2.0,
+            511.0 / crate::hle::rsp::DEPTH_RANGE,
+        ],
+    )];
+    scene.texcoord_table = vec![[0.0, 0.0]];
+
+    let cn = (127u32 << 16) | (255u32 << 24); // normal (0,0,127), alpha 255
+    for z in [0.0, 5.0] {
+        scene.raw_pos.push([0.0, 0.0, z]);
+        scene.mtx_index.push(0);
+        scene.viewport_index.push(0);
+        scene.raw_st.push([0.0, 0.0]);
+        scene.texcoord_index.push(0);
+        scene.cn.push(cn);
+        scene.light_index.push(0);
+        scene.light_count.push(2);
+    }
+
+    let gpu = run_compute_outputs(&device, &queue, &scene);
+    assert_eq!(gpu.len(), 2);
+    let tol = 1e-4_f32;
+    for (i, expected) in [0.5_f32, 0.1 + 0.8 * 2.0 / 3.0].into_iter().enumerate() {
+        let c = &gpu[i].color;
+        for (ch, &got) in c[..3].iter().enumerate() {
+            assert!(
+                (got - expected).abs() <= tol,
+                "vtx {i} channel {ch}: expected {expected} got {got}"
+            );
+        }
+        // The shared CPU oracle agrees with the hand-computed falloff.
+        let oracle = ref_color(&scene, i);
+        assert!(
+            (oracle[0] - expected).abs() <= tol,
+            "oracle vtx {i}: {oracle:?}"
+        );
+    }
+}
+
 /// Focused texgen-math GPU test (Step 9): proves the kernel's spherical + F3DEX2 cubic fold and
 /// the texgen ST-prefold INDEPENDENTLY of the kernel scale, since at the T3 commit no gallery scene
 /// exercises the golden texgen branch.
@@ -1568,6 +1721,7 @@ fn cull_back_mode_keeps_n64_front_drops_n64_back() {
         material_index: 0,
         render_mode_index: 0,
         cull: crate::hle::CullKind::Cull,
+        flat: false,
         index_count: 6,
         index_start: 0,
     }];
@@ -1602,6 +1756,15 @@ fn cull_back_mode_keeps_n64_front_drops_n64_back() {
         inv_tex1_size: [1.0, 1.0, 0.0, 0.0],
         lod_params: [0.0, 1.0, 0.0, 1.0],
         inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+        key_center: [0.0; 4],
+        key_scale: [0.0; 4],
+        key_width: [0.0; 4],
+        prim_depth: [0.0; 4],
+        raster: [0.0; 4],
+        tile: [0; 4],
+        tile_origin: [0.0; 4],
+        dither: [0; 4],
+        coverage: [0; 4],
     };
     let pipeline = TexturedPipeline::new(&device, format, DEPTH_FORMAT);
     let (group0_bg, group1_bg) = make_bind_groups(
@@ -2726,6 +2889,7 @@ fn render_source_to_rgba8(src: &str, tex_native: &[u8], w: u32, h: u32) -> Vec<u
             fog_enable: 0,
             fog_mul: 0.0,
             fog_offset: 0.0,
+            screen: crate::render::RSP_SCREEN,
         }),
         usage: wgpu::BufferUsages::UNIFORM,
     });
@@ -2961,9 +3125,16 @@ fn build_two_material_two_run_scene() -> crate::hle::Scene {
         prim: [255, 0, 0, 255], // RED
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -2985,9 +3156,16 @@ fn build_two_material_two_run_scene() -> crate::hle::Scene {
         prim: [0, 0, 255, 255], // BLUE
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3056,6 +3234,7 @@ fn build_two_material_two_run_scene() -> crate::hle::Scene {
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 0,
         },
@@ -3063,6 +3242,7 @@ fn build_two_material_two_run_scene() -> crate::hle::Scene {
             material_index: 1,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 6,
         },
@@ -3100,6 +3280,34 @@ fn two_material_scene_renders_two_distinct_regions() {
     );
 }
 
+/// G_CK_KEY: keying the RED run on pure red discards it (the clear color shows through) while the
+/// BLUE run, keyed with the same center but outside the range, still draws. Scale 255 with width
+/// 0xFFF accepts channels within 16 of the center.
+#[test]
+fn chroma_key_discards_only_pixels_inside_the_key_range() {
+    let mut scene = build_two_material_two_run_scene();
+    for mat in &mut scene.materials {
+        mat.chroma_key = true;
+        mat.key_center = [255, 0, 0];
+        mat.key_scale = [255; 3];
+        mat.key_width = [0xFFF; 3];
+    }
+    let px = render_scene_to_rgba8(&scene, 64, 64);
+    let left = ((32 * 64 + 16) * 4) as usize; // center of left half (row 32, col 16)
+    let right = ((32 * 64 + 48) * 4) as usize; // center of right half (row 32, col 48)
+    let clear = [CLEAR_COLOR.r, CLEAR_COLOR.g, CLEAR_COLOR.b].map(|c| (c * 255.0).round() as u8);
+    assert!(
+        (0..3).all(|i| px[left + i].abs_diff(clear[i]) < 4),
+        "keyed red run must show the clear color, got {:?}",
+        &px[left..left + 4]
+    );
+    assert!(
+        px[right + 2] > 200 && px[right] < 30,
+        "blue run is outside the key and must draw, got {:?}",
+        &px[right..right + 4]
+    );
+}
+
 /// Build a two-material / two-run Scene where the OUTPUT COLOR comes from each material's
 /// TEXTURE (not prim) — used to prove per-material `@group(0)` (texture) bind-group routing.
 ///
@@ -3133,9 +3341,16 @@ fn build_two_material_two_run_textured_scene() -> crate::hle::Scene {
         prim: [0, 255, 0, 255], // GREEN — never the asserted output, only the texture is
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: true,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3157,9 +3372,16 @@ fn build_two_material_two_run_textured_scene() -> crate::hle::Scene {
         prim: [0, 255, 0, 255], // GREEN
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: true,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3222,6 +3444,7 @@ fn build_two_material_two_run_textured_scene() -> crate::hle::Scene {
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 0,
         },
@@ -3229,6 +3452,7 @@ fn build_two_material_two_run_textured_scene() -> crate::hle::Scene {
             material_index: 1,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 6,
         },
@@ -3288,9 +3512,16 @@ fn build_dualsrc_over_green_scene(mux_low: u32, red_alpha: u8) -> crate::hle::Sc
         prim: [0, 255, 0, 255], // GREEN backdrop = framebuffer memory (CLR_MEM)
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3312,9 +3543,16 @@ fn build_dualsrc_over_green_scene(mux_low: u32, red_alpha: u8) -> crate::hle::Sc
         prim: [255, 0, 0, red_alpha], // RED prim; alpha drives the blender A coefficient
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3377,6 +3615,7 @@ fn build_dualsrc_over_green_scene(mux_low: u32, red_alpha: u8) -> crate::hle::Sc
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 0,
         },
@@ -3384,6 +3623,7 @@ fn build_dualsrc_over_green_scene(mux_low: u32, red_alpha: u8) -> crate::hle::Sc
             material_index: 1,
             render_mode_index: 1,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 6,
         },
@@ -3524,6 +3764,7 @@ fn run_fog_kernel_alpha(clip_z: f32, clip_w: f32, fm: f32, fo: f32) -> f32 {
             fog_enable: 1,
             fog_mul: fm,
             fog_offset: fo,
+            screen: crate::render::RSP_SCREEN,
         }),
         wgpu::BufferUsages::UNIFORM,
     );
@@ -3648,9 +3889,16 @@ fn build_decal_smoke_scene() -> crate::hle::Scene {
         prim: [0, 0, 0, 255], // BLACK base — pre-impl, the hidden decal leaves this black
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3672,9 +3920,16 @@ fn build_decal_smoke_scene() -> crate::hle::Scene {
         prim: [220, 40, 255, 255], // BRIGHT decal — only visible if the decal pass runs
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -3742,6 +3997,7 @@ fn build_decal_smoke_scene() -> crate::hle::Scene {
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 0,
         },
@@ -3749,6 +4005,7 @@ fn build_decal_smoke_scene() -> crate::hle::Scene {
             material_index: 1,
             render_mode_index: 1,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: 6,
             index_start: 6,
         },
@@ -3915,6 +4172,15 @@ fn render_two_texture_center(
         inv_tex1_size: [1.0, 1.0, 1.0, 0.0],
         lod_params: [0.0, 1.0, 0.0, 1.0],
         inv_detail_size: [1.0, 1.0, 0.0, 0.0],
+        key_center: [0.0; 4],
+        key_scale: [0.0; 4],
+        key_width: [0.0; 4],
+        prim_depth: [0.0; 4],
+        raster: [0.0; 4],
+        tile: [0; 4],
+        tile_origin: [0.0; 4],
+        dither: [0; 4],
+        coverage: [0; 4],
     };
 
     let pipeline = TexturedPipeline::new(device, format, DEPTH_FORMAT);
@@ -3985,6 +4251,7 @@ fn render_two_texture_center(
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: indices.len() as u32,
             index_start: 0,
         }],
@@ -4349,6 +4616,15 @@ fn render_lod_center(
             prim_lod_min,
             detail_mode,
         ],
+        key_center: [0.0; 4],
+        key_scale: [0.0; 4],
+        key_width: [0.0; 4],
+        prim_depth: [0.0; 4],
+        raster: [0.0; 4],
+        tile: [0; 4],
+        tile_origin: [0.0; 4],
+        dither: [0; 4],
+        coverage: [0; 4],
     };
 
     let pipeline = TexturedPipeline::new(device, format, DEPTH_FORMAT);
@@ -4426,6 +4702,7 @@ fn render_lod_center(
             material_index: 0,
             render_mode_index: 0,
             cull: crate::hle::CullKind::None,
+            flat: false,
             index_count: indices.len() as u32,
             index_start: 0,
         }],
@@ -4729,3 +5006,67 @@ fn sharpen_mode_extrapolates_below_plain_level0_at_magnification() {
          proving the negative-lodFraction path engaged, not just plain level0; got {px:?}"
     );
 }
+
+/// G_LINE3D corners: the kernel pushes each corner `line_offset` pixels perpendicular to the
+/// segment towards its partner endpoint, flipping side with the direction so the `b` corner with
+/// the same signed offset lands opposite the `a` one.
+#[test]
+fn kernel_offsets_line_corners_perpendicular_in_pixels() {
+    let (device, queue, _dual_source) = headless_device();
+    use crate::hle::math::identity;
+    use crate::hle::rsp::{FB_HEIGHT, FB_WIDTH};
+
+    let mut scene = crate::hle::Scene {
+        mvp_table: vec![identity()],
+        viewport_table: vec![(
+            [FB_WIDTH / 2.0, FB_HEIGHT / 2.0, 0.5],
+            [FB_WIDTH / 2.0, FB_HEIGHT / 2.0, 0.5],
+        )],
+        texcoord_table: vec![[0.0, 0.0]],
+        ..Default::default()
+    };
+    // Two endpoints (NDC x ±0.5 → pixels 80 and 240 on row 120), then four corners.
+    let rows = [
+        (-0.5, 0.0, 0),
+        (0.5, 0.0, 0),
+        (-0.5, 2.0, 1),
+        (-0.5, -2.0, 1),
+        (0.5, 2.0, 0),
+        (0.5, -2.0, 0),
+    ];
+    for (x, offset, other) in rows {
+        scene.raw_pos.push([x, 0.0, 0.0]);
+        scene.mtx_index.push(0);
+        scene.viewport_index.push(0);
+        scene.raw_st.push([0.0, 0.0]);
+        scene.texcoord_index.push(0);
+        scene.cn.push(0xFF00_0000);
+        scene.light_index.push(0);
+        scene.light_count.push(0);
+        scene.line_offset.push(offset);
+        scene.line_other.push(other);
+    }
+
+    let gpu = run_compute_outputs(&device, &queue, &scene);
+    let pixel = |o: &GpuOut| {
+        (
+            (o.pos[0] / o.pos[3] + 1.0) * FB_WIDTH / 2.0,
+            (1.0 - o.pos[1] / o.pos[3]) * FB_HEIGHT / 2.0,
+        )
+    };
+    let expected = [
+        (80.0, 120.0),
+        (240.0, 120.0),
+        (80.0, 118.0),
+        (80.0, 122.0),
+        (240.0, 122.0),
+        (240.0, 118.0),
+    ];
+    for (i, (ex, ey)) in expected.into_iter().enumerate() {
+        let (x, y) = pixel(&gpu[i]);
+        assert!(
+            (x - ex).abs() < 1e-3 && (y - ey).abs() < 1e-3,
+            "vtx {i}: expected ({ex}, {ey}) got ({x}, {y})"
+        );
+    }
+}
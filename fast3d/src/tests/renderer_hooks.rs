@@ -26,6 +26,9 @@ fn cfg() -> RendererConfig {
         format: Some(wgpu::TextureFormat::Rgba8Unorm),
         clear_policy: ClearPolicy::PerFrame,
         power_preference: wgpu::PowerPreference::LowPower,
+        quantize_5551: false,
+        emulate_coverage: false,
+        default_microcode: Microcode::F3dex2,
     }
 }
 
@@ -23,6 +23,9 @@ fn cfg() -> RendererConfig {
         format: Some(wgpu::TextureFormat::Rgba8Unorm),
         clear_policy: ClearPolicy::PerFrame,
         power_preference: wgpu::PowerPreference::LowPower,
+        quantize_5551: false,
+        emulate_coverage: false,
+        default_microcode: Microcode::F3dex2,
     }
 }
 
@@ -81,3 +84,59 @@ fn process_dl_of_out_of_bounds_entry_reports_error_without_panic() {
         "every diag was streamed to the sink"
     );
 }
+
+/// Append a graphics `OSTask` (KSEG0 pointers, as a game stores them) whose microcode is `image`
+/// (text, then data) and whose `data_ptr` is `entry`. Returns the task's address.
+fn push_task(hw: &mut ImgHw, task_type: u32, image: &[u8], entry: u64) -> u64 {
+    hw.rdram.resize(hw.rdram.len().next_multiple_of(8), 0);
+    let text_addr = hw.rdram.len() as u32;
+    hw.rdram.extend_from_slice(image);
+    let task_addr = hw.rdram.len() as u64;
+    let mut words = [0u32; 16];
+    words[0] = task_type;
+    words[4] = 0x8000_0000 | text_addr;
+    words[5] = 0x1000;
+    words[6] = 0x8000_0000 | (text_addr + 0x1000);
+    words[7] = 0x800;
+    words[12] = 0x8000_0000 | entry as u32;
+    for w in words {
+        hw.rdram.extend_from_slice(&w.to_be_bytes());
+    }
+    task_addr
+}
+
+#[test]
+fn process_task_walks_data_ptr_with_the_identified_microcode() {
+    let (mut hw, entry) = flat_color_hw();
+    let image = crate::hle::gbi::fixture_ucode(crate::hle::gbi::GbiUcode::F3dex2);
+    let task = push_task(&mut hw, crate::task::M_GFXTASK, &image, entry);
+    let mut r = headless_renderer();
+    let mut diags: Vec<Diagnostic> = Vec::new();
+    let s = r.process_task(&hw, task, &mut diags);
+    assert!(diags.is_empty(), "{diags:?}");
+    assert_eq!(s.tris, 2);
+    assert!(s.renderable);
+}
+
+#[test]
+fn process_task_falls_back_to_the_default_microcode_and_skips_other_tasks() {
+    let (mut hw, entry) = flat_color_hw();
+    let unknown = push_task(&mut hw, crate::task::M_GFXTASK, &[0xAA; 0x1800], entry);
+    let audio = push_task(&mut hw, 2, &[0xAA; 0x1800], entry);
+    let mut r = headless_renderer();
+    let mut diags: Vec<Diagnostic> = Vec::new();
+    let fallback = r.process_task(&hw, unknown, &mut diags);
+    assert_eq!((fallback.tris, fallback.errors), (2, 1));
+    assert!(
+        matches!(diags[..], [d] if matches!(d.kind, crate::DiagKind::UnknownTaskMicrocode { .. })),
+        "{diags:?}"
+    );
+    diags.clear();
+    let skipped = r.process_task(&hw, audio, &mut diags);
+    assert_eq!((skipped.commands, skipped.renderable), (0, false));
+    assert!(diags.is_empty(), "{diags:?}");
+
+    let past = r.process_task(&hw, hw.rdram.len() as u64, &mut diags);
+    assert_eq!(past.errors, 1);
+    assert_eq!(diags[0].kind, crate::DiagKind::DlPastRdram);
+}
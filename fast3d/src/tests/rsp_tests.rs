@@ -250,9 +250,16 @@ fn phase4_prim_material(prim: [u8; 4]) -> crate::hle::Material {
         prim,
         env: [0, 0, 0, 255],
         blend_color: [0, 0, 0, 255],
+        k4_k5: [0, 0],
+        key_center: [0; 3],
+        key_scale: [0; 3],
+        key_width: [0; 3],
+        chroma_key: false,
+        tex_filter: crate::hle::TextureFilter::Average,
         tex_enable: false,
         wrap_s: 2,
         wrap_t: 2,
+        tile_addr: crate::hle::TileAddress::whole(1, 1),
         fmt: 0,
         siz: 0,
         tile_count: 1,
@@ -15,6 +15,10 @@ pub mod rdp {
     pub const AC_NONE: u32 = 0;
     pub const AC_THRESHOLD: u32 = 1;
     pub const AC_DITHER: u32 = 3;
+    // Z source select (bit 2): G_ZS_PRIM takes every pixel's Z from G_SETPRIMDEPTH.
+    pub const G_MDSFT_ZSRCSEL: u32 = 2;
+    pub const G_ZS_PIXEL: u32 = 0;
+    pub const G_ZS_PRIM: u32 = 1 << G_MDSFT_ZSRCSEL;
     pub const AA_EN: u32 = 0x0008;
     pub const Z_CMP: u32 = 0x0010;
     pub const Z_UPD: u32 = 0x0020;
@@ -133,6 +137,71 @@ pub mod rdp {
     pub const G_RDPHALF_2: u8 = 0xF1;
     /// G_CYC_COPY (2): copy-mode cycle type (SETOTHERMODE_H CYC field value).
     pub const G_CYC_COPY: u32 = 2;
+    // othermode_H TexturePersp field (bit 19): G_TP_PERSP divides the interpolated S/T by W.
+    pub const G_MDSFT_TEXTPERSP: u32 = 19;
+    pub const G_TP_NONE: u32 = 0;
+    pub const G_TP_PERSP: u32 = 1 << G_MDSFT_TEXTPERSP;
+    // othermode_H TextureConvert field (bits [11:9]): G_TC_CONV runs the YUV->RGB conversion,
+    // G_TC_FILT bypasses it, G_TC_FILTCONV converts in the second cycle only.
+    pub const G_MDSFT_TEXTCONV: u32 = 9;
+    pub const G_TC_CONV: u32 = 0;
+    pub const G_TC_FILTCONV: u32 = 5 << G_MDSFT_TEXTCONV;
+    pub const G_TC_FILT: u32 = 6 << G_MDSFT_TEXTCONV;
+    // othermode_H TextureFilter field (bits [13:12]): G_TF_POINT samples the nearest texel,
+    // G_TF_BILERP runs the RDP's 3-point filter, G_TF_AVERAGE the 2x2 box average.
+    pub const G_MDSFT_TEXTFILT: u32 = 12;
+    pub const G_TF_POINT: u32 = 0;
+    pub const G_TF_AVERAGE: u32 = 3 << G_MDSFT_TEXTFILT;
+    pub const G_TF_BILERP: u32 = 2 << G_MDSFT_TEXTFILT;
+    // othermode_H CombineKey field (bit 8): G_CK_KEY enables the chroma-key test.
+    pub const G_MDSFT_COMBKEY: u32 = 8;
+    pub const G_CK_NONE: u32 = 0;
+    pub const G_CK_KEY: u32 = 1 << G_MDSFT_COMBKEY;
+    // othermode_H RGBDither field (bits [7:6]): the color dither the RDP applies when it rounds
+    // the blended color down to a 16-bit framebuffer.
+    pub const G_MDSFT_RGBDITHER: u32 = 6;
+    pub const G_CD_MAGICSQ: u32 = 0;
+    pub const G_CD_BAYER: u32 = 1 << G_MDSFT_RGBDITHER;
+    pub const G_CD_NOISE: u32 = 2 << G_MDSFT_RGBDITHER;
+    pub const G_CD_DISABLE: u32 = 3 << G_MDSFT_RGBDITHER;
+    // othermode_H AlphaDither field (bits [5:4]): the per-pixel pattern G_AC_DITHER compares the
+    // combined alpha against.
+    pub const G_MDSFT_ALPHADITHER: u32 = 4;
+    pub const G_AD_PATTERN: u32 = 0;
+    pub const G_AD_NOTPATTERN: u32 = 1 << G_MDSFT_ALPHADITHER;
+    pub const G_AD_NOISE: u32 = 2 << G_MDSFT_ALPHADITHER;
+    pub const G_AD_DISABLE: u32 = 3 << G_MDSFT_ALPHADITHER;
+    /// G_SETCONVERT (0xEC): the six 9-bit signed YUV->RGB conversion coefficients K0..K5. K0..K3
+    /// drive the texture-unit conversion; K4/K5 are also the combiner's K4/K5 inputs.
+    pub const G_SETCONVERT: u8 = 0xEC;
+    /// G_SETKEYR (0xEB): red chroma-key width/center/scale. Center/scale are the combiner's
+    /// KEY_CENTER/KEY_SCALE red components.
+    pub const G_SETKEYR: u8 = 0xEB;
+    /// G_SETKEYGB (0xEA): green/blue chroma-key width/center/scale.
+    pub const G_SETKEYGB: u8 = 0xEA;
+    /// G_SETPRIMDEPTH (0xEE): the constant primitive Z (w1 bits [31:16], 15-bit screen Z) and
+    /// delta Z (w1 bits [15:0]) used when othermode_L selects G_ZS_PRIM.
+    pub const G_SETPRIMDEPTH: u8 = 0xEE;
+    // libultra's default YUV->RGB coefficients (gsDPSetConvert(G_CV_K0, ..., G_CV_K5)).
+    pub const G_CV_K0: i16 = 175;
+    pub const G_CV_K1: i16 = -43;
+    pub const G_CV_K2: i16 = -89;
+    pub const G_CV_K3: i16 = 222;
+    pub const G_CV_K4: i16 = 114;
+    pub const G_CV_K5: i16 = 42;
+    // --- Edge-walker triangles: raw RDP command lists only, never emitted into a display list.
+    // The low three opcode bits add the shade, texture and depth coefficient blocks. ---
+    pub const G_TRI_FILL: u8 = 0xC8;
+    pub const G_TRI_FILL_ZBUFF: u8 = 0xC9;
+    pub const G_TRI_TXTR: u8 = 0xCA;
+    pub const G_TRI_TXTR_ZBUFF: u8 = 0xCB;
+    pub const G_TRI_SHADE: u8 = 0xCC;
+    pub const G_TRI_SHADE_ZBUFF: u8 = 0xCD;
+    pub const G_TRI_SHADE_TXTR: u8 = 0xCE;
+    pub const G_TRI_SHADE_TXTR_ZBUFF: u8 = 0xCF;
+    pub const G_RDP_TRI_SHADE_MASK: u8 = 0x04;
+    pub const G_RDP_TRI_TXTR_MASK: u8 = 0x02;
+    pub const G_RDP_TRI_ZBUFF_MASK: u8 = 0x01;
 }
 
 pub mod rsp_f3dex2 {
@@ -155,12 +224,25 @@ pub mod rsp_f3dex2 {
     pub const G_DL: u8 = 0xDE;
     pub const G_MOVEWORD: u8 = 0xDB;
     pub const G_POPMTX: u8 = 0xD8;
+    /// G_CULLDL (0x03): end the current DL when a vertex-cache range is entirely offscreen.
+    pub const G_CULLDL: u8 = 0x03;
+    /// G_BRANCH_Z (0x04): branch to the preceding G_RDPHALF_1 target on a vertex's screen Z.
+    pub const G_BRANCH_Z: u8 = 0x04;
+    /// G_LOAD_UCODE (0xDD): switch microcode; text in w1, data in the preceding G_RDPHALF_1.
+    pub const G_LOAD_UCODE: u8 = 0xDD;
     /// G_MOVEWORD sub-type selecting the segment table (F3DEX2 microcode).
     pub const G_MW_SEGMENT: u8 = 0x06;
     /// G_MOVEWORD sub-type selecting the perspective-normalize coefficient (libultra gbi.h).
     pub const G_MW_PERSPNORM: u8 = 0x0E;
     /// G_MOVEWORD sub-type selecting the clip ratio (libultra gbi.h).
     pub const G_MW_CLIP: u8 = 0x04;
+    /// G_MOVEWORD sub-type patching one word of the current MVP (gSPInsertMatrix).
+    pub const G_MW_MATRIX: u8 = 0x00;
+    /// gSPInsertMatrix offset of the first integer-half word (elements xx, xy); the integer
+    /// halves of all 16 elements fill 0x00..0x20, two per word.
+    pub const G_MWO_MATRIX_XX_XY_I: u16 = 0x00;
+    /// gSPInsertMatrix offset of the first fraction-half word; fractions fill 0x20..0x40.
+    pub const G_MWO_MATRIX_XX_XY_F: u16 = 0x20;
 
     // G_MOVEMEM index for the viewport (F3DEX2_G_MV_VIEWPORT).
     pub const G_MV_VIEWPORT: u8 = 0x08;
@@ -196,6 +278,37 @@ pub mod rsp_f3dex2 {
     pub const G_TEXTURE_GEN_LINEAR: u32 = 0x0008_0000;
 }
 
+pub mod rsp_f3dzex2 {
+    // F3DZEX2 (the OoT/MM fork of F3DEX2) keeps F3DEX2's opcode map, MTX bits and MOVEMEM/MOVEWORD
+    // selectors; the commands and geometry flag below are new relative to F3DEX2.
+    pub use super::rsp_f3dex2::*;
+
+    /// G_DMA_IO (0xD6): debug DMA between DMEM and DRAM; flag at bit 23, DMEM/8 at bits[22:13],
+    /// size-1 at bits[11:0], the DRAM address in w1.
+    pub const G_DMA_IO: u8 = 0xD6;
+    /// G_SPECIAL_1..3 (0xD5..0xD3): reserved microcode hooks; the stock build ignores them.
+    pub const G_SPECIAL_1: u8 = 0xD5;
+    pub const G_SPECIAL_2: u8 = 0xD4;
+    pub const G_SPECIAL_3: u8 = 0xD3;
+
+    /// Byte stride of one vertex in F3DZEX2 DMEM. G_BRANCH_Z addresses its vertex as `vtx * 5`
+    /// (in 8-byte units) from this layout, unlike F3DEX2's `vtx * 2`.
+    pub const VTX_DMEM_STRIDE: u32 = 40;
+
+    /// Treat lights with a non-zero constant attenuation as point lights (MM `G_LIGHTING_POSITIONAL`).
+    pub const G_LIGHTING_POSITIONAL: u32 = 0x0040_0000;
+}
+
+pub mod rsp_l3dex2 {
+    // L3DEX2 (the F3DEX2 line microcode) keeps F3DEX2's opcode map and replaces its triangle
+    // commands with G_LINE3D.
+    pub use super::rsp_f3dex2::*;
+
+    /// G_LINE3D (0x08): one screen-space line between two cached vertices; `v*2` indices at
+    /// bits[23:16] and [15:8], the width code `wd` at bits[7:0] (1.5 + wd/2 pixels).
+    pub const G_LINE3D: u8 = 0x08;
+}
+
 pub mod rsp_f3d {
     // RSP F3D opcodes (top byte of w0, bits [31:24]).
     pub const G_SPNOOP: u8 = 0x00;
@@ -272,6 +385,107 @@ pub mod rsp_f3d {
     pub const G_MW_PERSPNORM: u8 = 0x0E;
 }
 
+pub mod rsp_f3dex {
+    // F3DEX 1.x keeps the original F3D opcode map, MTX bits, geometry flags and MOVEMEM/MOVEWORD
+    // selectors; only the commands below are new (or re-encoded) relative to F3D.
+    pub use super::rsp_f3d::*;
+
+    // RSP F3DEX opcodes (top byte of w0, bits [31:24]) that do not exist in original F3D.
+    pub const G_MODIFYVTX: u8 = 0xB2;
+    pub const G_TRI2: u8 = 0xB1;
+    pub const G_BRANCH_Z: u8 = 0xB0;
+    pub const G_LOAD_UCODE: u8 = 0xAF;
+
+    /// F3DEX vertex cache size (F3D has 16).
+    pub const VTX_CACHE_SIZE: u32 = 32;
+    /// Byte stride of one vertex in F3DEX DMEM. G_BRANCH_Z addresses its vertex as `vtx * 5`
+    /// (in 8-byte units) from this layout.
+    pub const VTX_DMEM_STRIDE: u32 = 40;
+
+    // G_MODIFYVTX `where` selectors (byte offsets into the DMEM vertex).
+    pub const G_MWO_POINT_RGBA: u8 = 0x10;
+    pub const G_MWO_POINT_ST: u8 = 0x14;
+    pub const G_MWO_POINT_XYSCREEN: u8 = 0x18;
+    pub const G_MWO_POINT_ZSCREEN: u8 = 0x1C;
+}
+
+pub mod rsp_s2dex {
+    // S2DEX 1.x (the F3D-era sprite microcode) keeps F3D's DL/ENDDL/MOVEWORD/SETOTHERMODE/RDPHALF
+    // opcodes and replaces the 3D commands with the object/background commands below (gs2dex.h).
+    pub use super::rsp_f3d::*;
+    pub use super::s2dex_types::*;
+
+    pub const G_BG_1CYC: u8 = 0x01;
+    pub const G_BG_COPY: u8 = 0x02;
+    pub const G_OBJ_RECTANGLE: u8 = 0x03;
+    pub const G_OBJ_SPRITE: u8 = 0x04;
+    pub const G_OBJ_MOVEMEM: u8 = 0x05;
+    pub const G_SELECT_DL: u8 = 0xB0;
+    pub const G_OBJ_RENDERMODE: u8 = 0xB1;
+    pub const G_OBJ_RECTANGLE_R: u8 = 0xB2;
+    pub const G_OBJ_LOADTXTR: u8 = 0xC1;
+    pub const G_OBJ_LDTX_SPRITE: u8 = 0xC2;
+    pub const G_OBJ_LDTX_RECT: u8 = 0xC3;
+    pub const G_OBJ_LDTX_RECT_R: u8 = 0xC4;
+    pub const G_RDPHALF_0: u8 = 0xE4;
+    /// G_LOAD_UCODE (0xAF): the F3DEX 1.x encoding, shared by S2DEX 1.x.
+    pub const G_LOAD_UCODE: u8 = 0xAF;
+
+    /// G_OBJ_MOVEMEM `p` field (bits [23:16]) selecting a full uObjMtx load (gSPObjMatrix).
+    pub const G_OBJ_MV_MATRIX: u8 = 23;
+    /// G_OBJ_MOVEMEM `p` field selecting a uObjSubMtx load (gSPObjSubMatrix).
+    pub const G_OBJ_MV_SUBMATRIX: u8 = 7;
+}
+
+pub mod rsp_s2dex2 {
+    // S2DEX2 (the F3DEX2-era sprite microcode) keeps F3DEX2's DL/ENDDL/MOVEWORD/SETOTHERMODE/
+    // RDPHALF opcodes and replaces the 3D commands with the object/background commands below.
+    pub use super::rsp_f3dex2::*;
+    pub use super::s2dex_types::*;
+
+    pub const G_OBJ_RECTANGLE: u8 = 0x01;
+    pub const G_OBJ_SPRITE: u8 = 0x02;
+    pub const G_SELECT_DL: u8 = 0x04;
+    pub const G_OBJ_LOADTXTR: u8 = 0x05;
+    pub const G_OBJ_LDTX_SPRITE: u8 = 0x06;
+    pub const G_OBJ_LDTX_RECT: u8 = 0x07;
+    pub const G_OBJ_LDTX_RECT_R: u8 = 0x08;
+    pub const G_BG_1CYC: u8 = 0x09;
+    pub const G_BG_COPY: u8 = 0x0A;
+    pub const G_OBJ_RENDERMODE: u8 = 0x0B;
+    pub const G_OBJ_RECTANGLE_R: u8 = 0xDA;
+    pub const G_OBJ_MOVEMEM: u8 = 0xDC;
+    pub const G_RDPHALF_0: u8 = 0xE4;
+
+    /// G_OBJ_MOVEMEM index (bits [7:0]) for both matrix loads; the sub-matrix uses offset 2.
+    pub const G_MV_MATRIX: u8 = 0x00;
+}
+
+pub mod s2dex_types {
+    // Object structure vocabulary shared by S2DEX and S2DEX2 (gs2dex.h).
+
+    /// Byte sizes of the DMA'd object structures.
+    pub const OBJ_SPRITE_SIZE: u32 = 24;
+    pub const OBJ_TXTR_SIZE: u32 = 24;
+    pub const OBJ_MTX_SIZE: u32 = 24;
+    pub const OBJ_SUBMTX_SIZE: u32 = 8;
+    pub const OBJ_BG_SIZE: u32 = 40;
+
+    // uObjTxtr `type` words.
+    pub const G_OBJLT_TXTRBLOCK: u32 = 0x0000_1033;
+    pub const G_OBJLT_TXTRTILE: u32 = 0x00FC_1034;
+    pub const G_OBJLT_TLUT: u32 = 0x0000_0030;
+
+    // uObjBg `imageLoad` selectors.
+    pub const G_BGLT_LOADBLOCK: u16 = 0x0033;
+    pub const G_BGLT_LOADTILE: u16 = 0xFFF4;
+
+    // uObjSprite `imageFlags` / uObjBg `imageFlip`.
+    pub const G_OBJ_FLAG_FLIPS: u8 = 0x01;
+    pub const G_OBJ_FLAG_FLIPT: u8 = 0x10;
+    pub const G_BG_FLAG_FLIPS: u16 = 0x01;
+}
+
 pub use rdp::*;
 pub use rsp_f3dex2::*;
 
@@ -283,6 +497,9 @@ mod tests {
         assert_eq!(super::rdp::G_SETCOMBINE, 0xFC);
         assert_eq!(super::rsp_f3dex2::G_TEXTURE, 0xD7);
         assert_eq!(super::rsp_f3dex2::G_SETOTHERMODE_H, 0xE3);
+        assert_eq!(super::rsp_f3dex2::G_CULLDL, 0x03);
+        assert_eq!(super::rsp_f3dex2::G_BRANCH_Z, 0x04);
+        assert_eq!(super::rsp_f3dex2::G_LOAD_UCODE, 0xDD);
         assert_eq!(super::G_VTX, 0x01);
         assert_eq!(super::G_ENDDL, 0xDF);
         assert_eq!(super::G_NOOP, 0x00);
@@ -329,4 +546,62 @@ mod tests {
         assert_eq!(G_MW_POINTS, 0x0C);
         assert_eq!(G_MW_PERSPNORM, 0x0E);
     }
+
+    #[test]
+    fn f3dex_constants_extend_f3d() {
+        use super::rsp_f3dex::*;
+
+        // Inherited F3D vocabulary.
+        assert_eq!(G_VTX, 0x04);
+        assert_eq!(G_TRI1, 0xBF);
+        assert_eq!(G_RDPHALF_1, 0xB4);
+        assert_eq!(G_ENDDL, 0xB8);
+
+        assert_eq!(G_MODIFYVTX, 0xB2);
+        assert_eq!(G_TRI2, 0xB1);
+        assert_eq!(G_BRANCH_Z, 0xB0);
+        assert_eq!(G_LOAD_UCODE, 0xAF);
+        assert_eq!(VTX_CACHE_SIZE, 32);
+        assert_eq!(G_MWO_POINT_ZSCREEN, 0x1C);
+    }
+
+    #[test]
+    fn f3dzex2_constants_extend_f3dex2() {
+        use super::rsp_f3dzex2::*;
+
+        assert_eq!(G_VTX, 0x01);
+        assert_eq!(G_BRANCH_Z, 0x04);
+        assert_eq!(G_LIGHTING, 0x0002_0000);
+        assert_eq!(G_DMA_IO, 0xD6);
+        assert_eq!(G_SPECIAL_1, 0xD5);
+        assert_eq!(G_SPECIAL_3, 0xD3);
+        assert_eq!(G_LIGHTING_POSITIONAL, 0x0040_0000);
+    }
+
+    #[test]
+    fn l3dex2_constants_extend_f3dex2() {
+        use super::rsp_l3dex2::*;
+
+        assert_eq!(G_VTX, 0x01);
+        assert_eq!(G_ENDDL, 0xDF);
+        assert_eq!(G_LINE3D, 0x08);
+    }
+
+    #[test]
+    fn s2dex_constants_extend_their_base_microcode() {
+        use super::{rsp_s2dex, rsp_s2dex2};
+
+        // S2DEX 1.x sits on the F3D opcode map...
+        assert_eq!(rsp_s2dex::G_DL, 0x06);
+        assert_eq!(rsp_s2dex::G_ENDDL, 0xB8);
+        assert_eq!(rsp_s2dex::G_BG_COPY, 0x02);
+        assert_eq!(rsp_s2dex::G_OBJ_LOADTXTR, 0xC1);
+        assert_eq!(rsp_s2dex::G_LOAD_UCODE, super::rsp_f3dex::G_LOAD_UCODE);
+        // ...S2DEX2 on the F3DEX2 one.
+        assert_eq!(rsp_s2dex2::G_DL, 0xDE);
+        assert_eq!(rsp_s2dex2::G_ENDDL, 0xDF);
+        assert_eq!(rsp_s2dex2::G_BG_COPY, 0x0A);
+        assert_eq!(rsp_s2dex2::G_OBJ_MOVEMEM, rsp_s2dex2::G_MOVEMEM);
+        assert_eq!(rsp_s2dex2::G_OBJLT_TXTRBLOCK, 0x0000_1033);
+    }
 }
@@ -268,6 +268,53 @@ pub fn gsp_branchlist(addr: u32) -> (u32, u32) {
     (((G_DL as u32) << 24) | (1 << 16), addr)
 }
 
+/// gsSPCullDisplayList(vstart, vend): G_CULLDL with `vstart*2` at bits[15:0] and `vend*2` in
+/// w1. Ends the current DL when every vertex in `vstart..=vend` is outside the same clip plane.
+pub fn gsp_cull_displaylist(vstart: u8, vend: u8) -> (u32, u32) {
+    (
+        shiftl(G_CULLDL as u32, 24, 8) | shiftl(vstart as u32 * 2, 0, 16),
+        shiftl(vend as u32 * 2, 0, 16),
+    )
+}
+
+/// gsSPBranchLessZraw(dl, vtx, zval): G_RDPHALF_1 carrying the branch target, then G_BRANCH_Z
+/// with `vtx*5` at bits[23:12] and `vtx*2` at bits[11:0]. `zval` is the raw 16.16 screen Z.
+pub fn gsp_branch_less_z_raw(dl: u32, vtx: u8, zval: u32) -> [(u32, u32); 2] {
+    [
+        (shiftl(G_RDPHALF_1 as u32, 24, 8), dl),
+        (
+            shiftl(G_BRANCH_Z as u32, 24, 8)
+                | shiftl(vtx as u32 * 5, 12, 12)
+                | shiftl(vtx as u32 * 2, 0, 12),
+            zval,
+        ),
+    ]
+}
+
+/// gsSPLineW3D(v0, v1, wd, 0) for L3DEX2: G_LINE3D with `v0*2` at bits[23:16], `v1*2` at
+/// bits[15:8] and the width code at bits[7:0]; w1 is unused. gsSPLine3D is `wd = 0`.
+pub fn gsp_line_w3d(v0: u8, v1: u8, wd: u8) -> (u32, u32) {
+    (
+        shiftl(rsp_l3dex2::G_LINE3D as u32, 24, 8)
+            | shiftl(v0 as u32 * 2, 16, 8)
+            | shiftl(v1 as u32 * 2, 8, 8)
+            | shiftl(wd as u32, 0, 8),
+        0,
+    )
+}
+
+/// gsSPLoadUcodeEx(text, data, data_size): G_RDPHALF_1 carrying the data segment, then
+/// G_LOAD_UCODE with `data_size - 1` at bits[15:0] and the text segment in w1.
+pub fn gsp_load_ucode(text: u32, data: u32, data_size: u32) -> [(u32, u32); 2] {
+    [
+        (shiftl(G_RDPHALF_1 as u32, 24, 8), data),
+        (
+            shiftl(G_LOAD_UCODE as u32, 24, 8) | shiftl(data_size - 1, 0, 16),
+            text,
+        ),
+    ]
+}
+
 /// gsSPSegment(seg, value): G_MOVEWORD/G_MW_SEGMENT.
 /// type = p0(16,8) = 0x06, seg = p0(2,4), value = w1.
 /// gsSPSegment(2, 0x09000000) -> (0xDB060008, 0x09000000).
@@ -277,6 +324,25 @@ pub fn gsp_segment(seg: u8, value: u32) -> (u32, u32) {
     (w0, value)
 }
 
+/// gsSPLight(l, n): G_MOVEMEM of a 16-byte Light_t into light `n` (1-based; 0 and the two slots
+/// before it are the LookAt pair). len = (16-1)/8 at bits[23:19], offset = (n+1)*24/8 at
+/// bits[15:8], index = G_MV_LIGHT. gsSPLight(l, 1) -> (0xDC08060A, l).
+pub fn gsp_light(n: u8, addr: u32) -> (u32, u32) {
+    let w0 = shiftl(G_MOVEMEM as u32, 24, 8)
+        | shiftl((16 - 1) / 8, 19, 5)
+        | shiftl((n as u32 + 1) * 24 / 8, 8, 8)
+        | shiftl(G_MV_LIGHT as u32, 0, 8);
+    (w0, addr)
+}
+
+/// gsSPNumLights(n): G_MOVEWORD / G_MW_NUMLIGHT with w1 = n * 24.
+pub fn gsp_numlights(n: u8) -> (u32, u32) {
+    let w0 = shiftl(G_MOVEWORD as u32, 24, 8)
+        | shiftl(G_MW_NUMLIGHT as u32, 16, 8)
+        | shiftl(G_MWO_NUMLIGHT as u32, 0, 16);
+    (w0, n as u32 * 24)
+}
+
 /// gsSPPerspNormalize(s): G_MOVEWORD / G_MW_PERSPNORM. type = p0(16,8) = 0x0E, offset = 0,
 /// value = w1 = the perspNorm coefficient. gsSPPerspNormalize(129) -> (0xDB0E0000, 0x00000081).
 pub fn gsp_persp_normalize(pn: u16) -> (u32, u32) {
@@ -284,6 +350,13 @@ pub fn gsp_persp_normalize(pn: u16) -> (u32, u32) {
     (w0, pn as u32)
 }
 
+/// gsSPInsertMatrix(where, num): G_MOVEWORD G_MW_MATRIX writing `num` (two s16 integer or two
+/// u16 fraction halves) over the current MVP word at byte offset `where`.
+pub fn gsp_insert_matrix(where_: u16, num: u32) -> (u32, u32) {
+    let w0 = ((G_MOVEWORD as u32) << 24) | ((G_MW_MATRIX as u32) << 16) | where_ as u32;
+    (w0, num)
+}
+
 /// gsDPSetFogColor(rgba): set the scene-global fog color. Opcode G_SETFOGCOLOR=0xF8; w1=rgba.
 pub fn gdp_set_fog_color(rgba: u32) -> (u32, u32) {
     (shiftl(G_SETFOGCOLOR as u32, 24, 8), rgba)
@@ -295,6 +368,55 @@ pub fn gdp_set_blend_color(rgba: u32) -> (u32, u32) {
     (shiftl(G_SETBLENDCOLOR as u32, 24, 8), rgba)
 }
 
+/// gsDPSetConvert(k0, k1, k2, k3, k4, k5): set the six 9-bit signed YUV->RGB coefficients.
+/// Opcode G_SETCONVERT=0xEC; K0/K1 and the top 4 bits of K2 fill w0, the rest fill w1.
+pub fn gdp_set_convert(k0: i16, k1: i16, k2: i16, k3: i16, k4: i16, k5: i16) -> (u32, u32) {
+    let k2 = k2 as u32 & 0x1FF;
+    let w0 = shiftl(G_SETCONVERT as u32, 24, 8)
+        | shiftl(k0 as u32, 13, 9)
+        | shiftl(k1 as u32, 4, 9)
+        | shiftl(k2 >> 5, 0, 4);
+    let w1 = shiftl(k2, 27, 5)
+        | shiftl(k3 as u32, 18, 9)
+        | shiftl(k4 as u32, 9, 9)
+        | shiftl(k5 as u32, 0, 9);
+    (w0, w1)
+}
+
+/// gsDPSetKeyR(center, scale, width): red chroma-key parameters. Opcode G_SETKEYR=0xEB;
+/// w1 = width(12) << 16 | center << 8 | scale.
+pub fn gdp_set_key_r(center: u32, scale: u32, width: u32) -> (u32, u32) {
+    let w1 = shiftl(width, 16, 12) | shiftl(center, 8, 8) | shiftl(scale, 0, 8);
+    (shiftl(G_SETKEYR as u32, 24, 8), w1)
+}
+
+/// gsDPSetKeyGB(center_g, scale_g, width_g, center_b, scale_b, width_b): green/blue chroma-key
+/// parameters. Opcode G_SETKEYGB=0xEA; the two 12-bit widths fill w0, centers/scales fill w1.
+pub fn gdp_set_key_gb(
+    center_g: u32,
+    scale_g: u32,
+    width_g: u32,
+    center_b: u32,
+    scale_b: u32,
+    width_b: u32,
+) -> (u32, u32) {
+    let w0 = shiftl(G_SETKEYGB as u32, 24, 8) | shiftl(width_g, 12, 12) | shiftl(width_b, 0, 12);
+    let w1 = shiftl(center_g, 24, 8)
+        | shiftl(scale_g, 16, 8)
+        | shiftl(center_b, 8, 8)
+        | shiftl(scale_b, 0, 8);
+    (w0, w1)
+}
+
+/// gsDPSetPrimDepth(z, dz): constant primitive depth for G_ZS_PRIM. Opcode G_SETPRIMDEPTH=0xEE;
+/// w1 = z << 16 | dz.
+pub fn gdp_set_prim_depth(z: u32, dz: u32) -> (u32, u32) {
+    (
+        shiftl(G_SETPRIMDEPTH as u32, 24, 8),
+        shiftl(z, 16, 16) | shiftl(dz, 0, 16),
+    )
+}
+
 /// gsSPFogPosition(min, max): G_MOVEWORD / G_MW_FOG. Computes fog multiplier fm and offset fo
 /// from view-space z range [min, max], packs them as two int16s into w1, and encodes the
 /// MOVEWORD with index G_MW_FOG=0x08 at bits[16:23] (matching the move_word handler's
@@ -423,6 +545,14 @@ pub fn gsp_segment_f3d(seg: u8, value: u32) -> (u32, u32) {
     (w0, value)
 }
 
+/// gsSPInsertMatrix(where, num) for F3D: the offset at p0[8,16], G_MW_MATRIX in the low byte.
+pub fn gsp_insert_matrix_f3d(where_: u16, num: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_f3d::G_MOVEWORD as u32, 24, 8)
+        | shiftl(where_ as u32, 8, 16)
+        | shiftl(rsp_f3d::G_MW_MATRIX as u32, 0, 8);
+    (w0, num)
+}
+
 pub fn gsp_numlights_f3d(n: u8) -> (u32, u32) {
     let w0 =
         shiftl(rsp_f3d::G_MOVEWORD as u32, 24, 8) | shiftl(rsp_f3d::G_MW_NUMLIGHT as u32, 0, 8);
@@ -473,6 +603,373 @@ pub fn gsp_modifyvertex_f3d(vtx: u8, r#where: u16, val: u32) -> (u32, u32) {
     (w0, val)
 }
 
+/// gsSPVertex(v0, n, addr) for F3DEX 1.x: w0 = v0*2 at bits[23:16], n at bits[15:10] and the
+/// DMA length `16*n - 1` at bits[9:0]. Decode: dst = p0(16,8)/2, count = p0(10,6).
+pub fn gsp_vertex_f3dex(v0: u8, n: u8, addr: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_f3dex::G_VTX as u32, 24, 8)
+        | shiftl(v0 as u32 * 2, 16, 8)
+        | shiftl(n as u32, 10, 6)
+        | shiftl(16 * n as u32 - 1, 0, 10);
+    (w0, addr)
+}
+
+fn tri_f3dex(v0: u8, v1: u8, v2: u8) -> u32 {
+    shiftl(v0 as u32 * 2, 16, 8) | shiftl(v1 as u32 * 2, 8, 8) | shiftl(v2 as u32 * 2, 0, 8)
+}
+
+/// gsSP1Triangle(v0,v1,v2,0) for F3DEX 1.x: indices*2 at byte shifts 16/8/0 of w1.
+pub fn gsp_1triangle_f3dex(v0: u8, v1: u8, v2: u8) -> (u32, u32) {
+    (
+        shiftl(rsp_f3dex::G_TRI1 as u32, 24, 8),
+        tri_f3dex(v0, v1, v2),
+    )
+}
+
+/// gsSP2Triangles for F3DEX 1.x (G_TRI2 = 0xB1): the first triangle rides in w0's low 24 bits,
+/// the second in w1, both with the 1TRI `*2` packing.
+pub fn gsp_2triangles_f3dex(v0: u8, v1: u8, v2: u8, v3: u8, v4: u8, v5: u8) -> (u32, u32) {
+    (
+        shiftl(rsp_f3dex::G_TRI2 as u32, 24, 8) | tri_f3dex(v0, v1, v2),
+        tri_f3dex(v3, v4, v5),
+    )
+}
+
+/// gsSPModifyVertex(vtx, where, val) for F3DEX 1.x: G_MODIFYVTX with `where` at bits[23:16]
+/// and `vtx*2` at bits[15:0].
+pub fn gsp_modifyvertex_f3dex(vtx: u8, r#where: u8, val: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_f3dex::G_MODIFYVTX as u32, 24, 8)
+        | shiftl(r#where as u32, 16, 8)
+        | shiftl(vtx as u32 * 2, 0, 16);
+    (w0, val)
+}
+
+/// gsSPBranchLessZraw(dl, vtx, zval): G_RDPHALF_1 carrying the branch target, then G_BRANCH_Z
+/// with `vtx*5` at bits[23:12] and `vtx*2` at bits[11:0]. `zval` is the raw 16.16 screen Z.
+pub fn gsp_branch_less_z_raw_f3dex(dl: u32, vtx: u8, zval: u32) -> [(u32, u32); 2] {
+    [
+        (shiftl(rsp_f3dex::G_RDPHALF_1 as u32, 24, 8), dl),
+        (
+            shiftl(rsp_f3dex::G_BRANCH_Z as u32, 24, 8)
+                | shiftl(vtx as u32 * 5, 12, 12)
+                | shiftl(vtx as u32 * 2, 0, 12),
+            zval,
+        ),
+    ]
+}
+
+/// gsSPLoadUcodeEx(text, data, data_size): G_RDPHALF_1 carrying the data segment, then
+/// G_LOAD_UCODE with `data_size - 1` at bits[15:0] and the text segment in w1.
+pub fn gsp_load_ucode_f3dex(text: u32, data: u32, data_size: u32) -> [(u32, u32); 2] {
+    [
+        (shiftl(rsp_f3dex::G_RDPHALF_1 as u32, 24, 8), data),
+        (
+            shiftl(rsp_f3dex::G_LOAD_UCODE as u32, 24, 8) | shiftl(data_size - 1, 0, 16),
+            text,
+        ),
+    ]
+}
+
+/// gDma0p(c, s, l): the S2DEX single-pointer DMA form (`l` in bits[23:0]).
+fn dma0p(op: u8, addr: u32, len: u32) -> (u32, u32) {
+    (shiftl(op as u32, 24, 8) | shiftl(len, 0, 24), addr)
+}
+
+/// gsSPObjRectangle(mptr) for S2DEX2: draw the uObjSprite at `mptr` as an axis-aligned rect.
+pub fn gsp_obj_rectangle_s2dex2(mptr: u32) -> (u32, u32) {
+    dma0p(
+        rsp_s2dex2::G_OBJ_RECTANGLE,
+        mptr,
+        s2dex_types::OBJ_SPRITE_SIZE,
+    )
+}
+
+/// gsSPObjSprite(mptr) for S2DEX2: draw the uObjSprite at `mptr` through the object matrix.
+pub fn gsp_obj_sprite_s2dex2(mptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex2::G_OBJ_SPRITE, mptr, 0)
+}
+
+/// gsSPObjLoadTxtr(tptr) for S2DEX2: load the uObjTxtr at `tptr` into TMEM.
+pub fn gsp_obj_load_txtr_s2dex2(tptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex2::G_OBJ_LOADTXTR, tptr, 23)
+}
+
+/// gsSPObjMatrix(mptr) for S2DEX2: gDma2p(G_OBJ_MOVEMEM, mptr, sizeof(uObjMtx), G_MV_MATRIX, 0),
+/// with `(len-1)/8` at bits[23:19].
+pub fn gsp_obj_matrix_s2dex2(mptr: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_s2dex2::G_OBJ_MOVEMEM as u32, 24, 8)
+        | shiftl((s2dex_types::OBJ_MTX_SIZE - 1) / 8, 19, 5)
+        | shiftl(rsp_s2dex2::G_MV_MATRIX as u32, 0, 8);
+    (w0, mptr)
+}
+
+/// gsSPObjSubMatrix(mptr) for S2DEX2: the 8-byte uObjSubMtx form of [`gsp_obj_matrix_s2dex2`].
+/// Its `ofs` of 2 truncates to 0 under gDma2p's `/8`, so only the length tells the two apart.
+pub fn gsp_obj_sub_matrix_s2dex2(mptr: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_s2dex2::G_OBJ_MOVEMEM as u32, 24, 8)
+        | shiftl((s2dex_types::OBJ_SUBMTX_SIZE - 1) / 8, 19, 5)
+        | shiftl(rsp_s2dex2::G_MV_MATRIX as u32, 0, 8);
+    (w0, mptr)
+}
+
+/// gsSPBgRectCopy(mptr) for S2DEX2: copy-mode blit of the uObjBg at `mptr`.
+pub fn gsp_bg_rect_copy_s2dex2(mptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex2::G_BG_COPY, mptr, 0)
+}
+
+/// gsSPBgRect1Cyc(mptr) for S2DEX2: scaled 1-cycle blit of the uObjScaleBg at `mptr`.
+pub fn gsp_bg_rect_1cyc_s2dex2(mptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex2::G_BG_1CYC, mptr, 0)
+}
+
+/// gsSPObjRectangle(mptr) for S2DEX 1.x.
+pub fn gsp_obj_rectangle_s2dex(mptr: u32) -> (u32, u32) {
+    dma0p(
+        rsp_s2dex::G_OBJ_RECTANGLE,
+        mptr,
+        s2dex_types::OBJ_SPRITE_SIZE,
+    )
+}
+
+/// gsSPObjSprite(mptr) for S2DEX 1.x.
+pub fn gsp_obj_sprite_s2dex(mptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex::G_OBJ_SPRITE, mptr, 0)
+}
+
+/// gsSPObjLoadTxtr(tptr) for S2DEX 1.x.
+pub fn gsp_obj_load_txtr_s2dex(tptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex::G_OBJ_LOADTXTR, tptr, 23)
+}
+
+/// gsSPObjMatrix(mptr) for S2DEX 1.x: gDma1p(G_OBJ_MOVEMEM, mptr, 0, 23), `p` at bits[23:16].
+pub fn gsp_obj_matrix_s2dex(mptr: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_s2dex::G_OBJ_MOVEMEM as u32, 24, 8)
+        | shiftl(rsp_s2dex::G_OBJ_MV_MATRIX as u32, 16, 8);
+    (w0, mptr)
+}
+
+/// gsSPObjSubMatrix(mptr) for S2DEX 1.x: gDma1p(G_OBJ_MOVEMEM, mptr, 2, 7).
+pub fn gsp_obj_sub_matrix_s2dex(mptr: u32) -> (u32, u32) {
+    let w0 = shiftl(rsp_s2dex::G_OBJ_MOVEMEM as u32, 24, 8)
+        | shiftl(rsp_s2dex::G_OBJ_MV_SUBMATRIX as u32, 16, 8)
+        | shiftl(2, 0, 16);
+    (w0, mptr)
+}
+
+/// gsSPBgRectCopy(mptr) for S2DEX 1.x.
+pub fn gsp_bg_rect_copy_s2dex(mptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex::G_BG_COPY, mptr, 0)
+}
+
+/// gsSPBgRect1Cyc(mptr) for S2DEX 1.x.
+pub fn gsp_bg_rect_1cyc_s2dex(mptr: u32) -> (u32, u32) {
+    dma0p(rsp_s2dex::G_BG_1CYC, mptr, 0)
+}
+
+/// GS_PIX2TMEM(pix, siz): TMEM words (64-bit) spanned by `pix` texels of size `siz`.
+pub fn gs_pix2tmem(pix: u32, siz: u32) -> u32 {
+    pix >> (4 - siz)
+}
+
+/// GS_TB_TSIZE(pix, siz): the uObjTxtrBlock `tsize` field (TMEM words - 1).
+pub fn gs_tb_tsize(pix: u32, siz: u32) -> u16 {
+    (gs_pix2tmem(pix, siz) - 1) as u16
+}
+
+/// GS_TB_TLINE(pix, siz): the uObjTxtrBlock `tline` field (the CALC_DXT increment for a
+/// `pix`-texel row).
+pub fn gs_tb_tline(pix: u32, siz: u32) -> u16 {
+    (((1 << 11) - 1) / gs_pix2tmem(pix, siz) + 1) as u16
+}
+
+/// GS_TT_TWIDTH(pix, siz): the uObjTxtrTile `twidth` field.
+pub fn gs_tt_twidth(pix: u32, siz: u32) -> u16 {
+    ((gs_pix2tmem(pix, siz) << 2) - 1) as u16
+}
+
+/// GS_TT_THEIGHT(pix, siz): the uObjTxtrTile `theight` field.
+pub fn gs_tt_theight(pix: u32, _siz: u32) -> u16 {
+    ((pix << 2) - 1) as u16
+}
+
+/// On-disk uObjSprite (gs2dex.h), 24 bytes, big-endian.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ObjSprite {
+    /// s10.2 screen X.
+    pub obj_x: i16,
+    /// u5.10 texels per screen pixel.
+    pub scale_w: u16,
+    /// u10.5 texture width.
+    pub image_w: u16,
+    pub obj_y: i16,
+    pub scale_h: u16,
+    pub image_h: u16,
+    /// TMEM line stride in 64-bit words.
+    pub image_stride: u16,
+    /// TMEM word address of the texture.
+    pub image_adrs: u16,
+    pub image_fmt: u8,
+    pub image_siz: u8,
+    pub image_pal: u8,
+    pub image_flags: u8,
+}
+
+impl ObjSprite {
+    pub fn to_bytes(&self) -> [u8; 24] {
+        let mut b = [0u8; 24];
+        b[0..2].copy_from_slice(&self.obj_x.to_be_bytes());
+        b[2..4].copy_from_slice(&self.scale_w.to_be_bytes());
+        b[4..6].copy_from_slice(&self.image_w.to_be_bytes());
+        b[8..10].copy_from_slice(&self.obj_y.to_be_bytes());
+        b[10..12].copy_from_slice(&self.scale_h.to_be_bytes());
+        b[12..14].copy_from_slice(&self.image_h.to_be_bytes());
+        b[16..18].copy_from_slice(&self.image_stride.to_be_bytes());
+        b[18..20].copy_from_slice(&self.image_adrs.to_be_bytes());
+        b[20] = self.image_fmt;
+        b[21] = self.image_siz;
+        b[22] = self.image_pal;
+        b[23] = self.image_flags;
+        b
+    }
+}
+
+/// On-disk uObjTxtr (gs2dex.h), 24 bytes, big-endian. The load-skip `flag`/`mask` words are
+/// written as `image`/`!0`, the libultra convention for a texture that is always loaded.
+#[derive(Clone, Copy, Debug)]
+pub enum ObjTxtr {
+    /// uObjTxtrBlock: `tsize`/`tline` from [`gs_tb_tsize`]/[`gs_tb_tline`].
+    Block {
+        image: u32,
+        tmem: u16,
+        tsize: u16,
+        tline: u16,
+    },
+    /// uObjTxtrTile: `twidth`/`theight` from [`gs_tt_twidth`]/[`gs_tt_theight`].
+    Tile {
+        image: u32,
+        tmem: u16,
+        twidth: u16,
+        theight: u16,
+    },
+    /// uObjTxtrTLUT: `phead` = first palette entry + 256, `pnum` = entry count - 1.
+    Tlut { image: u32, phead: u16, pnum: u16 },
+}
+
+impl ObjTxtr {
+    pub fn to_bytes(&self) -> [u8; 24] {
+        let (ty, image, h) = match *self {
+            ObjTxtr::Block {
+                image,
+                tmem,
+                tsize,
+                tline,
+            } => (s2dex_types::G_OBJLT_TXTRBLOCK, image, [tmem, tsize, tline]),
+            ObjTxtr::Tile {
+                image,
+                tmem,
+                twidth,
+                theight,
+            } => (
+                s2dex_types::G_OBJLT_TXTRTILE,
+                image,
+                [tmem, twidth, theight],
+            ),
+            ObjTxtr::Tlut { image, phead, pnum } => {
+                (s2dex_types::G_OBJLT_TLUT, image, [phead, pnum, 0])
+            }
+        };
+        let mut b = [0u8; 24];
+        b[0..4].copy_from_slice(&ty.to_be_bytes());
+        b[4..8].copy_from_slice(&image.to_be_bytes());
+        for (i, v) in h.iter().enumerate() {
+            b[8 + i * 2..10 + i * 2].copy_from_slice(&v.to_be_bytes());
+        }
+        b[16..20].copy_from_slice(&image.to_be_bytes());
+        b[20..24].copy_from_slice(&u32::MAX.to_be_bytes());
+        b
+    }
+}
+
+/// On-disk uObjMtx (gs2dex.h), 24 bytes, big-endian: A..D are s15.16, X/Y s10.2 and the base
+/// scales u5.10. The trailing 8 bytes (X, Y, BaseScaleX, BaseScaleY) are the uObjSubMtx.
+#[derive(Clone, Copy, Debug)]
+pub struct ObjMtx {
+    pub a: i32,
+    pub b: i32,
+    pub c: i32,
+    pub d: i32,
+    pub x: i16,
+    pub y: i16,
+    pub base_scale_x: u16,
+    pub base_scale_y: u16,
+}
+
+impl ObjMtx {
+    pub fn to_bytes(&self) -> [u8; 24] {
+        let mut b = [0u8; 24];
+        b[0..4].copy_from_slice(&self.a.to_be_bytes());
+        b[4..8].copy_from_slice(&self.b.to_be_bytes());
+        b[8..12].copy_from_slice(&self.c.to_be_bytes());
+        b[12..16].copy_from_slice(&self.d.to_be_bytes());
+        b[16..18].copy_from_slice(&self.x.to_be_bytes());
+        b[18..20].copy_from_slice(&self.y.to_be_bytes());
+        b[20..22].copy_from_slice(&self.base_scale_x.to_be_bytes());
+        b[22..24].copy_from_slice(&self.base_scale_y.to_be_bytes());
+        b
+    }
+}
+
+/// On-disk uObjScaleBg (gs2dex.h), 40 bytes, big-endian. With `scale_w`/`scale_h` left at
+/// 1.0 (`0x400`) it is also a valid uObjBg for gsSPBgRectCopy, which ignores the scale words.
+#[derive(Clone, Copy, Debug)]
+pub struct ObjBg {
+    /// u10.5 source offset into the image.
+    pub image_x: u16,
+    /// u10.2 image width.
+    pub image_w: u16,
+    /// s10.2 screen position.
+    pub frame_x: i16,
+    /// u10.2 screen size.
+    pub frame_w: u16,
+    pub image_y: u16,
+    pub image_h: u16,
+    pub frame_y: i16,
+    pub frame_h: u16,
+    pub image_ptr: u32,
+    pub image_load: u16,
+    pub image_fmt: u8,
+    pub image_siz: u8,
+    pub image_pal: u16,
+    pub image_flip: u16,
+    /// u5.10 texels per screen pixel (gsSPBgRect1Cyc only).
+    pub scale_w: u16,
+    pub scale_h: u16,
+}
+
+impl ObjBg {
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut b = [0u8; 40];
+        b[0..2].copy_from_slice(&self.image_x.to_be_bytes());
+        b[2..4].copy_from_slice(&self.image_w.to_be_bytes());
+        b[4..6].copy_from_slice(&self.frame_x.to_be_bytes());
+        b[6..8].copy_from_slice(&self.frame_w.to_be_bytes());
+        b[8..10].copy_from_slice(&self.image_y.to_be_bytes());
+        b[10..12].copy_from_slice(&self.image_h.to_be_bytes());
+        b[12..14].copy_from_slice(&self.frame_y.to_be_bytes());
+        b[14..16].copy_from_slice(&self.frame_h.to_be_bytes());
+        b[16..20].copy_from_slice(&self.image_ptr.to_be_bytes());
+        b[20..22].copy_from_slice(&self.image_load.to_be_bytes());
+        b[22] = self.image_fmt;
+        b[23] = self.image_siz;
+        b[24..26].copy_from_slice(&self.image_pal.to_be_bytes());
+        b[26..28].copy_from_slice(&self.image_flip.to_be_bytes());
+        b[28..30].copy_from_slice(&self.scale_w.to_be_bytes());
+        b[30..32].copy_from_slice(&self.scale_h.to_be_bytes());
+        // imageYorig (s20.5) mirrors imageY; the 4 trailing bytes are padding.
+        b[32..36].copy_from_slice(&(self.image_y as i32).to_be_bytes());
+        b
+    }
+}
+
 /// On-disk N64 colored vertex (authentic libultra Vtx_t), 16 bytes, big-endian. No field swaps.
 #[derive(Clone, Copy, Debug)]
 pub struct VtxColored {
@@ -656,6 +1153,29 @@ mod encode_tests {
         assert_eq!(gdp_set_env_color(0x0000_00FF), (0xFB00_0000, 0x0000_00FF));
     }
     #[test]
+    fn golden_set_convert_libultra_defaults() {
+        // gsDPSetConvert(G_CV_K0..G_CV_K5): K2 = -89 straddles the two words.
+        assert_eq!(
+            gdp_set_convert(G_CV_K0, G_CV_K1, G_CV_K2, G_CV_K3, G_CV_K4, G_CV_K5),
+            (0xEC15_FD5D, 0x3B78_E42A)
+        );
+    }
+    #[test]
+    fn golden_set_key_r_gb() {
+        assert_eq!(gdp_set_key_r(0x80, 0x40, 0x123), (0xEB00_0000, 0x0123_8040));
+        assert_eq!(
+            gdp_set_key_gb(0x10, 0x20, 0x456, 0x30, 0x40, 0x789),
+            (0xEA45_6789, 0x1020_3040)
+        );
+    }
+    #[test]
+    fn golden_set_prim_depth() {
+        assert_eq!(
+            gdp_set_prim_depth(0x1234, 0x0001),
+            (0xEE00_0000, 0x1234_0001)
+        );
+    }
+    #[test]
     fn golden_cycle_type() {
         assert_eq!(gdp_set_cycle_type(0), (0xE300_0A01, 0x0000_0000)); // 1CYCLE
         assert_eq!(gdp_set_cycle_type(1), (0xE300_0A01, 0x0010_0000)); // 2CYCLE
@@ -744,6 +1264,45 @@ mod encode_tests {
         assert_eq!(w1, G_RM_AA_ZB_OPA_SURF | G_RM_AA_ZB_OPA_SURF2);
     }
 
+    #[test]
+    fn golden_cull_displaylist_and_branch_less_z() {
+        assert_eq!(gsp_cull_displaylist(0, 7), (0x0300_0000, 0x0000_000E));
+        assert_eq!(gsp_cull_displaylist(4, 31), (0x0300_0008, 0x0000_003E));
+        assert_eq!(
+            gsp_branch_less_z_raw(0x0600_0100, 2, 0x0100_0000),
+            [(0xE100_0000, 0x0600_0100), (0x0400_A004, 0x0100_0000)]
+        );
+    }
+
+    #[test]
+    fn golden_load_ucode() {
+        assert_eq!(
+            gsp_load_ucode(0x0010_0000, 0x0020_0000, 0x800),
+            [(0xE100_0000, 0x0020_0000), (0xDD00_07FF, 0x0010_0000)]
+        );
+    }
+
+    #[test]
+    fn golden_light_and_numlights() {
+        assert_eq!(gsp_light(1, 0x0600_0000), (0xDC08_060A, 0x0600_0000));
+        assert_eq!(gsp_light(3, 0x0600_0000), (0xDC08_0C0A, 0x0600_0000));
+        assert_eq!(gsp_numlights(2), (0xDB02_0000, 48));
+    }
+
+    #[test]
+    fn golden_insert_matrix() {
+        assert_eq!(
+            gsp_insert_matrix(G_MWO_MATRIX_XX_XY_F + 4, 0x1234_5678),
+            (0xDB00_0024, 0x1234_5678)
+        );
+    }
+
+    #[test]
+    fn golden_line_w3d() {
+        assert_eq!(gsp_line_w3d(0, 1, 0), (0x0800_0200, 0));
+        assert_eq!(gsp_line_w3d(3, 7, 4), (0x0806_0E04, 0));
+    }
+
     #[test]
     fn golden_persp_normalize() {
         assert_eq!(gsp_persp_normalize(129), (0xDB0E_0000, 0x0000_0081));
@@ -905,6 +1464,14 @@ mod encode_tests {
         assert_eq!(gsp_forcematrix_f3d(0x0123_4567), (0x039E_0000, 0x0123_4567));
     }
 
+    #[test]
+    fn golden_insert_matrix_f3d() {
+        assert_eq!(
+            gsp_insert_matrix_f3d(0x24, 0x1234_5678),
+            (0xBC00_2400, 0x1234_5678)
+        );
+    }
+
     #[test]
     fn golden_lightcolor_f3d() {
         assert_eq!(
@@ -920,4 +1487,123 @@ mod encode_tests {
             (0xBC00_8C0C, 0xFFE0_0020)
         );
     }
+
+    #[test]
+    fn golden_vertex_f3dex() {
+        assert_eq!(
+            gsp_vertex_f3dex(20, 3, 0x0123_4567),
+            (0x0428_0C2F, 0x0123_4567)
+        );
+    }
+
+    #[test]
+    fn golden_triangles_f3dex() {
+        assert_eq!(gsp_1triangle_f3dex(0, 1, 2), (0xBF00_0000, 0x0000_0204));
+        assert_eq!(
+            gsp_2triangles_f3dex(0, 1, 2, 31, 30, 29),
+            (0xB100_0204, 0x003E_3C3A)
+        );
+    }
+
+    #[test]
+    fn golden_modifyvertex_f3dex() {
+        assert_eq!(
+            gsp_modifyvertex_f3dex(3, 0x14, 0xFFE0_0020),
+            (0xB214_0006, 0xFFE0_0020)
+        );
+    }
+
+    #[test]
+    fn golden_branch_less_z_and_load_ucode_f3dex() {
+        assert_eq!(
+            gsp_branch_less_z_raw_f3dex(0x0600_0100, 2, 0x0100_0000),
+            [(0xB400_0000, 0x0600_0100), (0xB000_A004, 0x0100_0000)]
+        );
+        assert_eq!(
+            gsp_load_ucode_f3dex(0x0010_0000, 0x0020_0000, 0x800),
+            [(0xB400_0000, 0x0020_0000), (0xAF00_07FF, 0x0010_0000)]
+        );
+    }
+
+    #[test]
+    fn golden_obj_commands_s2dex2() {
+        assert_eq!(
+            gsp_obj_rectangle_s2dex2(0x0600_0000),
+            (0x0100_0018, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_sprite_s2dex2(0x0600_0000),
+            (0x0200_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_load_txtr_s2dex2(0x0600_0000),
+            (0x0500_0017, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_matrix_s2dex2(0x0600_0000),
+            (0xDC10_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_sub_matrix_s2dex2(0x0600_0000),
+            (0xDC00_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_bg_rect_copy_s2dex2(0x0600_0000),
+            (0x0A00_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_bg_rect_1cyc_s2dex2(0x0600_0000),
+            (0x0900_0000, 0x0600_0000)
+        );
+    }
+
+    #[test]
+    fn golden_obj_commands_s2dex() {
+        assert_eq!(
+            gsp_obj_rectangle_s2dex(0x0600_0000),
+            (0x0300_0018, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_sprite_s2dex(0x0600_0000),
+            (0x0400_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_load_txtr_s2dex(0x0600_0000),
+            (0xC100_0017, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_matrix_s2dex(0x0600_0000),
+            (0x0517_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_obj_sub_matrix_s2dex(0x0600_0000),
+            (0x0507_0002, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_bg_rect_copy_s2dex(0x0600_0000),
+            (0x0200_0000, 0x0600_0000)
+        );
+        assert_eq!(
+            gsp_bg_rect_1cyc_s2dex(0x0600_0000),
+            (0x0100_0000, 0x0600_0000)
+        );
+    }
+
+    #[test]
+    fn obj_txtr_macros_match_gs2dex() {
+        // A 32-texel RGBA16 row: 8 TMEM words.
+        assert_eq!(gs_tb_tsize(32 * 32, 2), 255);
+        assert_eq!(gs_tb_tline(32, 2), 256);
+        assert_eq!(gs_tt_twidth(32, 2), 31);
+        assert_eq!(gs_tt_theight(16, 2), 63);
+
+        let b = ObjTxtr::Tlut {
+            image: 0x0600_0100,
+            phead: 256,
+            pnum: 15,
+        }
+        .to_bytes();
+        assert_eq!(&b[0..4], &[0x00, 0x00, 0x00, 0x30]);
+        assert_eq!(&b[8..12], &[0x01, 0x00, 0x00, 0x0F]);
+    }
 }